@@ -1,7 +1,7 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
-use gateway::config::{RouteConfig, ServerConfig, GatewayConfig};
+use gateway::config::{GatewayConfig, RouteConfig};
 use gateway::router::Router;
-use http::Method;
+use http::{HeaderMap, Method};
 
 fn benchmark_router_exact_match(c: &mut Criterion) {
     let routes = vec![
@@ -20,6 +20,18 @@ fn benchmark_router_exact_match(c: &mut Criterion) {
             cors: None,
             ip_filter: None,
             cache: None,
+            honor_method_override: None,
+            circuit_breaker: None,
+            fallback: None,
+            mirror: None,
+            canary: None,
+            match_headers: Default::default(),
+            match_query: Default::default(),
+            max_response_bytes: None,
+            answer_options: None,
+            preserve_headers: Vec::new(),
+            enabled: true,
+            disabled_status: 503,
         },
         RouteConfig {
             path: "/api/orders".to_string(),
@@ -36,6 +48,18 @@ fn benchmark_router_exact_match(c: &mut Criterion) {
             cors: None,
             ip_filter: None,
             cache: None,
+            honor_method_override: None,
+            circuit_breaker: None,
+            fallback: None,
+            mirror: None,
+            canary: None,
+            match_headers: Default::default(),
+            match_query: Default::default(),
+            max_response_bytes: None,
+            answer_options: None,
+            preserve_headers: Vec::new(),
+            enabled: true,
+            disabled_status: 503,
         },
         RouteConfig {
             path: "/api/products".to_string(),
@@ -52,6 +76,18 @@ fn benchmark_router_exact_match(c: &mut Criterion) {
             cors: None,
             ip_filter: None,
             cache: None,
+            honor_method_override: None,
+            circuit_breaker: None,
+            fallback: None,
+            mirror: None,
+            canary: None,
+            match_headers: Default::default(),
+            match_query: Default::default(),
+            max_response_bytes: None,
+            answer_options: None,
+            preserve_headers: Vec::new(),
+            enabled: true,
+            disabled_status: 503,
         },
     ];
 
@@ -59,7 +95,7 @@ fn benchmark_router_exact_match(c: &mut Criterion) {
 
     c.bench_function("router_exact_match", |b| {
         b.iter(|| {
-            black_box(router.match_route(&Method::GET, "/api/users"))
+            black_box(router.match_route("/api/users", &Method::GET, &HeaderMap::new(), None))
         })
     });
 }
@@ -81,6 +117,18 @@ fn benchmark_router_param_match(c: &mut Criterion) {
             cors: None,
             ip_filter: None,
             cache: None,
+            honor_method_override: None,
+            circuit_breaker: None,
+            fallback: None,
+            mirror: None,
+            canary: None,
+            match_headers: Default::default(),
+            match_query: Default::default(),
+            max_response_bytes: None,
+            answer_options: None,
+            preserve_headers: Vec::new(),
+            enabled: true,
+            disabled_status: 503,
         },
     ];
 
@@ -88,7 +136,7 @@ fn benchmark_router_param_match(c: &mut Criterion) {
 
     c.bench_function("router_param_match", |b| {
         b.iter(|| {
-            black_box(router.match_route(&Method::GET, "/api/users/12345"))
+            black_box(router.match_route("/api/users/12345", &Method::GET, &HeaderMap::new(), None))
         })
     });
 }
@@ -110,6 +158,18 @@ fn benchmark_router_wildcard_match(c: &mut Criterion) {
             cors: None,
             ip_filter: None,
             cache: None,
+            honor_method_override: None,
+            circuit_breaker: None,
+            fallback: None,
+            mirror: None,
+            canary: None,
+            match_headers: Default::default(),
+            match_query: Default::default(),
+            max_response_bytes: None,
+            answer_options: None,
+            preserve_headers: Vec::new(),
+            enabled: true,
+            disabled_status: 503,
         },
     ];
 
@@ -117,7 +177,7 @@ fn benchmark_router_wildcard_match(c: &mut Criterion) {
 
     c.bench_function("router_wildcard_match", |b| {
         b.iter(|| {
-            black_box(router.match_route(&Method::GET, "/api/deeply/nested/path/to/resource"))
+            black_box(router.match_route("/api/deeply/nested/path/to/resource", &Method::GET, &HeaderMap::new(), None))
         })
     });
 }
@@ -143,6 +203,18 @@ fn benchmark_router_scale(c: &mut Criterion) {
                 cors: None,
                 ip_filter: None,
                 cache: None,
+                honor_method_override: None,
+                circuit_breaker: None,
+                fallback: None,
+                mirror: None,
+                canary: None,
+                match_headers: Default::default(),
+                match_query: Default::default(),
+                max_response_bytes: None,
+                answer_options: None,
+                preserve_headers: Vec::new(),
+                enabled: true,
+                disabled_status: 503,
             });
         }
 
@@ -153,7 +225,7 @@ fn benchmark_router_scale(c: &mut Criterion) {
             num_routes,
             |b, &_num| {
                 b.iter(|| {
-                    black_box(router.match_route(&Method::GET, "/api/service50"))
+                    black_box(router.match_route("/api/service50", &Method::GET, &HeaderMap::new(), None))
                 })
             },
         );
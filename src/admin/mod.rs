@@ -0,0 +1,1203 @@
+use crate::auth::api_key::ApiKeyValidator;
+use crate::cache::CacheStats;
+use crate::circuit_breaker::{CircuitBreakerMetrics, CircuitBreakerService, CircuitState};
+use crate::config::ApiKeyConfig;
+use crate::error::{GatewayError, Result};
+use crate::loadbalancer::backend::{BackendConfig, HealthStats};
+use crate::maintenance::MaintenanceConfig;
+use crate::proxy::ProxyState;
+use crate::router::{Route, Router};
+use axum::{
+    extract::{Path, Query, Request, State},
+    http::StatusCode,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router as AxumRouter,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+/// Admin API configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminConfig {
+    /// Enable the admin server
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Admin server bind address
+    #[serde(default = "default_host")]
+    pub host: String,
+    /// Admin server port (must differ from the gateway port)
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// API keys accepted by the admin API
+    pub api_key: ApiKeyConfig,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_host() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_port() -> u16 {
+    9091
+}
+
+impl AdminConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.enabled && self.api_key.keys.is_empty() && self.api_key.redis.is_none() {
+            return Err(GatewayError::Config(
+                "admin.api_key: At least one API key is required to protect the admin API"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Admin API handler state
+#[derive(Clone)]
+pub struct AdminState {
+    proxy: ProxyState,
+    api_keys: Arc<ApiKeyValidator>,
+}
+
+/// Configured route as reported by `GET /admin/routes`
+#[derive(Debug, Serialize)]
+pub struct RouteInfo {
+    pub path: String,
+    pub description: String,
+    pub methods: Vec<String>,
+    pub strip_prefix: bool,
+    pub enabled: bool,
+    pub backends: Vec<String>,
+    pub auth: bool,
+    pub cache: bool,
+    pub circuit_breaker: bool,
+}
+
+/// Backend health as reported by `GET /admin/backends`
+#[derive(Debug, Serialize)]
+pub struct BackendInfo {
+    pub route: String,
+    pub url: String,
+    pub weight: u32,
+    /// Whether the backend belongs to the route's canary group
+    pub canary: bool,
+    pub health: HealthStats,
+}
+
+/// Circuit breaker as reported by `GET /admin/circuit-breakers`
+#[derive(Debug, Serialize)]
+pub struct CircuitBreakerInfo {
+    /// Route owning the breaker (None for the global circuit breaker)
+    pub route: Option<String>,
+    pub backend: String,
+    pub state: CircuitState,
+    /// Whether the state was forced via the admin API
+    pub forced: bool,
+    pub metrics: CircuitBreakerMetrics,
+}
+
+/// Response cache size as reported by `GET /admin/cache/stats`
+#[derive(Debug, Serialize)]
+pub struct CacheStatsInfo {
+    pub route: String,
+    #[serde(flatten)]
+    pub stats: CacheStats,
+}
+
+/// Backend registration for `POST /admin/backends`
+#[derive(Debug, Deserialize)]
+struct AddBackendRequest {
+    /// Configured path of the route to add the backend to
+    route: String,
+    #[serde(flatten)]
+    backend: BackendConfig,
+}
+
+/// Backend targeted by `DELETE /admin/backends`
+#[derive(Debug, Deserialize)]
+struct BackendTarget {
+    route: String,
+    url: String,
+}
+
+/// Circuit breaker targeted by a force request
+#[derive(Debug, Deserialize)]
+struct BreakerTarget {
+    /// Route owning the breaker (the global circuit breaker if unset)
+    route: Option<String>,
+}
+
+/// Cache entries targeted by `POST /admin/cache/purge` (exactly one field)
+///
+/// Prefix and tag purges only work with the memory cache backend; Redis keys
+/// are digests that can't be matched, so routes cached in Redis answer
+/// `501 Not Implemented` and have to be cleared with `/admin/cache/clear`.
+#[derive(Debug, Deserialize)]
+struct PurgeRequest {
+    /// Request path prefix, e.g. `/api/products`
+    prefix: Option<String>,
+    /// Tag from the cached responses' `X-Cache-Tags` header
+    tag: Option<String>,
+}
+
+/// Build the admin API router, protected by `api_keys`
+pub fn admin_router(proxy: ProxyState, api_keys: ApiKeyValidator) -> AxumRouter {
+    let state = AdminState {
+        proxy,
+        api_keys: Arc::new(api_keys),
+    };
+
+    AxumRouter::new()
+        .route("/admin/routes", get(routes_handler))
+        .route(
+            "/admin/backends",
+            get(backends_handler)
+                .post(add_backend_handler)
+                .delete(remove_backend_handler),
+        )
+        .route("/admin/circuit-breakers", get(circuit_breakers_handler))
+        .route(
+            "/admin/circuit-breakers/:backend/:action",
+            post(force_circuit_breaker_handler),
+        )
+        .route("/admin/cache/stats", get(cache_stats_handler))
+        .route("/admin/cache/clear", post(clear_cache_handler))
+        .route("/admin/cache/purge", post(purge_cache_handler))
+        .route("/admin/maintenance", get(maintenance_handler))
+        .route("/admin/maintenance/:action", post(set_maintenance_handler))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_api_key,
+        ))
+        .with_state(state)
+}
+
+/// Bind the admin server and serve it in the background
+pub async fn spawn_admin_server(config: &AdminConfig, proxy: ProxyState) -> Result<()> {
+    let api_keys = ApiKeyValidator::new(&config.api_key).await?;
+    let app = admin_router(proxy, api_keys);
+
+    let addr = format!("{}:{}", config.host, config.port);
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .map_err(GatewayError::Io)?;
+    info!("Admin API listening on {}", addr);
+
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            error!("Admin server error: {}", e);
+        }
+    });
+
+    Ok(())
+}
+
+/// Reject admin requests without a valid API key
+async fn require_api_key(State(state): State<AdminState>, req: Request, next: Next) -> Response {
+    if let Err(e) = state.api_keys.validate(req.headers()).await {
+        warn!(path = %req.uri().path(), error = %e, "Rejected admin API request");
+        return e.into_response();
+    }
+    next.run(req).await
+}
+
+/// List configured routes
+async fn routes_handler(State(state): State<AdminState>) -> Json<Vec<RouteInfo>> {
+    let router = state.proxy.router.load_full();
+    let routes = router
+        .entries()
+        .map(|(path, route)| RouteInfo {
+            path: path.to_string(),
+            description: route.description.clone(),
+            methods: route.methods.iter().map(|m| m.to_string()).collect(),
+            strip_prefix: route.strip_prefix,
+            enabled: route.enabled,
+            backends: route
+                .load_balancer
+                .backends()
+                .iter()
+                .map(|b| b.url().to_string())
+                .collect(),
+            auth: route.auth.as_ref().is_some_and(|auth| auth.required),
+            cache: route.cache.is_some(),
+            circuit_breaker: route.circuit_breaker.is_some(),
+        })
+        .collect();
+
+    Json(routes)
+}
+
+/// Report health statistics for every backend
+async fn backends_handler(State(state): State<AdminState>) -> Json<Vec<BackendInfo>> {
+    let router = state.proxy.router.load_full();
+    let mut backends = Vec::new();
+    for (path, route) in router.entries() {
+        let canary_backends = route
+            .canary
+            .as_ref()
+            .map(|canary| canary.load_balancer().backends())
+            .unwrap_or_default();
+        let groups = [
+            (false, route.load_balancer.backends()),
+            (true, canary_backends),
+        ];
+        for (canary, group) in groups {
+            for backend in group.iter() {
+                backends.push(BackendInfo {
+                    route: path.to_string(),
+                    url: backend.url().to_string(),
+                    weight: backend.weight(),
+                    canary,
+                    health: backend.health_stats(),
+                });
+            }
+        }
+    }
+
+    Json(backends)
+}
+
+/// Register a backend with a route's load balancer
+async fn add_backend_handler(
+    State(state): State<AdminState>,
+    Json(request): Json<AddBackendRequest>,
+) -> Result<(StatusCode, Json<BackendInfo>)> {
+    if !request.backend.url.starts_with("http://") && !request.backend.url.starts_with("https://") {
+        return Err(GatewayError::InvalidRoute(format!(
+            "Backend URL must start with http:// or https://: {}",
+            request.backend.url
+        )));
+    }
+    // Weighted balancing never picks a zero-weight backend, as in config validation
+    if request.backend.weight == 0 {
+        return Err(GatewayError::InvalidRoute(format!(
+            "Backend weight must be > 0: {}",
+            request.backend.url
+        )));
+    }
+
+    let router = state.proxy.router.load_full();
+    let route = find_route(&router, &request.route)?;
+    let backend = route
+        .load_balancer
+        .add_backend(request.backend.clone())
+        .ok_or_else(|| {
+            GatewayError::InvalidRoute(format!(
+                "Backend {} is already registered for route: {}",
+                request.backend.url, request.route
+            ))
+        })?;
+
+    // Probe the new backend like the configured ones
+    if let Some(health_checker) = &route.health_checker {
+        health_checker.start_active_checks(vec![backend.clone()]);
+    }
+
+    info!(route = %request.route, backend = %backend.url(), "Backend added via admin API");
+    Ok((
+        StatusCode::CREATED,
+        Json(BackendInfo {
+            route: request.route,
+            url: backend.url().to_string(),
+            weight: backend.weight(),
+            canary: false,
+            health: backend.health_stats(),
+        }),
+    ))
+}
+
+/// Remove a backend from a route's load balancer
+///
+/// In-flight requests finish normally; the returned `active_connections`
+/// shows how many were still draining at removal time.
+async fn remove_backend_handler(
+    State(state): State<AdminState>,
+    Query(target): Query<BackendTarget>,
+) -> Result<Json<BackendInfo>> {
+    let router = state.proxy.router.load_full();
+    let backend = find_route(&router, &target.route)?
+        .load_balancer
+        .remove_backend(&target.url)
+        .ok_or_else(|| {
+            GatewayError::RouteNotFound(format!(
+                "Backend {} is not registered for route: {}",
+                target.url, target.route
+            ))
+        })?;
+
+    info!(
+        route = %target.route,
+        backend = %target.url,
+        in_flight = backend.active_connections(),
+        "Backend removed via admin API"
+    );
+    Ok(Json(BackendInfo {
+        route: target.route,
+        url: backend.url().to_string(),
+        weight: backend.weight(),
+        canary: false,
+        health: backend.health_stats(),
+    }))
+}
+
+/// Report state and metrics for the global and route circuit breakers
+async fn circuit_breakers_handler(
+    State(state): State<AdminState>,
+) -> Json<Vec<CircuitBreakerInfo>> {
+    let mut breakers = Vec::new();
+
+    if let Some(service) = state.proxy.circuit_breaker.load_full() {
+        breakers.extend(breaker_infos(None, &service).await);
+    }
+
+    let router = state.proxy.router.load_full();
+    for (path, route) in router.entries() {
+        if let Some(service) = &route.circuit_breaker {
+            breakers.extend(breaker_infos(Some(path), service).await);
+        }
+    }
+
+    Json(breakers)
+}
+
+/// Force a backend's circuit open or closed, or clear a forced state
+///
+/// Targets the global circuit breaker unless `?route=` names a route with its own breaker.
+async fn force_circuit_breaker_handler(
+    State(state): State<AdminState>,
+    Path((backend, action)): Path<(String, String)>,
+    Query(target): Query<BreakerTarget>,
+) -> Result<Json<CircuitBreakerInfo>> {
+    let service = match &target.route {
+        Some(path) => {
+            let router = state.proxy.router.load_full();
+            find_route(&router, path)?
+                .circuit_breaker
+                .clone()
+                .ok_or_else(|| {
+                    GatewayError::InvalidRoute(format!(
+                        "No circuit breaker configured for route: {}",
+                        path
+                    ))
+                })?
+        }
+        None => state.proxy.circuit_breaker.load_full().ok_or_else(|| {
+            GatewayError::InvalidRoute("No global circuit breaker configured".to_string())
+        })?,
+    };
+
+    match action.as_str() {
+        "open" => service.force_open(&backend).await,
+        "close" => service.force_close(&backend).await,
+        "clear" => service.clear_forced(&backend).await,
+        other => {
+            return Err(GatewayError::RouteNotFound(format!(
+                "Unknown circuit breaker action: {}",
+                other
+            )))
+        }
+    }
+    info!(
+        backend = %backend,
+        action = %action,
+        route = ?target.route,
+        "Circuit breaker updated via admin API"
+    );
+
+    Ok(Json(CircuitBreakerInfo {
+        route: target.route,
+        state: service.state(&backend).await,
+        forced: service.is_force_isolated(&backend).await,
+        metrics: service.metrics(&backend).await.unwrap_or_default(),
+        backend,
+    }))
+}
+
+/// Report the maintenance mode settings
+async fn maintenance_handler(State(state): State<AdminState>) -> Json<MaintenanceConfig> {
+    Json(state.proxy.maintenance.load().as_ref().clone())
+}
+
+/// Turn maintenance mode on or off, keeping its configured response and paths
+async fn set_maintenance_handler(
+    State(state): State<AdminState>,
+    Path(action): Path<String>,
+) -> Result<Json<MaintenanceConfig>> {
+    let enabled = match action.as_str() {
+        "enable" => true,
+        "disable" => false,
+        other => {
+            return Err(GatewayError::RouteNotFound(format!(
+                "Unknown maintenance action: {}",
+                other
+            )))
+        }
+    };
+
+    let mut maintenance = state.proxy.maintenance.load().as_ref().clone();
+    maintenance.enabled = enabled;
+    state.proxy.set_maintenance(maintenance.clone());
+    info!(enabled, "Maintenance mode updated via admin API");
+
+    Ok(Json(maintenance))
+}
+
+/// Find a route by its configured path
+fn find_route<'a>(router: &'a Router, path: &str) -> Result<&'a Route> {
+    router
+        .entries()
+        .find(|(route_path, _)| *route_path == path)
+        .map(|(_, route)| route)
+        .ok_or_else(|| GatewayError::RouteNotFound(path.to_string()))
+}
+
+/// Describe every breaker of a circuit breaker service, sorted by backend
+async fn breaker_infos(
+    route: Option<&str>,
+    service: &CircuitBreakerService,
+) -> Vec<CircuitBreakerInfo> {
+    let mut all = service.all_metrics().await;
+    all.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut infos = Vec::with_capacity(all.len());
+    for (backend, metrics, state) in all {
+        infos.push(CircuitBreakerInfo {
+            route: route.map(str::to_string),
+            forced: service.is_force_isolated(&backend).await,
+            backend,
+            state,
+            metrics,
+        });
+    }
+    infos
+}
+
+/// Report the size of every route's response cache
+///
+/// Routes cached in Redis are left out, since their size isn't known locally.
+async fn cache_stats_handler(State(state): State<AdminState>) -> Json<Vec<CacheStatsInfo>> {
+    let router = state.proxy.router.load_full();
+    let mut infos = Vec::new();
+    for (path, route) in router.entries() {
+        let Some(cache) = &route.cache else {
+            continue;
+        };
+        if let Some(stats) = cache.current_stats().await {
+            infos.push(CacheStatsInfo {
+                route: path.to_string(),
+                stats,
+            });
+        }
+    }
+    Json(infos)
+}
+
+/// Clear every route's response cache
+async fn clear_cache_handler(State(state): State<AdminState>) -> impl IntoResponse {
+    let router = state.proxy.router.load_full();
+    let mut cleared = 0;
+    for (_, route) in router.entries() {
+        if let Some(cache) = &route.cache {
+            cache.clear().await;
+            cleared += 1;
+        }
+    }
+
+    info!(routes = cleared, "Cleared response caches via admin API");
+    Json(serde_json::json!({ "cleared_routes": cleared }))
+}
+
+/// Purge cached responses by path prefix or tag across every route's cache
+async fn purge_cache_handler(
+    State(state): State<AdminState>,
+    Json(request): Json<PurgeRequest>,
+) -> Result<Json<serde_json::Value>> {
+    let router = state.proxy.router.load_full();
+    let mut purged = 0;
+    match (&request.prefix, &request.tag) {
+        (Some(prefix), None) => {
+            for (_, route) in router.entries() {
+                if let Some(cache) = &route.cache {
+                    purged += cache.purge_prefix(prefix).await?;
+                }
+            }
+        }
+        (None, Some(tag)) => {
+            for (_, route) in router.entries() {
+                if let Some(cache) = &route.cache {
+                    purged += cache.purge_tag(tag).await?;
+                }
+            }
+        }
+        _ => {
+            return Err(GatewayError::InvalidRoute(
+                "Specify exactly one of prefix or tag".to_string(),
+            ))
+        }
+    }
+
+    info!(
+        prefix = request.prefix.as_deref().unwrap_or("-"),
+        tag = request.tag.as_deref().unwrap_or("-"),
+        purged,
+        "Purged response caches via admin API"
+    );
+    Ok(Json(serde_json::json!({ "purged": purged })))
+}
+
+/// Gateway health as reported by the aggregate health endpoint
+#[derive(Debug, Serialize)]
+pub struct AggregateHealth {
+    /// Whether every route has at least one healthy backend
+    pub healthy: bool,
+    pub routes: Vec<RouteHealth>,
+}
+
+/// Backend health of a single route
+#[derive(Debug, Serialize)]
+pub struct RouteHealth {
+    pub path: String,
+    pub healthy: bool,
+    pub backends: Vec<BackendHealth>,
+}
+
+/// Health statistics of a single backend
+#[derive(Debug, Serialize)]
+pub struct BackendHealth {
+    pub url: String,
+    pub health: HealthStats,
+}
+
+/// Aggregate health endpoint handler
+///
+/// Responds `200` when every route has a healthy backend and `503` otherwise.
+pub async fn aggregate_health_handler(State(state): State<ProxyState>) -> impl IntoResponse {
+    let router = state.router.load_full();
+    let routes: Vec<RouteHealth> = router
+        .entries()
+        .map(|(path, route)| RouteHealth {
+            path: path.to_string(),
+            healthy: route.load_balancer.has_healthy_backend(),
+            backends: route
+                .load_balancer
+                .backends()
+                .iter()
+                .map(|backend| BackendHealth {
+                    url: backend.url().to_string(),
+                    health: backend.health_stats(),
+                })
+                .collect(),
+        })
+        .collect();
+
+    let healthy = routes.iter().all(|route| route.healthy);
+    let status = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(AggregateHealth { healthy, routes }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{CacheKey, CACHE_TAGS_HEADER};
+    use crate::circuit_breaker::CircuitBreakerConfig;
+    use crate::config::{ApiKeyEntry, GatewayConfig};
+    use axum::body::Body;
+    use axum::http::{HeaderMap, HeaderValue};
+    use bytes::Bytes;
+    use http_body_util::BodyExt;
+    use std::collections::HashMap;
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    const ADMIN_KEY: &str = "admin-secret";
+
+    async fn admin_app(circuit_breaker: Option<CircuitBreakerService>) -> (AxumRouter, ProxyState) {
+        let config = GatewayConfig::from_yaml(
+            r#"
+server:
+  host: "127.0.0.1"
+  port: 8080
+routes:
+  - path: "/api/users"
+    backends:
+      - url: "http://localhost:3001"
+        weight: 2
+      - url: "http://localhost:3002"
+    methods: ["GET"]
+    description: "User service"
+    cache:
+      enabled: true
+  - path: "/api/orders/:id"
+    backend: "http://localhost:3003"
+    strip_prefix: true
+    circuit_breaker:
+      failure_threshold: 1
+"#,
+        )
+        .unwrap();
+        let proxy = ProxyState::new(
+            Router::new(config.routes).unwrap(),
+            Duration::from_secs(5),
+            None,
+            circuit_breaker,
+            None,
+        );
+        let api_keys = ApiKeyValidator::new(&ApiKeyConfig {
+            header: "X-API-Key".to_string(),
+            keys: HashMap::from([(
+                ADMIN_KEY.to_string(),
+                ApiKeyEntry::Description("admin".to_string()),
+            )]),
+            redis: None,
+            hashed: false,
+        })
+        .await
+        .unwrap();
+
+        (admin_router(proxy.clone(), api_keys), proxy)
+    }
+
+    async fn call(app: AxumRouter, method: &str, uri: &str) -> (StatusCode, serde_json::Value) {
+        send(app, method, uri, Body::empty()).await
+    }
+
+    async fn call_json(
+        app: AxumRouter,
+        method: &str,
+        uri: &str,
+        body: serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        send(app, method, uri, Body::from(body.to_string())).await
+    }
+
+    async fn send(
+        app: AxumRouter,
+        method: &str,
+        uri: &str,
+        body: Body,
+    ) -> (StatusCode, serde_json::Value) {
+        let request = http::Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("X-API-Key", ADMIN_KEY)
+            .header("Content-Type", "application/json")
+            .body(body)
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_requires_api_key() {
+        let (app, _) = admin_app(None).await;
+
+        let missing = http::Request::builder()
+            .uri("/admin/routes")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(missing).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let wrong = http::Request::builder()
+            .uri("/admin/routes")
+            .header("X-API-Key", "wrong")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(wrong).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_routes_endpoint() {
+        let (app, _) = admin_app(None).await;
+        let (status, body) = call(app, "GET", "/admin/routes").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let routes = body.as_array().unwrap();
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0]["path"], "/api/users");
+        assert_eq!(routes[0]["description"], "User service");
+        assert_eq!(routes[0]["methods"], serde_json::json!(["GET"]));
+        assert_eq!(
+            routes[0]["backends"],
+            serde_json::json!(["http://localhost:3001", "http://localhost:3002"])
+        );
+        assert_eq!(routes[0]["cache"], true);
+        assert_eq!(routes[0]["enabled"], true);
+        assert_eq!(routes[1]["path"], "/api/orders/:id");
+        assert_eq!(routes[1]["strip_prefix"], true);
+        assert_eq!(routes[1]["circuit_breaker"], true);
+    }
+
+    #[tokio::test]
+    async fn test_backends_endpoint() {
+        let (app, proxy) = admin_app(None).await;
+        let router = proxy.router.load_full();
+        let (_, route) = router.entries().next().unwrap();
+        route.load_balancer.backends()[1].mark_unhealthy();
+
+        let (status, body) = call(app, "GET", "/admin/backends").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let backends = body.as_array().unwrap();
+        assert_eq!(backends.len(), 3);
+        assert_eq!(backends[0]["route"], "/api/users");
+        assert_eq!(backends[0]["url"], "http://localhost:3001");
+        assert_eq!(backends[0]["weight"], 2);
+        assert_eq!(backends[0]["health"]["is_healthy"], true);
+        assert_eq!(backends[1]["health"]["is_healthy"], false);
+        assert_eq!(backends[2]["route"], "/api/orders/:id");
+    }
+
+    #[tokio::test]
+    async fn test_add_backend_endpoint() {
+        let (app, proxy) = admin_app(None).await;
+
+        let request = serde_json::json!({
+            "route": "/api/orders/:id",
+            "url": "http://localhost:3004",
+            "weight": 3
+        });
+        let (status, body) = call_json(app.clone(), "POST", "/admin/backends", request).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["url"], "http://localhost:3004");
+        assert_eq!(body["weight"], 3);
+
+        let router = proxy.router.load_full();
+        let (_, route) = router.entries().nth(1).unwrap();
+        assert_eq!(route.load_balancer.total_count(), 2);
+
+        // Registering the same backend twice is rejected
+        let request =
+            serde_json::json!({"route": "/api/orders/:id", "url": "http://localhost:3004"});
+        let (status, _) = call_json(app.clone(), "POST", "/admin/backends", request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let request = serde_json::json!({"route": "/api/orders/:id", "url": "localhost:3005"});
+        let (status, _) = call_json(app.clone(), "POST", "/admin/backends", request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let request = serde_json::json!({
+            "route": "/api/orders/:id",
+            "url": "http://localhost:3005",
+            "weight": 0
+        });
+        let (status, _) = call_json(app.clone(), "POST", "/admin/backends", request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let request = serde_json::json!({"route": "/missing", "url": "http://localhost:3005"});
+        let (status, _) = call_json(app, "POST", "/admin/backends", request).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_remove_backend_endpoint() {
+        let (app, proxy) = admin_app(None).await;
+        let router = proxy.router.load_full();
+        let (_, route) = router.entries().next().unwrap();
+        let in_flight = route.load_balancer.backends()[0].track_connection();
+
+        let uri = "/admin/backends?route=/api/users&url=http://localhost:3001";
+        let (status, body) = call(app.clone(), "DELETE", uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["url"], "http://localhost:3001");
+        assert_eq!(body["health"]["active_connections"], 1);
+        assert_eq!(route.load_balancer.total_count(), 1);
+        for _ in 0..5 {
+            let backend = route.load_balancer.select_backend(None).unwrap();
+            assert_eq!(backend.url(), "http://localhost:3002");
+        }
+        drop(in_flight);
+
+        let (status, _) = call(app, "DELETE", uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breakers_endpoint() {
+        let global = CircuitBreakerService::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            ..Default::default()
+        });
+        global.record_failure("http://localhost:3001").await;
+        let (app, proxy) = admin_app(Some(global)).await;
+
+        let router = proxy.router.load_full();
+        let (_, route) = router.entries().nth(1).unwrap();
+        route
+            .circuit_breaker
+            .as_ref()
+            .unwrap()
+            .record_success("http://localhost:3003")
+            .await;
+
+        let (status, body) = call(app, "GET", "/admin/circuit-breakers").await;
+
+        assert_eq!(status, StatusCode::OK);
+        let breakers = body.as_array().unwrap();
+        assert_eq!(breakers.len(), 2);
+        assert_eq!(breakers[0]["route"], serde_json::Value::Null);
+        assert_eq!(breakers[0]["backend"], "http://localhost:3001");
+        assert_eq!(breakers[0]["state"], "Open");
+        assert_eq!(breakers[0]["forced"], false);
+        assert_eq!(breakers[0]["metrics"]["failed_requests"], 1);
+        assert_eq!(breakers[1]["route"], "/api/orders/:id");
+        assert_eq!(breakers[1]["state"], "Closed");
+        assert_eq!(breakers[1]["metrics"]["successful_requests"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_force_circuit_breaker_endpoint() {
+        let global = CircuitBreakerService::new(CircuitBreakerConfig::default());
+        let (app, proxy) = admin_app(Some(global)).await;
+        let backend = "/admin/circuit-breakers/http%3A%2F%2Flocalhost%3A3001";
+
+        let (status, body) = call(app.clone(), "POST", &format!("{}/open", backend)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["backend"], "http://localhost:3001");
+        assert_eq!(body["state"], "Open");
+        assert_eq!(body["forced"], true);
+
+        let service = proxy.circuit_breaker.load_full().unwrap();
+        service.record_success("http://localhost:3001").await;
+        assert!(!service.can_proceed("http://localhost:3001").await);
+
+        let (_, body) = call(app.clone(), "POST", &format!("{}/close", backend)).await;
+        assert_eq!(body["state"], "Closed");
+        assert_eq!(body["forced"], true);
+
+        let (_, body) = call(app.clone(), "POST", &format!("{}/clear", backend)).await;
+        assert_eq!(body["state"], "Closed");
+        assert_eq!(body["forced"], false);
+
+        let (status, _) = call(app, "POST", &format!("{}/explode", backend)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_force_route_circuit_breaker() {
+        let (app, proxy) = admin_app(None).await;
+
+        let uri =
+            "/admin/circuit-breakers/http%3A%2F%2Flocalhost%3A3003/open?route=/api/orders/:id";
+        let (status, body) = call(app.clone(), "POST", uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["route"], "/api/orders/:id");
+        assert_eq!(body["state"], "Open");
+
+        let router = proxy.router.load_full();
+        let (_, route) = router.entries().nth(1).unwrap();
+        let service = route.circuit_breaker.as_ref().unwrap();
+        assert!(!service.can_proceed("http://localhost:3003").await);
+
+        // No global breaker, and the users route has none of its own
+        let (status, _) = call(app.clone(), "POST", "/admin/circuit-breakers/x/open").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let uri = "/admin/circuit-breakers/x/open?route=/api/users";
+        let (status, _) = call(app.clone(), "POST", uri).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let uri = "/admin/circuit-breakers/x/open?route=/missing";
+        let (status, _) = call(app, "POST", uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_endpoint() {
+        let (app, proxy) = admin_app(None).await;
+        proxy.set_maintenance(MaintenanceConfig {
+            allow_paths: vec!["/api/status".to_string()],
+            ..Default::default()
+        });
+
+        let (status, body) = call(app.clone(), "GET", "/admin/maintenance").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["enabled"], false);
+
+        let (status, body) = call(app.clone(), "POST", "/admin/maintenance/enable").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["enabled"], true);
+        assert_eq!(body["allow_paths"], serde_json::json!(["/api/status"]));
+        assert!(proxy.maintenance.load().applies_to("/api/users"));
+        assert!(!proxy.maintenance.load().applies_to("/api/status"));
+
+        let (_, body) = call(app.clone(), "POST", "/admin/maintenance/disable").await;
+        assert_eq!(body["enabled"], false);
+        assert!(!proxy.maintenance.load().applies_to("/api/users"));
+
+        let (status, _) = call(app, "POST", "/admin/maintenance/explode").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_cache_stats_endpoint() {
+        let (app, proxy) = admin_app(None).await;
+        let router = proxy.router.load_full();
+        let cache = router
+            .find_route("/api/users")
+            .unwrap()
+            .cache
+            .clone()
+            .unwrap();
+
+        let (status, body) = call(app.clone(), "GET", "/admin/cache/stats").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            serde_json::json!([{"route": "/api/users", "entry_count": 0, "weighted_size": 0}])
+        );
+
+        for user in ["alice", "bob"] {
+            let key = CacheKey::new(
+                "GET".to_string(),
+                format!("/api/users/{user}"),
+                None,
+                &HeaderMap::new(),
+                &[],
+            );
+            cache
+                .put(
+                    key,
+                    &HeaderMap::new(),
+                    StatusCode::OK,
+                    HeaderMap::new(),
+                    Bytes::from(user),
+                )
+                .await
+                .unwrap();
+        }
+
+        let (_, body) = call(app, "GET", "/admin/cache/stats").await;
+        assert_eq!(body[0]["entry_count"], 2);
+        assert_eq!(body[0]["weighted_size"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_cache_stats_omit_redis_backed_routes() {
+        let (app, proxy) = admin_app(None).await;
+        let config = GatewayConfig::from_yaml(
+            r#"
+server: {}
+routes:
+  - path: "/api/users"
+    backend: "http://localhost:3001"
+    cache:
+      enabled: true
+  - path: "/api/products"
+    backend: "http://localhost:3002"
+    cache:
+      enabled: true
+      backend: redis
+      redis:
+        url: "redis://127.0.0.1:1"
+"#,
+        )
+        .unwrap();
+        proxy.set_router(Router::new(config.routes).unwrap());
+
+        let (_, body) = call(app, "GET", "/admin/cache/stats").await;
+        assert_eq!(
+            body,
+            serde_json::json!([{"route": "/api/users", "entry_count": 0, "weighted_size": 0}])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clear_cache_endpoint() {
+        let (app, _) = admin_app(None).await;
+
+        let (status, body) = call(app.clone(), "POST", "/admin/cache/clear").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["cleared_routes"], 1);
+
+        let request = http::Request::builder()
+            .uri("/admin/cache/clear")
+            .header("X-API-Key", ADMIN_KEY)
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[tokio::test]
+    async fn test_purge_cache_endpoint() {
+        let (app, proxy) = admin_app(None).await;
+        let router = proxy.router.load_full();
+        let cache = router
+            .find_route("/api/users")
+            .unwrap()
+            .cache
+            .clone()
+            .unwrap();
+
+        let store = |path: &'static str, tags: &'static str| {
+            let cache = cache.clone();
+            async move {
+                let key = CacheKey::new(
+                    "GET".to_string(),
+                    path.to_string(),
+                    None,
+                    &HeaderMap::new(),
+                    &[],
+                );
+                let mut headers = HeaderMap::new();
+                headers.insert(CACHE_TAGS_HEADER, HeaderValue::from_static(tags));
+                cache
+                    .put(
+                        key.clone(),
+                        &HeaderMap::new(),
+                        StatusCode::OK,
+                        headers,
+                        Bytes::from(path),
+                    )
+                    .await
+                    .unwrap();
+                key
+            }
+        };
+        let alice = store("/api/users/alice", "user-alice, users").await;
+        let bob = store("/api/users/bob", "user-bob, users").await;
+        let settings = store("/api/usersettings", "settings").await;
+        let cached = |key: CacheKey| {
+            let cache = cache.clone();
+            async move { cache.get(&key, &HeaderMap::new()).await.is_some() }
+        };
+
+        let uri = "/admin/cache/purge";
+        let (status, body) = call_json(
+            app.clone(),
+            "POST",
+            uri,
+            serde_json::json!({"tag": "user-bob"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["purged"], 1);
+        assert!(cached(alice.clone()).await);
+        assert!(!cached(bob.clone()).await);
+
+        let (status, body) = call_json(
+            app.clone(),
+            "POST",
+            uri,
+            serde_json::json!({"prefix": "/api/users/"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["purged"], 1);
+        assert!(!cached(alice).await);
+        assert!(cached(settings).await);
+
+        let (status, _) = call_json(app.clone(), "POST", uri, serde_json::json!({})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let both = serde_json::json!({"prefix": "/api", "tag": "users"});
+        let (status, _) = call_json(app, "POST", uri, both).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_validate_requires_api_key() {
+        let config: AdminConfig = serde_yaml::from_str("api_key: {}").unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.port, 9091);
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("admin.api_key"));
+    }
+
+    fn health_app() -> (axum::Router, ProxyState) {
+        let config = crate::config::GatewayConfig::from_yaml(
+            r#"
+server: {}
+routes:
+  - path: "/api/users"
+    backends:
+      - url: "http://localhost:3001"
+      - url: "http://localhost:3002"
+  - path: "/api/orders"
+    backend: "http://localhost:3003"
+    auth:
+      required: true
+"#,
+        )
+        .unwrap();
+        let proxy = ProxyState::new(
+            crate::router::Router::new(config.routes).unwrap(),
+            Duration::from_secs(5),
+            None,
+            None,
+            None,
+        );
+        let app = axum::Router::new()
+            .route("/healthz", axum::routing::get(aggregate_health_handler))
+            .with_state(proxy.clone());
+        (app, proxy)
+    }
+
+    async fn get_health(app: axum::Router) -> (StatusCode, serde_json::Value) {
+        let request = http::Request::builder()
+            .uri("/healthz")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_health_all_healthy() {
+        let (app, _) = health_app();
+        let (status, body) = get_health(app).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["healthy"], true);
+        assert_eq!(body["routes"].as_array().unwrap().len(), 2);
+        assert_eq!(body["routes"][0]["path"], "/api/users");
+        assert_eq!(
+            body["routes"][0]["backends"][1]["url"],
+            "http://localhost:3002"
+        );
+        assert_eq!(
+            body["routes"][0]["backends"][1]["health"]["is_healthy"],
+            true
+        );
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_health_partially_unhealthy() {
+        let (app, proxy) = health_app();
+        let router = proxy.router.load_full();
+
+        // One of two backends down keeps the route healthy
+        let users = router.find_route("/api/users").unwrap();
+        users.load_balancer.backends()[0].mark_unhealthy();
+        let (status, body) = get_health(app.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["routes"][0]["healthy"], true);
+        assert_eq!(
+            body["routes"][0]["backends"][0]["health"]["is_healthy"],
+            false
+        );
+
+        // A route without any healthy backend fails the check
+        let orders = router.find_route("/api/orders").unwrap();
+        orders.load_balancer.backends()[0].mark_unhealthy();
+        let (status, body) = get_health(app).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["healthy"], false);
+        assert_eq!(body["routes"][1]["healthy"], false);
+    }
+}
@@ -1,17 +1,23 @@
-use crate::config::ApiKeyConfig;
+use crate::config::{ApiKeyConfig, RedisConfig};
 use crate::error::{GatewayError, Result};
 use axum::http::HeaderMap;
 use redis::{aio::ConnectionManager, AsyncCommands};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::RwLock;
+use tracing::warn;
 
 use super::{AuthMethodType, AuthResult};
+use crate::rate_limit::RateLimitOverride;
+
+pub mod hashing;
 
 /// API key validator with in-memory and Redis support
 #[derive(Clone)]
 pub struct ApiKeyValidator {
     header_name: String,
+    /// In-memory keys indexed by their hex SHA-256 digest
     in_memory_keys: Arc<RwLock<HashMap<String, ApiKeyInfo>>>,
     redis_client: Option<Arc<RedisKeyStore>>,
 }
@@ -21,41 +27,83 @@ struct ApiKeyInfo {
     #[allow(dead_code)]
     description: String,
     metadata: HashMap<String, serde_json::Value>,
+    rate_limit: Option<RateLimitOverride>,
+    /// When the key stops being accepted (never if unset)
+    expires_at: Option<SystemTime>,
+}
+
+impl ApiKeyInfo {
+    fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| SystemTime::now() >= expires_at)
+    }
+
+    fn remaining_ttl(&self) -> Option<Duration> {
+        self.expires_at.map(|expires_at| {
+            expires_at
+                .duration_since(SystemTime::now())
+                .unwrap_or(Duration::ZERO)
+        })
+    }
 }
 
 /// Redis-backed key store
 pub struct RedisKeyStore {
     connection: Arc<RwLock<ConnectionManager>>,
+    /// Optional replica used for lookups, falling back to the primary on error
+    replica: Option<Arc<RwLock<ConnectionManager>>>,
     prefix: String,
 }
 
 impl ApiKeyValidator {
     /// Create a new API key validator from configuration
     pub async fn new(config: &ApiKeyConfig) -> Result<Self> {
-        // Load in-memory keys
+        // Load in-memory keys, hashing plaintext keys so lookups are by digest
         let mut in_memory_keys = HashMap::new();
-        for (key, description) in &config.keys {
+        for (key, entry) in &config.keys {
+            let digest = if config.hashed {
+                hashing::normalize_digest(key)?
+            } else {
+                hashing::hash_key(key)
+            };
+            if let Some(rate_limit) = entry.rate_limit() {
+                rate_limit.validate().map_err(|e| {
+                    GatewayError::Config(format!(
+                        "Invalid rate limit for API key '{}': {}",
+                        entry.description(),
+                        e
+                    ))
+                })?;
+            }
             in_memory_keys.insert(
-                key.clone(),
+                digest,
                 ApiKeyInfo {
-                    description: description.clone(),
+                    description: entry.description().to_string(),
                     metadata: HashMap::new(),
+                    rate_limit: entry.rate_limit().cloned(),
+                    expires_at: entry
+                        .expires_at()
+                        .map(|secs| UNIX_EPOCH + Duration::from_secs(secs)),
                 },
             );
         }
 
         // Initialize Redis client if configured
         let redis_client = if let Some(redis_config) = &config.redis {
-            let client = redis::Client::open(redis_config.url.as_str()).map_err(|e| {
-                GatewayError::Config(format!("Failed to create Redis client: {}", e))
-            })?;
+            let (client, replica_client) = RedisKeyStore::clients(redis_config)?;
 
             let connection = ConnectionManager::new(client)
                 .await
                 .map_err(|e| GatewayError::Config(format!("Failed to connect to Redis: {}", e)))?;
 
+            let replica = match replica_client {
+                Some(client) => RedisKeyStore::connect_replica(client).await,
+                None => None,
+            };
+
             Some(Arc::new(RedisKeyStore {
                 connection: Arc::new(RwLock::new(connection)),
+                replica: replica.map(|conn| Arc::new(RwLock::new(conn))),
                 prefix: redis_config.prefix.clone(),
             }))
         } else {
@@ -75,11 +123,15 @@ impl ApiKeyValidator {
 
         // Check in-memory keys first
         let in_memory = self.in_memory_keys.read().await;
-        if let Some(key_info) = in_memory.get(&api_key) {
+        if let Some(key_info) = Self::find_by_digest(&in_memory, &hashing::hash_key(&api_key)) {
+            if key_info.is_expired() {
+                return Err(GatewayError::InvalidApiKey);
+            }
             return Ok(AuthResult {
                 user_id: api_key.clone(),
                 method: AuthMethodType::ApiKey,
                 metadata: key_info.metadata.clone(),
+                rate_limit: key_info.rate_limit.clone(),
             });
         }
         drop(in_memory);
@@ -87,10 +139,12 @@ impl ApiKeyValidator {
         // Check Redis if configured
         if let Some(redis_store) = &self.redis_client {
             if let Some(key_info) = redis_store.get_key(&api_key).await? {
+                let rate_limit = Self::rate_limit_from_metadata(&key_info);
                 return Ok(AuthResult {
                     user_id: api_key,
                     method: AuthMethodType::ApiKey,
                     metadata: key_info,
+                    rate_limit,
                 });
             }
         }
@@ -98,6 +152,35 @@ impl ApiKeyValidator {
         Err(GatewayError::InvalidApiKey)
     }
 
+    /// Find a key by digest using constant-time comparison
+    fn find_by_digest<'a>(
+        keys: &'a HashMap<String, ApiKeyInfo>,
+        digest: &str,
+    ) -> Option<&'a ApiKeyInfo> {
+        keys.iter()
+            .find(|(stored, _)| hashing::constant_time_eq(stored.as_bytes(), digest.as_bytes()))
+            .map(|(_, info)| info)
+    }
+
+    /// Read a rate limit override from Redis key metadata (`rate_limit` field)
+    ///
+    /// Unusable overrides (e.g. zero requests) are ignored, so the key falls
+    /// back to the route limit.
+    fn rate_limit_from_metadata(
+        metadata: &HashMap<String, serde_json::Value>,
+    ) -> Option<RateLimitOverride> {
+        let rate_limit: RateLimitOverride = metadata
+            .get("rate_limit")
+            .and_then(|value| serde_json::from_value(value.clone()).ok())?;
+        match rate_limit.validate() {
+            Ok(()) => Some(rate_limit),
+            Err(e) => {
+                warn!(error = %e, "Ignoring invalid API key rate limit override");
+                None
+            }
+        }
+    }
+
     /// Extract API key from request headers
     fn extract_api_key(&self, headers: &HeaderMap) -> Result<String> {
         let header_value = headers
@@ -118,12 +201,24 @@ impl ApiKeyValidator {
 
     /// Add a new API key (in-memory)
     pub async fn add_key(&self, key: String, description: String) {
+        self.insert_key(&key, description, None).await;
+    }
+
+    /// Add a new API key (in-memory) that expires after `ttl`
+    pub async fn add_key_with_ttl(&self, key: String, description: String, ttl: Duration) {
+        self.insert_key(&key, description, Some(SystemTime::now() + ttl))
+            .await;
+    }
+
+    async fn insert_key(&self, key: &str, description: String, expires_at: Option<SystemTime>) {
         let mut keys = self.in_memory_keys.write().await;
         keys.insert(
-            key,
+            hashing::hash_key(key),
             ApiKeyInfo {
                 description,
                 metadata: HashMap::new(),
+                rate_limit: None,
+                expires_at,
             },
         );
     }
@@ -131,15 +226,15 @@ impl ApiKeyValidator {
     /// Remove an API key (in-memory)
     pub async fn remove_key(&self, key: &str) -> bool {
         let mut keys = self.in_memory_keys.write().await;
-        keys.remove(key).is_some()
+        keys.remove(&hashing::hash_key(key)).is_some()
     }
 
     /// Check if a key exists (in-memory or Redis)
     pub async fn key_exists(&self, key: &str) -> Result<bool> {
         // Check in-memory first
         let in_memory = self.in_memory_keys.read().await;
-        if in_memory.contains_key(key) {
-            return Ok(true);
+        if let Some(key_info) = Self::find_by_digest(&in_memory, &hashing::hash_key(key)) {
+            return Ok(!key_info.is_expired());
         }
         drop(in_memory);
 
@@ -150,28 +245,84 @@ impl ApiKeyValidator {
 
         Ok(false)
     }
+
+    /// Remaining lifetime of a key (in-memory expiry or Redis TTL)
+    ///
+    /// Returns `None` if the key does not exist or never expires.
+    pub async fn key_ttl(&self, key: &str) -> Result<Option<Duration>> {
+        let in_memory = self.in_memory_keys.read().await;
+        if let Some(key_info) = Self::find_by_digest(&in_memory, &hashing::hash_key(key)) {
+            return Ok(key_info.remaining_ttl());
+        }
+        drop(in_memory);
+
+        if let Some(redis_store) = &self.redis_client {
+            return redis_store.key_ttl(key).await;
+        }
+
+        Ok(None)
+    }
 }
 
 impl RedisKeyStore {
+    /// Build the primary and (if distinct) replica clients for a Redis config
+    fn clients(config: &RedisConfig) -> Result<(redis::Client, Option<redis::Client>)> {
+        let open = |url: &str| {
+            redis::Client::open(url)
+                .map_err(|e| GatewayError::Config(format!("Failed to create Redis client: {}", e)))
+        };
+
+        let primary = open(&config.url)?;
+        let replica = match &config.replica_url {
+            Some(url) if *url != config.url => Some(open(url)?),
+            _ => None,
+        };
+
+        Ok((primary, replica))
+    }
+
+    /// Connect to a replica, leaving lookups on the primary if it is unreachable
+    async fn connect_replica(client: redis::Client) -> Option<ConnectionManager> {
+        match ConnectionManager::new(client).await {
+            Ok(connection) => Some(connection),
+            Err(e) => {
+                warn!(
+                    "Redis replica unavailable: {}, reading API keys from primary",
+                    e
+                );
+                None
+            }
+        }
+    }
+
     /// Get API key information from Redis
     async fn get_key(&self, key: &str) -> Result<Option<HashMap<String, serde_json::Value>>> {
-        let mut conn = self.connection.write().await;
         let redis_key = format!("{}{}", self.prefix, key);
 
-        let exists: bool = conn
-            .exists(&redis_key)
+        if let Some(replica) = &self.replica {
+            match Self::fetch_key(&mut *replica.write().await, &redis_key).await {
+                Ok(metadata) => return Ok(metadata),
+                Err(e) => warn!("Redis replica read failed: {}, retrying on primary", e),
+            }
+        }
+
+        Self::fetch_key(&mut *self.connection.write().await, &redis_key)
             .await
-            .map_err(|e| GatewayError::Internal(format!("Redis error: {}", e)))?;
+            .map_err(|e| GatewayError::Internal(format!("Redis error: {}", e)))
+    }
+
+    async fn fetch_key(
+        conn: &mut ConnectionManager,
+        redis_key: &str,
+    ) -> redis::RedisResult<Option<HashMap<String, serde_json::Value>>> {
+        let exists: bool = conn.exists(redis_key).await?;
 
         if !exists {
             return Ok(None);
         }
 
         // Get key metadata (stored as JSON)
-        let metadata_json: Option<String> = conn
-            .get(&redis_key)
-            .await
-            .map_err(|e| GatewayError::Internal(format!("Redis error: {}", e)))?;
+        let metadata_json: Option<String> = conn.get(redis_key).await?;
 
         if let Some(json) = metadata_json {
             let metadata: HashMap<String, serde_json::Value> =
@@ -184,14 +335,53 @@ impl RedisKeyStore {
 
     /// Check if a key exists in Redis
     async fn key_exists(&self, key: &str) -> Result<bool> {
-        let mut conn = self.connection.write().await;
         let redis_key = format!("{}{}", self.prefix, key);
 
-        conn.exists(&redis_key)
+        if let Some(replica) = &self.replica {
+            match replica.write().await.exists(&redis_key).await {
+                Ok(exists) => return Ok(exists),
+                Err(e) => warn!("Redis replica read failed: {}, retrying on primary", e),
+            }
+        }
+
+        self.connection
+            .write()
+            .await
+            .exists(&redis_key)
             .await
             .map_err(|e| GatewayError::Internal(format!("Redis error: {}", e)))
     }
 
+    /// Get the remaining TTL of a key in Redis
+    async fn key_ttl(&self, key: &str) -> Result<Option<Duration>> {
+        let redis_key = format!("{}{}", self.prefix, key);
+
+        // TTL returns -2 for missing keys and -1 for keys without expiry
+        let replica_ttl = match &self.replica {
+            Some(replica) => match replica.write().await.ttl::<_, i64>(&redis_key).await {
+                Ok(ttl) => Some(ttl),
+                Err(e) => {
+                    warn!("Redis replica read failed: {}, retrying on primary", e);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let ttl = match replica_ttl {
+            Some(ttl) => ttl,
+            None => self
+                .connection
+                .write()
+                .await
+                .ttl(&redis_key)
+                .await
+                .map_err(|e| GatewayError::Internal(format!("Redis error: {}", e)))?,
+        };
+
+        Ok(u64::try_from(ttl).ok().map(Duration::from_secs))
+    }
+
     /// Store an API key in Redis
     #[allow(dead_code)]
     async fn set_key(
@@ -239,17 +429,21 @@ impl RedisKeyStore {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::ApiKeyConfig;
+    use crate::config::{ApiKeyConfig, ApiKeyDefinition, ApiKeyEntry};
 
     #[tokio::test]
     async fn test_in_memory_api_key_validation() {
         let mut keys = HashMap::new();
-        keys.insert("test-key-123".to_string(), "Test API key".to_string());
+        keys.insert(
+            "test-key-123".to_string(),
+            "Test API key".to_string().into(),
+        );
 
         let config = ApiKeyConfig {
             header: "X-API-Key".to_string(),
             keys,
             redis: None,
+            hashed: false,
         };
 
         let validator = ApiKeyValidator::new(&config).await.unwrap();
@@ -268,6 +462,7 @@ mod tests {
             header: "X-API-Key".to_string(),
             keys: HashMap::new(),
             redis: None,
+            hashed: false,
         };
 
         let validator = ApiKeyValidator::new(&config).await.unwrap();
@@ -286,6 +481,7 @@ mod tests {
             header: "X-API-Key".to_string(),
             keys: HashMap::new(),
             redis: None,
+            hashed: false,
         };
 
         let validator = ApiKeyValidator::new(&config).await.unwrap();
@@ -305,6 +501,7 @@ mod tests {
             header: "X-API-Key".to_string(),
             keys: HashMap::new(),
             redis: None,
+            hashed: false,
         };
 
         let validator = ApiKeyValidator::new(&config).await.unwrap();
@@ -327,15 +524,68 @@ mod tests {
         assert!(!exists);
     }
 
+    #[tokio::test]
+    async fn test_hashed_api_key_validation() {
+        let mut keys = HashMap::new();
+        keys.insert(
+            hashing::hash_key("test-key-123").to_uppercase(),
+            "Hashed test key".to_string().into(),
+        );
+
+        let config = ApiKeyConfig {
+            header: "X-API-Key".to_string(),
+            keys,
+            redis: None,
+            hashed: true,
+        };
+
+        let validator = ApiKeyValidator::new(&config).await.unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-API-Key", "test-key-123".parse().unwrap());
+        let result = validator.validate(&headers).await;
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().user_id, "test-key-123");
+
+        // The digest itself is not a valid key
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "X-API-Key",
+            hashing::hash_key("test-key-123").parse().unwrap(),
+        );
+        let result = validator.validate(&headers).await;
+        assert!(matches!(result.unwrap_err(), GatewayError::InvalidApiKey));
+    }
+
+    #[tokio::test]
+    async fn test_hashed_api_key_invalid_digest() {
+        let mut keys = HashMap::new();
+        keys.insert(
+            "test-key-123".to_string(),
+            "Not a digest".to_string().into(),
+        );
+
+        let config = ApiKeyConfig {
+            header: "X-API-Key".to_string(),
+            keys,
+            redis: None,
+            hashed: true,
+        };
+
+        let result = ApiKeyValidator::new(&config).await;
+        assert!(matches!(result, Err(GatewayError::Config(_))));
+    }
+
     #[tokio::test]
     async fn test_custom_header_name() {
         let mut keys = HashMap::new();
-        keys.insert("test-key".to_string(), "Test".to_string());
+        keys.insert("test-key".to_string(), "Test".to_string().into());
 
         let config = ApiKeyConfig {
             header: "X-Custom-API-Key".to_string(),
             keys,
             redis: None,
+            hashed: false,
         };
 
         let validator = ApiKeyValidator::new(&config).await.unwrap();
@@ -346,4 +596,227 @@ mod tests {
         let result = validator.validate(&headers).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_api_key_rate_limit_override() {
+        let mut keys = HashMap::new();
+        keys.insert(
+            "pro-key".to_string(),
+            ApiKeyEntry::Detailed(ApiKeyDefinition {
+                description: "Pro tier".to_string(),
+                rate_limit: Some(RateLimitOverride {
+                    requests: 1000,
+                    window_secs: 60,
+                    burst: None,
+                }),
+                expires_at: None,
+            }),
+        );
+        keys.insert("free-key".to_string(), "Free tier".to_string().into());
+
+        let config = ApiKeyConfig {
+            header: "X-API-Key".to_string(),
+            keys,
+            redis: None,
+            hashed: false,
+        };
+
+        let validator = ApiKeyValidator::new(&config).await.unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-API-Key", "pro-key".parse().unwrap());
+        let result = validator.validate(&headers).await.unwrap();
+        assert_eq!(result.rate_limit.unwrap().requests, 1000);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-API-Key", "free-key".parse().unwrap());
+        let result = validator.validate(&headers).await.unwrap();
+        assert!(result.rate_limit.is_none());
+    }
+
+    #[test]
+    fn test_rate_limit_from_metadata() {
+        let metadata: HashMap<String, serde_json::Value> = serde_json::from_str(
+            r#"{"tier": "pro", "rate_limit": {"requests": 500, "window_secs": 60}}"#,
+        )
+        .unwrap();
+
+        let rate_limit = ApiKeyValidator::rate_limit_from_metadata(&metadata).unwrap();
+        assert_eq!(rate_limit.requests, 500);
+        assert_eq!(rate_limit.window_secs, 60);
+
+        assert!(ApiKeyValidator::rate_limit_from_metadata(&HashMap::new()).is_none());
+
+        for rate_limit in [
+            r#"{"requests": 0, "window_secs": 60}"#,
+            r#"{"requests": 10, "window_secs": 0}"#,
+            r#"{"requests": 10, "window_secs": 60, "burst": 0}"#,
+        ] {
+            let metadata: HashMap<String, serde_json::Value> =
+                serde_json::from_str(&format!(r#"{{"rate_limit": {}}}"#, rate_limit)).unwrap();
+            assert!(
+                ApiKeyValidator::rate_limit_from_metadata(&metadata).is_none(),
+                "{rate_limit}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_zero_rate_limit_override_rejected() {
+        let keys = HashMap::from([(
+            "broken-key".to_string(),
+            ApiKeyEntry::Detailed(ApiKeyDefinition {
+                description: "Broken tier".to_string(),
+                rate_limit: Some(RateLimitOverride {
+                    requests: 10,
+                    window_secs: 60,
+                    burst: Some(0),
+                }),
+                expires_at: None,
+            }),
+        )]);
+        let config = ApiKeyConfig {
+            header: "X-API-Key".to_string(),
+            keys,
+            redis: None,
+            hashed: false,
+        };
+
+        let err = ApiKeyValidator::new(&config).await.err().unwrap();
+        assert!(err.to_string().contains("Broken tier"), "{err}");
+        assert!(err.to_string().contains("burst"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_add_key_with_ttl_expires() {
+        let config = ApiKeyConfig {
+            header: "X-API-Key".to_string(),
+            keys: HashMap::new(),
+            redis: None,
+            hashed: false,
+        };
+
+        let validator = ApiKeyValidator::new(&config).await.unwrap();
+        validator
+            .add_key_with_ttl(
+                "rotating-key".to_string(),
+                "Rotating key".to_string(),
+                Duration::from_millis(100),
+            )
+            .await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-API-Key", "rotating-key".parse().unwrap());
+
+        // Valid before expiry
+        assert!(validator.validate(&headers).await.is_ok());
+        let ttl = validator.key_ttl("rotating-key").await.unwrap().unwrap();
+        assert!(ttl <= Duration::from_millis(100));
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+
+        // Rejected after expiry
+        let result = validator.validate(&headers).await;
+        assert!(matches!(result.unwrap_err(), GatewayError::InvalidApiKey));
+        assert!(!validator.key_exists("rotating-key").await.unwrap());
+        assert_eq!(
+            validator.key_ttl("rotating-key").await.unwrap(),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_configured_key_expiry() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let mut keys = HashMap::new();
+        for (key, expires_at) in [("expired-key", now - 60), ("current-key", now + 3600)] {
+            keys.insert(
+                key.to_string(),
+                ApiKeyEntry::Detailed(ApiKeyDefinition {
+                    description: key.to_string(),
+                    rate_limit: None,
+                    expires_at: Some(expires_at),
+                }),
+            );
+        }
+
+        let config = ApiKeyConfig {
+            header: "X-API-Key".to_string(),
+            keys,
+            redis: None,
+            hashed: false,
+        };
+
+        let validator = ApiKeyValidator::new(&config).await.unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-API-Key", "current-key".parse().unwrap());
+        assert!(validator.validate(&headers).await.is_ok());
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-API-Key", "expired-key".parse().unwrap());
+        let result = validator.validate(&headers).await;
+        assert!(matches!(result.unwrap_err(), GatewayError::InvalidApiKey));
+
+        // Keys without expiry report no TTL
+        validator
+            .add_key("permanent-key".to_string(), "Permanent".to_string())
+            .await;
+        assert_eq!(validator.key_ttl("permanent-key").await.unwrap(), None);
+    }
+
+    fn redis_config(url: &str, replica_url: Option<&str>) -> RedisConfig {
+        RedisConfig {
+            url: url.to_string(),
+            prefix: "gateway:apikey:".to_string(),
+            replica_url: replica_url.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_distinct_replica_url_creates_replica_client() {
+        let config = redis_config("redis://primary:6379", Some("redis://replica:6380"));
+
+        let (primary, replica) = RedisKeyStore::clients(&config).unwrap();
+        let replica = replica.expect("replica client");
+
+        assert_eq!(
+            primary.get_connection_info().addr.to_string(),
+            "primary:6379"
+        );
+        assert_eq!(
+            replica.get_connection_info().addr.to_string(),
+            "replica:6380"
+        );
+    }
+
+    #[test]
+    fn test_same_replica_url_uses_primary_only() {
+        let config = redis_config("redis://primary:6379", Some("redis://primary:6379"));
+        let (_, replica) = RedisKeyStore::clients(&config).unwrap();
+        assert!(replica.is_none());
+
+        let config = redis_config("redis://primary:6379", None);
+        let (_, replica) = RedisKeyStore::clients(&config).unwrap();
+        assert!(replica.is_none());
+    }
+
+    #[test]
+    fn test_invalid_replica_url_is_config_error() {
+        let config = redis_config("redis://primary:6379", Some("not a url"));
+
+        let result = RedisKeyStore::clients(&config);
+        assert!(matches!(result, Err(GatewayError::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_replica_falls_back_to_primary() {
+        let client = redis::Client::open("redis://127.0.0.1:1").unwrap();
+
+        // Lookups stay on the primary rather than failing startup
+        assert!(RedisKeyStore::connect_replica(client).await.is_none());
+    }
 }
@@ -0,0 +1,65 @@
+use crate::error::{GatewayError, Result};
+use sha2::{Digest, Sha256};
+
+/// Length of a hex-encoded SHA-256 digest
+const DIGEST_HEX_LEN: usize = 64;
+
+/// Hash an API key into its lowercase hex SHA-256 digest
+pub fn hash_key(key: &str) -> String {
+    Sha256::digest(key.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Validate and normalize a pre-hashed key from configuration
+pub fn normalize_digest(digest: &str) -> Result<String> {
+    let digest = digest.trim();
+    if digest.len() != DIGEST_HEX_LEN || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(GatewayError::Config(format!(
+            "Hashed API key must be a {}-character hex SHA-256 digest",
+            DIGEST_HEX_LEN
+        )));
+    }
+    Ok(digest.to_ascii_lowercase())
+}
+
+/// Compare two byte strings in constant time (for equal lengths)
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter()
+        .zip(b.iter())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_key() {
+        assert_eq!(
+            hash_key("test-key-123"),
+            "625faa3fbbc3d2bd9d6ee7678d04cc5339cb33dc68d9b58451853d60046e226a"
+        );
+        assert_eq!(hash_key("a").len(), DIGEST_HEX_LEN);
+    }
+
+    #[test]
+    fn test_normalize_digest() {
+        let digest = hash_key("test-key-123");
+        assert_eq!(normalize_digest(&digest.to_uppercase()).unwrap(), digest);
+        assert!(normalize_digest("not-a-digest").is_err());
+        assert!(normalize_digest(&"z".repeat(DIGEST_HEX_LEN)).is_err());
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"abc", b"abc"));
+        assert!(!constant_time_eq(b"abc", b"abd"));
+        assert!(!constant_time_eq(b"abc", b"abcd"));
+    }
+}
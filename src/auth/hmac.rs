@@ -0,0 +1,313 @@
+use crate::config::HmacConfig;
+use crate::error::{GatewayError, Result};
+use crate::secrets::{resolve_secret_ref, SecretsManager};
+use axum::http::{HeaderMap, HeaderName};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{AuthMethodType, AuthRequest, AuthResult};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// HMAC request signature validator
+pub struct HmacValidator {
+    /// Shared secrets by key ID
+    keys: HashMap<String, Vec<u8>>,
+    signature_header: HeaderName,
+    timestamp_header: HeaderName,
+    key_id_header: HeaderName,
+    max_skew_secs: u64,
+    max_body_bytes: usize,
+}
+
+impl HmacValidator {
+    /// Create a new HMAC validator from configuration
+    pub fn new(config: &HmacConfig) -> Result<Self> {
+        if config.keys.is_empty() {
+            return Err(GatewayError::Config(
+                "HMAC authentication requires at least one key".to_string(),
+            ));
+        }
+        if config.max_body_bytes == 0 {
+            return Err(GatewayError::Config(
+                "HMAC max_body_bytes must be greater than 0".to_string(),
+            ));
+        }
+
+        let header = |name: &str| {
+            HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| GatewayError::Config(format!("Invalid HMAC header name: {}", name)))
+        };
+
+        let secrets = SecretsManager::default();
+        let mut keys = HashMap::new();
+        for (key_id, secret) in &config.keys {
+            let secret = resolve_secret_ref(secret, &secrets)?;
+            if secret.is_empty() {
+                return Err(GatewayError::Config(format!(
+                    "HMAC key {} has an empty secret",
+                    key_id
+                )));
+            }
+            keys.insert(key_id.clone(), secret.into_bytes());
+        }
+
+        Ok(Self {
+            keys,
+            signature_header: header(&config.signature_header)?,
+            timestamp_header: header(&config.timestamp_header)?,
+            key_id_header: header(&config.key_id_header)?,
+            max_skew_secs: config.max_skew_secs,
+            max_body_bytes: config.max_body_bytes,
+        })
+    }
+
+    /// Largest body buffered for a signature check, in bytes
+    pub fn max_body_bytes(&self) -> usize {
+        self.max_body_bytes
+    }
+
+    /// Whether the request carries a signature, so its body must be buffered
+    pub fn is_signed(&self, headers: &HeaderMap) -> bool {
+        headers.contains_key(&self.signature_header)
+    }
+
+    /// Verify the signature of a request
+    pub fn validate(&self, request: &AuthRequest<'_>) -> Result<AuthResult> {
+        let header = |name: &HeaderName| {
+            request
+                .headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        };
+
+        let signature = header(&self.signature_header).ok_or(GatewayError::MissingCredentials)?;
+        let key_id = header(&self.key_id_header).ok_or_else(|| {
+            GatewayError::Unauthorized(format!("Missing {} header", self.key_id_header))
+        })?;
+        let timestamp = header(&self.timestamp_header).ok_or_else(|| {
+            GatewayError::Unauthorized(format!("Missing {} header", self.timestamp_header))
+        })?;
+
+        let secret = self.keys.get(key_id).ok_or_else(|| {
+            GatewayError::Unauthorized(format!("Unknown signing key: {}", key_id))
+        })?;
+
+        // Replay protection: only accept recently signed requests
+        let signed_at: u64 = timestamp.parse().map_err(|_| {
+            GatewayError::Unauthorized(format!("Invalid request timestamp: {}", timestamp))
+        })?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        if now.abs_diff(signed_at) > self.max_skew_secs {
+            return Err(GatewayError::Unauthorized(
+                "Request timestamp is outside the allowed window".to_string(),
+            ));
+        }
+
+        let body = request.body.ok_or_else(|| {
+            GatewayError::Internal("Request body not buffered for signature check".to_string())
+        })?;
+        let message = string_to_sign(
+            request.method.as_str(),
+            request.path_and_query,
+            timestamp,
+            body,
+        );
+        let mismatch = || GatewayError::Unauthorized("Request signature mismatch".to_string());
+        let signature = decode_hex(signature.trim()).ok_or_else(mismatch)?;
+        let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+        mac.update(&message);
+        // Constant-time comparison
+        mac.verify_slice(&signature).map_err(|_| mismatch())?;
+
+        Ok(AuthResult {
+            user_id: key_id.to_string(),
+            method: AuthMethodType::Hmac,
+            metadata: HashMap::new(),
+            rate_limit: None,
+        })
+    }
+}
+
+/// Message signed by clients: `METHOD\npath?query\ntimestamp\nbody`
+pub fn string_to_sign(method: &str, path_and_query: &str, timestamp: &str, body: &[u8]) -> Vec<u8> {
+    let mut message = format!("{}\n{}\n{}\n", method, path_and_query, timestamp).into_bytes();
+    message.extend_from_slice(body);
+    message
+}
+
+/// Hex-encoded HMAC-SHA256 of `message`
+pub fn sign(secret: &[u8], message: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Decode a hex string (either case); None if it is not valid hex
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Method;
+
+    fn validator() -> HmacValidator {
+        let config: HmacConfig =
+            serde_yaml::from_str("keys:\n  partner-a: shared-secret\nmax_skew_secs: 60").unwrap();
+        HmacValidator::new(&config).unwrap()
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    fn signed_headers(timestamp: u64, signature: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Key-Id", "partner-a".parse().unwrap());
+        headers.insert("X-Timestamp", timestamp.to_string().parse().unwrap());
+        headers.insert("X-Signature", signature.parse().unwrap());
+        headers
+    }
+
+    fn request<'a>(headers: &'a HeaderMap, body: &'a [u8]) -> AuthRequest<'a> {
+        AuthRequest {
+            method: &Method::POST,
+            path_and_query: "/api/orders?dry_run=true",
+            query: Some("dry_run=true"),
+            headers,
+            body: Some(body),
+        }
+    }
+
+    fn signature(timestamp: u64, body: &[u8]) -> String {
+        let message = string_to_sign(
+            "POST",
+            "/api/orders?dry_run=true",
+            &timestamp.to_string(),
+            body,
+        );
+        sign(b"shared-secret", &message)
+    }
+
+    #[test]
+    fn test_hmac_sha256_rfc4231() {
+        // RFC 4231 test cases 2 and 6 (key longer than the block size)
+        assert_eq!(
+            sign(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            sign(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            ),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn test_valid_signature() {
+        let body = br#"{"item":"widget","quantity":2}"#;
+        let timestamp = now();
+        let headers = signed_headers(timestamp, &signature(timestamp, body));
+
+        let result = validator().validate(&request(&headers, body)).unwrap();
+        assert_eq!(result.user_id, "partner-a");
+        assert_eq!(result.method, AuthMethodType::Hmac);
+
+        // Hex case does not matter
+        let headers = signed_headers(timestamp, &signature(timestamp, body).to_uppercase());
+        assert!(validator().validate(&request(&headers, body)).is_ok());
+
+        // Malformed hex is a mismatch, not an error
+        for bad in ["zz", "abc", ""] {
+            let headers = signed_headers(timestamp, bad);
+            assert!(matches!(
+                validator().validate(&request(&headers, body)),
+                Err(GatewayError::Unauthorized(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_tampered_body() {
+        let timestamp = now();
+        let headers = signed_headers(timestamp, &signature(timestamp, b"{\"quantity\":2}"));
+
+        let result = validator().validate(&request(&headers, b"{\"quantity\":200}"));
+        assert!(matches!(result, Err(GatewayError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn test_tampered_path() {
+        let body = b"{}";
+        let timestamp = now();
+        let headers = signed_headers(timestamp, &signature(timestamp, body));
+
+        let mut tampered = request(&headers, body);
+        tampered.path_and_query = "/api/orders?dry_run=false";
+        assert!(validator().validate(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_expired_timestamp() {
+        let body = b"{}";
+        for timestamp in [now() - 120, now() + 120] {
+            let headers = signed_headers(timestamp, &signature(timestamp, body));
+            let err = validator().validate(&request(&headers, body)).unwrap_err();
+            assert!(err.to_string().contains("allowed window"), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_unknown_key_and_missing_headers() {
+        let body = b"{}";
+        let timestamp = now();
+        let mut headers = signed_headers(timestamp, &signature(timestamp, body));
+        headers.insert("X-Key-Id", "partner-b".parse().unwrap());
+        assert!(matches!(
+            validator().validate(&request(&headers, body)),
+            Err(GatewayError::Unauthorized(_))
+        ));
+
+        headers.remove("X-Key-Id");
+        assert!(validator().validate(&request(&headers, body)).is_err());
+
+        let headers = HeaderMap::new();
+        assert!(!validator().is_signed(&headers));
+        assert!(matches!(
+            validator().validate(&request(&headers, body)),
+            Err(GatewayError::MissingCredentials)
+        ));
+    }
+
+    #[test]
+    fn test_config_errors() {
+        let new = |yaml: &str| HmacValidator::new(&serde_yaml::from_str(yaml).unwrap());
+        assert!(new("keys: {}").is_err());
+        assert!(new("keys: {a: ''}").is_err());
+        assert!(new("keys: {a: s}\nsignature_header: 'bad header'").is_err());
+        assert!(new("keys: {a: s}\nmax_body_bytes: 0").is_err());
+        assert!(new("keys: {a: s}").is_ok());
+    }
+}
@@ -0,0 +1,341 @@
+use crate::config::IntrospectionConfig;
+use crate::error::{GatewayError, Result};
+use crate::secrets::{resolve_secret_ref, SecretsManager};
+use axum::http::HeaderMap;
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::RwLock;
+use tracing::warn;
+
+use super::api_key::hashing;
+use super::jwt::JwtValidator;
+use super::{AuthMethodType, AuthResult};
+
+/// Upper bound on cached introspection results
+const MAX_CACHE_ENTRIES: usize = 10_000;
+
+/// OAuth2 token introspection (RFC 7662) validator
+pub struct IntrospectionValidator {
+    client: reqwest::Client,
+    url: String,
+    client_id: String,
+    client_secret: String,
+    cache_ttl: Duration,
+    /// Introspection results indexed by the hex SHA-256 digest of the token
+    cache: RwLock<HashMap<String, CachedIntrospection>>,
+}
+
+/// Cached introspection result; `None` for inactive tokens
+struct CachedIntrospection {
+    result: Option<AuthResult>,
+    expires_at: Instant,
+}
+
+impl IntrospectionValidator {
+    /// Create a new introspection validator from configuration
+    pub fn new(config: &IntrospectionConfig) -> Result<Self> {
+        let url = reqwest::Url::parse(&config.url).map_err(|e| {
+            GatewayError::Config(format!(
+                "Invalid token introspection URL {}: {}",
+                config.url, e
+            ))
+        })?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(GatewayError::Config(format!(
+                "Token introspection URL must be http or https: {}",
+                config.url
+            )));
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()
+            .map_err(|e| {
+                GatewayError::Config(format!("Failed to create introspection client: {}", e))
+            })?;
+
+        Ok(Self {
+            client,
+            url: config.url.clone(),
+            client_id: config.client_id.clone(),
+            client_secret: resolve_secret_ref(&config.client_secret, &SecretsManager::default())?,
+            cache_ttl: Duration::from_secs(config.cache_ttl_secs),
+            cache: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// Validate the bearer token of a request against the introspection endpoint
+    pub async fn validate(&self, headers: &HeaderMap) -> Result<AuthResult> {
+        let token = JwtValidator::extract_bearer_token(headers)?;
+        let digest = hashing::hash_key(&token);
+
+        let cached = self
+            .cache
+            .read()
+            .await
+            .get(&digest)
+            .filter(|entry| entry.expires_at > Instant::now())
+            .map(|entry| entry.result.clone());
+        let result = match cached {
+            Some(result) => result,
+            None => {
+                let (result, ttl) = self.introspect(&token).await?;
+                self.store(digest, result.clone(), ttl).await;
+                result
+            }
+        };
+
+        result.ok_or_else(|| GatewayError::InvalidToken("Token is not active".to_string()))
+    }
+
+    /// Post the token to the introspection endpoint
+    ///
+    /// Returns the result for an active token (`None` if inactive) and how
+    /// long the result may be cached.
+    async fn introspect(&self, token: &str) -> Result<(Option<AuthResult>, Duration)> {
+        let response = self
+            .client
+            .post(&self.url)
+            .basic_auth(&self.client_id, Some(&self.client_secret))
+            .form(&[("token", token), ("token_type_hint", "access_token")])
+            .send()
+            .await
+            .map_err(|e| GatewayError::Backend(format!("Token introspection failed: {}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            warn!(url = %self.url, status = %status, "Token introspection request rejected");
+            return Err(GatewayError::Backend(format!(
+                "Token introspection returned {}",
+                status
+            )));
+        }
+
+        let claims: HashMap<String, serde_json::Value> = response.json().await.map_err(|e| {
+            GatewayError::Backend(format!("Invalid token introspection response: {}", e))
+        })?;
+
+        if claims.get("active") != Some(&serde_json::Value::Bool(true)) {
+            return Ok((None, self.cache_ttl));
+        }
+
+        // Never cache an active result beyond the token's expiry
+        let ttl = match claims.get("exp").and_then(|exp| exp.as_u64()) {
+            Some(exp) => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                self.cache_ttl
+                    .min(Duration::from_secs(exp.saturating_sub(now)))
+            }
+            None => self.cache_ttl,
+        };
+
+        let user_id = ["sub", "username", "client_id"]
+            .iter()
+            .find_map(|claim| claims.get(*claim)?.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let metadata = claims
+            .into_iter()
+            .filter(|(claim, _)| claim != "active")
+            .collect();
+
+        Ok((
+            Some(AuthResult {
+                user_id,
+                method: AuthMethodType::Introspection,
+                metadata,
+                rate_limit: None,
+            }),
+            ttl,
+        ))
+    }
+
+    /// Cache an introspection result for `ttl`
+    async fn store(&self, digest: String, result: Option<AuthResult>, ttl: Duration) {
+        if ttl.is_zero() {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut cache = self.cache.write().await;
+        if cache.len() >= MAX_CACHE_ENTRIES {
+            cache.retain(|_, entry| entry.expires_at > now);
+            if cache.len() >= MAX_CACHE_ENTRIES {
+                cache.clear();
+            }
+        }
+        cache.insert(
+            digest,
+            CachedIntrospection {
+                result,
+                expires_at: now + ttl,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{body_string_contains, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn validator(server: &MockServer, cache_ttl_secs: u64) -> IntrospectionValidator {
+        IntrospectionValidator::new(&IntrospectionConfig {
+            url: format!("{}/oauth2/introspect", server.uri()),
+            client_id: "gateway".to_string(),
+            client_secret: "gateway-secret".to_string(),
+            cache_ttl_secs,
+            timeout_ms: 2000,
+        })
+        .unwrap()
+    }
+
+    fn bearer_headers(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "Authorization",
+            format!("Bearer {}", token).parse().unwrap(),
+        );
+        headers
+    }
+
+    async fn mock_introspection(server: &MockServer, token: &str, body: serde_json::Value) {
+        Mock::given(method("POST"))
+            .and(path("/oauth2/introspect"))
+            // Basic base64("gateway:gateway-secret")
+            .and(header(
+                "authorization",
+                "Basic Z2F0ZXdheTpnYXRld2F5LXNlY3JldA==",
+            ))
+            .and(body_string_contains(format!("token={}", token)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_active_token() {
+        let server = MockServer::start().await;
+        mock_introspection(
+            &server,
+            "opaque-active",
+            json!({"active": true, "sub": "user42", "scope": "read write"}),
+        )
+        .await;
+
+        let validator = validator(&server, 30);
+        let result = validator
+            .validate(&bearer_headers("opaque-active"))
+            .await
+            .unwrap();
+        assert_eq!(result.user_id, "user42");
+        assert_eq!(result.method, AuthMethodType::Introspection);
+        assert_eq!(result.metadata["scope"], "read write");
+        assert!(!result.metadata.contains_key("active"));
+
+        // Served from the cache; the mock expects a single call
+        let result = validator
+            .validate(&bearer_headers("opaque-active"))
+            .await
+            .unwrap();
+        assert_eq!(result.user_id, "user42");
+    }
+
+    #[tokio::test]
+    async fn test_inactive_token() {
+        let server = MockServer::start().await;
+        mock_introspection(&server, "opaque-revoked", json!({"active": false})).await;
+
+        let validator = validator(&server, 30);
+        for _ in 0..2 {
+            let result = validator.validate(&bearer_headers("opaque-revoked")).await;
+            assert!(matches!(result, Err(GatewayError::InvalidToken(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_disabled() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/introspect"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"active": true, "sub": "u"})),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let validator = validator(&server, 0);
+        for _ in 0..2 {
+            assert!(validator.validate(&bearer_headers("opaque")).await.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_bounded_by_token_expiry() {
+        let server = MockServer::start().await;
+        let exp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        Mock::given(method("POST"))
+            .and(path("/oauth2/introspect"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"active": true, "sub": "u", "exp": exp})),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let validator = validator(&server, 30);
+        for _ in 0..2 {
+            assert!(validator.validate(&bearer_headers("opaque")).await.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_endpoint_error_is_not_cached() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/introspect"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let validator = validator(&server, 30);
+        for _ in 0..2 {
+            let result = validator.validate(&bearer_headers("opaque")).await;
+            assert!(matches!(result, Err(GatewayError::Backend(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing_token() {
+        let server = MockServer::start().await;
+        let result = validator(&server, 30).validate(&HeaderMap::new()).await;
+        assert!(matches!(result, Err(GatewayError::MissingCredentials)));
+    }
+
+    #[test]
+    fn test_invalid_url() {
+        let config = IntrospectionConfig {
+            url: "ftp://auth.example.com/introspect".to_string(),
+            client_id: "gateway".to_string(),
+            client_secret: "secret".to_string(),
+            cache_ttl_secs: 30,
+            timeout_ms: 2000,
+        };
+        assert!(matches!(
+            IntrospectionValidator::new(&config),
+            Err(GatewayError::Config(_))
+        ));
+    }
+}
@@ -0,0 +1,305 @@
+use crate::error::{GatewayError, Result};
+use crate::metrics;
+use arc_swap::ArcSwap;
+use jsonwebtoken::jwk::{JwkSet, PublicKeyUse};
+use jsonwebtoken::DecodingKey;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Delay before retrying a failed refresh, if shorter than the refresh interval
+const RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Timeout of JWKS requests
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Cached JWKS key set, refreshed in the background
+///
+/// A failed refresh keeps the last-known keys, so an identity provider outage
+/// does not break validation of tokens signed with keys already fetched.
+pub struct JwksCache {
+    url: String,
+    client: reqwest::Client,
+    refresh_interval: Duration,
+    keys: ArcSwap<Vec<JwksKey>>,
+}
+
+/// A verification key and its key ID
+struct JwksKey {
+    kid: Option<String>,
+    key: DecodingKey,
+}
+
+impl JwksCache {
+    /// Create an empty cache for the key set at `url`
+    pub fn new(url: &str, refresh_interval: Duration) -> Result<Self> {
+        let parsed = reqwest::Url::parse(url)
+            .map_err(|e| GatewayError::Config(format!("Invalid JWKS URL {}: {}", url, e)))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(GatewayError::Config(format!(
+                "JWKS URL must be http or https: {}",
+                url
+            )));
+        }
+        if refresh_interval.is_zero() {
+            return Err(GatewayError::Config(
+                "JWKS refresh interval must be greater than 0".to_string(),
+            ));
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(FETCH_TIMEOUT)
+            .build()
+            .map_err(|e| GatewayError::Config(format!("Failed to create JWKS client: {}", e)))?;
+
+        Ok(Self {
+            url: url.to_string(),
+            client,
+            refresh_interval,
+            keys: ArcSwap::from_pointee(Vec::new()),
+        })
+    }
+
+    /// Key for a token with the given key ID
+    ///
+    /// Tokens without a `kid` are accepted only if the key set has one key.
+    pub fn key(&self, kid: Option<&str>) -> Result<DecodingKey> {
+        let keys = self.keys.load();
+        let key = match kid {
+            Some(kid) => keys.iter().find(|key| key.kid.as_deref() == Some(kid)),
+            None if keys.len() == 1 => keys.first(),
+            None => None,
+        };
+        key.map(|key| key.key.clone()).ok_or_else(|| {
+            GatewayError::InvalidToken(format!(
+                "Token validation failed: no JWKS key for kid {}",
+                kid.unwrap_or("(none)")
+            ))
+        })
+    }
+
+    /// Fetch the key set, keeping the last-known keys on failure
+    ///
+    /// Returns the number of keys loaded.
+    pub async fn refresh(&self) -> Result<usize> {
+        let result = self.fetch().await;
+        metrics::record_jwks_fetch(&self.url, result.is_ok());
+
+        match result {
+            Ok(keys) => {
+                let count = keys.len();
+                debug!(url = %self.url, keys = count, "Refreshed JWKS key set");
+                self.keys.store(Arc::new(keys));
+                Ok(count)
+            }
+            Err(e) => {
+                warn!(
+                    url = %self.url,
+                    error = %e,
+                    cached_keys = self.keys.load().len(),
+                    "JWKS refresh failed, keeping last-known keys"
+                );
+                Err(e)
+            }
+        }
+    }
+
+    async fn fetch(&self) -> Result<Vec<JwksKey>> {
+        let response = self
+            .client
+            .get(&self.url)
+            .send()
+            .await
+            .map_err(|e| GatewayError::Backend(format!("Failed to fetch JWKS: {}", e)))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(GatewayError::Backend(format!(
+                "JWKS endpoint returned {}",
+                status
+            )));
+        }
+
+        let set: JwkSet = response
+            .json()
+            .await
+            .map_err(|e| GatewayError::Backend(format!("Invalid JWKS response: {}", e)))?;
+
+        let keys: Vec<JwksKey> = set
+            .keys
+            .iter()
+            .filter(|jwk| jwk.common.public_key_use != Some(PublicKeyUse::Encryption))
+            .filter_map(|jwk| match DecodingKey::from_jwk(jwk) {
+                Ok(key) => Some(JwksKey {
+                    kid: jwk.common.key_id.clone(),
+                    key,
+                }),
+                Err(e) => {
+                    warn!(
+                        url = %self.url,
+                        kid = ?jwk.common.key_id,
+                        error = %e,
+                        "Skipping unusable JWKS key"
+                    );
+                    None
+                }
+            })
+            .collect();
+
+        // An empty set would reject every token; keep the previous keys instead
+        if keys.is_empty() {
+            return Err(GatewayError::Backend(
+                "JWKS response contains no usable keys".to_string(),
+            ));
+        }
+        Ok(keys)
+    }
+
+    /// Refresh the key set in the background until the cache is dropped
+    ///
+    /// Failed refreshes are retried sooner than the regular interval.
+    pub fn spawn_refresh(cache: &Arc<Self>) {
+        let weak = Arc::downgrade(cache);
+        let refresh_interval = cache.refresh_interval;
+        let mut delay = refresh_interval;
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(delay).await;
+                let Some(cache) = weak.upgrade() else {
+                    break;
+                };
+                delay = match cache.refresh().await {
+                    Ok(_) => refresh_interval,
+                    Err(_) => refresh_interval.min(RETRY_INTERVAL),
+                };
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn jwks_body(keys: &[(&str, &str)]) -> serde_json::Value {
+        let keys: Vec<_> = keys
+            .iter()
+            .map(|(kid, secret)| {
+                json!({
+                    "kty": "oct",
+                    "kid": kid,
+                    "alg": "HS256",
+                    "k": URL_SAFE_NO_PAD.encode(secret),
+                })
+            })
+            .collect();
+        json!({ "keys": keys })
+    }
+
+    async fn serve_jwks(server: &MockServer, status: u16, body: serde_json::Value) {
+        server.reset().await;
+        Mock::given(method("GET"))
+            .and(path("/.well-known/jwks.json"))
+            .respond_with(ResponseTemplate::new(status).set_body_json(body))
+            .mount(server)
+            .await;
+    }
+
+    fn cache(server: &MockServer, refresh_interval: Duration) -> Arc<JwksCache> {
+        let url = format!("{}/.well-known/jwks.json", server.uri());
+        Arc::new(JwksCache::new(&url, refresh_interval).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_refresh_keeps_keys_on_error() {
+        let server = MockServer::start().await;
+        serve_jwks(&server, 200, jwks_body(&[("k1", "secret-1")])).await;
+        let cache = cache(&server, Duration::from_secs(300));
+
+        assert!(cache.key(Some("k1")).is_err());
+        assert_eq!(cache.refresh().await.unwrap(), 1);
+        assert!(cache.key(Some("k1")).is_ok());
+        assert!(cache.key(None).is_ok());
+        assert!(cache.key(Some("k2")).is_err());
+
+        serve_jwks(&server, 503, json!({})).await;
+        assert!(cache.refresh().await.is_err());
+        assert!(cache.key(Some("k1")).is_ok());
+
+        serve_jwks(&server, 200, json!({ "keys": [] })).await;
+        assert!(cache.refresh().await.is_err());
+        assert!(cache.key(Some("k1")).is_ok());
+
+        serve_jwks(&server, 200, jwks_body(&[("k2", "secret-2")])).await;
+        assert_eq!(cache.refresh().await.unwrap(), 1);
+        assert!(cache.key(Some("k1")).is_err());
+        assert!(cache.key(Some("k2")).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_kid_required_with_multiple_keys() {
+        let server = MockServer::start().await;
+        serve_jwks(
+            &server,
+            200,
+            jwks_body(&[("k1", "secret-1"), ("k2", "secret-2")]),
+        )
+        .await;
+        let cache = cache(&server, Duration::from_secs(300));
+
+        assert_eq!(cache.refresh().await.unwrap(), 2);
+        assert!(cache.key(Some("k2")).is_ok());
+        assert!(cache.key(None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_background_refresh() {
+        let server = MockServer::start().await;
+        serve_jwks(&server, 200, jwks_body(&[("k1", "secret-1")])).await;
+        let cache = cache(&server, Duration::from_millis(50));
+        JwksCache::spawn_refresh(&cache);
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert!(cache.key(Some("k1")).is_ok());
+
+        serve_jwks(&server, 200, jwks_body(&[("k2", "secret-2")])).await;
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert!(cache.key(Some("k2")).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_metrics() {
+        let recorder = metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        let _guard = ::metrics::set_default_local_recorder(&recorder);
+
+        let server = MockServer::start().await;
+        serve_jwks(&server, 200, jwks_body(&[("k1", "secret-1")])).await;
+        let cache = cache(&server, Duration::from_secs(300));
+        cache.refresh().await.unwrap();
+        serve_jwks(&server, 500, json!({})).await;
+        assert!(cache.refresh().await.is_err());
+
+        let rendered = handle.render();
+        let url = format!("{}/.well-known/jwks.json", server.uri());
+        assert!(rendered.contains(&format!(
+            r#"gateway_jwks_fetch_total{{url="{}",result="success"}} 1"#,
+            url
+        )));
+        assert!(rendered.contains(&format!(
+            r#"gateway_jwks_fetch_total{{url="{}",result="failure"}} 1"#,
+            url
+        )));
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let interval = Duration::from_secs(300);
+        assert!(JwksCache::new("ftp://idp.example.com/jwks.json", interval).is_err());
+        assert!(JwksCache::new("not a url", interval).is_err());
+        assert!(JwksCache::new("https://idp.example.com/jwks.json", Duration::ZERO).is_err());
+    }
+}
@@ -1,10 +1,11 @@
 use crate::auth::AuthResult;
 
-/// Extension type to hold authentication result (for future use in request handlers)
+/// Extension holding the authentication result, inserted into the request
+/// after successful authentication so later stages (e.g. rate limiting) can
+/// key on the caller
 #[derive(Clone, Debug)]
 pub struct AuthExtension {
     pub auth_result: Option<AuthResult>,
 }
 
 // Middleware functions removed - authentication is now handled directly in proxy_handler
-// This module is kept for the AuthExtension type which may be useful for future enhancements
@@ -1,10 +1,14 @@
 pub mod api_key;
+pub mod hmac;
+pub mod introspection;
+pub mod jwks;
 pub mod jwt;
 pub mod middleware;
 
-use crate::config::{AuthConfig, RouteAuthConfig};
+use crate::config::{AuthConfig, AuthMethod, RouteAuthConfig};
 use crate::error::{GatewayError, Result};
-use axum::http::HeaderMap;
+use crate::rate_limit::RateLimitOverride;
+use axum::http::{HeaderMap, HeaderValue, Method};
 use std::sync::Arc;
 
 /// Authentication result containing user information
@@ -16,12 +20,27 @@ pub struct AuthResult {
     pub method: AuthMethodType,
     /// Additional claims or metadata
     pub metadata: std::collections::HashMap<String, serde_json::Value>,
+    /// Per-key rate limit override (API key authentication only)
+    pub rate_limit: Option<RateLimitOverride>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AuthMethodType {
     Jwt,
     ApiKey,
+    Introspection,
+    Hmac,
+}
+
+/// Request data available to authentication methods
+pub struct AuthRequest<'a> {
+    pub method: &'a Method,
+    /// Path and query string of the request URI
+    pub path_and_query: &'a str,
+    pub query: Option<&'a str>,
+    pub headers: &'a HeaderMap,
+    /// Buffered request body, set when [`AuthService::body_limit`] is some
+    pub body: Option<&'a [u8]>,
 }
 
 /// Authentication service that handles all authentication methods
@@ -29,6 +48,11 @@ pub enum AuthMethodType {
 pub struct AuthService {
     jwt_validator: Option<Arc<jwt::JwtValidator>>,
     api_key_validator: Option<Arc<api_key::ApiKeyValidator>>,
+    introspection_validator: Option<Arc<introspection::IntrospectionValidator>>,
+    hmac_validator: Option<Arc<hmac::HmacValidator>>,
+    /// `WWW-Authenticate` challenges of the configured methods, in the order
+    /// methods are tried
+    challenges: Vec<(AuthMethod, HeaderValue)>,
 }
 
 impl AuthService {
@@ -40,12 +64,17 @@ impl AuthService {
                 return Ok(Self {
                     jwt_validator: None,
                     api_key_validator: None,
+                    introspection_validator: None,
+                    hmac_validator: None,
+                    challenges: Vec::new(),
                 })
             }
         };
 
         let jwt_validator = if let Some(jwt_config) = &config.jwt {
-            Some(Arc::new(jwt::JwtValidator::new(jwt_config)?))
+            let validator = jwt::JwtValidator::new(jwt_config)?;
+            validator.load_jwks().await;
+            Some(Arc::new(validator))
         } else {
             None
         };
@@ -58,18 +87,103 @@ impl AuthService {
             None
         };
 
+        let introspection_validator = if let Some(introspection_config) = &config.introspection {
+            Some(Arc::new(introspection::IntrospectionValidator::new(
+                introspection_config,
+            )?))
+        } else {
+            None
+        };
+
+        let hmac_validator = if let Some(hmac_config) = &config.hmac {
+            Some(Arc::new(hmac::HmacValidator::new(hmac_config)?))
+        } else {
+            None
+        };
+
+        // Default challenges of the configured methods, in authentication order
+        let mut default_challenges = Vec::new();
+        if config.jwt.is_some() {
+            default_challenges.push((AuthMethod::Jwt, "Bearer".to_string()));
+        }
+        if config.introspection.is_some() {
+            default_challenges.push((AuthMethod::Introspection, "Bearer".to_string()));
+        }
+        if let Some(hmac_config) = &config.hmac {
+            default_challenges.push((
+                AuthMethod::Hmac,
+                format!("HMAC-SHA256 header=\"{}\"", hmac_config.signature_header),
+            ));
+        }
+        if let Some(api_key_config) = &config.api_key {
+            default_challenges.push((
+                AuthMethod::ApiKey,
+                format!("ApiKey header=\"{}\"", api_key_config.header),
+            ));
+        }
+
+        let mut challenges = Vec::new();
+        for (method, default) in default_challenges {
+            let challenge = config.challenges.get(&method).unwrap_or(&default);
+            if challenge.is_empty() {
+                continue;
+            }
+            let value = HeaderValue::from_str(challenge).map_err(|_| {
+                GatewayError::Config(format!(
+                    "auth.challenges.{}: Invalid WWW-Authenticate value: {}",
+                    format!("{:?}", method).to_lowercase(),
+                    challenge
+                ))
+            })?;
+            challenges.push((method, value));
+        }
+
         Ok(Self {
             jwt_validator,
             api_key_validator,
+            introspection_validator,
+            hmac_validator,
+            challenges,
         })
     }
 
+    /// `WWW-Authenticate` challenges for a 401 on a route
+    ///
+    /// One per method the route accepts, without duplicates.
+    pub fn challenges(&self, route_auth: &RouteAuthConfig) -> Vec<HeaderValue> {
+        let mut challenges: Vec<HeaderValue> = Vec::new();
+        for (method, challenge) in &self.challenges {
+            let allowed = route_auth.methods.is_empty() || route_auth.methods.contains(method);
+            if allowed && !challenges.contains(challenge) {
+                challenges.push(challenge.clone());
+            }
+        }
+        challenges
+    }
+
+    /// Largest body to buffer if authenticating the request requires it
+    ///
+    /// Some for signed requests on routes that accept HMAC signatures.
+    pub fn body_limit(&self, headers: &HeaderMap, route_auth: &RouteAuthConfig) -> Option<usize> {
+        let allowed = route_auth.methods.is_empty()
+            || route_auth
+                .methods
+                .contains(&crate::config::AuthMethod::Hmac);
+        self.hmac_validator
+            .as_ref()
+            .filter(|validator| allowed && validator.is_signed(headers))
+            .map(|validator| validator.max_body_bytes())
+    }
+
     /// Authenticate a request based on route configuration
     pub async fn authenticate(
         &self,
-        headers: &HeaderMap,
+        request: &AuthRequest<'_>,
         route_auth: &RouteAuthConfig,
     ) -> Result<AuthResult> {
+        let headers = request.headers;
+        let query = request.query;
+
         // If no methods specified, try all available methods
         let methods = if route_auth.methods.is_empty() {
             vec![]
@@ -82,13 +196,33 @@ impl AuthService {
         // Try JWT authentication
         if methods.is_empty() || methods.contains(&crate::config::AuthMethod::Jwt) {
             if let Some(validator) = &self.jwt_validator {
-                match validator.validate(headers).await {
+                match validator.validate(headers, query).await {
                     Ok(result) => return Ok(result),
                     Err(e) => errors.push(format!("JWT: {}", e)),
                 }
             }
         }
 
+        // Try OAuth2 token introspection
+        if methods.is_empty() || methods.contains(&crate::config::AuthMethod::Introspection) {
+            if let Some(validator) = &self.introspection_validator {
+                match validator.validate(headers).await {
+                    Ok(result) => return Ok(result),
+                    Err(e) => errors.push(format!("Introspection: {}", e)),
+                }
+            }
+        }
+
+        // Try HMAC request signature verification
+        if methods.is_empty() || methods.contains(&crate::config::AuthMethod::Hmac) {
+            if let Some(validator) = &self.hmac_validator {
+                match validator.validate(request) {
+                    Ok(result) => return Ok(result),
+                    Err(e) => errors.push(format!("HMAC: {}", e)),
+                }
+            }
+        }
+
         // Try API key authentication
         if methods.is_empty() || methods.contains(&crate::config::AuthMethod::ApiKey) {
             if let Some(validator) = &self.api_key_validator {
@@ -107,9 +241,19 @@ impl AuthService {
         }
     }
 
+    /// Query parameter carrying a JWT, which is not forwarded to backends
+    pub fn token_query_param(&self) -> Option<&str> {
+        self.jwt_validator
+            .as_ref()
+            .and_then(|validator| validator.token_query_param())
+    }
+
     /// Check if authentication is available
     pub fn is_available(&self) -> bool {
-        self.jwt_validator.is_some() || self.api_key_validator.is_some()
+        self.jwt_validator.is_some()
+            || self.api_key_validator.is_some()
+            || self.introspection_validator.is_some()
+            || self.hmac_validator.is_some()
     }
 }
 
@@ -122,4 +266,74 @@ mod tests {
         let service = AuthService::new(None).await.unwrap();
         assert!(!service.is_available());
     }
+
+    fn route_auth(methods: &[AuthMethod]) -> RouteAuthConfig {
+        RouteAuthConfig {
+            required: true,
+            methods: methods.to_vec(),
+        }
+    }
+
+    async fn auth_service(yaml: &str) -> Result<AuthService> {
+        let config: AuthConfig = serde_yaml::from_str(yaml).unwrap();
+        AuthService::new(Some(&config)).await
+    }
+
+    #[tokio::test]
+    async fn test_default_challenges() {
+        let service = auth_service(
+            r#"
+jwt: {secret: s}
+introspection: {url: "https://auth.example.com/introspect", client_id: c, client_secret: s}
+api_key: {header: X-Partner-Key}
+hmac: {keys: {a: s}}
+"#,
+        )
+        .await
+        .unwrap();
+
+        // Any method: one challenge per scheme, in authentication order
+        assert_eq!(
+            service.challenges(&route_auth(&[])),
+            [
+                "Bearer",
+                r#"HMAC-SHA256 header="X-Signature""#,
+                r#"ApiKey header="X-Partner-Key""#
+            ]
+        );
+        assert_eq!(
+            service.challenges(&route_auth(&[AuthMethod::ApiKey])),
+            [r#"ApiKey header="X-Partner-Key""#]
+        );
+        assert_eq!(
+            service.challenges(&route_auth(&[AuthMethod::Introspection])),
+            ["Bearer"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_configured_challenges() {
+        let service = auth_service(
+            r#"
+jwt: {secret: s}
+api_key: {}
+challenges:
+  jwt: 'Bearer realm="api.example.com"'
+  apikey: ""
+"#,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            service.challenges(&route_auth(&[])),
+            [r#"Bearer realm="api.example.com""#]
+        );
+        assert!(service
+            .challenges(&route_auth(&[AuthMethod::ApiKey]))
+            .is_empty());
+
+        let result = auth_service("jwt: {secret: s}\nchallenges: {jwt: \"Bearer\\n\"}").await;
+        assert!(matches!(result, Err(GatewayError::Config(_))));
+    }
 }
@@ -0,0 +1,77 @@
+use super::{CacheKey, CacheStats, CachedResponse};
+use async_trait::async_trait;
+use moka::future::Cache;
+use std::time::Duration;
+
+/// Storage for cached responses
+///
+/// Entries are kept for the TTL plus the stale window; `CacheService`
+/// decides whether a returned entry is fresh or stale from `stored_at`.
+#[async_trait]
+pub trait CacheBackend: Send + Sync + std::fmt::Debug {
+    /// Look up an entry (errors are treated as misses)
+    async fn get(&self, key: &CacheKey) -> Option<CachedResponse>;
+
+    /// Store an entry
+    async fn put(&self, key: CacheKey, response: CachedResponse);
+
+    /// Remove an entry
+    async fn invalidate(&self, key: &CacheKey);
+
+    /// Remove all entries
+    async fn clear(&self);
+
+    /// Entry statistics, or `None` where the backend does not track them
+    fn stats(&self) -> Option<CacheStats>;
+
+    /// Apply pending inserts and evictions to the statistics
+    async fn run_pending_tasks(&self) {}
+}
+
+/// In-process cache, local to each gateway replica
+#[derive(Debug)]
+pub struct MemoryCacheBackend {
+    cache: Cache<CacheKey, CachedResponse>,
+}
+
+impl MemoryCacheBackend {
+    /// Create an in-memory cache holding up to `max_capacity` entries for `retention`
+    pub fn new(max_capacity: u64, retention: Duration) -> Self {
+        Self {
+            cache: Cache::builder()
+                .max_capacity(max_capacity)
+                .time_to_live(retention)
+                .build(),
+        }
+    }
+}
+
+#[async_trait]
+impl CacheBackend for MemoryCacheBackend {
+    async fn get(&self, key: &CacheKey) -> Option<CachedResponse> {
+        self.cache.get(key).await
+    }
+
+    async fn put(&self, key: CacheKey, response: CachedResponse) {
+        self.cache.insert(key, response).await;
+    }
+
+    async fn invalidate(&self, key: &CacheKey) {
+        self.cache.invalidate(key).await;
+    }
+
+    async fn clear(&self) {
+        self.cache.invalidate_all();
+    }
+
+    fn stats(&self) -> Option<CacheStats> {
+        Some(CacheStats {
+            entry_count: self.cache.entry_count(),
+            weighted_size: self.cache.weighted_size(),
+        })
+    }
+
+    async fn run_pending_tasks(&self) {
+        self.cache.run_pending_tasks().await;
+    }
+}
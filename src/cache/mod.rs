@@ -1,11 +1,24 @@
-use crate::error::Result;
+pub mod backend;
+pub mod redis;
+
+use crate::error::{GatewayError, Result};
 use axum::body::Body;
-use axum::http::{HeaderMap, Response, StatusCode};
+use axum::http::header::{
+    HeaderName, CACHE_CONTROL, CONTENT_LOCATION, DATE, ETAG, EXPIRES, IF_MODIFIED_SINCE,
+    IF_NONE_MATCH, LAST_MODIFIED, VARY,
+};
+use axum::http::{HeaderMap, HeaderValue, Response, StatusCode};
+use backend::{CacheBackend, MemoryCacheBackend};
 use bytes::Bytes;
+use dashmap::mapref::entry::Entry;
+use dashmap::{DashMap, DashSet};
 use moka::future::Cache;
+use redis::RedisCacheBackend;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::watch;
 use tracing::{debug, info};
 
 /// Cache configuration
@@ -26,12 +39,56 @@ pub struct CacheConfig {
     /// List of status codes to cache (e.g., [200, 301])
     #[serde(default = "default_cacheable_status_codes")]
     pub cacheable_status_codes: Vec<u16>,
+    /// Error status codes cached briefly, with `negative_ttl_secs` (e.g., [502, 503])
+    #[serde(default)]
+    pub negative_status_codes: Vec<u16>,
+    /// Time-to-live in seconds for responses with a `negative_status_codes` status
+    #[serde(default = "default_negative_ttl_secs")]
+    pub negative_ttl_secs: u64,
     /// Headers to include in cache key (in addition to path and method)
     #[serde(default)]
     pub key_headers: Vec<String>,
     /// Whether to cache responses with Set-Cookie headers
     #[serde(default)]
     pub cache_with_cookies: bool,
+    /// Request headers treated as if the backend always returned them in `Vary`
+    #[serde(default)]
+    pub force_vary_headers: Vec<String>,
+    /// How long expired entries are kept after their TTL. Within this window
+    /// they are served while refreshed in the background
+    /// (stale-while-revalidate) and when the backend fails
+    #[serde(default)]
+    pub stale_ttl_secs: u64,
+    /// Where cached responses are stored
+    #[serde(default)]
+    pub backend: CacheBackendType,
+    /// Redis settings for the `redis` backend
+    #[serde(default)]
+    pub redis: Option<CacheRedisConfig>,
+}
+
+/// Cache storage backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheBackendType {
+    /// In-process cache, local to each replica
+    #[default]
+    Memory,
+    /// Redis cache shared by all replicas
+    Redis,
+}
+
+/// Redis settings for the shared response cache
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheRedisConfig {
+    /// Redis connection URL
+    pub url: String,
+    /// Key prefix for cached responses
+    #[serde(default = "default_redis_prefix")]
+    pub prefix: String,
+    /// Read-only replica for lookups (writes always go to `url`)
+    #[serde(default)]
+    pub replica_url: Option<String>,
 }
 
 fn default_enabled() -> bool {
@@ -46,6 +103,10 @@ fn default_ttl_secs() -> u64 {
     300 // 5 minutes
 }
 
+fn default_negative_ttl_secs() -> u64 {
+    5
+}
+
 fn default_cacheable_methods() -> Vec<String> {
     vec!["GET".to_string(), "HEAD".to_string()]
 }
@@ -54,6 +115,10 @@ fn default_cacheable_status_codes() -> Vec<u16> {
     vec![200, 301, 302, 404]
 }
 
+fn default_redis_prefix() -> String {
+    "gateway:cache:".to_string()
+}
+
 impl Default for CacheConfig {
     fn default() -> Self {
         Self {
@@ -62,8 +127,14 @@ impl Default for CacheConfig {
             ttl_secs: default_ttl_secs(),
             cacheable_methods: default_cacheable_methods(),
             cacheable_status_codes: default_cacheable_status_codes(),
+            negative_status_codes: vec![],
+            negative_ttl_secs: default_negative_ttl_secs(),
             key_headers: vec![],
             cache_with_cookies: false,
+            force_vary_headers: vec![],
+            stale_ttl_secs: 0,
+            backend: CacheBackendType::default(),
+            redis: None,
         }
     }
 }
@@ -74,6 +145,8 @@ pub struct CachedResponse {
     pub status: StatusCode,
     pub headers: HeaderMap,
     pub body: Bytes,
+    /// When the response was stored
+    pub stored_at: Instant,
 }
 
 impl CachedResponse {
@@ -98,6 +171,195 @@ impl CachedResponse {
                     .unwrap()
             })
     }
+
+    /// Convert to Axum response, marked as served stale
+    pub fn to_stale_response(&self) -> Response<Body> {
+        let mut response = self.to_response();
+        response
+            .headers_mut()
+            .insert("X-Cache", axum::http::HeaderValue::from_static("STALE"));
+        response
+    }
+
+    /// Whether the request's conditional headers show the client already has
+    /// this response, so it can be answered with `304 Not Modified`
+    ///
+    /// `If-None-Match` takes precedence over `If-Modified-Since`, and entity
+    /// tags use weak comparison (RFC 9110, section 13.1). Only `2xx` entries
+    /// qualify; cached error responses are always sent in full.
+    pub fn is_not_modified(&self, request_headers: &HeaderMap) -> bool {
+        if !self.status.is_success() {
+            return false;
+        }
+        if request_headers.contains_key(IF_NONE_MATCH) {
+            let Some(etag) = self.headers.get(ETAG).and_then(|v| v.to_str().ok()) else {
+                return false;
+            };
+            return request_headers
+                .get_all(IF_NONE_MATCH)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .flat_map(|value| value.split(','))
+                .map(str::trim)
+                .any(|tag| tag == "*" || weak_etag(tag) == weak_etag(etag));
+        }
+
+        match (
+            header_date(request_headers, &IF_MODIFIED_SINCE),
+            header_date(&self.headers, &LAST_MODIFIED),
+        ) {
+            (Some(since), Some(last_modified)) => last_modified <= since,
+            _ => false,
+        }
+    }
+
+    /// Convert to a `304 Not Modified` response without a body
+    pub fn to_not_modified_response(&self) -> Response<Body> {
+        let mut response = Response::builder().status(StatusCode::NOT_MODIFIED);
+
+        // Only the headers a 304 is expected to repeat (RFC 9110, section 15.4.5)
+        for name in [
+            CACHE_CONTROL,
+            CONTENT_LOCATION,
+            DATE,
+            ETAG,
+            EXPIRES,
+            LAST_MODIFIED,
+            VARY,
+        ] {
+            for value in self.headers.get_all(&name) {
+                response = response.header(&name, value);
+            }
+        }
+
+        response
+            .header("X-Cache", "HIT")
+            .body(Body::empty())
+            .unwrap_or_else(|_| {
+                Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(Body::empty())
+                    .unwrap()
+            })
+    }
+}
+
+/// Entity tag without its weak indicator
+fn weak_etag(tag: &str) -> &str {
+    tag.strip_prefix("W/").unwrap_or(tag)
+}
+
+/// Parse an HTTP-date header
+fn header_date(headers: &HeaderMap, name: &HeaderName) -> Option<SystemTime> {
+    httpdate::parse_http_date(headers.get(name)?.to_str().ok()?).ok()
+}
+
+/// Result of looking up a request in the cache
+#[derive(Debug)]
+pub enum CacheLookup {
+    /// Entry within its TTL
+    Fresh(CachedResponse),
+    /// Expired entry within the `stale_ttl_secs` window that should be served
+    /// while the stored entry under `key` is refreshed
+    Stale {
+        response: CachedResponse,
+        key: CacheKey,
+    },
+    /// No usable entry
+    Miss,
+}
+
+/// Marks a background refresh of a cache entry as in flight; released on drop
+#[derive(Debug)]
+pub struct RefreshGuard {
+    refreshing: Arc<DashSet<CacheKey>>,
+    key: CacheKey,
+}
+
+impl Drop for RefreshGuard {
+    fn drop(&mut self) {
+        self.refreshing.remove(&self.key);
+    }
+}
+
+/// Outcome of joining the fill of a missing cache entry
+#[derive(Debug)]
+pub enum CacheFill {
+    /// This request fetches the entry; other requests wait until the guard drops
+    Leader(FillGuard),
+    /// Another request is already fetching the entry
+    Follower(FillWaiter),
+}
+
+/// Marks a cache fill as in flight; waiting requests are woken on drop
+#[derive(Debug)]
+pub struct FillGuard {
+    filling: Arc<DashMap<CacheKey, watch::Receiver<()>>>,
+    key: CacheKey,
+    /// Dropped after the map entry is removed, which wakes the followers
+    _done: watch::Sender<()>,
+}
+
+impl Drop for FillGuard {
+    fn drop(&mut self) {
+        self.filling.remove(&self.key);
+    }
+}
+
+/// Waits for another request's cache fill to finish
+#[derive(Debug)]
+pub struct FillWaiter(watch::Receiver<()>);
+
+impl FillWaiter {
+    /// Wait until the leading request has stored its response (or given up)
+    pub async fn wait(mut self) {
+        // The sender never sends, so this only returns once it is dropped
+        let _ = self.0.changed().await;
+    }
+}
+
+/// Response header listing the tags of a cached response, for purging by tag
+pub const CACHE_TAGS_HEADER: &str = "x-cache-tags";
+
+/// Tags listed in a response's `X-Cache-Tags` headers (comma-separated)
+fn cache_tags(headers: &HeaderMap) -> Arc<[String]> {
+    let mut tags: Vec<String> = headers
+        .get_all(CACHE_TAGS_HEADER)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags.into()
+}
+
+/// Lowercased, deduplicated header names listed in a response's `Vary` headers
+fn vary_headers(headers: &HeaderMap) -> Vec<String> {
+    let mut names: Vec<String> = headers
+        .get_all(VARY)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|name| name.trim().to_ascii_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Check if a request's `Cache-Control` headers carry `directive` (e.g. `no-cache`)
+pub fn has_request_directive(headers: &HeaderMap, directive: &str) -> bool {
+    headers
+        .get_all(CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|entry| entry.split('=').next().unwrap_or_default().trim())
+        .any(|name| name.eq_ignore_ascii_case(directive))
 }
 
 /// Cache key for requests
@@ -138,33 +400,145 @@ impl CacheKey {
             headers,
         }
     }
+
+    /// Key for the variant selected by the response's `Vary` headers
+    ///
+    /// Vary headers already part of the key are skipped; headers missing from
+    /// the request are included with an empty value so that their absence
+    /// also selects a distinct variant.
+    pub fn with_vary(&self, vary: &[String], request_headers: &HeaderMap) -> Self {
+        let mut key = self.clone();
+        for name in vary {
+            if key
+                .headers
+                .iter()
+                .any(|(existing, _)| existing.eq_ignore_ascii_case(name))
+            {
+                continue;
+            }
+            let value = request_headers
+                .get_all(name.as_str())
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .collect::<Vec<_>>()
+                .join(", ");
+            key.headers.push((name.clone(), value));
+        }
+        key.headers.sort();
+        key
+    }
+
+    /// Stable hex SHA-256 digest of the key, identical across gateway replicas
+    pub fn digest(&self) -> String {
+        let mut hasher = Sha256::new();
+        for part in [
+            self.method.as_str(),
+            self.path.as_str(),
+            self.query.as_deref().unwrap_or(""),
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        for (name, value) in &self.headers {
+            hasher.update(name.to_ascii_lowercase().as_bytes());
+            hasher.update([b':']);
+            hasher.update(value.as_bytes());
+            hasher.update([0]);
+        }
+
+        hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
 }
 
 /// Cache service for storing and retrieving responses
 #[derive(Debug)]
 pub struct CacheService {
     config: CacheConfig,
-    cache: Arc<Cache<CacheKey, CachedResponse>>,
+    backend: Arc<dyn CacheBackend>,
+    /// `key_headers` plus `force_vary_headers`, lowercased and deduplicated
+    key_headers: Vec<String>,
+    /// Entries with a background refresh in flight
+    refreshing: Arc<DashSet<CacheKey>>,
+    /// Missing entries being fetched by a request, which others wait on
+    filling: Arc<DashMap<CacheKey, watch::Receiver<()>>>,
+    /// Keys stored through this service with their tags, so entries can be
+    /// purged by path prefix or tag (the backends only look up exact keys)
+    ///
+    /// Only kept for the memory backend: the index is local to this process,
+    /// so it would miss entries other replicas stored in a shared Redis cache.
+    index: Option<Cache<CacheKey, IndexEntry>>,
+}
+
+/// Purge index entry for a stored key
+#[derive(Clone, Debug)]
+struct IndexEntry {
+    tags: Arc<[String]>,
+    /// The key holds the `Vary` marker rather than a response
+    vary_marker: bool,
 }
 
 impl CacheService {
-    /// Create a new cache service
-    pub fn new(config: CacheConfig) -> Self {
-        let cache = Cache::builder()
-            .max_capacity(config.max_capacity)
-            .time_to_live(Duration::from_secs(config.ttl_secs))
-            .build();
+    /// Create a new cache service using the configured backend
+    pub fn new(config: CacheConfig) -> Result<Self> {
+        // Entries outlive their TTL by the stale window so they can still be
+        // served while refreshing or when the backend is unavailable
+        let retention = Duration::from_secs(
+            config.ttl_secs.max(config.negative_ttl_secs) + config.stale_ttl_secs,
+        );
+        let backend: Arc<dyn CacheBackend> = match config.backend {
+            CacheBackendType::Memory => {
+                Arc::new(MemoryCacheBackend::new(config.max_capacity, retention))
+            }
+            CacheBackendType::Redis => {
+                let redis = config.redis.as_ref().ok_or_else(|| {
+                    GatewayError::Config(
+                        "cache.redis: Redis settings are required for the redis cache backend"
+                            .to_string(),
+                    )
+                })?;
+                let mut backend =
+                    RedisCacheBackend::new(&redis.url, redis.prefix.clone(), retention)?;
+                if let Some(replica_url) = &redis.replica_url {
+                    backend = backend.with_replica(replica_url)?;
+                }
+                Arc::new(backend)
+            }
+        };
 
         info!(
+            backend = ?config.backend,
             max_capacity = config.max_capacity,
             ttl_secs = config.ttl_secs,
+            stale_ttl_secs = config.stale_ttl_secs,
             "Initialized cache service"
         );
 
-        Self {
+        let mut key_headers: Vec<String> = config
+            .key_headers
+            .iter()
+            .chain(config.force_vary_headers.iter())
+            .map(|name| name.to_ascii_lowercase())
+            .collect();
+        key_headers.sort();
+        key_headers.dedup();
+
+        Ok(Self {
+            backend,
+            key_headers,
+            refreshing: Arc::new(DashSet::new()),
+            filling: Arc::new(DashMap::new()),
+            index: (config.backend == CacheBackendType::Memory).then(|| {
+                Cache::builder()
+                    .max_capacity(config.max_capacity)
+                    .time_to_live(retention)
+                    .build()
+            }),
             config,
-            cache: Arc::new(cache),
-        }
+        })
     }
 
     /// Check if a method is cacheable
@@ -177,7 +551,24 @@ impl CacheService {
 
     /// Check if a status code is cacheable
     pub fn is_cacheable_status(&self, status: u16) -> bool {
-        self.config.cacheable_status_codes.contains(&status)
+        self.config.cacheable_status_codes.contains(&status) || self.is_negative_status(status)
+    }
+
+    /// Check if a status code is cached as a negative (error) entry
+    pub fn is_negative_status(&self, status: u16) -> bool {
+        self.config.negative_status_codes.contains(&status)
+    }
+
+    /// Time-to-live of an entry, chosen by its status
+    ///
+    /// Negative entries use `negative_ttl_secs` so a flapping backend is
+    /// shielded briefly without pinning its errors for the full `ttl_secs`.
+    pub fn ttl_for(&self, status: StatusCode) -> Duration {
+        if self.is_negative_status(status.as_u16()) {
+            Duration::from_secs(self.config.negative_ttl_secs)
+        } else {
+            Duration::from_secs(self.config.ttl_secs)
+        }
     }
 
     /// Check if response can be cached based on headers
@@ -187,6 +578,11 @@ impl CacheService {
             return false;
         }
 
+        // A response varying on `*` can never be matched to a later request
+        if vary_headers(headers).iter().any(|name| name == "*") {
+            return false;
+        }
+
         // Check Cache-Control header
         if let Some(cache_control) = headers.get("cache-control") {
             if let Ok(cc_str) = cache_control.to_str() {
@@ -204,9 +600,36 @@ impl CacheService {
         true
     }
 
+    /// Look up the entry for a request, following the `Vary` headers of the
+    /// stored response to the variant matching `request_headers`
+    ///
+    /// Returns the key the entry is stored under along with the entry.
+    async fn lookup(
+        &self,
+        key: &CacheKey,
+        request_headers: &HeaderMap,
+    ) -> Option<(CacheKey, CachedResponse)> {
+        let cached = self.backend.get(key).await?;
+        let vary = vary_headers(&cached.headers);
+        if vary.is_empty() {
+            return Some((key.clone(), cached));
+        }
+
+        let variant = key.with_vary(&vary, request_headers);
+        if variant == *key {
+            return Some((variant, cached));
+        }
+        let cached = self.backend.get(&variant).await?;
+        Some((variant, cached))
+    }
+
     /// Get a cached response
-    pub async fn get(&self, key: &CacheKey) -> Option<CachedResponse> {
-        let cached = self.cache.get(key).await;
+    pub async fn get(&self, key: &CacheKey, request_headers: &HeaderMap) -> Option<CachedResponse> {
+        let cached = self
+            .lookup(key, request_headers)
+            .await
+            .map(|(_, cached)| cached)
+            .filter(|cached| cached.stored_at.elapsed() < self.ttl_for(cached.status));
         if cached.is_some() {
             debug!(
                 method = %key.method,
@@ -217,10 +640,110 @@ impl CacheService {
         cached
     }
 
+    /// Get a cached response even if it has expired, within the stale window
+    ///
+    /// Negative entries are never served stale.
+    pub async fn get_stale(
+        &self,
+        key: &CacheKey,
+        request_headers: &HeaderMap,
+    ) -> Option<CachedResponse> {
+        let cached = self
+            .lookup(key, request_headers)
+            .await
+            .map(|(_, cached)| cached)
+            .filter(|cached| {
+                let ttl = self.ttl_for(cached.status);
+                let window = if self.is_negative_status(cached.status.as_u16()) {
+                    ttl
+                } else {
+                    ttl + Duration::from_secs(self.config.stale_ttl_secs)
+                };
+                cached.stored_at.elapsed() < window
+            });
+        if cached.is_some() {
+            debug!(
+                method = %key.method,
+                path = %key.path,
+                "Stale cache hit"
+            );
+        }
+        cached
+    }
+
+    /// Look up a request, also returning entries that expired within the
+    /// `stale_ttl_secs` window so they can be served while being refreshed
+    pub async fn get_or_stale(&self, key: &CacheKey, request_headers: &HeaderMap) -> CacheLookup {
+        let Some((stored_key, cached)) = self.lookup(key, request_headers).await else {
+            return CacheLookup::Miss;
+        };
+
+        let age = cached.stored_at.elapsed();
+        let ttl = self.ttl_for(cached.status);
+        if age < ttl {
+            debug!(method = %key.method, path = %key.path, "Cache hit");
+            CacheLookup::Fresh(cached)
+        } else if age < ttl + Duration::from_secs(self.config.stale_ttl_secs)
+            && !self.is_negative_status(cached.status.as_u16())
+        {
+            debug!(
+                method = %key.method,
+                path = %key.path,
+                "Serving stale cache entry while revalidating"
+            );
+            CacheLookup::Stale {
+                response: cached,
+                key: stored_key,
+            }
+        } else {
+            CacheLookup::Miss
+        }
+    }
+
+    /// Claim the background refresh of an entry
+    ///
+    /// Returns `None` if a refresh of the entry is already in flight, so only
+    /// one request per entry goes to the backend.
+    pub fn try_begin_refresh(&self, key: &CacheKey) -> Option<RefreshGuard> {
+        if !self.refreshing.insert(key.clone()) {
+            return None;
+        }
+        Some(RefreshGuard {
+            refreshing: self.refreshing.clone(),
+            key: key.clone(),
+        })
+    }
+
+    /// Join the fill of a missing entry (single-flight)
+    ///
+    /// The first request for a key becomes the leader and goes to the
+    /// backend, holding the returned guard until its response is stored.
+    /// Concurrent requests for the same key become followers that wait for
+    /// the leader and then look the entry up again.
+    pub fn join_fill(&self, key: &CacheKey) -> CacheFill {
+        match self.filling.entry(key.clone()) {
+            Entry::Occupied(entry) => CacheFill::Follower(FillWaiter(entry.get().clone())),
+            Entry::Vacant(entry) => {
+                let (done, waiters) = watch::channel(());
+                entry.insert(waiters);
+                CacheFill::Leader(FillGuard {
+                    filling: self.filling.clone(),
+                    key: key.clone(),
+                    _done: done,
+                })
+            }
+        }
+    }
+
     /// Store a response in cache
+    ///
+    /// Responses with a `Vary` header are stored under a variant key built
+    /// from `request_headers`, and the key itself records the `Vary` list
+    /// so lookups can find the variant.
     pub async fn put(
         &self,
         key: CacheKey,
+        request_headers: &HeaderMap,
         status: StatusCode,
         headers: HeaderMap,
         body: Bytes,
@@ -240,27 +763,66 @@ impl CacheService {
             return Ok(());
         }
 
+        // A client sending `no-store` doesn't want its response kept
+        if has_request_directive(request_headers, "no-store") {
+            debug!(method = %key.method, path = %key.path, "Request has no-store, not caching");
+            return Ok(());
+        }
+
+        let vary = vary_headers(&headers);
+        let tags = cache_tags(&headers);
+        let stored_at = Instant::now();
         let cached = CachedResponse {
             status,
             headers,
             body,
+            stored_at,
         };
 
-        self.cache.insert(key.clone(), cached).await;
+        let variant = key.with_vary(&vary, request_headers);
+        if variant != key {
+            let mut marker_headers = HeaderMap::new();
+            if let Ok(value) = HeaderValue::from_str(&vary.join(", ")) {
+                marker_headers.insert(VARY, value);
+            }
+            self.index_key(variant.clone(), &tags, false).await;
+            self.backend.put(variant, cached).await;
+            self.backend
+                .put(
+                    key.clone(),
+                    CachedResponse {
+                        status,
+                        headers: marker_headers,
+                        body: Bytes::new(),
+                        stored_at,
+                    },
+                )
+                .await;
+            self.index_key(key.clone(), &tags, true).await;
+        } else {
+            self.backend.put(key.clone(), cached).await;
+            self.index_key(key.clone(), &tags, false).await;
+        }
 
         debug!(
             method = %key.method,
             path = %key.path,
             status = %status.as_u16(),
+            ttl_secs = self.ttl_for(status).as_secs(),
+            vary = ?vary,
             "Cached response"
         );
 
         Ok(())
     }
 
-    /// Invalidate cache entry
+    /// Invalidate cache entry (including any `Vary` variants, which are no
+    /// longer reachable once the key's entry is gone)
     pub async fn invalidate(&self, key: &CacheKey) {
-        self.cache.invalidate(key).await;
+        self.backend.invalidate(key).await;
+        if let Some(index) = &self.index {
+            index.invalidate(key).await;
+        }
         debug!(
             method = %key.method,
             path = %key.path,
@@ -270,26 +832,98 @@ impl CacheService {
 
     /// Clear all cache entries
     pub async fn clear(&self) {
-        self.cache.invalidate_all();
+        self.backend.clear().await;
+        if let Some(index) = &self.index {
+            index.invalidate_all();
+        }
         info!("Cleared all cache entries");
     }
 
-    /// Get cache statistics
-    pub fn stats(&self) -> CacheStats {
-        CacheStats {
-            entry_count: self.cache.entry_count(),
-            weighted_size: self.cache.weighted_size(),
+    async fn index_key(&self, key: CacheKey, tags: &Arc<[String]>, vary_marker: bool) {
+        if let Some(index) = &self.index {
+            let entry = IndexEntry {
+                tags: tags.clone(),
+                vary_marker,
+            };
+            index.insert(key, entry).await;
         }
     }
 
-    /// Get cache key headers configuration
+    /// Remove all entries whose request path starts with `prefix`
+    ///
+    /// Returns the number of responses removed. Not supported with the Redis
+    /// backend, whose keys are digests that can't be matched.
+    pub async fn purge_prefix(&self, prefix: &str) -> Result<usize> {
+        let purged = self
+            .purge_matching(|key, _| key.path.starts_with(prefix))
+            .await?;
+        info!(prefix = %prefix, purged, "Purged cache entries by prefix");
+        Ok(purged)
+    }
+
+    /// Remove all entries whose response was tagged with `tag`
+    ///
+    /// Returns the number of responses removed. Not supported with the Redis
+    /// backend, whose keys are digests that can't be matched.
+    pub async fn purge_tag(&self, tag: &str) -> Result<usize> {
+        let purged = self
+            .purge_matching(|_, tags| tags.iter().any(|t| t == tag))
+            .await?;
+        info!(tag = %tag, purged, "Purged cache entries by tag");
+        Ok(purged)
+    }
+
+    async fn purge_matching(
+        &self,
+        matches: impl Fn(&CacheKey, &[String]) -> bool,
+    ) -> Result<usize> {
+        let Some(index) = &self.index else {
+            return Err(GatewayError::NotSupported(
+                "Purging by prefix or tag is not supported with the redis cache backend; \
+                 use /admin/cache/clear instead"
+                    .to_string(),
+            ));
+        };
+
+        let keys: Vec<(CacheKey, bool)> = index
+            .iter()
+            .filter(|(key, entry)| matches(key, &entry.tags))
+            .map(|(key, entry)| (CacheKey::clone(&key), entry.vary_marker))
+            .collect();
+
+        let mut purged = 0;
+        for (key, vary_marker) in &keys {
+            self.backend.invalidate(key).await;
+            index.invalidate(key).await;
+            if !vary_marker {
+                purged += 1;
+            }
+        }
+        Ok(purged)
+    }
+
+    /// Get cache statistics, or `None` when the backend doesn't track them (Redis)
+    pub fn stats(&self) -> Option<CacheStats> {
+        self.backend.stats()
+    }
+
+    /// Get cache statistics after applying pending inserts and evictions
+    ///
+    /// `stats` is cheap but may lag recent writes; use this where accuracy
+    /// matters more than the extra work (reporting, the admin API).
+    pub async fn current_stats(&self) -> Option<CacheStats> {
+        self.backend.run_pending_tasks().await;
+        self.backend.stats()
+    }
+
+    /// Get the headers included in cache keys (`key_headers` and `force_vary_headers`)
     pub fn key_headers(&self) -> &[String] {
-        &self.config.key_headers
+        &self.key_headers
     }
 }
 
 /// Cache statistics
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct CacheStats {
     pub entry_count: u64,
     pub weighted_size: u64,
@@ -312,7 +946,7 @@ mod tests {
     #[test]
     fn test_cache_service_creation() {
         let config = CacheConfig::default();
-        let service = CacheService::new(config);
+        let service = CacheService::new(config).unwrap();
         assert!(service.is_cacheable_method("GET"));
         assert!(service.is_cacheable_method("get")); // case insensitive
         assert!(!service.is_cacheable_method("POST"));
@@ -321,7 +955,7 @@ mod tests {
     #[test]
     fn test_cacheable_status_codes() {
         let config = CacheConfig::default();
-        let service = CacheService::new(config);
+        let service = CacheService::new(config).unwrap();
         assert!(service.is_cacheable_status(200));
         assert!(service.is_cacheable_status(301));
         assert!(service.is_cacheable_status(404));
@@ -334,7 +968,7 @@ mod tests {
             cache_with_cookies: false,
             ..Default::default()
         };
-        let service = CacheService::new(config);
+        let service = CacheService::new(config).unwrap();
 
         let mut headers = HeaderMap::new();
         headers.insert("set-cookie", HeaderValue::from_static("session=abc"));
@@ -345,7 +979,7 @@ mod tests {
     #[test]
     fn test_response_cacheable_with_no_store() {
         let config = CacheConfig::default();
-        let service = CacheService::new(config);
+        let service = CacheService::new(config).unwrap();
 
         let mut headers = HeaderMap::new();
         headers.insert("cache-control", HeaderValue::from_static("no-store"));
@@ -356,7 +990,7 @@ mod tests {
     #[test]
     fn test_response_cacheable_with_private() {
         let config = CacheConfig::default();
-        let service = CacheService::new(config);
+        let service = CacheService::new(config).unwrap();
 
         let mut headers = HeaderMap::new();
         headers.insert("cache-control", HeaderValue::from_static("private"));
@@ -367,20 +1001,23 @@ mod tests {
     #[test]
     fn test_response_cacheable() {
         let config = CacheConfig::default();
-        let service = CacheService::new(config);
+        let service = CacheService::new(config).unwrap();
 
         let headers = HeaderMap::new();
         assert!(service.is_response_cacheable(&headers));
 
         let mut headers_with_public = HeaderMap::new();
-        headers_with_public.insert("cache-control", HeaderValue::from_static("public, max-age=3600"));
+        headers_with_public.insert(
+            "cache-control",
+            HeaderValue::from_static("public, max-age=3600"),
+        );
         assert!(service.is_response_cacheable(&headers_with_public));
     }
 
     #[tokio::test]
     async fn test_cache_put_and_get() {
         let config = CacheConfig::default();
-        let service = CacheService::new(config);
+        let service = CacheService::new(config).unwrap();
 
         let key = CacheKey::new(
             "GET".to_string(),
@@ -394,11 +1031,17 @@ mod tests {
         let body = Bytes::from("test response");
 
         service
-            .put(key.clone(), StatusCode::OK, headers.clone(), body.clone())
+            .put(
+                key.clone(),
+                &HeaderMap::new(),
+                StatusCode::OK,
+                headers.clone(),
+                body.clone(),
+            )
             .await
             .unwrap();
 
-        let cached = service.get(&key).await;
+        let cached = service.get(&key, &HeaderMap::new()).await;
         assert!(cached.is_some());
 
         let cached_response = cached.unwrap();
@@ -406,10 +1049,341 @@ mod tests {
         assert_eq!(cached_response.body, body);
     }
 
+    #[tokio::test]
+    async fn test_expired_entry_served_stale() {
+        let config = CacheConfig {
+            ttl_secs: 0,
+            stale_ttl_secs: 60,
+            ..Default::default()
+        };
+        let service = CacheService::new(config).unwrap();
+
+        let key = CacheKey::new(
+            "GET".to_string(),
+            "/test".to_string(),
+            None,
+            &HeaderMap::new(),
+            &[],
+        );
+        service
+            .put(
+                key.clone(),
+                &HeaderMap::new(),
+                StatusCode::OK,
+                HeaderMap::new(),
+                Bytes::from("last known good"),
+            )
+            .await
+            .unwrap();
+
+        // Already past its TTL, but still inside the stale window
+        assert!(service.get(&key, &HeaderMap::new()).await.is_none());
+
+        let stale = service.get_stale(&key, &HeaderMap::new()).await.unwrap();
+        assert_eq!(stale.body, Bytes::from("last known good"));
+        assert_eq!(stale.to_stale_response().headers()["X-Cache"], "STALE");
+    }
+
+    #[tokio::test]
+    async fn test_get_or_stale_within_stale_window() {
+        let config = CacheConfig {
+            ttl_secs: 0,
+            stale_ttl_secs: 60,
+            ..Default::default()
+        };
+        let service = CacheService::new(config).unwrap();
+        let key = CacheKey::new(
+            "GET".to_string(),
+            "/test".to_string(),
+            None,
+            &HeaderMap::new(),
+            &[],
+        );
+
+        assert!(matches!(
+            service.get_or_stale(&key, &HeaderMap::new()).await,
+            CacheLookup::Miss
+        ));
+
+        service
+            .put(
+                key.clone(),
+                &HeaderMap::new(),
+                StatusCode::OK,
+                HeaderMap::new(),
+                Bytes::from("old"),
+            )
+            .await
+            .unwrap();
+
+        // Past its TTL, but still served stale while it is refreshed
+        assert!(service.get(&key, &HeaderMap::new()).await.is_none());
+        match service.get_or_stale(&key, &HeaderMap::new()).await {
+            CacheLookup::Stale {
+                response,
+                key: stored_key,
+            } => {
+                assert_eq!(response.body, Bytes::from("old"));
+                assert_eq!(stored_key, key);
+            }
+            other => panic!("expected stale entry, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_or_stale_outside_stale_window() {
+        let config = CacheConfig {
+            ttl_secs: 0,
+            stale_ttl_secs: 0,
+            ..Default::default()
+        };
+        let service = CacheService::new(config).unwrap();
+        let key = CacheKey::new(
+            "GET".to_string(),
+            "/test".to_string(),
+            None,
+            &HeaderMap::new(),
+            &[],
+        );
+        service
+            .put(
+                key.clone(),
+                &HeaderMap::new(),
+                StatusCode::OK,
+                HeaderMap::new(),
+                Bytes::from("old"),
+            )
+            .await
+            .unwrap();
+
+        // Neither served while revalidating nor kept for fallbacks
+        assert!(matches!(
+            service.get_or_stale(&key, &HeaderMap::new()).await,
+            CacheLookup::Miss
+        ));
+        assert!(service.get_stale(&key, &HeaderMap::new()).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_negative_entry_expires_before_success() {
+        let config = CacheConfig {
+            ttl_secs: 60,
+            stale_ttl_secs: 60,
+            negative_status_codes: vec![503],
+            negative_ttl_secs: 1,
+            ..Default::default()
+        };
+        let service = CacheService::new(config).unwrap();
+        assert!(service.is_cacheable_status(503));
+        assert!(!service.is_cacheable_status(500));
+
+        let key = |path: &str| {
+            CacheKey::new(
+                "GET".to_string(),
+                path.to_string(),
+                None,
+                &HeaderMap::new(),
+                &[],
+            )
+        };
+        for (path, status) in [
+            ("/ok", StatusCode::OK),
+            ("/down", StatusCode::SERVICE_UNAVAILABLE),
+        ] {
+            service
+                .put(
+                    key(path),
+                    &HeaderMap::new(),
+                    status,
+                    HeaderMap::new(),
+                    Bytes::from("body"),
+                )
+                .await
+                .unwrap();
+        }
+        assert!(service
+            .get(&key("/down"), &HeaderMap::new())
+            .await
+            .is_some());
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+
+        // The error is gone and never served stale; the success is still fresh
+        assert!(service
+            .get(&key("/down"), &HeaderMap::new())
+            .await
+            .is_none());
+        assert!(matches!(
+            service.get_or_stale(&key("/down"), &HeaderMap::new()).await,
+            CacheLookup::Miss
+        ));
+        assert!(service
+            .get_stale(&key("/down"), &HeaderMap::new())
+            .await
+            .is_none());
+        assert!(service.get(&key("/ok"), &HeaderMap::new()).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_request_no_store_not_cached() {
+        let service = CacheService::new(CacheConfig::default()).unwrap();
+        let key = CacheKey::new(
+            "GET".to_string(),
+            "/test".to_string(),
+            None,
+            &HeaderMap::new(),
+            &[],
+        );
+        let mut request_headers = HeaderMap::new();
+        request_headers.insert(
+            CACHE_CONTROL,
+            HeaderValue::from_static("max-age=0, No-Store"),
+        );
+        assert!(has_request_directive(&request_headers, "no-store"));
+        assert!(!has_request_directive(&request_headers, "no-cache"));
+
+        service
+            .put(
+                key.clone(),
+                &request_headers,
+                StatusCode::OK,
+                HeaderMap::new(),
+                Bytes::from("private"),
+            )
+            .await
+            .unwrap();
+        assert!(service.get(&key, &HeaderMap::new()).await.is_none());
+    }
+
+    #[test]
+    fn test_refresh_is_single_flight() {
+        let service = CacheService::new(CacheConfig::default()).unwrap();
+        let key = CacheKey::new(
+            "GET".to_string(),
+            "/test".to_string(),
+            None,
+            &HeaderMap::new(),
+            &[],
+        );
+
+        let guard = service.try_begin_refresh(&key).unwrap();
+        assert!(service.try_begin_refresh(&key).is_none());
+
+        drop(guard);
+        assert!(service.try_begin_refresh(&key).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_fill_is_single_flight() {
+        let service = CacheService::new(CacheConfig::default()).unwrap();
+        let key = CacheKey::new(
+            "GET".to_string(),
+            "/test".to_string(),
+            None,
+            &HeaderMap::new(),
+            &[],
+        );
+
+        let CacheFill::Leader(guard) = service.join_fill(&key) else {
+            panic!("first request should lead the fill");
+        };
+        let CacheFill::Follower(waiter) = service.join_fill(&key) else {
+            panic!("concurrent request should follow the fill");
+        };
+        let waiting = tokio::spawn(waiter.wait());
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiting.is_finished());
+
+        drop(guard);
+        tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(service.join_fill(&key), CacheFill::Leader(_)));
+    }
+
+    fn cached_with(headers: &[(&'static str, &'static str)]) -> CachedResponse {
+        let mut header_map = HeaderMap::new();
+        for (name, value) in headers {
+            header_map.insert(*name, HeaderValue::from_static(value));
+        }
+        CachedResponse {
+            status: StatusCode::OK,
+            headers: header_map,
+            body: Bytes::from("payload"),
+            stored_at: Instant::now(),
+        }
+    }
+
+    fn request_with(name: &'static str, value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_static(value));
+        headers
+    }
+
+    #[test]
+    fn test_if_none_match() {
+        let cached = cached_with(&[("etag", "\"v1\"")]);
+
+        assert!(cached.is_not_modified(&request_with("if-none-match", "\"v1\"")));
+        assert!(cached.is_not_modified(&request_with("if-none-match", "\"v0\", W/\"v1\"")));
+        assert!(cached.is_not_modified(&request_with("if-none-match", "*")));
+        assert!(!cached.is_not_modified(&request_with("if-none-match", "\"v2\"")));
+        assert!(!cached.is_not_modified(&HeaderMap::new()));
+
+        // Without an ETag there is nothing to match
+        let untagged = cached_with(&[]);
+        assert!(!untagged.is_not_modified(&request_with("if-none-match", "\"v1\"")));
+
+        // Negatively cached errors are never answered with a 304
+        let error = CachedResponse {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            ..cached_with(&[("etag", "\"v1\"")])
+        };
+        assert!(!error.is_not_modified(&request_with("if-none-match", "*")));
+    }
+
+    #[test]
+    fn test_if_modified_since() {
+        let cached = cached_with(&[
+            ("etag", "\"v1\""),
+            ("last-modified", "Wed, 21 Oct 2015 07:28:00 GMT"),
+        ]);
+
+        let same = "Wed, 21 Oct 2015 07:28:00 GMT";
+        let later = "Thu, 22 Oct 2015 07:28:00 GMT";
+        let earlier = "Tue, 20 Oct 2015 07:28:00 GMT";
+        assert!(cached.is_not_modified(&request_with("if-modified-since", same)));
+        assert!(cached.is_not_modified(&request_with("if-modified-since", later)));
+        assert!(!cached.is_not_modified(&request_with("if-modified-since", earlier)));
+        assert!(!cached.is_not_modified(&request_with("if-modified-since", "yesterday")));
+
+        // If-None-Match takes precedence over If-Modified-Since
+        let mut headers = request_with("if-modified-since", later);
+        headers.insert("if-none-match", HeaderValue::from_static("\"v2\""));
+        assert!(!cached.is_not_modified(&headers));
+    }
+
+    #[test]
+    fn test_not_modified_response() {
+        let cached = cached_with(&[
+            ("etag", "\"v1\""),
+            ("cache-control", "max-age=60"),
+            ("content-type", "application/json"),
+        ]);
+
+        let response = cached.to_not_modified_response();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()["etag"], "\"v1\"");
+        assert_eq!(response.headers()["cache-control"], "max-age=60");
+        assert_eq!(response.headers()["X-Cache"], "HIT");
+        assert!(response.headers().get("content-type").is_none());
+    }
+
     #[tokio::test]
     async fn test_cache_invalidation() {
         let config = CacheConfig::default();
-        let service = CacheService::new(config);
+        let service = CacheService::new(config).unwrap();
 
         let key = CacheKey::new(
             "GET".to_string(),
@@ -422,6 +1396,7 @@ mod tests {
         service
             .put(
                 key.clone(),
+                &HeaderMap::new(),
                 StatusCode::OK,
                 HeaderMap::new(),
                 Bytes::from("test"),
@@ -429,11 +1404,11 @@ mod tests {
             .await
             .unwrap();
 
-        assert!(service.get(&key).await.is_some());
+        assert!(service.get(&key, &HeaderMap::new()).await.is_some());
 
         service.invalidate(&key).await;
 
-        assert!(service.get(&key).await.is_none());
+        assert!(service.get(&key, &HeaderMap::new()).await.is_none());
     }
 
     #[test]
@@ -463,4 +1438,307 @@ mod tests {
         assert_eq!(key1.headers.len(), 1);
         assert_eq!(key2.headers.len(), 0);
     }
+
+    #[test]
+    fn test_cache_key_digest_is_stable() {
+        let mut request_headers = HeaderMap::new();
+        request_headers.insert("accept-language", HeaderValue::from_static("en-US"));
+        let key_headers = ["Accept-Language".to_string()];
+
+        let key = |path: &str, query: Option<&str>| {
+            CacheKey::new(
+                "GET".to_string(),
+                path.to_string(),
+                query.map(str::to_string),
+                &request_headers,
+                &key_headers,
+            )
+        };
+
+        let digest = key("/test", Some("a=1")).digest();
+        assert_eq!(digest.len(), 64);
+        assert_eq!(digest, key("/test", Some("a=1")).digest());
+        assert_ne!(digest, key("/test", None).digest());
+        assert_ne!(digest, key("/test?a=1", None).digest());
+    }
+
+    #[test]
+    fn test_redis_backend_requires_settings() {
+        let config = CacheConfig {
+            backend: CacheBackendType::Redis,
+            ..Default::default()
+        };
+        let err = CacheService::new(config).unwrap_err();
+        assert!(err.to_string().contains("cache.redis"));
+
+        let config = CacheConfig {
+            backend: CacheBackendType::Redis,
+            redis: Some(CacheRedisConfig {
+                url: "redis://127.0.0.1:6379".to_string(),
+                prefix: default_redis_prefix(),
+                replica_url: None,
+            }),
+            ..Default::default()
+        };
+        assert!(CacheService::new(config).is_ok());
+    }
+
+    #[test]
+    fn test_backend_config_parsing() {
+        let config: CacheConfig =
+            serde_yaml::from_str("backend: redis\nredis:\n  url: redis://cache:6379\n").unwrap();
+        assert_eq!(config.backend, CacheBackendType::Redis);
+        let redis = config.redis.unwrap();
+        assert_eq!(redis.url, "redis://cache:6379");
+        assert_eq!(redis.prefix, "gateway:cache:");
+
+        let config: CacheConfig = serde_yaml::from_str("enabled: true").unwrap();
+        assert_eq!(config.backend, CacheBackendType::Memory);
+    }
+
+    #[tokio::test]
+    async fn test_force_vary_headers_separate_entries() {
+        let config = CacheConfig {
+            force_vary_headers: vec!["X-Tenant".to_string()],
+            ..Default::default()
+        };
+        let service = CacheService::new(config).unwrap();
+        assert_eq!(service.key_headers(), &["x-tenant".to_string()]);
+
+        let key_for = |tenant: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert("x-tenant", HeaderValue::from_static(tenant));
+            CacheKey::new(
+                "GET".to_string(),
+                "/profile".to_string(),
+                None,
+                &headers,
+                service.key_headers(),
+            )
+        };
+
+        let acme = key_for("acme");
+        let globex = key_for("globex");
+        assert_ne!(acme, globex);
+
+        service
+            .put(
+                acme.clone(),
+                &HeaderMap::new(),
+                StatusCode::OK,
+                HeaderMap::new(),
+                Bytes::from("acme"),
+            )
+            .await
+            .unwrap();
+        service
+            .put(
+                globex.clone(),
+                &HeaderMap::new(),
+                StatusCode::OK,
+                HeaderMap::new(),
+                Bytes::from("globex"),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            service.get(&acme, &HeaderMap::new()).await.unwrap().body,
+            Bytes::from("acme")
+        );
+        assert_eq!(
+            service.get(&globex, &HeaderMap::new()).await.unwrap().body,
+            Bytes::from("globex")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_vary_header_separates_entries() {
+        let service = CacheService::new(CacheConfig::default()).unwrap();
+        let key = CacheKey::new(
+            "GET".to_string(),
+            "/greeting".to_string(),
+            None,
+            &HeaderMap::new(),
+            service.key_headers(),
+        );
+        let request_for = |language: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert("accept-language", HeaderValue::from_static(language));
+            headers
+        };
+        let mut response_headers = HeaderMap::new();
+        response_headers.insert(VARY, HeaderValue::from_static("Accept-Language"));
+
+        for (language, body) in [("en", "hello"), ("fr", "bonjour")] {
+            service
+                .put(
+                    key.clone(),
+                    &request_for(language),
+                    StatusCode::OK,
+                    response_headers.clone(),
+                    Bytes::from(body),
+                )
+                .await
+                .unwrap();
+        }
+
+        let english = service.get(&key, &request_for("en")).await.unwrap();
+        assert_eq!(english.body, Bytes::from("hello"));
+        assert_eq!(english.headers[VARY], "Accept-Language");
+        assert_eq!(
+            service.get(&key, &request_for("fr")).await.unwrap().body,
+            Bytes::from("bonjour")
+        );
+        assert!(service.get(&key, &request_for("de")).await.is_none());
+        assert!(service.get(&key, &HeaderMap::new()).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_vary_on_key_header_stored_directly() {
+        let config = CacheConfig {
+            key_headers: vec!["Accept-Language".to_string()],
+            ..Default::default()
+        };
+        let service = CacheService::new(config).unwrap();
+        let mut request_headers = HeaderMap::new();
+        request_headers.insert("accept-language", HeaderValue::from_static("en"));
+        let key = CacheKey::new(
+            "GET".to_string(),
+            "/greeting".to_string(),
+            None,
+            &request_headers,
+            service.key_headers(),
+        );
+        let mut response_headers = HeaderMap::new();
+        response_headers.insert(VARY, HeaderValue::from_static("accept-language"));
+
+        service
+            .put(
+                key.clone(),
+                &request_headers,
+                StatusCode::OK,
+                response_headers,
+                Bytes::from("hello"),
+            )
+            .await
+            .unwrap();
+
+        // The key already varies on the header, so no variant entry is needed
+        assert_eq!(
+            key.with_vary(&["accept-language".to_string()], &request_headers),
+            key
+        );
+        assert_eq!(
+            service.backend.get(&key).await.unwrap().body,
+            Bytes::from("hello")
+        );
+    }
+
+    #[test]
+    fn test_vary_star_not_cacheable() {
+        let service = CacheService::new(CacheConfig::default()).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(VARY, HeaderValue::from_static("Accept-Encoding, *"));
+        assert!(!service.is_response_cacheable(&headers));
+    }
+
+    #[tokio::test]
+    async fn test_purge_by_prefix_and_tag() {
+        let service = CacheService::new(CacheConfig::default()).unwrap();
+        let key_for = |path: &str| {
+            CacheKey::new(
+                "GET".to_string(),
+                path.to_string(),
+                None,
+                &HeaderMap::new(),
+                &[],
+            )
+        };
+        let mut request_headers = HeaderMap::new();
+        request_headers.insert("accept-language", HeaderValue::from_static("en"));
+
+        for (path, tags) in [
+            ("/api/products/1", "product-1, products"),
+            ("/api/products/2", "product-2, products"),
+            ("/api/orders/1", "orders"),
+        ] {
+            let mut headers = HeaderMap::new();
+            headers.insert(CACHE_TAGS_HEADER, HeaderValue::from_static(tags));
+            headers.insert(VARY, HeaderValue::from_static("Accept-Language"));
+            service
+                .put(
+                    key_for(path),
+                    &request_headers,
+                    StatusCode::OK,
+                    headers,
+                    Bytes::from(path.to_string()),
+                )
+                .await
+                .unwrap();
+        }
+        let cached = |path: &'static str| {
+            let key = key_for(path);
+            let service = &service;
+            let request_headers = &request_headers;
+            async move { service.get(&key, request_headers).await.is_some() }
+        };
+
+        // Each Vary response is stored as a variant plus the key's marker,
+        // but only the response counts as purged
+        assert_eq!(service.purge_tag("product-1").await.unwrap(), 1);
+        assert!(!cached("/api/products/1").await);
+        assert!(cached("/api/products/2").await);
+
+        assert_eq!(service.purge_tag("missing").await.unwrap(), 0);
+        assert_eq!(service.purge_prefix("/api/products").await.unwrap(), 1);
+        assert!(!cached("/api/products/2").await);
+        assert!(cached("/api/orders/1").await);
+    }
+
+    #[tokio::test]
+    async fn test_purge_rejected_with_redis_backend() {
+        let service = CacheService::new(CacheConfig {
+            backend: CacheBackendType::Redis,
+            redis: Some(CacheRedisConfig {
+                url: "redis://127.0.0.1:1".to_string(),
+                prefix: default_redis_prefix(),
+                replica_url: None,
+            }),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert!(matches!(
+            service.purge_prefix("/api").await,
+            Err(GatewayError::NotSupported(_))
+        ));
+        assert!(matches!(
+            service.purge_tag("products").await,
+            Err(GatewayError::NotSupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_cache_tags_parsing() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CACHE_TAGS_HEADER, HeaderValue::from_static("b, a,,a "));
+        headers.append(CACHE_TAGS_HEADER, HeaderValue::from_static("c"));
+        assert_eq!(&*cache_tags(&headers), ["a", "b", "c"]);
+        assert!(cache_tags(&HeaderMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_force_vary_headers_merged_with_key_headers() {
+        let config = CacheConfig {
+            key_headers: vec!["Accept-Language".to_string(), "X-Tenant".to_string()],
+            force_vary_headers: vec!["x-tenant".to_string()],
+            ..Default::default()
+        };
+        let service = CacheService::new(config).unwrap();
+        assert_eq!(
+            service.key_headers(),
+            &["accept-language".to_string(), "x-tenant".to_string()]
+        );
+    }
 }
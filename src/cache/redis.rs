@@ -0,0 +1,495 @@
+use super::backend::CacheBackend;
+use super::{CacheKey, CacheStats, CachedResponse};
+use crate::error::{GatewayError, Result};
+use async_trait::async_trait;
+use axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use redis::{aio::ConnectionManager, AsyncCommands};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::OnceCell;
+use tracing::{debug, warn};
+
+/// Connection and command timeout; a slow cache must not stall requests
+const REDIS_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Reconnection attempts before an operation gives up and counts as a miss
+const REDIS_CONNECT_RETRIES: usize = 1;
+
+/// Cache shared by all gateway replicas through Redis
+///
+/// Entries are stored under the key prefix plus the `CacheKey` digest and
+/// expire after the retention period. Redis errors are logged and treated
+/// as cache misses so the backend is always the source of truth.
+pub struct RedisCacheBackend {
+    primary: RedisConnection,
+    /// Read replica for lookups; writes always go to the primary
+    replica: Option<RedisConnection>,
+    prefix: String,
+    retention: Duration,
+}
+
+impl std::fmt::Debug for RedisCacheBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisCacheBackend")
+            .field("prefix", &self.prefix)
+            .field("retention", &self.retention)
+            .field("replica", &self.replica.is_some())
+            .finish()
+    }
+}
+
+/// Redis client connected on first use, so startup does not depend on Redis being up
+struct RedisConnection {
+    url: String,
+    client: redis::Client,
+    connection: OnceCell<ConnectionManager>,
+}
+
+impl RedisConnection {
+    fn open(url: &str) -> Result<Self> {
+        let client = redis::Client::open(url)
+            .map_err(|e| GatewayError::Config(format!("Invalid cache Redis URL: {}", e)))?;
+
+        Ok(Self {
+            url: url.to_string(),
+            client,
+            connection: OnceCell::new(),
+        })
+    }
+
+    async fn get(&self) -> redis::RedisResult<ConnectionManager> {
+        self.connection
+            .get_or_try_init(|| {
+                ConnectionManager::new_with_backoff_and_timeouts(
+                    self.client.clone(),
+                    2,
+                    100,
+                    REDIS_CONNECT_RETRIES,
+                    REDIS_TIMEOUT,
+                    REDIS_TIMEOUT,
+                )
+            })
+            .await
+            .cloned()
+    }
+}
+
+/// Serialized form of a cached response
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    /// Base64-encoded body
+    body: String,
+    /// When the response was stored (Unix epoch milliseconds)
+    stored_at_ms: u64,
+}
+
+impl RedisCacheBackend {
+    /// Create a Redis cache backend keeping entries for `retention`
+    pub fn new(url: &str, prefix: String, retention: Duration) -> Result<Self> {
+        Ok(Self {
+            primary: RedisConnection::open(url)?,
+            replica: None,
+            prefix,
+            retention,
+        })
+    }
+
+    /// Read entries from a replica, falling back to the primary when a read fails
+    ///
+    /// A replica URL equal to the primary's is ignored.
+    pub fn with_replica(mut self, url: &str) -> Result<Self> {
+        let replica = RedisConnection::open(url)?;
+        self.replica = (replica.url != self.primary.url).then_some(replica);
+        Ok(self)
+    }
+
+    /// Whether lookups go to a separate replica
+    pub fn has_replica(&self) -> bool {
+        self.replica.is_some()
+    }
+
+    fn redis_key(&self, key: &CacheKey) -> String {
+        format!("{}{}", self.prefix, key.digest())
+    }
+
+    async fn connection(&self) -> redis::RedisResult<ConnectionManager> {
+        self.primary.get().await
+    }
+
+    /// Read a stored entry, from the replica if there is one
+    async fn read(&self, redis_key: &str) -> redis::RedisResult<Option<String>> {
+        if let Some(replica) = &self.replica {
+            let result = match replica.get().await {
+                Ok(mut conn) => conn.get(redis_key).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(data) => return Ok(data),
+                Err(e) => warn!(error = %e, "Redis cache replica read failed, retrying on primary"),
+            }
+        }
+
+        self.connection().await?.get(redis_key).await
+    }
+
+    fn encode(response: &CachedResponse) -> serde_json::Result<String> {
+        let age = response.stored_at.elapsed();
+        let stored_at = SystemTime::now()
+            .checked_sub(age)
+            .unwrap_or(SystemTime::now());
+
+        let headers = response
+            .headers
+            .iter()
+            .filter_map(|(name, value)| {
+                let value = value.to_str().ok()?;
+                Some((name.as_str().to_string(), value.to_string()))
+            })
+            .collect();
+
+        serde_json::to_string(&StoredResponse {
+            status: response.status.as_u16(),
+            headers,
+            body: BASE64.encode(&response.body),
+            stored_at_ms: stored_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        })
+    }
+
+    fn decode(data: &str) -> Option<CachedResponse> {
+        let stored: StoredResponse = serde_json::from_str(data).ok()?;
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in stored.headers {
+            let name = HeaderName::from_bytes(name.as_bytes()).ok()?;
+            let value = HeaderValue::from_str(&value).ok()?;
+            headers.append(name, value);
+        }
+
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let age = Duration::from_millis(now_ms.saturating_sub(stored.stored_at_ms));
+
+        Some(CachedResponse {
+            status: StatusCode::from_u16(stored.status).ok()?,
+            headers,
+            body: BASE64.decode(stored.body).ok()?.into(),
+            stored_at: Instant::now().checked_sub(age).unwrap_or_else(Instant::now),
+        })
+    }
+}
+
+#[async_trait]
+impl CacheBackend for RedisCacheBackend {
+    async fn get(&self, key: &CacheKey) -> Option<CachedResponse> {
+        let redis_key = self.redis_key(key);
+        let data = match self.read(&redis_key).await {
+            Ok(data) => data,
+            Err(e) => {
+                warn!(error = %e, "Redis cache lookup failed, treating as miss");
+                return None;
+            }
+        };
+
+        let cached = Self::decode(&data?);
+        if cached.is_none() {
+            warn!(key = %redis_key, "Discarding undecodable Redis cache entry");
+        }
+        cached
+    }
+
+    async fn put(&self, key: CacheKey, response: CachedResponse) {
+        let retention_ms = self.retention.as_millis() as u64;
+        if retention_ms == 0 {
+            return;
+        }
+
+        let data = match Self::encode(&response) {
+            Ok(data) => data,
+            Err(e) => {
+                warn!(error = %e, "Failed to serialize response for Redis cache");
+                return;
+            }
+        };
+
+        let redis_key = self.redis_key(&key);
+        let result = match self.connection().await {
+            Ok(mut conn) => {
+                conn.pset_ex::<_, _, ()>(&redis_key, data, retention_ms)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => debug!(key = %redis_key, "Stored response in Redis cache"),
+            Err(e) => warn!(error = %e, "Failed to store response in Redis cache"),
+        }
+    }
+
+    async fn invalidate(&self, key: &CacheKey) {
+        let redis_key = self.redis_key(key);
+        let result = match self.connection().await {
+            Ok(mut conn) => conn.del::<_, ()>(&redis_key).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            warn!(error = %e, "Failed to invalidate Redis cache entry");
+        }
+    }
+
+    async fn clear(&self) {
+        let mut conn = match self.connection().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!(error = %e, "Redis cache unavailable, cannot clear");
+                return;
+            }
+        };
+
+        let pattern = format!("{}*", self.prefix);
+        let keys: Vec<String> = {
+            let mut scan = match conn.scan_match::<_, String>(&pattern).await {
+                Ok(scan) => scan,
+                Err(e) => {
+                    warn!(error = %e, "Failed to scan Redis cache entries");
+                    return;
+                }
+            };
+            let mut keys = Vec::new();
+            while let Some(key) = scan.next_item().await {
+                keys.push(key);
+            }
+            keys
+        };
+
+        if keys.is_empty() {
+            return;
+        }
+        if let Err(e) = conn.del::<_, ()>(keys).await {
+            warn!(error = %e, "Failed to clear Redis cache entries");
+        }
+    }
+
+    /// Entries are shared across replicas and expire in Redis, so their
+    /// count is not known locally
+    fn stats(&self) -> Option<CacheStats> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::{TcpListener, TcpStream};
+
+    // Note: Tests marked #[ignore] require a running Redis instance
+    // Run with: cargo test -- --ignored
+
+    fn test_key(path: &str) -> CacheKey {
+        CacheKey::new(
+            "GET".to_string(),
+            path.to_string(),
+            None,
+            &HeaderMap::new(),
+            &[],
+        )
+    }
+
+    fn test_response(body: &'static str) -> CachedResponse {
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", HeaderValue::from_static("text/plain"));
+        headers.append("x-multi", HeaderValue::from_static("a"));
+        headers.append("x-multi", HeaderValue::from_static("b"));
+        CachedResponse {
+            status: StatusCode::OK,
+            headers,
+            body: Bytes::from(body),
+            stored_at: Instant::now(),
+        }
+    }
+
+    fn test_backend() -> RedisCacheBackend {
+        RedisCacheBackend::new(
+            "redis://127.0.0.1:6379",
+            format!("gateway:cache:test-{}:", rand::random::<u32>()),
+            Duration::from_secs(60),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let response = test_response("hello");
+        let decoded =
+            RedisCacheBackend::decode(&RedisCacheBackend::encode(&response).unwrap()).unwrap();
+
+        assert_eq!(decoded.status, StatusCode::OK);
+        assert_eq!(decoded.body, Bytes::from("hello"));
+        assert_eq!(decoded.headers["content-type"], "text/plain");
+        assert_eq!(decoded.headers.get_all("x-multi").iter().count(), 2);
+        assert!(decoded.stored_at.elapsed() < Duration::from_secs(1));
+    }
+
+    /// Start a fake Redis that answers every `GET` with `value`
+    async fn spawn_fake_redis(value: String) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(serve_fake_redis(socket, value.clone()));
+            }
+        });
+
+        format!("redis://{}", addr)
+    }
+
+    async fn serve_fake_redis(socket: TcpStream, value: String) {
+        let (reader, mut writer) = socket.into_split();
+        let mut reader = BufReader::new(reader);
+        let mut line = String::new();
+
+        loop {
+            // Each command is an array of bulk strings: *<argc>, then $<len> <arg>
+            line.clear();
+            if reader.read_line(&mut line).await.unwrap_or(0) == 0 {
+                return;
+            }
+            let argc: usize = line.trim().trim_start_matches('*').parse().unwrap_or(0);
+
+            let mut command = String::new();
+            for i in 0..argc {
+                line.clear();
+                reader.read_line(&mut line).await.unwrap();
+                let len: usize = line.trim().trim_start_matches('$').parse().unwrap();
+                let mut arg = vec![0; len + 2];
+                reader.read_exact(&mut arg).await.unwrap();
+                if i == 0 {
+                    command = String::from_utf8_lossy(&arg[..len]).to_uppercase();
+                }
+            }
+
+            let reply = if command == "GET" {
+                format!("${}\r\n{}\r\n", value.len(), value)
+            } else {
+                "+OK\r\n".to_string()
+            };
+            if writer.write_all(reply.as_bytes()).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    #[test]
+    fn test_distinct_replica_url_creates_replica() {
+        let backend = |replica: &str| {
+            RedisCacheBackend::new(
+                "redis://primary:6379",
+                String::new(),
+                Duration::from_secs(1),
+            )
+            .unwrap()
+            .with_replica(replica)
+        };
+
+        assert!(backend("redis://replica:6379").unwrap().has_replica());
+        assert!(!backend("redis://primary:6379").unwrap().has_replica());
+        assert!(backend("not a url").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_replica_reads_from_primary() {
+        let stored = RedisCacheBackend::encode(&test_response("from primary")).unwrap();
+        let primary = spawn_fake_redis(stored).await;
+        let backend = RedisCacheBackend::new(
+            &primary,
+            "gateway:cache:".to_string(),
+            Duration::from_secs(60),
+        )
+        .unwrap()
+        .with_replica("redis://127.0.0.1:1")
+        .unwrap();
+
+        let cached = backend.get(&test_key("/replicated")).await.unwrap();
+        assert_eq!(cached.body, Bytes::from("from primary"));
+    }
+
+    #[test]
+    fn test_invalid_url_rejected() {
+        let result = RedisCacheBackend::new("not a url", String::new(), Duration::from_secs(1));
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_redis_is_a_miss() {
+        let backend = RedisCacheBackend::new(
+            "redis://127.0.0.1:1",
+            "gateway:cache:".to_string(),
+            Duration::from_secs(60),
+        )
+        .unwrap();
+
+        backend.put(test_key("/down"), test_response("x")).await;
+        assert!(backend.get(&test_key("/down")).await.is_none());
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_put_get_round_trip() {
+        let backend = test_backend();
+        let key = test_key("/round-trip");
+
+        backend.put(key.clone(), test_response("shared")).await;
+
+        let cached = backend.get(&key).await.unwrap();
+        assert_eq!(cached.status, StatusCode::OK);
+        assert_eq!(cached.body, Bytes::from("shared"));
+        assert_eq!(cached.headers.get_all("x-multi").iter().count(), 2);
+        assert!(backend.get(&test_key("/other")).await.is_none());
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_invalidate_and_clear() {
+        let backend = test_backend();
+        let first = test_key("/first");
+        let second = test_key("/second");
+
+        backend.put(first.clone(), test_response("1")).await;
+        backend.put(second.clone(), test_response("2")).await;
+
+        backend.invalidate(&first).await;
+        assert!(backend.get(&first).await.is_none());
+        assert!(backend.get(&second).await.is_some());
+
+        backend.clear().await;
+        assert!(backend.get(&second).await.is_none());
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_entries_expire() {
+        let backend = RedisCacheBackend::new(
+            "redis://127.0.0.1:6379",
+            format!("gateway:cache:test-{}:", rand::random::<u32>()),
+            Duration::from_millis(100),
+        )
+        .unwrap();
+        let key = test_key("/expiring");
+
+        backend.put(key.clone(), test_response("short-lived")).await;
+        assert!(backend.get(&key).await.is_some());
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(backend.get(&key).await.is_none());
+    }
+}
@@ -1,4 +1,5 @@
-use super::types::{CircuitBreakerConfig, CircuitBreakerMetrics, CircuitState};
+use super::types::{CircuitBreakerConfig, CircuitBreakerMetrics, CircuitBreakerMode, CircuitState};
+use std::collections::VecDeque;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::RwLock;
@@ -21,12 +22,19 @@ struct State {
     circuit_state: CircuitState,
     /// Number of consecutive failures in closed state
     consecutive_failures: u32,
+    /// Number of consecutive timeouts in closed state
+    consecutive_timeouts: u32,
+    /// Outcomes of the most recent requests in closed state (`true` = failed)
+    recent_outcomes: VecDeque<bool>,
     /// Number of consecutive successes in half-open state
     consecutive_successes: u32,
     /// Number of half-open requests in flight
     half_open_requests: u32,
     /// Time when the circuit was opened
     opened_at: Option<Instant>,
+    /// Whether an operator forced the current state; automatic transitions
+    /// are suspended until the force is cleared
+    force_isolated: bool,
     /// Metrics
     metrics: CircuitBreakerMetrics,
 }
@@ -36,9 +44,11 @@ impl CircuitBreaker {
     pub fn new(backend: String, config: CircuitBreakerConfig) -> Self {
         info!(
             backend = %backend,
+            mode = ?config.mode,
             failure_threshold = config.failure_threshold,
             success_threshold = config.success_threshold,
             timeout_secs = config.timeout_secs,
+            timeout_threshold = ?config.timeout_threshold,
             "Creating circuit breaker"
         );
 
@@ -47,9 +57,12 @@ impl CircuitBreaker {
             state: Arc::new(RwLock::new(State {
                 circuit_state: CircuitState::Closed,
                 consecutive_failures: 0,
+                consecutive_timeouts: 0,
+                recent_outcomes: VecDeque::new(),
                 consecutive_successes: 0,
                 half_open_requests: 0,
                 opened_at: None,
+                force_isolated: false,
                 metrics: CircuitBreakerMetrics::default(),
             })),
             backend,
@@ -60,6 +73,16 @@ impl CircuitBreaker {
     pub async fn can_proceed(&self) -> bool {
         let mut state = self.state.write().await;
 
+        if state.force_isolated {
+            if state.circuit_state == CircuitState::Open {
+                state.metrics.rejected_requests += 1;
+                debug!(backend = %self.backend, "Circuit breaker forced open, rejecting request");
+                return false;
+            }
+            state.metrics.total_requests += 1;
+            return true;
+        }
+
         match state.circuit_state {
             CircuitState::Closed => {
                 state.metrics.total_requests += 1;
@@ -118,11 +141,16 @@ impl CircuitBreaker {
     pub async fn record_success(&self) {
         let mut state = self.state.write().await;
         state.metrics.successful_requests += 1;
+        if state.force_isolated {
+            return;
+        }
 
         match state.circuit_state {
             CircuitState::Closed => {
-                // Reset failure count on success
+                // Reset failure and timeout counts on success
                 state.consecutive_failures = 0;
+                state.consecutive_timeouts = 0;
+                self.record_outcome(&mut state, false);
             }
             CircuitState::HalfOpen => {
                 state.consecutive_successes += 1;
@@ -151,10 +179,14 @@ impl CircuitBreaker {
     pub async fn record_failure(&self) {
         let mut state = self.state.write().await;
         state.metrics.failed_requests += 1;
+        if state.force_isolated {
+            return;
+        }
 
         match state.circuit_state {
             CircuitState::Closed => {
                 state.consecutive_failures += 1;
+                self.record_outcome(&mut state, true);
 
                 debug!(
                     backend = %self.backend,
@@ -164,7 +196,7 @@ impl CircuitBreaker {
                 );
 
                 // Check if we should open the circuit
-                if state.consecutive_failures >= self.config.failure_threshold {
+                if self.should_trip(&state) {
                     self.transition_to_open(&mut state);
                 }
             }
@@ -184,13 +216,65 @@ impl CircuitBreaker {
         }
     }
 
+    /// Release a half-open probe slot without counting the request
+    ///
+    /// For requests the gateway rejected after the backend answered (e.g. an
+    /// oversized response), which say nothing about the backend's health.
+    pub async fn record_ignored(&self) {
+        let mut state = self.state.write().await;
+        if state.circuit_state == CircuitState::HalfOpen {
+            state.half_open_requests = state.half_open_requests.saturating_sub(1);
+        }
+    }
+
     /// Record a timeout
+    ///
+    /// Without a `timeout_threshold`, a timeout is treated as a failure.
+    /// Otherwise timeouts are counted separately and open the circuit once
+    /// the timeout threshold is reached.
     pub async fn record_timeout(&self) {
+        let Some(timeout_threshold) = self.config.timeout_threshold else {
+            let mut state = self.state.write().await;
+            state.metrics.timeout_count += 1;
+            drop(state);
+            // Treat timeout as failure
+            self.record_failure().await;
+            return;
+        };
+
         let mut state = self.state.write().await;
         state.metrics.timeout_count += 1;
-        drop(state);
-        // Treat timeout as failure
-        self.record_failure().await;
+        if state.force_isolated {
+            return;
+        }
+
+        match state.circuit_state {
+            CircuitState::Closed => {
+                state.consecutive_timeouts += 1;
+
+                debug!(
+                    backend = %self.backend,
+                    consecutive_timeouts = state.consecutive_timeouts,
+                    threshold = timeout_threshold,
+                    "Request timed out in closed state"
+                );
+
+                if state.consecutive_timeouts >= timeout_threshold {
+                    self.transition_to_open(&mut state);
+                }
+            }
+            CircuitState::HalfOpen => {
+                state.half_open_requests = state.half_open_requests.saturating_sub(1);
+                warn!(
+                    backend = %self.backend,
+                    "Half-open probe request timed out, reopening circuit"
+                );
+                self.transition_to_open(&mut state);
+            }
+            CircuitState::Open => {
+                debug!(backend = %self.backend, "Recording timeout in open state");
+            }
+        }
     }
 
     /// Get current state
@@ -203,17 +287,109 @@ impl CircuitBreaker {
         self.state.read().await.metrics.clone()
     }
 
+    /// Whether the current state was forced and ignores automatic transitions
+    pub async fn is_force_isolated(&self) -> bool {
+        self.state.read().await.force_isolated
+    }
+
+    /// Force the circuit open, rejecting all requests until the force is cleared
+    pub async fn force_open(&self) {
+        let mut state = self.state.write().await;
+        if state.circuit_state != CircuitState::Open {
+            self.transition_to_open(&mut state);
+        }
+        state.force_isolated = true;
+        warn!(backend = %self.backend, "Circuit breaker forced open");
+    }
+
+    /// Force the circuit closed, allowing all requests until the force is cleared
+    pub async fn force_close(&self) {
+        let mut state = self.state.write().await;
+        if state.circuit_state != CircuitState::Closed {
+            self.transition_to_closed(&mut state);
+        }
+        state.force_isolated = true;
+        warn!(backend = %self.backend, "Circuit breaker forced closed");
+    }
+
+    /// Clear a forced state and resume automatic transitions
+    ///
+    /// A circuit cleared while open waits a full `timeout` before probing.
+    pub async fn clear_forced(&self) {
+        let mut state = self.state.write().await;
+        if !state.force_isolated {
+            return;
+        }
+
+        state.force_isolated = false;
+        if state.circuit_state == CircuitState::Open {
+            state.opened_at = Some(Instant::now());
+        }
+        info!(
+            backend = %self.backend,
+            state = %state.circuit_state,
+            "Circuit breaker force cleared, resuming automatic transitions"
+        );
+    }
+
+    /// Remember the outcome of a request for error rate mode
+    fn record_outcome(&self, state: &mut State, failed: bool) {
+        if self.config.mode != CircuitBreakerMode::ErrorRate {
+            return;
+        }
+
+        state.recent_outcomes.push_back(failed);
+        while state.recent_outcomes.len() > self.config.window_size as usize {
+            state.recent_outcomes.pop_front();
+        }
+    }
+
+    /// Whether the failures recorded in closed state should open the circuit
+    fn should_trip(&self, state: &State) -> bool {
+        match self.config.mode {
+            CircuitBreakerMode::Consecutive => {
+                state.consecutive_failures >= self.config.failure_threshold
+            }
+            CircuitBreakerMode::ErrorRate => {
+                let total = state.recent_outcomes.len();
+                if total == 0 || total < self.config.minimum_requests as usize {
+                    return false;
+                }
+
+                let failures = state
+                    .recent_outcomes
+                    .iter()
+                    .filter(|&&failed| failed)
+                    .count();
+                let error_rate = failures as f64 / total as f64;
+
+                debug!(
+                    backend = %self.backend,
+                    error_rate,
+                    threshold = self.config.error_rate_threshold,
+                    window = total,
+                    "Evaluated error rate"
+                );
+
+                error_rate > self.config.error_rate_threshold
+            }
+        }
+    }
+
     /// Transition to open state
     fn transition_to_open(&self, state: &mut State) {
         info!(
             backend = %self.backend,
             consecutive_failures = state.consecutive_failures,
+            consecutive_timeouts = state.consecutive_timeouts,
             "Circuit breaker opening"
         );
 
         state.circuit_state = CircuitState::Open;
         state.opened_at = Some(Instant::now());
         state.consecutive_failures = 0;
+        state.consecutive_timeouts = 0;
+        state.recent_outcomes.clear();
         state.consecutive_successes = 0;
         state.half_open_requests = 0;
         state.metrics.circuit_opened_count += 1;
@@ -229,6 +405,8 @@ impl CircuitBreaker {
 
         state.circuit_state = CircuitState::HalfOpen;
         state.consecutive_failures = 0;
+        state.consecutive_timeouts = 0;
+        state.recent_outcomes.clear();
         state.consecutive_successes = 0;
         state.half_open_requests = 0;
         state.metrics.circuit_half_opened_count += 1;
@@ -245,6 +423,8 @@ impl CircuitBreaker {
         state.circuit_state = CircuitState::Closed;
         state.opened_at = None;
         state.consecutive_failures = 0;
+        state.consecutive_timeouts = 0;
+        state.recent_outcomes.clear();
         state.consecutive_successes = 0;
         state.half_open_requests = 0;
         state.metrics.circuit_closed_count += 1;
@@ -344,6 +524,30 @@ mod tests {
         assert!(!cb.can_proceed().await);
     }
 
+    #[tokio::test]
+    async fn test_ignored_request_frees_half_open_slot() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            half_open_requests: 1,
+            timeout_secs: 0,
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("test-backend".to_string(), config);
+        cb.record_failure().await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert!(cb.can_proceed().await);
+        assert!(!cb.can_proceed().await);
+        cb.record_ignored().await;
+
+        // The probe counted neither way, so another one may go out
+        assert_eq!(cb.state().await, CircuitState::HalfOpen);
+        assert!(cb.can_proceed().await);
+        let metrics = cb.metrics().await;
+        assert_eq!(metrics.successful_requests, 0);
+        assert_eq!(metrics.failed_requests, 1);
+    }
+
     #[tokio::test]
     async fn test_half_open_closes_on_success() {
         let config = CircuitBreakerConfig {
@@ -437,4 +641,266 @@ mod tests {
         assert_eq!(metrics.rejected_requests, 1);
         assert_eq!(metrics.circuit_opened_count, 1);
     }
+
+    #[tokio::test]
+    async fn test_timeouts_trip_at_timeout_threshold() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 10,
+            timeout_threshold: Some(2),
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("test-backend".to_string(), config);
+
+        assert!(cb.can_proceed().await);
+        cb.record_timeout().await;
+        assert_eq!(cb.state().await, CircuitState::Closed);
+
+        assert!(cb.can_proceed().await);
+        cb.record_timeout().await;
+        assert_eq!(cb.state().await, CircuitState::Open);
+
+        let metrics = cb.metrics().await;
+        assert_eq!(metrics.timeout_count, 2);
+        assert_eq!(metrics.failed_requests, 0);
+        assert_eq!(metrics.circuit_opened_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_timeouts_counted_separately_from_failures() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 3,
+            timeout_threshold: Some(3),
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("test-backend".to_string(), config);
+
+        // Two failures and two timeouts: neither threshold is reached
+        for _ in 0..2 {
+            assert!(cb.can_proceed().await);
+            cb.record_failure().await;
+            assert!(cb.can_proceed().await);
+            cb.record_timeout().await;
+        }
+        assert_eq!(cb.state().await, CircuitState::Closed);
+
+        // A success resets the timeout streak
+        assert!(cb.can_proceed().await);
+        cb.record_success().await;
+        for _ in 0..2 {
+            assert!(cb.can_proceed().await);
+            cb.record_timeout().await;
+        }
+        assert_eq!(cb.state().await, CircuitState::Closed);
+
+        assert!(cb.can_proceed().await);
+        cb.record_timeout().await;
+        assert_eq!(cb.state().await, CircuitState::Open);
+    }
+
+    #[tokio::test]
+    async fn test_timeouts_count_as_failures_without_threshold() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 2,
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("test-backend".to_string(), config);
+
+        assert!(cb.can_proceed().await);
+        cb.record_failure().await;
+        assert!(cb.can_proceed().await);
+        cb.record_timeout().await;
+
+        assert_eq!(cb.state().await, CircuitState::Open);
+        let metrics = cb.metrics().await;
+        assert_eq!(metrics.timeout_count, 1);
+        assert_eq!(metrics.failed_requests, 2);
+    }
+
+    fn error_rate_config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            mode: CircuitBreakerMode::ErrorRate,
+            error_rate_threshold: 0.5,
+            window_size: 20,
+            minimum_requests: 10,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_error_rate_trips_on_interleaved_failures() {
+        let cb = CircuitBreaker::new("test-backend".to_string(), error_rate_config());
+
+        // Two failures for every success: never enough consecutive failures,
+        // but above a 50% error rate once the minimum volume is reached
+        for i in 0..11 {
+            assert!(cb.can_proceed().await);
+            if i % 3 == 0 {
+                cb.record_success().await;
+            } else {
+                cb.record_failure().await;
+            }
+        }
+
+        assert_eq!(cb.state().await, CircuitState::Open);
+        assert!(!cb.can_proceed().await);
+    }
+
+    #[tokio::test]
+    async fn test_error_rate_respects_minimum_volume() {
+        let cb = CircuitBreaker::new("test-backend".to_string(), error_rate_config());
+
+        // 100% failure rate, but fewer requests than the minimum volume
+        for _ in 0..9 {
+            assert!(cb.can_proceed().await);
+            cb.record_failure().await;
+        }
+        assert_eq!(cb.state().await, CircuitState::Closed);
+
+        assert!(cb.can_proceed().await);
+        cb.record_failure().await;
+        assert_eq!(cb.state().await, CircuitState::Open);
+    }
+
+    #[tokio::test]
+    async fn test_error_rate_at_threshold_stays_closed() {
+        let cb = CircuitBreaker::new("test-backend".to_string(), error_rate_config());
+
+        // Alternating outcomes keep the error rate at exactly 50%
+        for i in 0..40 {
+            assert!(cb.can_proceed().await);
+            if i % 2 == 0 {
+                cb.record_success().await;
+            } else {
+                cb.record_failure().await;
+            }
+        }
+
+        assert_eq!(cb.state().await, CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_error_rate_window_forgets_old_failures() {
+        let cb = CircuitBreaker::new("test-backend".to_string(), error_rate_config());
+
+        for _ in 0..5 {
+            assert!(cb.can_proceed().await);
+            cb.record_failure().await;
+        }
+        // Enough successes to push the failures out of the 20-request window
+        for _ in 0..20 {
+            assert!(cb.can_proceed().await);
+            cb.record_success().await;
+        }
+        for _ in 0..10 {
+            assert!(cb.can_proceed().await);
+            cb.record_failure().await;
+        }
+
+        // 10 failures out of the last 20 is not above 50%
+        assert_eq!(cb.state().await, CircuitState::Closed);
+
+        assert!(cb.can_proceed().await);
+        cb.record_failure().await;
+        assert_eq!(cb.state().await, CircuitState::Open);
+    }
+
+    #[tokio::test]
+    async fn test_error_rate_half_open_recovery() {
+        let config = CircuitBreakerConfig {
+            timeout_secs: 0,
+            success_threshold: 1,
+            ..error_rate_config()
+        };
+        let cb = CircuitBreaker::new("test-backend".to_string(), config);
+
+        for _ in 0..10 {
+            assert!(cb.can_proceed().await);
+            cb.record_failure().await;
+        }
+        assert_eq!(cb.state().await, CircuitState::Open);
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(cb.can_proceed().await);
+        assert_eq!(cb.state().await, CircuitState::HalfOpen);
+        cb.record_success().await;
+        assert_eq!(cb.state().await, CircuitState::Closed);
+
+        // The window starts fresh after closing
+        for _ in 0..9 {
+            assert!(cb.can_proceed().await);
+            cb.record_failure().await;
+        }
+        assert_eq!(cb.state().await, CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_forced_open_rejects_regardless_of_successes() {
+        let config = CircuitBreakerConfig {
+            timeout_secs: 0,
+            success_threshold: 1,
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("test-backend".to_string(), config);
+
+        cb.force_open().await;
+        assert!(cb.is_force_isolated().await);
+        assert_eq!(cb.state().await, CircuitState::Open);
+
+        // Neither the elapsed timeout nor successes move a forced circuit
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        for _ in 0..5 {
+            assert!(!cb.can_proceed().await);
+            cb.record_success().await;
+        }
+        assert_eq!(cb.state().await, CircuitState::Open);
+        assert_eq!(cb.metrics().await.rejected_requests, 5);
+    }
+
+    #[tokio::test]
+    async fn test_forced_closed_ignores_failures() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 2,
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("test-backend".to_string(), config);
+
+        cb.force_close().await;
+        for _ in 0..5 {
+            assert!(cb.can_proceed().await);
+            cb.record_failure().await;
+            cb.record_timeout().await;
+        }
+        assert_eq!(cb.state().await, CircuitState::Closed);
+        assert_eq!(cb.metrics().await.failed_requests, 10);
+    }
+
+    #[tokio::test]
+    async fn test_clearing_force_restores_automatic_behavior() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 2,
+            success_threshold: 1,
+            timeout_secs: 0,
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("test-backend".to_string(), config);
+
+        // Cleared while open: recovers through half-open as usual
+        cb.force_open().await;
+        cb.clear_forced().await;
+        assert!(!cb.is_force_isolated().await);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(cb.can_proceed().await);
+        assert_eq!(cb.state().await, CircuitState::HalfOpen);
+        cb.record_success().await;
+        assert_eq!(cb.state().await, CircuitState::Closed);
+
+        // Cleared while closed: failures trip the circuit again
+        cb.force_close().await;
+        cb.clear_forced().await;
+        for _ in 0..2 {
+            assert!(cb.can_proceed().await);
+            cb.record_failure().await;
+        }
+        assert_eq!(cb.state().await, CircuitState::Open);
+    }
 }
@@ -4,6 +4,9 @@ pub mod service;
 pub mod types;
 
 pub use breaker::CircuitBreaker;
-pub use retry::RetryExecutor;
+pub use retry::{parse_retry_after, RetryExecutor, RetryLogContext};
 pub use service::CircuitBreakerService;
-pub use types::{CircuitBreakerConfig, CircuitBreakerMetrics, CircuitState, RetryConfig};
+pub use types::{
+    CircuitBreakerConfig, CircuitBreakerMetrics, CircuitBreakerMode, CircuitState, FallbackConfig,
+    RetryConfig,
+};
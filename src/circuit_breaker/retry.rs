@@ -1,7 +1,17 @@
 use super::types::RetryConfig;
 use backoff::{backoff::Backoff, ExponentialBackoff, ExponentialBackoffBuilder};
+use std::time::{Duration, Instant, SystemTime};
 use tracing::{debug, warn};
 
+/// Identifies the request being retried in per-attempt logs
+#[derive(Debug, Clone)]
+pub struct RetryLogContext {
+    /// Request ID, if one was assigned
+    pub request_id: Option<String>,
+    /// Backend the attempts are sent to
+    pub backend: String,
+}
+
 /// Retry executor with exponential backoff
 pub struct RetryExecutor {
     config: RetryConfig,
@@ -13,6 +23,11 @@ impl RetryExecutor {
         Self { config }
     }
 
+    /// Retry configuration in use
+    pub fn config(&self) -> &RetryConfig {
+        &self.config
+    }
+
     /// Execute a function with retries
     pub async fn execute<F, Fut, T, E>(&self, mut f: F) -> Result<T, E>
     where
@@ -70,7 +85,7 @@ impl RetryExecutor {
     /// Execute with retries, but only if error matches predicate
     pub async fn execute_with_predicate<F, Fut, T, E, P>(
         &self,
-        mut f: F,
+        f: F,
         should_retry: P,
     ) -> Result<T, E>
     where
@@ -78,9 +93,59 @@ impl RetryExecutor {
         Fut: std::future::Future<Output = Result<T, E>>,
         E: std::fmt::Display,
         P: Fn(&E) -> bool,
+    {
+        self.run(None, None, f, should_retry, |_| None).await.0
+    }
+
+    /// Execute with retries on behalf of a proxied request
+    ///
+    /// Behaves like [`execute_with_predicate`](Self::execute_with_predicate)
+    /// and, when `log_attempts` is enabled, logs every attempt with the
+    /// request ID and backend. Also returns the number of attempts made.
+    ///
+    /// `retry_after` can return a server-requested delay for a failure (e.g.
+    /// from a `Retry-After` header); the next attempt then waits at least that
+    /// long, capped at `max_retry_after_ms`.
+    ///
+    /// With a `deadline`, no retry is attempted once the next wait would
+    /// reach it, and the last failure is returned instead.
+    pub async fn execute_for_request<F, Fut, T, E, P, R>(
+        &self,
+        context: &RetryLogContext,
+        deadline: Option<Instant>,
+        f: F,
+        should_retry: P,
+        retry_after: R,
+    ) -> (Result<T, E>, u32)
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, E>>,
+        E: std::fmt::Display,
+        P: Fn(&E) -> bool,
+        R: Fn(&E) -> Option<Duration>,
+    {
+        self.run(Some(context), deadline, f, should_retry, retry_after)
+            .await
+    }
+
+    async fn run<F, Fut, T, E, P, R>(
+        &self,
+        context: Option<&RetryLogContext>,
+        deadline: Option<Instant>,
+        mut f: F,
+        should_retry: P,
+        retry_after: R,
+    ) -> (Result<T, E>, u32)
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, E>>,
+        E: std::fmt::Display,
+        P: Fn(&E) -> bool,
+        R: Fn(&E) -> Option<Duration>,
     {
         let mut backoff = self.create_backoff();
         let mut attempt = 0;
+        let context = context.filter(|_| self.config.log_attempts);
 
         loop {
             attempt += 1;
@@ -90,17 +155,43 @@ impl RetryExecutor {
                 "Executing request"
             );
 
-            match f().await {
+            let attempt_start = Instant::now();
+            let result = f().await;
+
+            if let Some(context) = context {
+                let latency_ms = attempt_start.elapsed().as_secs_f64() * 1000.0;
+                match &result {
+                    Ok(_) => debug!(
+                        request_id = context.request_id.as_deref().unwrap_or("-"),
+                        attempt,
+                        backend = %context.backend,
+                        latency_ms,
+                        outcome = "success",
+                        "Request attempt finished"
+                    ),
+                    Err(e) => debug!(
+                        request_id = context.request_id.as_deref().unwrap_or("-"),
+                        attempt,
+                        backend = %context.backend,
+                        latency_ms,
+                        outcome = "error",
+                        error = %e,
+                        "Request attempt finished"
+                    ),
+                }
+            }
+
+            match result {
                 Ok(result) => {
                     if attempt > 1 {
                         debug!(attempt, "Request succeeded after retries");
                     }
-                    return Ok(result);
+                    return (Ok(result), attempt);
                 }
                 Err(e) => {
                     if !should_retry(&e) {
                         debug!(attempt, error = %e, "Error not retryable");
-                        return Err(e);
+                        return (Err(e), attempt);
                     }
 
                     if attempt > self.config.max_retries {
@@ -110,10 +201,26 @@ impl RetryExecutor {
                             error = %e,
                             "Request failed after max retries"
                         );
-                        return Err(e);
+                        return (Err(e), attempt);
                     }
 
                     if let Some(wait) = backoff.next_backoff() {
+                        // Never retry sooner than the backend asked us to
+                        let wait = match retry_after(&e) {
+                            Some(requested) => {
+                                wait.max(requested.min(self.config.max_retry_after()))
+                            }
+                            None => wait,
+                        };
+                        if deadline.is_some_and(|deadline| Instant::now() + wait >= deadline) {
+                            warn!(
+                                attempt,
+                                wait_ms = wait.as_millis(),
+                                error = %e,
+                                "Request deadline reached before the next retry"
+                            );
+                            return (Err(e), attempt);
+                        }
                         debug!(
                             attempt,
                             wait_ms = wait.as_millis(),
@@ -123,7 +230,7 @@ impl RetryExecutor {
                         tokio::time::sleep(wait).await;
                     } else {
                         warn!(attempt, error = %e, "Backoff exhausted");
-                        return Err(e);
+                        return (Err(e), attempt);
                     }
                 }
             }
@@ -140,12 +247,54 @@ impl RetryExecutor {
     }
 }
 
+/// Parse a `Retry-After` header value: delay seconds or an HTTP-date
+///
+/// Dates in the past yield a zero delay.
+pub fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(now).unwrap_or(Duration::ZERO))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
     use std::sync::atomic::{AtomicU32, Ordering};
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
     use std::time::Duration;
+    use tracing::field::{Field, Visit};
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::Layer;
+
+    /// Collects the fields of every event emitted while it is installed
+    #[derive(Clone, Default)]
+    struct CapturedEvents(Arc<Mutex<Vec<HashMap<String, String>>>>);
+
+    impl<S: tracing::Subscriber> Layer<S> for CapturedEvents {
+        fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+            let mut fields = FieldMap::default();
+            event.record(&mut fields);
+            self.0.lock().unwrap().push(fields.0);
+        }
+    }
+
+    #[derive(Default)]
+    struct FieldMap(HashMap<String, String>);
+
+    impl Visit for FieldMap {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{:?}", value));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+    }
 
     #[tokio::test]
     async fn test_retry_succeeds_immediately() {
@@ -154,6 +303,7 @@ mod tests {
             initial_backoff_ms: 10,
             max_backoff_ms: 100,
             backoff_multiplier: 2.0,
+            ..Default::default()
         };
         let executor = RetryExecutor::new(config);
 
@@ -171,6 +321,7 @@ mod tests {
             initial_backoff_ms: 10,
             max_backoff_ms: 100,
             backoff_multiplier: 2.0,
+            ..Default::default()
         };
         let executor = RetryExecutor::new(config);
 
@@ -202,6 +353,7 @@ mod tests {
             initial_backoff_ms: 10,
             max_backoff_ms: 100,
             backoff_multiplier: 2.0,
+            ..Default::default()
         };
         let executor = RetryExecutor::new(config);
 
@@ -229,6 +381,7 @@ mod tests {
             initial_backoff_ms: 10,
             max_backoff_ms: 100,
             backoff_multiplier: 2.0,
+            ..Default::default()
         };
         let executor = RetryExecutor::new(config);
 
@@ -260,6 +413,7 @@ mod tests {
             initial_backoff_ms: 50,
             max_backoff_ms: 500,
             backoff_multiplier: 2.0,
+            ..Default::default()
         };
         let executor = RetryExecutor::new(config);
 
@@ -279,9 +433,224 @@ mod tests {
 
         let elapsed = start.elapsed();
 
-        // Should have waited roughly: 50ms + 100ms + 200ms = 350ms
+        // Should have waited roughly: 50ms + 100ms + 200ms = 350ms, with each
+        // delay jittered by up to ±50%, so somewhere between 175ms and 525ms
         // Allow some tolerance for execution overhead
-        assert!(elapsed >= Duration::from_millis(300));
-        assert!(elapsed < Duration::from_millis(600));
+        assert!(elapsed >= Duration::from_millis(150));
+        assert!(elapsed < Duration::from_millis(800));
+    }
+
+    #[tokio::test]
+    async fn test_attempts_are_logged_per_request() {
+        let config = RetryConfig {
+            max_retries: 3,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 10,
+            backoff_multiplier: 2.0,
+            log_attempts: true,
+            ..Default::default()
+        };
+        let executor = RetryExecutor::new(config);
+        let context = RetryLogContext {
+            request_id: Some("req-42".to_string()),
+            backend: "http://backend:8080".to_string(),
+        };
+
+        let captured = CapturedEvents::default();
+        let subscriber = tracing_subscriber::registry().with(captured.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let attempts = Arc::new(AtomicU32::new(0));
+        let attempts_clone = attempts.clone();
+        let (result, attempt_count) = executor
+            .execute_for_request(
+                &context,
+                None,
+                || {
+                    let attempts = attempts_clone.clone();
+                    async move {
+                        if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                            Err("connection reset".to_string())
+                        } else {
+                            Ok("success")
+                        }
+                    }
+                },
+                |_| true,
+                |_| None,
+            )
+            .await;
+
+        assert_eq!(result, Ok("success"));
+        assert_eq!(attempt_count, 2);
+
+        let events = captured.0.lock().unwrap();
+        let attempt_logs: Vec<_> = events
+            .iter()
+            .filter(|fields| fields["message"] == "Request attempt finished")
+            .collect();
+
+        assert_eq!(attempt_logs.len(), 2);
+        for (index, fields) in attempt_logs.iter().enumerate() {
+            assert_eq!(fields["request_id"], "req-42");
+            assert_eq!(fields["backend"], "http://backend:8080");
+            assert_eq!(fields["attempt"], (index + 1).to_string());
+            assert!(fields.contains_key("latency_ms"));
+        }
+        assert_eq!(attempt_logs[0]["outcome"], "error");
+        assert_eq!(attempt_logs[0]["error"], "connection reset");
+        assert_eq!(attempt_logs[1]["outcome"], "success");
+    }
+
+    #[tokio::test]
+    async fn test_attempts_not_logged_by_default() {
+        let executor = RetryExecutor::new(RetryConfig::default());
+        let context = RetryLogContext {
+            request_id: None,
+            backend: "http://backend:8080".to_string(),
+        };
+
+        let captured = CapturedEvents::default();
+        let subscriber = tracing_subscriber::registry().with(captured.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let (result, attempt_count) = executor
+            .execute_for_request(
+                &context,
+                None,
+                || async { Ok::<_, String>(()) },
+                |_| true,
+                |_| None,
+            )
+            .await;
+
+        assert!(result.is_ok());
+        assert_eq!(attempt_count, 1);
+        assert!(!captured
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|fields| fields["message"] == "Request attempt finished"));
+    }
+
+    #[test]
+    fn test_parse_retry_after_seconds() {
+        let now = SystemTime::now();
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(parse_retry_after(" 0 ", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("-5", now), None);
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date() {
+        // Wed, 21 Oct 2015 07:28:00 GMT
+        let date = SystemTime::UNIX_EPOCH + Duration::from_secs(1_445_412_480);
+        let value = "Wed, 21 Oct 2015 07:28:00 GMT";
+
+        assert_eq!(
+            parse_retry_after(value, date - Duration::from_secs(90)),
+            Some(Duration::from_secs(90))
+        );
+        // Dates that already passed mean "retry now"
+        assert_eq!(
+            parse_retry_after(value, date + Duration::from_secs(5)),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_waits_for_retry_after() {
+        let config = RetryConfig {
+            max_retries: 1,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 10,
+            ..Default::default()
+        };
+        let executor = RetryExecutor::new(config);
+        let context = RetryLogContext {
+            request_id: None,
+            backend: "http://backend:8080".to_string(),
+        };
+
+        let start = std::time::Instant::now();
+        let (result, attempt_count) = executor
+            .execute_for_request(
+                &context,
+                None,
+                || async { Err::<(), _>("busy") },
+                |_| true,
+                |_| Some(Duration::from_millis(200)),
+            )
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempt_count, 2);
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_no_retry_past_deadline() {
+        let config = RetryConfig {
+            max_retries: 3,
+            initial_backoff_ms: 200,
+            max_backoff_ms: 1000,
+            ..Default::default()
+        };
+        let executor = RetryExecutor::new(config);
+        let context = RetryLogContext {
+            request_id: None,
+            backend: "http://backend:8080".to_string(),
+        };
+
+        let start = Instant::now();
+        let (result, attempt_count) = executor
+            .execute_for_request(
+                &context,
+                Some(start + Duration::from_millis(50)),
+                || async { Err::<(), _>("connection reset") },
+                |_| true,
+                |_| None,
+            )
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempt_count, 1);
+        assert!(start.elapsed() < Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_is_capped() {
+        let config = RetryConfig {
+            max_retries: 1,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 10,
+            max_retry_after_ms: 50,
+            ..Default::default()
+        };
+        let executor = RetryExecutor::new(config);
+        let context = RetryLogContext {
+            request_id: None,
+            backend: "http://backend:8080".to_string(),
+        };
+
+        let start = std::time::Instant::now();
+        let _ = executor
+            .execute_for_request(
+                &context,
+                None,
+                || async { Err::<(), _>("busy") },
+                |_| true,
+                |_| Some(Duration::from_secs(3600)),
+            )
+            .await;
+
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(50));
+        assert!(elapsed < Duration::from_secs(1));
     }
 }
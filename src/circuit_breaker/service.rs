@@ -36,26 +36,52 @@ impl CircuitBreakerService {
             .clone()
     }
 
+    /// Whether a backend response with this status counts as a failure
+    pub fn is_failure_status(&self, status: u16) -> bool {
+        self.config.is_failure_status(status)
+    }
+
     /// Check if a request can proceed for a backend
     pub async fn can_proceed(&self, backend: &str) -> bool {
+        if !self.config.enabled {
+            return true;
+        }
         let breaker = self.get_or_create_breaker(backend);
         breaker.can_proceed().await
     }
 
     /// Record a successful request for a backend
     pub async fn record_success(&self, backend: &str) {
+        if !self.config.enabled {
+            return;
+        }
         let breaker = self.get_or_create_breaker(backend);
         breaker.record_success().await;
     }
 
     /// Record a failed request for a backend
     pub async fn record_failure(&self, backend: &str) {
+        if !self.config.enabled {
+            return;
+        }
         let breaker = self.get_or_create_breaker(backend);
         breaker.record_failure().await;
     }
 
+    /// Release a backend's half-open probe slot without counting the request
+    pub async fn record_ignored(&self, backend: &str) {
+        if !self.config.enabled {
+            return;
+        }
+        let breaker = self.get_or_create_breaker(backend);
+        breaker.record_ignored().await;
+    }
+
     /// Record a timeout for a backend
     pub async fn record_timeout(&self, backend: &str) {
+        if !self.config.enabled {
+            return;
+        }
         let breaker = self.get_or_create_breaker(backend);
         breaker.record_timeout().await;
     }
@@ -78,6 +104,32 @@ impl CircuitBreakerService {
         }
     }
 
+    /// Force a backend's circuit open until cleared
+    pub async fn force_open(&self, backend: &str) {
+        self.get_or_create_breaker(backend).force_open().await;
+    }
+
+    /// Force a backend's circuit closed until cleared
+    pub async fn force_close(&self, backend: &str) {
+        self.get_or_create_breaker(backend).force_close().await;
+    }
+
+    /// Clear a forced state and resume automatic transitions for a backend
+    pub async fn clear_forced(&self, backend: &str) {
+        if let Some(breaker) = self.breakers.get(backend) {
+            breaker.clear_forced().await;
+        }
+    }
+
+    /// Whether a backend's circuit state was forced
+    pub async fn is_force_isolated(&self, backend: &str) -> bool {
+        if let Some(breaker) = self.breakers.get(backend) {
+            breaker.is_force_isolated().await
+        } else {
+            false
+        }
+    }
+
     /// Get all backend names with circuit breakers
     pub fn backends(&self) -> Vec<String> {
         self.breakers.iter().map(|e| e.key().clone()).collect()
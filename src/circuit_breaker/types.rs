@@ -22,9 +22,29 @@ impl std::fmt::Display for CircuitState {
     }
 }
 
+/// Policy that decides when a closed circuit opens
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitBreakerMode {
+    /// Open after `failure_threshold` consecutive failures
+    #[default]
+    Consecutive,
+    /// Open when the failure rate over the last `window_size` requests
+    /// exceeds `error_rate_threshold`
+    ErrorRate,
+}
+
 /// Circuit breaker configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CircuitBreakerConfig {
+    /// Whether the breaker is active; a disabled breaker never opens
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Trip policy: consecutive failures or error rate over a rolling window
+    #[serde(default)]
+    pub mode: CircuitBreakerMode,
+
     /// Number of consecutive failures before opening the circuit
     #[serde(default = "default_failure_threshold")]
     pub failure_threshold: u32,
@@ -44,6 +64,31 @@ pub struct CircuitBreakerConfig {
     /// Timeout for individual requests in seconds
     #[serde(default = "default_request_timeout_secs")]
     pub request_timeout_secs: u64,
+
+    /// Number of consecutive timeouts before opening the circuit.
+    /// When unset, timeouts count as regular failures.
+    #[serde(default)]
+    pub timeout_threshold: Option<u32>,
+
+    /// Failure rate (0.0-1.0) above which the circuit opens in error rate mode
+    #[serde(default = "default_error_rate_threshold")]
+    pub error_rate_threshold: f64,
+
+    /// Number of most recent requests considered in error rate mode
+    #[serde(default = "default_window_size")]
+    pub window_size: u32,
+
+    /// Minimum requests in the window before the error rate is evaluated
+    #[serde(default = "default_minimum_requests")]
+    pub minimum_requests: u32,
+
+    /// Backend status codes counted as failures (all 5xx when unset)
+    #[serde(default)]
+    pub failure_status_codes: Option<Vec<u16>>,
+}
+
+fn default_enabled() -> bool {
+    true
 }
 
 fn default_failure_threshold() -> u32 {
@@ -66,14 +111,33 @@ fn default_request_timeout_secs() -> u64 {
     30
 }
 
+fn default_error_rate_threshold() -> f64 {
+    0.5
+}
+
+fn default_window_size() -> u32 {
+    20
+}
+
+fn default_minimum_requests() -> u32 {
+    10
+}
+
 impl Default for CircuitBreakerConfig {
     fn default() -> Self {
         Self {
+            enabled: default_enabled(),
+            mode: CircuitBreakerMode::default(),
             failure_threshold: default_failure_threshold(),
             success_threshold: default_success_threshold(),
             timeout_secs: default_timeout_secs(),
             half_open_requests: default_half_open_requests(),
             request_timeout_secs: default_request_timeout_secs(),
+            timeout_threshold: None,
+            error_rate_threshold: default_error_rate_threshold(),
+            window_size: default_window_size(),
+            minimum_requests: default_minimum_requests(),
+            failure_status_codes: None,
         }
     }
 }
@@ -86,6 +150,42 @@ impl CircuitBreakerConfig {
     pub fn request_timeout(&self) -> Duration {
         Duration::from_secs(self.request_timeout_secs)
     }
+
+    /// Whether a backend response with this status counts as a failure
+    pub fn is_failure_status(&self, status: u16) -> bool {
+        match &self.failure_status_codes {
+            Some(codes) => codes.contains(&status),
+            None => (500..600).contains(&status),
+        }
+    }
+}
+
+/// Response served instead of a 503 while a route's circuit is open
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FallbackConfig {
+    /// Serve an expired cached response for the request if one is available
+    #[serde(default)]
+    pub serve_stale: bool,
+
+    /// Status code of the static fallback response
+    #[serde(default = "default_fallback_status")]
+    pub status: u16,
+
+    /// Body of the static fallback response; without one, no static response is served
+    #[serde(default)]
+    pub body: Option<String>,
+
+    /// Content type of the static fallback response
+    #[serde(default = "default_fallback_content_type")]
+    pub content_type: String,
+}
+
+fn default_fallback_status() -> u16 {
+    503
+}
+
+fn default_fallback_content_type() -> String {
+    "application/json".to_string()
 }
 
 /// Retry configuration
@@ -106,6 +206,19 @@ pub struct RetryConfig {
     /// Backoff multiplier
     #[serde(default = "default_backoff_multiplier")]
     pub backoff_multiplier: f64,
+
+    /// Log every attempt of a retried request at debug level
+    #[serde(default)]
+    pub log_attempts: bool,
+
+    /// Backend response statuses that are retried like connection errors
+    /// (none by default, since retrying may repeat non-idempotent requests)
+    #[serde(default)]
+    pub retry_on_status: Vec<u16>,
+
+    /// Longest wait honored from a response's Retry-After header, in milliseconds
+    #[serde(default = "default_max_retry_after_ms")]
+    pub max_retry_after_ms: u64,
 }
 
 fn default_max_retries() -> u32 {
@@ -124,6 +237,10 @@ fn default_backoff_multiplier() -> f64 {
     2.0
 }
 
+fn default_max_retry_after_ms() -> u64 {
+    10000
+}
+
 impl Default for RetryConfig {
     fn default() -> Self {
         Self {
@@ -131,6 +248,9 @@ impl Default for RetryConfig {
             initial_backoff_ms: default_initial_backoff_ms(),
             max_backoff_ms: default_max_backoff_ms(),
             backoff_multiplier: default_backoff_multiplier(),
+            log_attempts: false,
+            retry_on_status: Vec::new(),
+            max_retry_after_ms: default_max_retry_after_ms(),
         }
     }
 }
@@ -143,10 +263,14 @@ impl RetryConfig {
     pub fn max_backoff(&self) -> Duration {
         Duration::from_millis(self.max_backoff_ms)
     }
+
+    pub fn max_retry_after(&self) -> Duration {
+        Duration::from_millis(self.max_retry_after_ms)
+    }
 }
 
 /// Circuit breaker metrics
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct CircuitBreakerMetrics {
     /// Total number of requests
     pub total_requests: u64,
@@ -180,11 +304,44 @@ mod tests {
     #[test]
     fn test_default_config() {
         let config = CircuitBreakerConfig::default();
+        assert!(config.enabled);
         assert_eq!(config.failure_threshold, 5);
         assert_eq!(config.success_threshold, 2);
         assert_eq!(config.timeout_secs, 60);
         assert_eq!(config.half_open_requests, 3);
         assert_eq!(config.request_timeout_secs, 30);
+        assert_eq!(config.timeout_threshold, None);
+        assert_eq!(config.mode, CircuitBreakerMode::Consecutive);
+        assert_eq!(config.error_rate_threshold, 0.5);
+        assert_eq!(config.window_size, 20);
+        assert_eq!(config.minimum_requests, 10);
+    }
+
+    #[test]
+    fn test_failure_status_codes() {
+        let config = CircuitBreakerConfig::default();
+        assert!(config.is_failure_status(500));
+        assert!(config.is_failure_status(503));
+        assert!(!config.is_failure_status(404));
+
+        let config = CircuitBreakerConfig {
+            failure_status_codes: Some(vec![500, 502, 504]),
+            ..Default::default()
+        };
+        assert!(config.is_failure_status(502));
+        assert!(!config.is_failure_status(501));
+        assert!(!config.is_failure_status(503));
+    }
+
+    #[test]
+    fn test_error_rate_mode_from_yaml() {
+        let config: CircuitBreakerConfig =
+            serde_yaml::from_str("mode: error_rate\nerror_rate_threshold: 0.25\nwindow_size: 50\n")
+                .unwrap();
+        assert_eq!(config.mode, CircuitBreakerMode::ErrorRate);
+        assert_eq!(config.error_rate_threshold, 0.25);
+        assert_eq!(config.window_size, 50);
+        assert_eq!(config.minimum_requests, 10);
     }
 
     #[test]
@@ -194,5 +351,6 @@ mod tests {
         assert_eq!(config.initial_backoff_ms, 100);
         assert_eq!(config.max_backoff_ms, 10000);
         assert_eq!(config.backoff_multiplier, 2.0);
+        assert!(!config.log_attempts);
     }
 }
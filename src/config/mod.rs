@@ -1,15 +1,27 @@
+use crate::admin::AdminConfig;
 use crate::cache::CacheConfig;
-use crate::circuit_breaker::types::{CircuitBreakerConfig, RetryConfig};
+use crate::circuit_breaker::types::{
+    CircuitBreakerConfig, CircuitBreakerMode, FallbackConfig, RetryConfig,
+};
 use crate::cors::CorsConfig;
+use crate::dns::DnsCacheConfig;
 use crate::error::{GatewayError, Result};
 use crate::healthcheck::HealthCheckConfig;
 use crate::hotreload::HotReloadConfig;
-use crate::ipfilter::IpFilterConfig;
+use crate::ipfilter::{IpFilterConfig, TrustedProxies};
 use crate::loadbalancer::backend::BackendConfig;
-use crate::rate_limit::types::RateLimitConfig;
-use crate::tls::TlsConfig;
+use crate::loadbalancer::canary::CanaryConfig;
+use crate::maintenance::MaintenanceConfig;
+use crate::mirror::MirrorConfig;
+use crate::proxy::is_hop_by_hop_header;
+use crate::rate_limit::types::{RateLimitConfig, RateLimitExemptConfig, RateLimitOverride};
+use crate::rate_limit::RateLimitAlgorithm;
+use crate::rate_limit::{QuotaConfig, QuotaService, RateLimitExemptions};
+use crate::router::parse_strategy;
+use crate::tls::{BackendTlsConfig, TlsConfig};
 use crate::transform::TransformConfig;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 
 /// Main gateway configuration
@@ -52,6 +64,18 @@ pub struct GatewayConfig {
     /// TLS/mTLS configuration
     #[serde(default)]
     pub tls: Option<TlsConfig>,
+    /// TLS configuration for connections to backends
+    #[serde(default)]
+    pub backend_tls: Option<BackendTlsConfig>,
+    /// Backend DNS cache configuration
+    #[serde(default)]
+    pub dns: Option<DnsCacheConfig>,
+    /// Admin API configuration
+    #[serde(default)]
+    pub admin: Option<AdminConfig>,
+    /// Maintenance mode configuration
+    #[serde(default)]
+    pub maintenance: Option<MaintenanceConfig>,
 }
 
 /// Server configuration
@@ -66,6 +90,79 @@ pub struct ServerConfig {
     /// Request timeout in seconds
     #[serde(default = "default_timeout")]
     pub timeout_secs: u64,
+    /// Honor and propagate the X-Request-Deadline header
+    #[serde(default)]
+    pub enforce_request_deadline: bool,
+    /// Honor X-HTTP-Method-Override on POST requests (routes may override)
+    #[serde(default)]
+    pub honor_method_override: bool,
+    /// Answer OPTIONS requests with the route's allowed methods instead of
+    /// forwarding them (routes may override)
+    #[serde(default)]
+    pub answer_options: bool,
+    /// Path of the aggregate backend health endpoint (disabled when unset)
+    ///
+    /// Served ahead of the proxy, so it shadows any route matching the path.
+    #[serde(default)]
+    pub health_path: Option<String>,
+    /// Request paths that bypass authentication (health checks and probes)
+    #[serde(default = "default_health_check_paths")]
+    pub health_check_paths: Vec<String>,
+    /// Proxies (IPs or CIDR ranges) whose X-Forwarded-For/Forwarded headers
+    /// are trusted to carry the client IP
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>,
+    /// JSON body template for gateway error responses, with `{status}` and
+    /// `{error}` placeholders (clients accepting text get plain text instead)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_template: Option<String>,
+    /// Connection pool and keep-alive settings for backend connections
+    #[serde(default)]
+    pub upstream: UpstreamConfig,
+    /// How request paths differing from a route only by a trailing slash match
+    #[serde(default)]
+    pub trailing_slash: TrailingSlash,
+}
+
+/// Policy for request paths that differ from a route only by a trailing slash
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrailingSlash {
+    /// `/api/users` and `/api/users/` are different paths
+    #[default]
+    Strict,
+    /// Match both forms to the same route
+    Ignore,
+    /// Answer the other form with a 308 redirect to the configured one
+    Redirect,
+}
+
+/// Connection settings of the HTTP client used for backend requests
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpstreamConfig {
+    /// Idle connections kept per backend host (unlimited if not set)
+    #[serde(default)]
+    pub pool_max_idle_per_host: Option<usize>,
+    /// Seconds an idle connection is kept before closing (null to keep forever)
+    #[serde(default = "default_pool_idle_timeout_secs")]
+    pub pool_idle_timeout_secs: Option<u64>,
+    /// Interval of TCP keepalive probes in seconds (disabled if not set)
+    #[serde(default)]
+    pub tcp_keepalive_secs: Option<u64>,
+    /// Speak HTTP/2 to backends without negotiating it first
+    #[serde(default)]
+    pub http2_prior_knowledge: bool,
+}
+
+impl Default for UpstreamConfig {
+    fn default() -> Self {
+        Self {
+            pool_max_idle_per_host: None,
+            pool_idle_timeout_secs: default_pool_idle_timeout_secs(),
+            tcp_keepalive_secs: None,
+            http2_prior_knowledge: false,
+        }
+    }
 }
 
 /// Route configuration
@@ -82,18 +179,34 @@ pub struct RouteConfig {
     /// Load balancer configuration
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub load_balancer: Option<LoadBalancerConfig>,
+    /// Canary backend group receiving a share of traffic
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canary: Option<CanaryConfig>,
     /// Health check configuration
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub health_check: Option<HealthCheckConfig>,
     /// Allowed HTTP methods (if empty, all methods allowed)
     #[serde(default)]
     pub methods: Vec<String>,
+    /// Request headers that must match for this route to be selected
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub match_headers: HashMap<String, ValueMatch>,
+    /// Query parameters that must match for this route to be selected
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub match_query: HashMap<String, ValueMatch>,
     /// Whether to strip the prefix when forwarding
     #[serde(default)]
     pub strip_prefix: bool,
     /// Route description
     #[serde(default)]
     pub description: String,
+    /// Serve this route; a disabled route still matches but is answered
+    /// with `disabled_status` instead of being proxied
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Status returned while the route is disabled
+    #[serde(default = "default_disabled_status")]
+    pub disabled_status: u16,
     /// Authentication requirement for this route
     #[serde(default)]
     pub auth: Option<RouteAuthConfig>,
@@ -112,6 +225,61 @@ pub struct RouteConfig {
     /// Cache configuration for this route (overrides global)
     #[serde(default)]
     pub cache: Option<CacheConfig>,
+    /// Honor X-HTTP-Method-Override for this route (overrides global)
+    #[serde(default)]
+    pub honor_method_override: Option<bool>,
+    /// Answer OPTIONS requests for this route at the gateway (overrides global)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer_options: Option<bool>,
+    /// Circuit breaker configuration for this route (overrides global)
+    #[serde(default)]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// Response served while this route's circuit is open
+    #[serde(default)]
+    pub fallback: Option<FallbackConfig>,
+    /// Copy a share of this route's requests to a shadow backend
+    #[serde(default)]
+    pub mirror: Option<MirrorConfig>,
+    /// Largest backend response body accepted, in bytes; larger responses
+    /// are rejected with a 502 rather than buffered
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_response_bytes: Option<usize>,
+    /// Hop-by-hop request headers (e.g. `TE`, `Upgrade`) forwarded to the
+    /// backend instead of being stripped
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preserve_headers: Vec<String>,
+}
+
+/// Expected value of a request header or query parameter for routing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged, from = "ValueMatchRepr")]
+pub enum ValueMatch {
+    /// Exact value
+    Exact(String),
+    /// Regular expression that must match the whole value
+    Regex { regex: String },
+}
+
+/// Value match as written in configuration, where bare numbers and
+/// booleans (`X-API-Version: 2`) are accepted as exact values
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ValueMatchRepr {
+    Text(String),
+    Integer(i64),
+    Bool(bool),
+    Regex { regex: String },
+}
+
+impl From<ValueMatchRepr> for ValueMatch {
+    fn from(value: ValueMatchRepr) -> Self {
+        match value {
+            ValueMatchRepr::Text(value) => ValueMatch::Exact(value),
+            ValueMatchRepr::Integer(value) => ValueMatch::Exact(value.to_string()),
+            ValueMatchRepr::Bool(value) => ValueMatch::Exact(value.to_string()),
+            ValueMatchRepr::Regex { regex } => ValueMatch::Regex { regex },
+        }
+    }
 }
 
 /// Load balancer configuration
@@ -138,11 +306,13 @@ pub struct RouteAuthConfig {
 }
 
 /// Authentication method types
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum AuthMethod {
     Jwt,
     ApiKey,
+    Introspection,
+    Hmac,
 }
 
 /// Global authentication configuration
@@ -152,6 +322,63 @@ pub struct AuthConfig {
     pub jwt: Option<JwtConfig>,
     /// API key configuration
     pub api_key: Option<ApiKeyConfig>,
+    /// OAuth2 token introspection (RFC 7662) configuration
+    pub introspection: Option<IntrospectionConfig>,
+    /// HMAC request signature configuration
+    pub hmac: Option<HmacConfig>,
+    /// `WWW-Authenticate` challenge sent with 401 responses, by method
+    /// (overrides the method's default; an empty value sends none)
+    #[serde(default)]
+    pub challenges: std::collections::HashMap<AuthMethod, String>,
+}
+
+/// HMAC request signature configuration
+///
+/// Clients sign `METHOD\npath?query\ntimestamp\nbody` with HMAC-SHA256
+/// using the shared secret of their key ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HmacConfig {
+    /// Shared secrets by key ID; values may be `secret://`, `env://` or
+    /// `file://` references
+    #[serde(default)]
+    pub keys: std::collections::HashMap<String, String>,
+    /// Header carrying the hex-encoded signature
+    #[serde(default = "default_hmac_signature_header")]
+    pub signature_header: String,
+    /// Header carrying the Unix timestamp (seconds) the request was signed at
+    #[serde(default = "default_hmac_timestamp_header")]
+    pub timestamp_header: String,
+    /// Header carrying the key ID
+    #[serde(default = "default_hmac_key_id_header")]
+    pub key_id_header: String,
+    /// Maximum age (or clock skew) of the timestamp, in seconds
+    #[serde(default = "default_hmac_max_skew")]
+    pub max_skew_secs: u64,
+    /// Largest request body buffered to check a signature, in bytes; larger
+    /// signed requests are rejected with a 413
+    #[serde(default = "default_hmac_max_body_bytes")]
+    pub max_body_bytes: usize,
+}
+
+/// OAuth2 token introspection configuration
+///
+/// Opaque bearer tokens are posted to the authorization server's
+/// introspection endpoint; tokens reported as inactive are rejected.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntrospectionConfig {
+    /// Introspection endpoint URL
+    pub url: String,
+    /// Client ID used to authenticate to the endpoint (HTTP Basic)
+    pub client_id: String,
+    /// Client secret used to authenticate to the endpoint (HTTP Basic); may be
+    /// a `secret://`, `env://` or `file://` reference
+    pub client_secret: String,
+    /// How long introspection results are cached, in seconds (0 disables)
+    #[serde(default = "default_introspection_cache_ttl")]
+    pub cache_ttl_secs: u64,
+    /// Timeout of introspection requests, in milliseconds
+    #[serde(default = "default_introspection_timeout")]
+    pub timeout_ms: u64,
 }
 
 /// JWT authentication configuration
@@ -159,15 +386,108 @@ pub struct AuthConfig {
 pub struct JwtConfig {
     /// Secret key for HS256 (if using symmetric encryption)
     pub secret: Option<String>,
-    /// Public key for RS256 (if using asymmetric encryption)
+    /// PEM public key for RS*, ES256/ES384 or EdDSA (if using asymmetric encryption)
     pub public_key: Option<String>,
-    /// Algorithm to use (HS256 or RS256)
+    /// JWKS endpoint to fetch verification keys from, selected by the token's `kid`
+    #[serde(default)]
+    pub jwks_url: Option<String>,
+    /// How often JWKS key sets are refreshed, in seconds
+    #[serde(default = "default_jwks_refresh")]
+    pub jwks_refresh_secs: u64,
+    /// Algorithm to use (HS256/384/512, RS256/384/512, ES256/384 or EdDSA)
     #[serde(default = "default_jwt_algorithm")]
     pub algorithm: String,
     /// Issuer to validate
     pub issuer: Option<String>,
     /// Audience to validate
     pub audience: Option<String>,
+    /// Clock-skew leeway in seconds applied to expiration checks
+    #[serde(default = "default_jwt_leeway")]
+    pub leeway_secs: u64,
+    /// Where to read the token from: `header`, `cookie:<name>` or `query:<name>`
+    #[serde(default)]
+    pub token_source: TokenSource,
+    /// Trusted issuers, each with its own key (replaces the single key above)
+    ///
+    /// The token's `iss` claim selects the key; tokens from other issuers are
+    /// rejected.
+    #[serde(default)]
+    pub issuers: Vec<JwtIssuerConfig>,
+}
+
+/// Key and claims of one trusted JWT issuer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JwtIssuerConfig {
+    /// Issuer (`iss` claim) of tokens signed with this key
+    pub issuer: String,
+    /// Secret key for HS256/384/512
+    pub secret: Option<String>,
+    /// PEM public key for RS*, ES256/ES384 or EdDSA
+    pub public_key: Option<String>,
+    /// JWKS endpoint to fetch verification keys from
+    #[serde(default)]
+    pub jwks_url: Option<String>,
+    /// Algorithm to use (HS256/384/512, RS256/384/512, ES256/384 or EdDSA)
+    #[serde(default = "default_jwt_algorithm")]
+    pub algorithm: String,
+    /// Audience to validate
+    pub audience: Option<String>,
+}
+
+/// Source of the JWT in incoming requests
+///
+/// Cookie and query sources are checked before the `Authorization` header,
+/// which remains a fallback.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum TokenSource {
+    /// `Authorization: Bearer <token>`
+    #[default]
+    Header,
+    /// Cookie with the given name
+    Cookie(String),
+    /// Query parameter with the given name
+    Query(String),
+}
+
+impl std::str::FromStr for TokenSource {
+    type Err = GatewayError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split_once(':') {
+            None if s.eq_ignore_ascii_case("header") => Ok(TokenSource::Header),
+            Some((kind, name)) if !name.is_empty() => match kind.to_lowercase().as_str() {
+                "cookie" => Ok(TokenSource::Cookie(name.to_string())),
+                "query" => Ok(TokenSource::Query(name.to_string())),
+                _ => Err(GatewayError::Config(format!(
+                    "Invalid JWT token source: {}",
+                    s
+                ))),
+            },
+            _ => Err(GatewayError::Config(format!(
+                "Invalid JWT token source: {} (expected header, cookie:<name> or query:<name>)",
+                s
+            ))),
+        }
+    }
+}
+
+impl TryFrom<String> for TokenSource {
+    type Error = GatewayError;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl From<TokenSource> for String {
+    fn from(source: TokenSource) -> Self {
+        match source {
+            TokenSource::Header => "header".to_string(),
+            TokenSource::Cookie(name) => format!("cookie:{}", name),
+            TokenSource::Query(name) => format!("query:{}", name),
+        }
+    }
 }
 
 /// API key configuration
@@ -176,11 +496,64 @@ pub struct ApiKeyConfig {
     /// Header name for API key
     #[serde(default = "default_api_key_header")]
     pub header: String,
-    /// In-memory API keys (key -> description)
+    /// In-memory API keys (key -> description or detailed entry)
     #[serde(default)]
-    pub keys: std::collections::HashMap<String, String>,
+    pub keys: std::collections::HashMap<String, ApiKeyEntry>,
     /// Redis configuration for distributed key storage
     pub redis: Option<RedisConfig>,
+    /// Whether `keys` holds hex SHA-256 digests instead of plaintext keys
+    #[serde(default)]
+    pub hashed: bool,
+}
+
+/// In-memory API key entry: a plain description or a detailed definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ApiKeyEntry {
+    Description(String),
+    Detailed(ApiKeyDefinition),
+}
+
+/// Detailed in-memory API key definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeyDefinition {
+    #[serde(default)]
+    pub description: String,
+    /// Rate limit override for this key (falls back to the route limit)
+    #[serde(default)]
+    pub rate_limit: Option<RateLimitOverride>,
+    /// Expiry as a Unix timestamp in seconds (never expires if unset)
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+}
+
+impl ApiKeyEntry {
+    pub fn description(&self) -> &str {
+        match self {
+            ApiKeyEntry::Description(description) => description,
+            ApiKeyEntry::Detailed(definition) => &definition.description,
+        }
+    }
+
+    pub fn rate_limit(&self) -> Option<&RateLimitOverride> {
+        match self {
+            ApiKeyEntry::Description(_) => None,
+            ApiKeyEntry::Detailed(definition) => definition.rate_limit.as_ref(),
+        }
+    }
+
+    pub fn expires_at(&self) -> Option<u64> {
+        match self {
+            ApiKeyEntry::Description(_) => None,
+            ApiKeyEntry::Detailed(definition) => definition.expires_at,
+        }
+    }
+}
+
+impl From<String> for ApiKeyEntry {
+    fn from(description: String) -> Self {
+        ApiKeyEntry::Description(description)
+    }
 }
 
 /// Redis configuration
@@ -191,6 +564,9 @@ pub struct RedisConfig {
     /// Key prefix for API keys
     #[serde(default = "default_redis_prefix")]
     pub prefix: String,
+    /// Read-only replica for key lookups (writes always go to `url`)
+    #[serde(default)]
+    pub replica_url: Option<String>,
 }
 
 /// Global rate limiting configuration
@@ -204,9 +580,15 @@ pub struct GlobalRateLimitConfig {
     pub global: Vec<RateLimitConfig>,
     /// Redis configuration for distributed rate limiting
     pub redis: Option<RateLimitRedisConfig>,
-    /// Algorithm to use for Redis rate limiting
+    /// Algorithm to use: token_bucket, sliding_window, fixed_window, gcra or leaky_bucket
     #[serde(default = "default_rate_limit_algorithm")]
     pub algorithm: String,
+    /// Client IPs and API keys that are never rate limited
+    #[serde(default)]
+    pub exempt: RateLimitExemptConfig,
+    /// Daily or monthly request quota per API key
+    #[serde(default)]
+    pub quota: Option<QuotaConfig>,
 }
 
 /// Redis configuration for rate limiting
@@ -214,16 +596,104 @@ pub struct GlobalRateLimitConfig {
 pub struct RateLimitRedisConfig {
     /// Redis connection URL
     pub url: String,
+    /// Fall back to local rate limiting on Redis errors instead of denying
+    #[serde(default)]
+    pub fail_open: bool,
 }
 
 fn default_true() -> bool {
     true
 }
 
+/// Check a circuit breaker block, reporting errors under `field`
+fn validate_circuit_breaker(
+    config: &CircuitBreakerConfig,
+    field: &str,
+    errors: &mut Vec<GatewayError>,
+) {
+    for (name, value) in [
+        ("failure_threshold", config.failure_threshold as u64),
+        ("success_threshold", config.success_threshold as u64),
+        ("half_open_requests", config.half_open_requests as u64),
+        ("timeout_secs", config.timeout_secs),
+        ("request_timeout_secs", config.request_timeout_secs),
+    ] {
+        if value == 0 {
+            errors.push(GatewayError::Config(format!(
+                "{}.{}: Must be > 0",
+                field, name
+            )));
+        }
+    }
+    if config.timeout_threshold == Some(0) {
+        errors.push(GatewayError::Config(format!(
+            "{}.timeout_threshold: Must be > 0",
+            field
+        )));
+    }
+
+    if config.mode == CircuitBreakerMode::ErrorRate {
+        if !(config.error_rate_threshold > 0.0 && config.error_rate_threshold < 1.0) {
+            errors.push(GatewayError::Config(format!(
+                "{}.error_rate_threshold: Error rate threshold must be between 0 and 1",
+                field
+            )));
+        }
+        if config.window_size == 0 {
+            errors.push(GatewayError::Config(format!(
+                "{}.window_size: Window size must be > 0",
+                field
+            )));
+        }
+        if config.minimum_requests > config.window_size {
+            errors.push(GatewayError::Config(format!(
+                "{}.minimum_requests: Minimum requests must not exceed window_size",
+                field
+            )));
+        }
+    }
+}
+
 fn default_jwt_algorithm() -> String {
     "HS256".to_string()
 }
 
+fn default_jwt_leeway() -> u64 {
+    60
+}
+
+fn default_jwks_refresh() -> u64 {
+    300
+}
+
+fn default_hmac_signature_header() -> String {
+    "X-Signature".to_string()
+}
+
+fn default_hmac_timestamp_header() -> String {
+    "X-Timestamp".to_string()
+}
+
+fn default_hmac_key_id_header() -> String {
+    "X-Key-Id".to_string()
+}
+
+fn default_hmac_max_body_bytes() -> usize {
+    1024 * 1024
+}
+
+fn default_hmac_max_skew() -> u64 {
+    300
+}
+
+fn default_introspection_cache_ttl() -> u64 {
+    30
+}
+
+fn default_introspection_timeout() -> u64 {
+    2000
+}
+
 fn default_api_key_header() -> String {
     "X-API-Key".to_string()
 }
@@ -244,6 +714,21 @@ fn default_timeout() -> u64 {
     30
 }
 
+fn default_pool_idle_timeout_secs() -> Option<u64> {
+    Some(90)
+}
+
+fn default_disabled_status() -> u16 {
+    503
+}
+
+pub(crate) fn default_health_check_paths() -> Vec<String> {
+    ["/health", "/healthz", "/ready", "/readiness", "/ping"]
+        .iter()
+        .map(|path| path.to_string())
+        .collect()
+}
+
 fn default_rate_limit_algorithm() -> String {
     "sliding_window".to_string()
 }
@@ -257,17 +742,135 @@ pub struct ObservabilityConfig {
     /// Tracing configuration
     #[serde(default)]
     pub tracing: Option<TracingConfigOptions>,
+    /// Access log configuration
+    #[serde(default)]
+    pub access_log: Option<AccessLogConfig>,
+    /// Request ID source and format
+    #[serde(default)]
+    pub request_id: RequestIdConfig,
+}
+
+/// Request ID configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestIdConfig {
+    /// Headers to read an incoming request ID from, in priority order
+    #[serde(default = "default_request_id_headers")]
+    pub headers: Vec<String>,
+    /// Format of generated request IDs
+    #[serde(default)]
+    pub format: RequestIdFormat,
+}
+
+impl Default for RequestIdConfig {
+    fn default() -> Self {
+        Self {
+            headers: default_request_id_headers(),
+            format: RequestIdFormat::default(),
+        }
+    }
+}
+
+fn default_request_id_headers() -> Vec<String> {
+    vec!["x-request-id".to_string()]
+}
+
+/// Format of generated request IDs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestIdFormat {
+    /// Random UUID, e.g. `0b1c9d3e-...`
+    #[default]
+    UuidV4,
+    /// Time-ordered UUID; IDs generated later sort after earlier ones, which
+    /// suits range queries in log stores
+    UuidV7,
+    /// 21-character URL-safe random id
+    Nanoid,
+}
+
+/// Access log configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessLogConfig {
+    /// Emit one access log event per completed request
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Output format of access log events
+    #[serde(default)]
+    pub format: AccessLogFormat,
+    /// Fraction of successful (2xx/3xx) requests to log (0.0 to 1.0);
+    /// other statuses are always logged
+    #[serde(default = "default_sample_rate")]
+    pub sample_rate: f64,
+    /// Always log requests taking at least this long, regardless of sampling
+    #[serde(default)]
+    pub slow_threshold_ms: Option<u64>,
+}
+
+/// Access log output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessLogFormat {
+    /// Human-readable line alongside the other logs
+    #[default]
+    Text,
+    /// One flat JSON object per request
+    Json,
 }
 
 /// Metrics configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricsConfig {
-    /// Enable Prometheus metrics endpoint
+    /// Enable metrics collection
     #[serde(default = "default_true")]
     pub enabled: bool,
-    /// Metrics endpoint path
+    /// Where metrics are exported
+    #[serde(default)]
+    pub exporter: MetricsExporter,
+    /// Metrics endpoint path (Prometheus exporter only)
     #[serde(default = "default_metrics_path")]
     pub path: String,
+    /// Histogram buckets in seconds for request and backend durations
+    /// (exporter defaults if unset; Prometheus exporter only)
+    #[serde(default)]
+    pub duration_buckets: Option<Vec<f64>>,
+    /// StatsD settings (StatsD exporter only)
+    #[serde(default)]
+    pub statsd: StatsdConfig,
+}
+
+/// Metrics exporter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricsExporter {
+    /// Serve a Prometheus scrape endpoint at `path`
+    #[default]
+    Prometheus,
+    /// Push metrics over UDP to a StatsD/DogStatsD agent
+    Statsd,
+}
+
+/// StatsD exporter configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsdConfig {
+    /// StatsD agent host
+    #[serde(default = "default_statsd_host")]
+    pub host: String,
+    /// StatsD agent UDP port
+    #[serde(default = "default_statsd_port")]
+    pub port: u16,
+    /// Prefix prepended to every metric name (e.g. "gateway")
+    #[serde(default)]
+    pub prefix: Option<String>,
+}
+
+impl Default for StatsdConfig {
+    fn default() -> Self {
+        Self {
+            host: default_statsd_host(),
+            port: default_statsd_port(),
+            prefix: None,
+        }
+    }
 }
 
 /// Tracing configuration options
@@ -288,12 +891,37 @@ pub struct TracingConfigOptions {
     /// Sample rate (0.0 to 1.0)
     #[serde(default = "default_sample_rate")]
     pub sample_rate: f64,
+    /// Formats used to extract and inject trace context, in order
+    #[serde(default = "default_propagators")]
+    pub propagators: Vec<TracePropagator>,
+}
+
+/// Trace context propagation format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TracePropagator {
+    /// W3C `traceparent`/`tracestate` headers
+    TraceContext,
+    /// W3C `baggage` header
+    Baggage,
+    /// Zipkin single `b3` header
+    B3,
+    /// Zipkin `X-B3-*` headers
+    B3Multi,
 }
 
 fn default_metrics_path() -> String {
     "/metrics".to_string()
 }
 
+fn default_statsd_host() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_statsd_port() -> u16 {
+    8125
+}
+
 fn default_otlp_endpoint() -> String {
     "http://localhost:4317".to_string()
 }
@@ -310,12 +938,25 @@ fn default_sample_rate() -> f64 {
     1.0
 }
 
+pub(crate) fn default_propagators() -> Vec<TracePropagator> {
+    vec![TracePropagator::TraceContext]
+}
+
 impl Default for ServerConfig {
     fn default() -> Self {
         Self {
             host: default_host(),
             port: default_port(),
             timeout_secs: default_timeout(),
+            enforce_request_deadline: false,
+            honor_method_override: false,
+            answer_options: false,
+            health_path: None,
+            health_check_paths: default_health_check_paths(),
+            trusted_proxies: Vec::new(),
+            error_template: None,
+            upstream: UpstreamConfig::default(),
+            trailing_slash: TrailingSlash::default(),
         }
     }
 }
@@ -330,10 +971,7 @@ impl RouteConfig {
 
         // Otherwise, use the single backend field (for backward compatibility)
         if let Some(backend) = &self.backend {
-            return Ok(vec![BackendConfig {
-                url: backend.clone(),
-                weight: 1,
-            }]);
+            return Ok(vec![backend.parse()?]);
         }
 
         // No backends configured
@@ -351,77 +989,246 @@ impl RouteConfig {
 
 impl GatewayConfig {
     /// Load configuration from a YAML file
+    ///
+    /// The format is chosen by extension: `.yaml`/`.yml` (or no extension),
+    /// `.json` or `.toml`.
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase());
+
+        let parse: fn(&str) -> Result<Self> = match extension.as_deref() {
+            None | Some("yaml") | Some("yml") => Self::from_yaml,
+            Some("json") => Self::from_json,
+            Some("toml") => Self::from_toml,
+            Some(other) => {
+                return Err(GatewayError::Config(format!(
+                    "Unsupported config file extension '.{}' for {} (expected .yaml, .yml, .json or .toml)",
+                    other,
+                    path.display()
+                )))
+            }
+        };
+
         let content = std::fs::read_to_string(path)
             .map_err(|e| GatewayError::Config(format!("Failed to read config file: {}", e)))?;
 
-        Self::from_yaml(&content)
+        parse(&content)
     }
 
     /// Parse configuration from YAML string
+    ///
+    /// Parse errors report the line and column of the offending YAML.
     pub fn from_yaml(yaml: &str) -> Result<Self> {
-        serde_yaml::from_str(yaml)
-            .map_err(|e| GatewayError::Config(format!("Failed to parse config: {}", e)))
+        serde_yaml::from_str(yaml).map_err(|e| {
+            let message = e.to_string();
+            match e.location() {
+                Some(location) => {
+                    // serde_yaml appends the location; report it up front instead
+                    let suffix =
+                        format!(" at line {} column {}", location.line(), location.column());
+                    GatewayError::Config(format!(
+                        "Failed to parse config at line {}, column {}: {}",
+                        location.line(),
+                        location.column(),
+                        message.strip_suffix(&suffix).unwrap_or(&message)
+                    ))
+                }
+                None => GatewayError::Config(format!("Failed to parse config: {}", message)),
+            }
+        })
+    }
+
+    /// Parse configuration from JSON string
+    ///
+    /// Parse errors report the line and column of the offending JSON.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| {
+            // serde_json appends the location; report it up front instead
+            let message = e.to_string();
+            let suffix = format!(" at line {} column {}", e.line(), e.column());
+            GatewayError::Config(format!(
+                "Failed to parse config at line {}, column {}: {}",
+                e.line(),
+                e.column(),
+                message.strip_suffix(&suffix).unwrap_or(&message)
+            ))
+        })
+    }
+
+    /// Parse configuration from TOML string
+    pub fn from_toml(toml: &str) -> Result<Self> {
+        toml::from_str(toml).map_err(|e| {
+            let message = e.message();
+            match e.span() {
+                Some(span) => {
+                    let before = &toml[..span.start];
+                    let line = before.matches('\n').count() + 1;
+                    let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+                    GatewayError::Config(format!(
+                        "Failed to parse config at line {}, column {}: {}",
+                        line, column, message
+                    ))
+                }
+                None => GatewayError::Config(format!("Failed to parse config: {}", message)),
+            }
+        })
     }
 
     /// Validate configuration
+    ///
+    /// Errors name the offending field by path, e.g. `routes[3].backend`.
+    /// Every problem is collected so a broken configuration can be fixed in
+    /// one pass; a single problem is returned as is.
     pub fn validate(&self) -> Result<()> {
+        let mut errors = Vec::new();
+        self.validate_route_patterns(&mut errors);
+
         // Validate routes
-        for route in &self.routes {
+        for (i, route) in self.routes.iter().enumerate() {
             if route.path.is_empty() {
-                return Err(GatewayError::InvalidRoute(
-                    "Route path cannot be empty".to_string(),
-                ));
+                errors.push(GatewayError::InvalidRoute(format!(
+                    "routes[{}].path: Route path cannot be empty",
+                    i
+                )));
             }
 
             // Get backends for validation
-            let backends = route.get_backends()?;
-
-            // Validate each backend URL
-            for backend_config in &backends {
-                if backend_config.url.is_empty() {
-                    return Err(GatewayError::InvalidRoute(format!(
-                        "Backend URL cannot be empty for route: {}",
-                        route.path
+            let backends = route.get_backends().unwrap_or_else(|e| {
+                errors.push(if route.backend.is_none() && route.backends.is_empty() {
+                    GatewayError::InvalidRoute(format!(
+                        "routes[{}].backend: No backend configured for route: {}",
+                        i, route.path
+                    ))
+                } else {
+                    GatewayError::InvalidRoute(format!("routes[{}].backend: {}", i, e))
+                });
+                Vec::new()
+            });
+
+            // Weighted load balancing needs every backend to take some traffic
+            let canary_backends = route.canary.as_ref().map(|c| c.backends.as_slice());
+            let groups = [
+                ("backends", route.backends.as_slice()),
+                ("canary.backends", canary_backends.unwrap_or_default()),
+            ];
+            for (field, group) in groups {
+                for (j, _) in group.iter().enumerate().filter(|(_, b)| b.weight == 0) {
+                    errors.push(GatewayError::InvalidRoute(format!(
+                        "routes[{}].{}[{}].weight: Backend weight must be > 0 for route: {}",
+                        i, field, j, route.path
                     )));
                 }
+            }
 
-                if !backend_config.url.starts_with("http://")
-                    && !backend_config.url.starts_with("https://")
-                {
-                    return Err(GatewayError::InvalidRoute(format!(
-                        "Backend URL must start with http:// or https:// for route: {}",
-                        route.path
+            // Validate every backend URL, including a single backend shadowed
+            // by a backends list and the canary group
+            let mut backend_urls: Vec<(String, &str)> = backends
+                .iter()
+                .enumerate()
+                .map(|(j, backend_config)| {
+                    let field = if route.backends.is_empty() {
+                        format!("routes[{}].backend", i)
+                    } else {
+                        format!("routes[{}].backends[{}].url", i, j)
+                    };
+                    (field, backend_config.url.as_str())
+                })
+                .collect();
+            if let Some(backend) = route
+                .backend
+                .as_deref()
+                .filter(|_| !route.backends.is_empty())
+            {
+                backend_urls.push((format!("routes[{}].backend", i), backend));
+            }
+            if let Some(canary) = &route.canary {
+                backend_urls.extend(canary.backends.iter().enumerate().map(|(j, b)| {
+                    (
+                        format!("routes[{}].canary.backends[{}].url", i, j),
+                        b.url.as_str(),
+                    )
+                }));
+            }
+
+            for (field, url) in backend_urls {
+                if url.is_empty() {
+                    errors.push(GatewayError::InvalidRoute(format!(
+                        "{}: Backend URL cannot be empty for route: {}",
+                        field, route.path
+                    )));
+                } else if !url.starts_with("http://") && !url.starts_with("https://") {
+                    errors.push(GatewayError::InvalidRoute(format!(
+                        "{}: Backend URL must start with http:// or https:// for route: {}",
+                        field, route.path
                     )));
                 }
             }
 
             // Validate methods
-            for method in &route.methods {
+            for (j, method) in route.methods.iter().enumerate() {
                 let method_upper = method.to_uppercase();
-                if !["GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS"]
-                    .contains(&method_upper.as_str())
+                if ![
+                    "GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS", "TRACE", "CONNECT",
+                ]
+                .contains(&method_upper.as_str())
                 {
-                    return Err(GatewayError::InvalidRoute(format!(
-                        "Invalid HTTP method '{}' for route: {}",
-                        method, route.path
+                    errors.push(GatewayError::InvalidRoute(format!(
+                        "routes[{}].methods[{}]: Invalid HTTP method '{}' for route: {}",
+                        i, j, method, route.path
+                    )));
+                }
+            }
+
+            // Validate circuit-open fallback
+            if let Some(fallback) = &route.fallback {
+                if !(100..=599).contains(&fallback.status) {
+                    errors.push(GatewayError::InvalidRoute(format!(
+                        "routes[{}].fallback.status: Invalid status code {} for route: {}",
+                        i, fallback.status, route.path
+                    )));
+                }
+            }
+
+            if ![404, 503].contains(&route.disabled_status) {
+                errors.push(GatewayError::InvalidRoute(format!(
+                    "routes[{}].disabled_status: Disabled status must be 404 or 503 for route: {}",
+                    i, route.path
+                )));
+            }
+
+            if route.max_response_bytes == Some(0) {
+                errors.push(GatewayError::InvalidRoute(format!(
+                    "routes[{}].max_response_bytes: Response size limit must be > 0 for route: {}",
+                    i, route.path
+                )));
+            }
+
+            // Only hop-by-hop headers need preserving; the body is re-framed,
+            // so Transfer-Encoding never can be
+            for (j, name) in route.preserve_headers.iter().enumerate() {
+                if !is_hop_by_hop_header(name) || name.eq_ignore_ascii_case("transfer-encoding") {
+                    errors.push(GatewayError::InvalidRoute(format!(
+                        "routes[{}].preserve_headers[{}]: '{}' cannot be preserved for route: {} (expected connection, keep-alive, proxy-authenticate, proxy-authorization, te, trailers or upgrade)",
+                        i, j, name, route.path
                     )));
                 }
             }
 
             // Validate rate limits
             if let Some(rate_limits) = &route.rate_limit {
-                for limit in rate_limits {
+                for (j, limit) in rate_limits.iter().enumerate() {
                     if limit.requests == 0 {
-                        return Err(GatewayError::Config(format!(
-                            "Rate limit requests must be > 0 for route: {}",
-                            route.path
+                        errors.push(GatewayError::Config(format!(
+                            "routes[{}].rate_limit[{}].requests: Rate limit requests must be > 0 for route: {}",
+                            i, j, route.path
                         )));
                     }
                     if limit.window_secs == 0 {
-                        return Err(GatewayError::Config(format!(
-                            "Rate limit window must be > 0 for route: {}",
-                            route.path
+                        errors.push(GatewayError::Config(format!(
+                            "routes[{}].rate_limit[{}].window_secs: Rate limit window must be > 0 for route: {}",
+                            i, j, route.path
                         )));
                     }
                 }
@@ -430,46 +1237,219 @@ impl GatewayConfig {
 
         // Validate global rate limits
         if let Some(rate_limiting) = &self.rate_limiting {
-            for limit in &rate_limiting.global {
+            if let Err(e) = rate_limiting.algorithm.parse::<RateLimitAlgorithm>() {
+                errors.push(GatewayError::Config(format!(
+                    "rate_limiting.algorithm: {}",
+                    e
+                )));
+            }
+            if let Err(e) = RateLimitExemptions::new(&rate_limiting.exempt) {
+                errors.push(e);
+            }
+            if let Some(Err(e)) = rate_limiting
+                .quota
+                .as_ref()
+                .map(|quota| QuotaService::new(quota.clone()))
+            {
+                errors.push(e);
+            }
+
+            for (j, limit) in rate_limiting.global.iter().enumerate() {
                 if limit.requests == 0 {
-                    return Err(GatewayError::Config(
-                        "Global rate limit requests must be > 0".to_string(),
-                    ));
+                    errors.push(GatewayError::Config(format!(
+                        "rate_limiting.global[{}].requests: Global rate limit requests must be > 0",
+                        j
+                    )));
                 }
                 if limit.window_secs == 0 {
-                    return Err(GatewayError::Config(
-                        "Global rate limit window must be > 0".to_string(),
-                    ));
+                    errors.push(GatewayError::Config(format!(
+                        "rate_limiting.global[{}].window_secs: Global rate limit window must be > 0",
+                        j
+                    )));
                 }
             }
         }
 
-        Ok(())
-    }
-
-    /// Create a default configuration for testing
-    pub fn default_config() -> Self {
-        Self {
-            server: ServerConfig::default(),
-            routes: vec![],
-            auth: None,
-            rate_limiting: None,
-            circuit_breaker: None,
-            retry: None,
-            observability: None,
-            hot_reload: None,
-            cors: None,
-            ip_filter: None,
-            cache: None,
-            max_request_size: None,
-            tls: None,
+        // Validate the global and per-route circuit breaker settings
+        if let Some(circuit_breaker) = &self.circuit_breaker {
+            validate_circuit_breaker(circuit_breaker, "circuit_breaker", &mut errors);
         }
-    }
-}
-
+        for (i, route) in self.routes.iter().enumerate() {
+            if let Some(circuit_breaker) = &route.circuit_breaker {
+                validate_circuit_breaker(
+                    circuit_breaker,
+                    &format!("routes[{}].circuit_breaker", i),
+                    &mut errors,
+                );
+            }
+        }
+
+        // Validate the request duration histogram buckets
+        if let Some(buckets) = self
+            .observability
+            .as_ref()
+            .and_then(|o| o.metrics.as_ref())
+            .and_then(|m| m.duration_buckets.as_ref())
+        {
+            if buckets.is_empty()
+                || buckets.iter().any(|b| !b.is_finite() || *b <= 0.0)
+                || buckets.windows(2).any(|w| w[0] >= w[1])
+            {
+                errors.push(GatewayError::Config(
+                    "observability.metrics.duration_buckets: Buckets must be positive and strictly increasing"
+                        .to_string(),
+                ));
+            }
+        }
+
+        // Validate the access log sample rate
+        if let Some(access_log) = self
+            .observability
+            .as_ref()
+            .and_then(|o| o.access_log.as_ref())
+        {
+            if !(0.0..=1.0).contains(&access_log.sample_rate) {
+                errors.push(GatewayError::Config(
+                    "observability.access_log.sample_rate: Sample rate must be between 0 and 1"
+                        .to_string(),
+                ));
+            }
+        }
+
+        // Validate the request ID headers
+        if let Some(observability) = &self.observability {
+            for (i, name) in observability.request_id.headers.iter().enumerate() {
+                if http::HeaderName::from_bytes(name.as_bytes()).is_err() {
+                    errors.push(GatewayError::Config(format!(
+                        "observability.request_id.headers[{}]: Invalid header name: {}",
+                        i, name
+                    )));
+                }
+            }
+        }
+
+        // Validate TLS protocol version and cipher suites
+        if let Some(Err(e)) = self.tls.as_ref().map(|tls| tls.validate()) {
+            errors.push(e);
+        }
+
+        if let Some(health_path) = &self.server.health_path {
+            if !health_path.starts_with('/') {
+                errors.push(GatewayError::Config(
+                    "server.health_path: Health path must start with '/'".to_string(),
+                ));
+            }
+        }
+
+        for (i, path) in self.server.health_check_paths.iter().enumerate() {
+            if !path.starts_with('/') {
+                errors.push(GatewayError::Config(format!(
+                    "server.health_check_paths[{}]: Path must start with '/': {}",
+                    i, path
+                )));
+            }
+        }
+
+        if let Err(e) = TrustedProxies::new(&self.server.trusted_proxies) {
+            errors.push(e);
+        }
+
+        if self.server.upstream.tcp_keepalive_secs == Some(0) {
+            errors.push(GatewayError::Config(
+                "server.upstream.tcp_keepalive_secs: TCP keepalive interval must be > 0"
+                    .to_string(),
+            ));
+        }
+
+        // Validate the admin API settings
+        if let Some(admin) = self.admin.as_ref().filter(|a| a.enabled) {
+            if let Err(e) = admin.validate() {
+                errors.push(e);
+            }
+            if admin.port == self.server.port {
+                errors.push(GatewayError::Config(
+                    "admin.port: Admin port must differ from server.port".to_string(),
+                ));
+            }
+        }
+
+        if let Some(Err(e)) = self.maintenance.as_ref().map(|m| m.validate()) {
+            errors.push(e);
+        }
+
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            count => Err(GatewayError::Config(format!(
+                "{} problems found:\n{}",
+                count,
+                errors
+                    .iter()
+                    .map(|e| format!("  - {}", e))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ))),
+        }
+    }
+
+    /// Compile every route's path rewrite regexes and check its load
+    /// balancing strategy
+    fn validate_route_patterns(&self, errors: &mut Vec<GatewayError>) {
+        for (i, route) in self.routes.iter().enumerate() {
+            let rewrites = route
+                .transform
+                .as_ref()
+                .and_then(|t| t.request.as_ref())
+                .map(|r| r.path_rewrites.as_slice())
+                .unwrap_or_default();
+            for (j, rewrite) in rewrites.iter().enumerate() {
+                if let Err(e) = regex::Regex::new(&rewrite.pattern) {
+                    errors.push(GatewayError::Config(format!(
+                        "routes[{}].transform.request.path_rewrites[{}].pattern: Invalid regex '{}' for route {}: {}",
+                        i, j, rewrite.pattern, route.path, e
+                    )));
+                }
+            }
+
+            if let Some(load_balancer) = &route.load_balancer {
+                if parse_strategy(&load_balancer.strategy).is_err() {
+                    errors.push(GatewayError::Config(format!(
+                        "routes[{}].load_balancer.strategy: Unknown strategy '{}' for route {} (expected round_robin, least_connections, weighted or ip_hash)",
+                        i, load_balancer.strategy, route.path
+                    )));
+                }
+            }
+        }
+    }
+
+    /// Create a default configuration for testing
+    pub fn default_config() -> Self {
+        Self {
+            server: ServerConfig::default(),
+            routes: vec![],
+            auth: None,
+            rate_limiting: None,
+            circuit_breaker: None,
+            retry: None,
+            observability: None,
+            hot_reload: None,
+            cors: None,
+            ip_filter: None,
+            cache: None,
+            max_request_size: None,
+            tls: None,
+            backend_tls: None,
+            dns: None,
+            admin: None,
+            maintenance: None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rate_limit::QuotaPeriod;
 
     #[test]
     fn test_parse_valid_config() {
@@ -510,6 +1490,62 @@ routes: []
         assert_eq!(config.server.host, "0.0.0.0");
         assert_eq!(config.server.port, 8080);
         assert_eq!(config.server.timeout_secs, 30);
+        assert!(config.server.health_path.is_none());
+        assert_eq!(
+            config.server.health_check_paths,
+            ["/health", "/healthz", "/ready", "/readiness", "/ping"]
+        );
+        assert!(config.server.trusted_proxies.is_empty());
+        assert!(config.server.error_template.is_none());
+        assert_eq!(config.server.upstream, UpstreamConfig::default());
+        assert_eq!(config.server.trailing_slash, TrailingSlash::Strict);
+    }
+
+    #[test]
+    fn test_jwt_leeway_default() {
+        let yaml = r#"
+server: {}
+auth:
+  jwt:
+    secret: "test-secret"
+routes: []
+"#;
+
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+        let jwt = config.auth.unwrap().jwt.unwrap();
+        assert_eq!(jwt.leeway_secs, 60);
+    }
+
+    #[test]
+    fn test_upstream_config() {
+        let yaml = r#"
+server:
+  upstream:
+    pool_max_idle_per_host: 32
+    pool_idle_timeout_secs: null
+    tcp_keepalive_secs: 60
+    http2_prior_knowledge: true
+routes: []
+"#;
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+        assert!(config.validate().is_ok());
+        let upstream = &config.server.upstream;
+        assert_eq!(upstream.pool_max_idle_per_host, Some(32));
+        assert_eq!(upstream.pool_idle_timeout_secs, None);
+        assert_eq!(upstream.tcp_keepalive_secs, Some(60));
+        assert!(upstream.http2_prior_knowledge);
+
+        let yaml = r#"
+server:
+  upstream:
+    tcp_keepalive_secs: 0
+routes: []
+"#;
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+        let err = config.validate().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("server.upstream.tcp_keepalive_secs"));
     }
 
     #[test]
@@ -531,6 +1567,18 @@ routes: []
                 cors: None,
                 ip_filter: None,
                 cache: None,
+                honor_method_override: None,
+                circuit_breaker: None,
+                fallback: None,
+                mirror: None,
+                canary: None,
+                match_headers: Default::default(),
+                match_query: Default::default(),
+                max_response_bytes: None,
+                answer_options: None,
+                preserve_headers: Vec::new(),
+                enabled: true,
+                disabled_status: 503,
             }],
             auth: None,
             rate_limiting: None,
@@ -542,6 +1590,11 @@ routes: []
             ip_filter: None,
             cache: None,
             max_request_size: None,
+            tls: None,
+            backend_tls: None,
+            dns: None,
+            admin: None,
+            maintenance: None,
         };
 
         assert!(config.validate().is_err());
@@ -566,6 +1619,18 @@ routes: []
                 cors: None,
                 ip_filter: None,
                 cache: None,
+                honor_method_override: None,
+                circuit_breaker: None,
+                fallback: None,
+                mirror: None,
+                canary: None,
+                match_headers: Default::default(),
+                match_query: Default::default(),
+                max_response_bytes: None,
+                answer_options: None,
+                preserve_headers: Vec::new(),
+                enabled: true,
+                disabled_status: 503,
             }],
             auth: None,
             rate_limiting: None,
@@ -577,11 +1642,285 @@ routes: []
             ip_filter: None,
             cache: None,
             max_request_size: None,
+            tls: None,
+            backend_tls: None,
+            dns: None,
+            admin: None,
+            maintenance: None,
         };
 
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_from_yaml_reports_line_number() {
+        let yaml = "server:\n  port: abc\nroutes: []\n";
+
+        let err = GatewayConfig::from_yaml(yaml).unwrap_err().to_string();
+        assert!(err.contains("line 2"), "unexpected error: {}", err);
+        assert!(err.contains("server.port"), "unexpected error: {}", err);
+    }
+
+    #[test]
+    fn test_validate_error_names_route_index() {
+        let yaml = r#"
+server:
+  port: 8080
+routes:
+  - path: /api/ok
+    backend: http://localhost:3000
+  - path: /api/bad
+    backend: invalid-url
+"#;
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+
+        let err = config.validate().unwrap_err().to_string();
+        assert!(
+            err.contains("routes[1].backend"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_validate_error_rate_circuit_breaker() {
+        let yaml = r#"
+server:
+  port: 8080
+routes:
+  - path: /api
+    backend: http://localhost:3000
+circuit_breaker:
+  mode: error_rate
+  window_size: 5
+  minimum_requests: 10
+"#;
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+
+        let err = config.validate().unwrap_err().to_string();
+        assert!(
+            err.contains("circuit_breaker.minimum_requests"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_validate_route_circuit_breaker() {
+        let yaml = r#"
+server:
+  port: 8080
+circuit_breaker:
+  failure_threshold: 0
+routes:
+  - path: /api
+    backend: http://localhost:3000
+  - path: /api/reports
+    backend: http://localhost:3001
+    circuit_breaker:
+      timeout_secs: 0
+      success_threshold: 0
+      mode: error_rate
+      error_rate_threshold: 1.5
+"#;
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+
+        let err = config.validate().unwrap_err().to_string();
+        for field in [
+            "circuit_breaker.failure_threshold",
+            "routes[1].circuit_breaker.timeout_secs",
+            "routes[1].circuit_breaker.success_threshold",
+            "routes[1].circuit_breaker.error_rate_threshold",
+        ] {
+            assert!(err.contains(field), "missing {}: {}", field, err);
+        }
+        assert!(!err.contains("routes[0]"), "unexpected error: {}", err);
+    }
+
+    #[test]
+    fn test_validate_route_without_backends() {
+        let yaml = r#"
+server: {}
+routes:
+  - path: /api/empty
+    backends: []
+"#;
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+
+        let err = config.validate().unwrap_err().to_string();
+        assert!(
+            err.contains("routes[0].backend: No backend configured"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_validate_every_backend_url() {
+        let yaml = r#"
+server: {}
+routes:
+  - path: /api/users
+    backends:
+      - url: http://users-1:3000
+      - url: users-2:3000
+"#;
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("routes[0].backends[1].url"), "{err}");
+
+        let yaml = r#"
+server: {}
+routes:
+  - path: /api/users
+    backend: ftp://legacy:21
+    backends:
+      - url: http://users-1:3000
+"#;
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("routes[0].backend:"), "{err}");
+    }
+
+    #[test]
+    fn test_shorthand_backend_weights() {
+        let yaml = r#"
+server: {}
+routes:
+  - path: /api/users
+    backends:
+      - "http://users-1:3000|weight=3"
+      - "http://users-2:3000"
+    load_balancer:
+      strategy: weighted
+  - path: /api/orders
+    backend: "http://orders:3000|weight=2"
+"#;
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let backends = config.routes[0].get_backends().unwrap();
+        assert_eq!(backends[0].url, "http://users-1:3000");
+        assert_eq!(backends[0].weight, 3);
+        assert_eq!(backends[1].weight, 1);
+        assert_eq!(
+            config.routes[1].get_backends().unwrap()[0].url,
+            "http://orders:3000"
+        );
+
+        let yaml = r#"
+server: {}
+routes:
+  - path: /api/users
+    backends:
+      - "http://users-1:3000|weight=0"
+"#;
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("routes[0].backends[0].weight"), "{err}");
+
+        let yaml = r#"
+server: {}
+routes:
+  - path: /api/users
+    backends:
+      - "http://users-1:3000|weight=heavy"
+"#;
+        let err = GatewayConfig::from_yaml(yaml).unwrap_err().to_string();
+        assert!(err.contains("Invalid backend"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_load_balancer_strategy() {
+        let yaml = r#"
+server: {}
+routes:
+  - path: /api/users
+    backends:
+      - url: http://users-1:3000
+      - url: http://users-2:3000
+    load_balancer:
+      strategy: fastest
+"#;
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("routes[0].load_balancer.strategy"), "{err}");
+        assert!(err.contains("fastest"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_reports_all_pattern_errors() {
+        let yaml = r#"
+server: {}
+routes:
+  - path: /api/users
+    backend: http://users:3000
+    transform:
+      request:
+        path_rewrites:
+          - pattern: "^/api/users/(\\d+)$"
+            replacement: "/users/$1"
+          - pattern: "^/api/(users"
+            replacement: "/users"
+  - path: /api/orders
+    backends:
+      - url: http://orders-1:3000
+    load_balancer:
+      strategy: fastest
+"#;
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+
+        let err = config.validate().unwrap_err().to_string();
+        assert!(
+            err.contains("routes[0].transform.request.path_rewrites[1].pattern"),
+            "{err}"
+        );
+        assert!(
+            err.contains("Invalid regex '^/api/(users' for route /api/users"),
+            "{err}"
+        );
+        assert!(
+            err.contains("Unknown strategy 'fastest' for route /api/orders"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_every_error() {
+        let yaml = r#"
+server:
+  health_path: healthz
+routes:
+  - path: ""
+    backend: http://localhost:3000
+  - path: /api/users
+    backend: localhost:3000
+    methods: [GET, FETCH]
+    rate_limit:
+      - dimension: ip
+        requests: 0
+        window_secs: 60
+  - path: /api/orders
+rate_limiting:
+  algorithm: leaky
+"#;
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("7 problems found"), "{err}");
+        for field in [
+            "routes[0].path",
+            "routes[1].backend",
+            "routes[1].methods[1]",
+            "routes[1].rate_limit[0].requests",
+            "routes[2].backend",
+            "rate_limiting.algorithm",
+            "server.health_path",
+        ] {
+            assert!(err.contains(field), "{field} missing from: {err}");
+        }
+    }
+
     #[test]
     fn test_validate_invalid_method() {
         let config = GatewayConfig {
@@ -601,6 +1940,18 @@ routes: []
                 cors: None,
                 ip_filter: None,
                 cache: None,
+                honor_method_override: None,
+                circuit_breaker: None,
+                fallback: None,
+                mirror: None,
+                canary: None,
+                match_headers: Default::default(),
+                match_query: Default::default(),
+                max_response_bytes: None,
+                answer_options: None,
+                preserve_headers: Vec::new(),
+                enabled: true,
+                disabled_status: 503,
             }],
             auth: None,
             rate_limiting: None,
@@ -612,11 +1963,31 @@ routes: []
             ip_filter: None,
             cache: None,
             max_request_size: None,
+            tls: None,
+            backend_tls: None,
+            dns: None,
+            admin: None,
+            maintenance: None,
         };
 
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_validate_trace_and_connect_opt_in() {
+        let config = GatewayConfig::from_yaml(
+            r#"
+server: {}
+routes:
+  - path: "/debug"
+    backend: "http://localhost:3000"
+    methods: ["GET", "TRACE", "connect"]
+"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn test_validate_valid_config() {
         let config = GatewayConfig {
@@ -636,6 +2007,18 @@ routes: []
                 cors: None,
                 ip_filter: None,
                 cache: None,
+                honor_method_override: None,
+                circuit_breaker: None,
+                fallback: None,
+                mirror: None,
+                canary: None,
+                match_headers: Default::default(),
+                match_query: Default::default(),
+                max_response_bytes: None,
+                answer_options: None,
+                preserve_headers: Vec::new(),
+                enabled: true,
+                disabled_status: 503,
             }],
             auth: None,
             rate_limiting: None,
@@ -647,9 +2030,175 @@ routes: []
             ip_filter: None,
             cache: None,
             max_request_size: None,
+            tls: None,
+            backend_tls: None,
+            dns: None,
+            admin: None,
+            maintenance: None,
+        };
+
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_duration_buckets() {
+        let config_with = |buckets: &str| {
+            GatewayConfig::from_yaml(&format!(
+                "server: {{}}\nobservability:\n  metrics:\n    duration_buckets: {}\nroutes: []\n",
+                buckets
+            ))
+            .unwrap()
+        };
+
+        assert!(config_with("[0.001, 0.005, 0.01]").validate().is_ok());
+        assert!(config_with("[]").validate().is_err());
+        assert!(config_with("[0.01, 0.005]").validate().is_err());
+        assert!(config_with("[0, 0.005]").validate().is_err());
+    }
+
+    #[test]
+    fn test_health_check_paths_config() {
+        let config_with = |paths: &str| {
+            GatewayConfig::from_yaml(&format!(
+                "server:\n  health_check_paths: {}\nroutes: []\n",
+                paths
+            ))
+            .unwrap()
+        };
+
+        let config = config_with("[/status, /_health]");
+        assert!(config.validate().is_ok());
+        assert_eq!(config.server.health_check_paths, ["/status", "/_health"]);
+
+        assert!(config_with("[]").validate().is_ok());
+
+        let err = config_with("[/status, status]")
+            .validate()
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("server.health_check_paths[1]"), "{err}");
+    }
+
+    #[test]
+    fn test_route_enabled_config() {
+        let config_with = |route: &str| {
+            GatewayConfig::from_yaml(&format!(
+                "server: {{}}\nroutes:\n  - path: /api/users\n    backend: http://localhost:3000\n{}",
+                route
+            ))
+            .unwrap()
+        };
+
+        let config = config_with("");
+        assert!(config.routes[0].enabled);
+        assert_eq!(config.routes[0].disabled_status, 503);
+
+        let config = config_with("    enabled: false\n    disabled_status: 404\n");
+        assert!(config.validate().is_ok());
+        assert!(!config.routes[0].enabled);
+        assert_eq!(config.routes[0].disabled_status, 404);
+
+        let err = config_with("    disabled_status: 200\n")
+            .validate()
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("routes[0].disabled_status"), "{err}");
+    }
+
+    #[test]
+    fn test_max_response_bytes_config() {
+        let config_with = |limit: &str| {
+            GatewayConfig::from_yaml(&format!(
+                "server: {{}}\nroutes:\n  - path: /api/files\n    backend: http://localhost:3000\n    max_response_bytes: {}\n",
+                limit
+            ))
+            .unwrap()
+        };
+
+        let config = config_with("1048576");
+        assert!(config.validate().is_ok());
+        assert_eq!(config.routes[0].max_response_bytes, Some(1_048_576));
+
+        let err = config_with("0").validate().unwrap_err().to_string();
+        assert!(err.contains("routes[0].max_response_bytes"), "{err}");
+    }
+
+    #[test]
+    fn test_preserve_headers_config() {
+        let config_with = |headers: &str| {
+            GatewayConfig::from_yaml(&format!(
+                "server: {{}}\nroutes:\n  - path: /ws\n    backend: http://localhost:3000\n    preserve_headers: {}\n",
+                headers
+            ))
+            .unwrap()
+        };
+
+        let config = config_with("[TE, Upgrade, Connection]");
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.routes[0].preserve_headers,
+            ["TE", "Upgrade", "Connection"]
+        );
+
+        let err = config_with("[X-Custom]")
+            .validate()
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("routes[0].preserve_headers[0]"), "{err}");
+        assert!(config_with("[Transfer-Encoding]").validate().is_err());
+    }
+
+    #[test]
+    fn test_access_log_sampling_config() {
+        let config_with = |access_log: &str| {
+            GatewayConfig::from_yaml(&format!(
+                "server: {{}}\nobservability:\n  access_log: {}\nroutes: []\n",
+                access_log
+            ))
+            .unwrap()
+        };
+
+        let config = config_with("{}");
+        let access_log = config.observability.unwrap().access_log.unwrap();
+        assert_eq!(access_log.sample_rate, 1.0);
+        assert_eq!(access_log.slow_threshold_ms, None);
+
+        let config = config_with("{sample_rate: 0.01, slow_threshold_ms: 500}");
+        assert!(config.validate().is_ok());
+        let access_log = config.observability.unwrap().access_log.unwrap();
+        assert_eq!(access_log.sample_rate, 0.01);
+        assert_eq!(access_log.slow_threshold_ms, Some(500));
+
+        assert!(config_with("{sample_rate: 1.5}").validate().is_err());
+        assert!(config_with("{sample_rate: -0.1}").validate().is_err());
+    }
+
+    #[test]
+    fn test_request_id_config() {
+        let config_with = |request_id: &str| {
+            GatewayConfig::from_yaml(&format!(
+                "server: {{}}\nobservability:\n  request_id: {}\nroutes: []\n",
+                request_id
+            ))
+            .unwrap()
         };
 
+        let config = config_with("{}");
+        let request_id = config.observability.unwrap().request_id;
+        assert_eq!(request_id.headers, vec!["x-request-id"]);
+        assert_eq!(request_id.format, RequestIdFormat::UuidV4);
+
+        let config = config_with("{headers: [x-amzn-trace-id, x-request-id], format: uuid_v7}");
         assert!(config.validate().is_ok());
+        let request_id = config.observability.unwrap().request_id;
+        assert_eq!(request_id.headers, vec!["x-amzn-trace-id", "x-request-id"]);
+        assert_eq!(request_id.format, RequestIdFormat::UuidV7);
+
+        let err = config_with("{headers: ['bad header']}")
+            .validate()
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("observability.request_id.headers[0]"), "{err}");
     }
 
     #[test]
@@ -699,6 +2248,18 @@ routes:
             cors: None,
             ip_filter: None,
             cache: None,
+            honor_method_override: None,
+            circuit_breaker: None,
+            fallback: None,
+            mirror: None,
+            canary: None,
+            match_headers: Default::default(),
+            match_query: Default::default(),
+            max_response_bytes: None,
+            answer_options: None,
+            preserve_headers: Vec::new(),
+            enabled: true,
+            disabled_status: 503,
         };
 
         let backends = route.get_backends().unwrap();
@@ -732,6 +2293,18 @@ routes:
             cors: None,
             ip_filter: None,
             cache: None,
+            honor_method_override: None,
+            circuit_breaker: None,
+            fallback: None,
+            mirror: None,
+            canary: None,
+            match_headers: Default::default(),
+            match_query: Default::default(),
+            max_response_bytes: None,
+            answer_options: None,
+            preserve_headers: Vec::new(),
+            enabled: true,
+            disabled_status: 503,
         };
 
         let backends = route.get_backends().unwrap();
@@ -769,4 +2342,208 @@ rate_limiting:
         assert_eq!(config.routes[0].rate_limit.as_ref().unwrap().len(), 1);
         assert!(config.validate().is_ok());
     }
+
+    #[test]
+    fn test_header_rate_limit_dimension() {
+        let yaml = r#"
+server: {}
+routes:
+  - path: "/api/reports"
+    backend: "http://localhost:3000"
+    rate_limit:
+      - dimension: "header:X-Tenant-ID"
+        requests: 100
+        window_secs: 60
+"#;
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let err = GatewayConfig::from_yaml(&yaml.replace("X-Tenant-ID", "X Tenant")).unwrap_err();
+        assert!(err.to_string().contains("Invalid rate limit dimension"));
+    }
+
+    #[test]
+    fn test_rate_limit_quota_config() {
+        let yaml = r#"
+server: {}
+routes: []
+rate_limiting:
+  quota:
+    requests: 1000000
+    period: monthly
+    utc_offset: "+05:30"
+"#;
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+        let quota = config
+            .rate_limiting
+            .as_ref()
+            .unwrap()
+            .quota
+            .as_ref()
+            .unwrap();
+        assert_eq!(quota.period, QuotaPeriod::Monthly);
+        assert!(config.validate().is_ok());
+
+        let config = GatewayConfig::from_yaml(&yaml.replace("+05:30", "Asia/Kolkata")).unwrap();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("rate_limiting.quota.utc_offset"));
+    }
+
+    #[test]
+    fn test_api_key_entries_with_rate_limit() {
+        let yaml = r#"
+server: {}
+routes: []
+auth:
+  api_key:
+    keys:
+      free-key: "Free tier"
+      pro-key:
+        description: "Pro tier"
+        rate_limit:
+          requests: 1000
+          window_secs: 60
+"#;
+
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+        let keys = &config.auth.unwrap().api_key.unwrap().keys;
+        assert_eq!(keys["free-key"].description(), "Free tier");
+        assert!(keys["free-key"].rate_limit().is_none());
+        assert_eq!(keys["pro-key"].description(), "Pro tier");
+        assert_eq!(keys["pro-key"].rate_limit().unwrap().requests, 1000);
+    }
+
+    #[test]
+    fn test_rate_limit_algorithm_validation() {
+        let yaml = r#"
+server: {}
+routes: []
+rate_limiting:
+  algorithm: gcra
+"#;
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let yaml = r#"
+server: {}
+routes: []
+rate_limiting:
+  algorithm: leaky
+"#;
+        let config = GatewayConfig::from_yaml(yaml).unwrap();
+        assert!(matches!(config.validate(), Err(GatewayError::Config(_))));
+    }
+
+    const EQUIVALENT_YAML: &str = r#"
+server:
+  host: "127.0.0.1"
+  port: 9000
+routes:
+  - path: "/api/users/:id"
+    backend: "http://localhost:3000"
+    methods: ["GET", "PUT"]
+    strip_prefix: true
+    rate_limit:
+      - dimension: ip
+        requests: 100
+        window_secs: 60
+rate_limiting:
+  enabled: true
+"#;
+
+    const EQUIVALENT_JSON: &str = r#"{
+  "server": { "host": "127.0.0.1", "port": 9000 },
+  "routes": [
+    {
+      "path": "/api/users/:id",
+      "backend": "http://localhost:3000",
+      "methods": ["GET", "PUT"],
+      "strip_prefix": true,
+      "rate_limit": [{ "dimension": "ip", "requests": 100, "window_secs": 60 }]
+    }
+  ],
+  "rate_limiting": { "enabled": true }
+}"#;
+
+    const EQUIVALENT_TOML: &str = r#"
+[server]
+host = "127.0.0.1"
+port = 9000
+
+[[routes]]
+path = "/api/users/:id"
+backend = "http://localhost:3000"
+methods = ["GET", "PUT"]
+strip_prefix = true
+rate_limit = [{ dimension = "ip", requests = 100, window_secs = 60 }]
+
+[rate_limiting]
+enabled = true
+"#;
+
+    fn as_value(config: &GatewayConfig) -> serde_json::Value {
+        serde_json::to_value(config).unwrap()
+    }
+
+    #[test]
+    fn test_json_and_toml_match_yaml() {
+        let yaml = GatewayConfig::from_yaml(EQUIVALENT_YAML).unwrap();
+        let json = GatewayConfig::from_json(EQUIVALENT_JSON).unwrap();
+        let toml = GatewayConfig::from_toml(EQUIVALENT_TOML).unwrap();
+
+        assert_eq!(yaml.server.port, 9000);
+        assert_eq!(yaml.routes[0].methods, vec!["GET", "PUT"]);
+        assert_eq!(as_value(&json), as_value(&yaml));
+        assert_eq!(as_value(&toml), as_value(&yaml));
+    }
+
+    #[test]
+    fn test_from_file_dispatches_on_extension() {
+        let expected = as_value(&GatewayConfig::from_yaml(EQUIVALENT_YAML).unwrap());
+
+        for (suffix, content) in [
+            (".yaml", EQUIVALENT_YAML),
+            (".yml", EQUIVALENT_YAML),
+            (".json", EQUIVALENT_JSON),
+            (".TOML", EQUIVALENT_TOML),
+        ] {
+            let file = tempfile::Builder::new().suffix(suffix).tempfile().unwrap();
+            std::fs::write(file.path(), content).unwrap();
+
+            let config = GatewayConfig::from_file(file.path()).unwrap();
+            assert_eq!(as_value(&config), expected, "format {}", suffix);
+        }
+    }
+
+    #[test]
+    fn test_from_file_rejects_unknown_extension() {
+        let file = tempfile::Builder::new().suffix(".ini").tempfile().unwrap();
+        std::fs::write(file.path(), EQUIVALENT_YAML).unwrap();
+
+        let err = GatewayConfig::from_file(file.path())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Unsupported config file extension '.ini'"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_json_and_toml_parse_errors_report_location() {
+        let err = GatewayConfig::from_json("{\n  \"server\": {\n    \"port\": \"abc\"\n  }\n}")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("line 3"), "unexpected error: {}", err);
+
+        let err = GatewayConfig::from_toml("routes = []\n\n[server]\nport = \"abc\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("line 4, column 8"),
+            "unexpected error: {}",
+            err
+        );
+    }
 }
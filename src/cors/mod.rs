@@ -1,5 +1,12 @@
 use crate::error::{GatewayError, Result};
-use axum::http::{HeaderValue, Method};
+use axum::body::Body;
+use axum::http::header::{
+    ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
+    ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS, ACCESS_CONTROL_MAX_AGE,
+    ACCESS_CONTROL_REQUEST_HEADERS, ACCESS_CONTROL_REQUEST_METHOD, ORIGIN, VARY,
+};
+use axum::http::{HeaderMap, HeaderValue, Method, Response, StatusCode};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use tower_http::cors::{AllowOrigin, CorsLayer};
@@ -11,6 +18,10 @@ pub struct CorsConfig {
     /// Allowed origins (use ["*"] for all origins)
     #[serde(default)]
     pub allowed_origins: Vec<String>,
+    /// Regular expressions for allowed origins (e.g. `https://[a-z0-9-]+\.example\.com`).
+    /// Patterns must match the whole origin; matching origins are reflected back.
+    #[serde(default)]
+    pub allowed_origin_patterns: Vec<String>,
     /// Allowed HTTP methods
     #[serde(default = "default_methods")]
     pub allowed_methods: Vec<String>,
@@ -55,6 +66,7 @@ impl Default for CorsConfig {
     fn default() -> Self {
         Self {
             allowed_origins: vec!["*".to_string()],
+            allowed_origin_patterns: vec![],
             allowed_methods: default_methods(),
             allowed_headers: default_headers(),
             exposed_headers: vec![],
@@ -69,6 +81,7 @@ impl CorsConfig {
     pub fn permissive() -> Self {
         Self {
             allowed_origins: vec!["*".to_string()],
+            allowed_origin_patterns: vec![],
             allowed_methods: vec![
                 "GET".to_string(),
                 "POST".to_string(),
@@ -89,6 +102,7 @@ impl CorsConfig {
     pub fn restrictive(origins: Vec<String>) -> Self {
         Self {
             allowed_origins: origins,
+            allowed_origin_patterns: vec![],
             allowed_methods: vec!["GET".to_string(), "POST".to_string()],
             allowed_headers: vec!["Content-Type".to_string(), "Authorization".to_string()],
             exposed_headers: vec![],
@@ -207,6 +221,162 @@ impl CorsConfig {
     }
 }
 
+/// Per-route CORS handling built from a [`CorsConfig`]
+#[derive(Debug)]
+pub struct CorsService {
+    config: CorsConfig,
+    origin_patterns: Vec<Regex>,
+}
+
+impl CorsService {
+    /// Create a new CORS service, compiling the origin patterns
+    pub fn new(config: CorsConfig) -> Result<Self> {
+        if config.allow_credentials && config.allowed_origins.iter().any(|o| o == "*") {
+            return Err(GatewayError::Config(
+                "CORS allowed_origins cannot be \"*\" when allow_credentials is true; \
+                 list the origins or use allowed_origin_patterns"
+                    .to_string(),
+            ));
+        }
+
+        let origin_patterns = config
+            .allowed_origin_patterns
+            .iter()
+            .map(|pattern| {
+                // Anchor so a pattern cannot match part of a longer origin
+                Regex::new(&format!("^(?:{})$", pattern)).map_err(|e| {
+                    GatewayError::Config(format!(
+                        "Invalid CORS origin pattern '{}': {}",
+                        pattern, e
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            config,
+            origin_patterns,
+        })
+    }
+
+    /// Whether the request is a CORS preflight (OPTIONS with Origin and
+    /// Access-Control-Request-Method)
+    pub fn is_preflight(method: &Method, headers: &HeaderMap) -> bool {
+        method == Method::OPTIONS
+            && headers.contains_key(ORIGIN)
+            && headers.contains_key(ACCESS_CONTROL_REQUEST_METHOD)
+    }
+
+    /// Value for Access-Control-Allow-Origin if `origin` is allowed.
+    ///
+    /// Origins matched by an exact entry or a pattern are reflected back;
+    /// the wildcard answers with "*".
+    fn allow_origin_value(&self, origin: &HeaderValue) -> Option<HeaderValue> {
+        if self.config.allowed_origins.iter().any(|o| o == "*") {
+            return Some(HeaderValue::from_static("*"));
+        }
+
+        let origin_str = origin.to_str().ok()?;
+        let allowed = self
+            .config
+            .allowed_origins
+            .iter()
+            .any(|o| o.eq_ignore_ascii_case(origin_str))
+            || self
+                .origin_patterns
+                .iter()
+                .any(|pattern| pattern.is_match(origin_str));
+
+        allowed.then(|| origin.clone())
+    }
+
+    /// Answer a preflight request without contacting the backend.
+    ///
+    /// CORS headers are omitted when the origin or requested method is not
+    /// allowed, so the browser rejects the actual request.
+    pub fn preflight_response(&self, request_headers: &HeaderMap) -> Response<Body> {
+        let mut response = Response::new(Body::empty());
+        *response.status_mut() = StatusCode::NO_CONTENT;
+        let headers = response.headers_mut();
+        headers.insert(VARY, HeaderValue::from_static("Origin"));
+
+        let Some(allow_origin) = request_headers
+            .get(ORIGIN)
+            .and_then(|origin| self.allow_origin_value(origin))
+        else {
+            debug!("CORS: Preflight origin not allowed");
+            return response;
+        };
+
+        let method_allowed = request_headers
+            .get(ACCESS_CONTROL_REQUEST_METHOD)
+            .and_then(|m| m.to_str().ok())
+            .is_some_and(|requested| {
+                self.config
+                    .allowed_methods
+                    .iter()
+                    .any(|m| m.eq_ignore_ascii_case(requested))
+            });
+        if !method_allowed {
+            debug!("CORS: Preflight method not allowed");
+            return response;
+        }
+
+        headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, allow_origin);
+        if let Ok(methods) = HeaderValue::from_str(&self.config.allowed_methods.join(", ")) {
+            headers.insert(ACCESS_CONTROL_ALLOW_METHODS, methods);
+        }
+
+        // "*" reflects whatever the browser asked for, which also works with credentials
+        let allow_headers = if self.config.allowed_headers.iter().any(|h| h == "*") {
+            request_headers.get(ACCESS_CONTROL_REQUEST_HEADERS).cloned()
+        } else {
+            HeaderValue::from_str(&self.config.allowed_headers.join(", ")).ok()
+        };
+        if let Some(allow_headers) = allow_headers {
+            headers.insert(ACCESS_CONTROL_ALLOW_HEADERS, allow_headers);
+        }
+
+        if self.config.allow_credentials {
+            headers.insert(
+                ACCESS_CONTROL_ALLOW_CREDENTIALS,
+                HeaderValue::from_static("true"),
+            );
+        }
+        headers.insert(
+            ACCESS_CONTROL_MAX_AGE,
+            HeaderValue::from(self.config.max_age_secs),
+        );
+
+        response
+    }
+
+    /// Add CORS headers to the response of an actual (non-preflight) request
+    pub fn apply_response_headers(&self, request_headers: &HeaderMap, headers: &mut HeaderMap) {
+        headers.append(VARY, HeaderValue::from_static("Origin"));
+
+        let Some(allow_origin) = request_headers
+            .get(ORIGIN)
+            .and_then(|origin| self.allow_origin_value(origin))
+        else {
+            return;
+        };
+
+        headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, allow_origin);
+        if self.config.allow_credentials {
+            headers.insert(
+                ACCESS_CONTROL_ALLOW_CREDENTIALS,
+                HeaderValue::from_static("true"),
+            );
+        }
+        if !self.config.exposed_headers.is_empty() {
+            if let Ok(exposed) = HeaderValue::from_str(&self.config.exposed_headers.join(", ")) {
+                headers.insert(ACCESS_CONTROL_EXPOSE_HEADERS, exposed);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -300,4 +470,125 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    fn preflight_headers(origin: &str, method: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(ORIGIN, HeaderValue::from_str(origin).unwrap());
+        headers.insert(
+            ACCESS_CONTROL_REQUEST_METHOD,
+            HeaderValue::from_str(method).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_is_preflight() {
+        let headers = preflight_headers("https://example.com", "POST");
+        assert!(CorsService::is_preflight(&Method::OPTIONS, &headers));
+        assert!(!CorsService::is_preflight(&Method::GET, &headers));
+
+        let mut plain_options = HeaderMap::new();
+        plain_options.insert(ORIGIN, HeaderValue::from_static("https://example.com"));
+        assert!(!CorsService::is_preflight(&Method::OPTIONS, &plain_options));
+    }
+
+    #[test]
+    fn test_preflight_reflects_requested_headers_for_wildcard() {
+        let cors = CorsService::new(CorsConfig::permissive()).unwrap();
+        let mut headers = preflight_headers("https://example.com", "PUT");
+        headers.insert(
+            ACCESS_CONTROL_REQUEST_HEADERS,
+            HeaderValue::from_static("x-custom, content-type"),
+        );
+
+        let response = cors.preflight_response(&headers);
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+        assert_eq!(
+            response.headers()[ACCESS_CONTROL_ALLOW_HEADERS],
+            "x-custom, content-type"
+        );
+        assert_eq!(response.headers()[ACCESS_CONTROL_MAX_AGE], "86400");
+    }
+
+    #[test]
+    fn test_preflight_rejects_disallowed_method() {
+        let config = CorsConfig::restrictive(vec!["https://example.com".to_string()]);
+        let response = CorsService::new(config)
+            .unwrap()
+            .preflight_response(&preflight_headers("https://example.com", "DELETE"));
+        assert!(response
+            .headers()
+            .get(ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none());
+    }
+
+    fn subdomain_cors() -> CorsService {
+        CorsService::new(CorsConfig {
+            allowed_origins: vec![],
+            allowed_origin_patterns: vec![r"https://[a-z0-9-]+\.example\.com".to_string()],
+            allow_credentials: true,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_origin_pattern_reflects_matching_subdomain() {
+        let cors = subdomain_cors();
+        let response =
+            cors.preflight_response(&preflight_headers("https://app.example.com", "GET"));
+
+        assert_eq!(
+            response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://app.example.com"
+        );
+        assert_eq!(response.headers()[ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+    }
+
+    #[test]
+    fn test_origin_pattern_rejects_non_matching_origin() {
+        let cors = subdomain_cors();
+
+        // Patterns are anchored, so look-alike hosts and other schemes don't match
+        for origin in [
+            "https://example.com",
+            "http://app.example.com",
+            "https://app.example.com.evil.org",
+            "https://evil.org",
+        ] {
+            let mut headers = HeaderMap::new();
+            cors.apply_response_headers(&preflight_headers(origin, "GET"), &mut headers);
+            assert!(
+                headers.get(ACCESS_CONTROL_ALLOW_ORIGIN).is_none(),
+                "{} should not be allowed",
+                origin
+            );
+        }
+    }
+
+    #[test]
+    fn test_credentials_with_wildcard_origin_rejected() {
+        let config = CorsConfig {
+            allowed_origins: vec!["*".to_string()],
+            allow_credentials: true,
+            ..Default::default()
+        };
+
+        let err = CorsService::new(config).unwrap_err().to_string();
+        assert!(
+            err.contains("allow_credentials"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_invalid_origin_pattern_rejected() {
+        let config = CorsConfig {
+            allowed_origin_patterns: vec!["https://(".to_string()],
+            ..Default::default()
+        };
+
+        assert!(CorsService::new(config).is_err());
+    }
 }
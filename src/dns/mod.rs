@@ -0,0 +1,317 @@
+use crate::error::{GatewayError, Result};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{debug, warn};
+
+/// DNS cache configuration for backend hostname resolution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DnsCacheConfig {
+    /// Enable the gateway-managed DNS cache
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// How long resolved addresses are reused before resolving again
+    #[serde(default = "default_dns_cache_ttl_secs")]
+    pub dns_cache_ttl_secs: u64,
+    /// Serve the last known good addresses when resolution fails
+    #[serde(default = "default_stale_on_error")]
+    pub stale_on_error: bool,
+    /// Minimum delay before retrying a hostname whose resolution failed
+    #[serde(default = "default_failure_backoff_ms")]
+    pub failure_backoff_ms: u64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_dns_cache_ttl_secs() -> u64 {
+    30
+}
+
+fn default_stale_on_error() -> bool {
+    true
+}
+
+fn default_failure_backoff_ms() -> u64 {
+    1000
+}
+
+impl Default for DnsCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            dns_cache_ttl_secs: default_dns_cache_ttl_secs(),
+            stale_on_error: default_stale_on_error(),
+            failure_backoff_ms: default_failure_backoff_ms(),
+        }
+    }
+}
+
+impl DnsCacheConfig {
+    pub fn ttl(&self) -> Duration {
+        Duration::from_secs(self.dns_cache_ttl_secs)
+    }
+
+    pub fn failure_backoff(&self) -> Duration {
+        Duration::from_millis(self.failure_backoff_ms)
+    }
+}
+
+/// Hostname resolver used to populate the DNS cache
+#[async_trait]
+pub trait DnsResolver: Send + Sync {
+    /// Resolve a hostname into socket addresses (port is ignored)
+    async fn resolve(&self, host: &str) -> std::io::Result<Vec<SocketAddr>>;
+}
+
+/// Resolver backed by the system resolver
+#[derive(Debug, Clone, Default)]
+pub struct SystemResolver;
+
+#[async_trait]
+impl DnsResolver for SystemResolver {
+    async fn resolve(&self, host: &str) -> std::io::Result<Vec<SocketAddr>> {
+        Ok(tokio::net::lookup_host((host, 0)).await?.collect())
+    }
+}
+
+/// Cached resolution result for a single hostname
+#[derive(Debug, Clone)]
+struct CachedAddrs {
+    addrs: Vec<SocketAddr>,
+    resolved_at: Instant,
+    last_failure: Option<Instant>,
+}
+
+/// DNS cache with TTL, failure backoff and stale-on-error fallback
+#[derive(Clone)]
+pub struct DnsCache {
+    config: DnsCacheConfig,
+    resolver: Arc<dyn DnsResolver>,
+    entries: Arc<DashMap<String, CachedAddrs>>,
+}
+
+impl DnsCache {
+    /// Create a new DNS cache using the system resolver
+    pub fn new(config: DnsCacheConfig) -> Self {
+        Self::with_resolver(config, Arc::new(SystemResolver))
+    }
+
+    /// Create a new DNS cache using a custom resolver
+    pub fn with_resolver(config: DnsCacheConfig, resolver: Arc<dyn DnsResolver>) -> Self {
+        Self {
+            config,
+            resolver,
+            entries: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Resolve a hostname, using cached addresses while they are fresh
+    pub async fn lookup(&self, host: &str) -> Result<Vec<SocketAddr>> {
+        let now = Instant::now();
+        let cached = self.entries.get(host).map(|entry| entry.clone());
+
+        if let Some(entry) = &cached {
+            if now.duration_since(entry.resolved_at) < self.config.ttl() {
+                debug!(host = %host, "DNS cache hit");
+                return Ok(entry.addrs.clone());
+            }
+
+            // Back off from a failing resolver and keep serving stale addresses
+            if let Some(failed_at) = entry.last_failure {
+                if self.config.stale_on_error
+                    && now.duration_since(failed_at) < self.config.failure_backoff()
+                {
+                    debug!(host = %host, "DNS resolution backing off, serving stale addresses");
+                    return Ok(entry.addrs.clone());
+                }
+            }
+        }
+
+        match self.resolver.resolve(host).await {
+            Ok(addrs) if !addrs.is_empty() => {
+                debug!(host = %host, count = addrs.len(), "DNS resolved");
+                self.entries.insert(
+                    host.to_string(),
+                    CachedAddrs {
+                        addrs: addrs.clone(),
+                        resolved_at: now,
+                        last_failure: None,
+                    },
+                );
+                Ok(addrs)
+            }
+            result => {
+                let error = match result {
+                    Ok(_) => "no addresses returned".to_string(),
+                    Err(e) => e.to_string(),
+                };
+
+                match cached {
+                    Some(mut entry) if self.config.stale_on_error => {
+                        warn!(
+                            host = %host,
+                            error = %error,
+                            "DNS resolution failed, serving last known good addresses"
+                        );
+                        entry.last_failure = Some(now);
+                        let addrs = entry.addrs.clone();
+                        self.entries.insert(host.to_string(), entry);
+                        Ok(addrs)
+                    }
+                    _ => Err(GatewayError::Backend(format!(
+                        "Failed to resolve backend host {}: {}",
+                        host, error
+                    ))),
+                }
+            }
+        }
+    }
+
+    /// Remove all cached entries
+    pub fn clear(&self) {
+        self.entries.clear();
+    }
+}
+
+impl Resolve for DnsCache {
+    fn resolve(&self, name: Name) -> Resolving {
+        let cache = self.clone();
+        Box::pin(async move {
+            let addrs = cache.lookup(name.as_str()).await?;
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+    /// Resolver that counts calls and can be switched into a failing state
+    struct MockResolver {
+        calls: AtomicU32,
+        failing: AtomicBool,
+        addr: SocketAddr,
+    }
+
+    impl MockResolver {
+        fn new() -> Arc<Self> {
+            Arc::new(Self {
+                calls: AtomicU32::new(0),
+                failing: AtomicBool::new(false),
+                addr: "10.0.0.1:0".parse().unwrap(),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl DnsResolver for MockResolver {
+        async fn resolve(&self, _host: &str) -> std::io::Result<Vec<SocketAddr>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.failing.load(Ordering::SeqCst) {
+                Err(std::io::Error::other(
+                    "temporary failure in name resolution",
+                ))
+            } else {
+                Ok(vec![self.addr])
+            }
+        }
+    }
+
+    fn test_config() -> DnsCacheConfig {
+        DnsCacheConfig {
+            enabled: true,
+            dns_cache_ttl_secs: 30,
+            stale_on_error: true,
+            failure_backoff_ms: 1000,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_resolved_address_cached_for_ttl() {
+        let resolver = MockResolver::new();
+        let cache = DnsCache::with_resolver(test_config(), resolver.clone());
+
+        let first = cache.lookup("backend.local").await.unwrap();
+        let second = cache.lookup("backend.local").await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 1);
+
+        // Still within TTL
+        tokio::time::advance(Duration::from_secs(29)).await;
+        cache.lookup("backend.local").await.unwrap();
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 1);
+
+        // TTL expired, resolve again
+        tokio::time::advance(Duration::from_secs(2)).await;
+        cache.lookup("backend.local").await.unwrap();
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_transient_failure_falls_back_to_cached_value() {
+        let resolver = MockResolver::new();
+        let cache = DnsCache::with_resolver(test_config(), resolver.clone());
+
+        let good = cache.lookup("backend.local").await.unwrap();
+
+        resolver.failing.store(true, Ordering::SeqCst);
+        tokio::time::advance(Duration::from_secs(31)).await;
+
+        let stale = cache.lookup("backend.local").await.unwrap();
+        assert_eq!(stale, good);
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 2);
+
+        // Within the failure backoff the resolver is not consulted again
+        cache.lookup("backend.local").await.unwrap();
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 2);
+
+        // After the backoff, a recovered resolver refreshes the entry
+        resolver.failing.store(false, Ordering::SeqCst);
+        tokio::time::advance(Duration::from_millis(1001)).await;
+        cache.lookup("backend.local").await.unwrap();
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failure_without_cached_value() {
+        let resolver = MockResolver::new();
+        resolver.failing.store(true, Ordering::SeqCst);
+        let cache = DnsCache::with_resolver(test_config(), resolver);
+
+        assert!(cache.lookup("backend.local").await.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stale_on_error_disabled() {
+        let resolver = MockResolver::new();
+        let config = DnsCacheConfig {
+            stale_on_error: false,
+            ..test_config()
+        };
+        let cache = DnsCache::with_resolver(config, resolver.clone());
+
+        cache.lookup("backend.local").await.unwrap();
+        resolver.failing.store(true, Ordering::SeqCst);
+        tokio::time::advance(Duration::from_secs(31)).await;
+
+        assert!(cache.lookup("backend.local").await.is_err());
+    }
+
+    #[test]
+    fn test_dns_cache_config_defaults() {
+        let config: DnsCacheConfig = serde_yaml::from_str("{}").unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.dns_cache_ttl_secs, 30);
+        assert!(config.stale_on_error);
+        assert_eq!(config.failure_backoff_ms, 1000);
+    }
+}
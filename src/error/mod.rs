@@ -1,9 +1,12 @@
 use axum::{
-    http::StatusCode,
+    extract::{Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
     response::{IntoResponse, Response},
     Json,
 };
 use serde_json::json;
+use std::sync::Arc;
 use thiserror::Error;
 
 /// Result type for gateway operations
@@ -18,6 +21,9 @@ pub enum GatewayError {
     #[error("Route not found: {0}")]
     RouteNotFound(String),
 
+    #[error("Route disabled: {0}")]
+    RouteDisabled(String),
+
     #[error("Invalid route configuration: {0}")]
     InvalidRoute(String),
 
@@ -65,6 +71,15 @@ pub enum GatewayError {
 
     #[error("Forbidden: {0}")]
     Forbidden(String),
+
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
+
+    #[error("Not supported: {0}")]
+    NotSupported(String),
+
+    #[error("Response too large: {0}")]
+    ResponseTooLarge(String),
 }
 
 impl GatewayError {
@@ -73,6 +88,7 @@ impl GatewayError {
         match self {
             GatewayError::Config(_) => StatusCode::INTERNAL_SERVER_ERROR,
             GatewayError::RouteNotFound(_) => StatusCode::NOT_FOUND,
+            GatewayError::RouteDisabled(_) => StatusCode::SERVICE_UNAVAILABLE,
             GatewayError::InvalidRoute(_) => StatusCode::BAD_REQUEST,
             GatewayError::Proxy(_) => StatusCode::BAD_GATEWAY,
             GatewayError::Backend(_) => StatusCode::BAD_GATEWAY,
@@ -89,6 +105,9 @@ impl GatewayError {
             GatewayError::CircuitBreakerOpen(_) => StatusCode::SERVICE_UNAVAILABLE,
             GatewayError::RateLimitExceeded(_) => StatusCode::TOO_MANY_REQUESTS,
             GatewayError::Forbidden(_) => StatusCode::FORBIDDEN,
+            GatewayError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            GatewayError::NotSupported(_) => StatusCode::NOT_IMPLEMENTED,
+            GatewayError::ResponseTooLarge(_) => StatusCode::BAD_GATEWAY,
         }
     }
 }
@@ -96,15 +115,147 @@ impl GatewayError {
 impl IntoResponse for GatewayError {
     fn into_response(self) -> Response {
         let status = self.status_code();
+        let message = self.to_string();
         let body = Json(json!({
-            "error": self.to_string(),
+            "error": message,
             "status": status.as_u16(),
         }));
 
-        (status, body).into_response()
+        let mut response = (status, body).into_response();
+        // Let error_response_middleware re-render the body for the client
+        response
+            .extensions_mut()
+            .insert(ErrorDetails { status, message });
+        response
     }
 }
 
+/// Status and message of a gateway error response
+#[derive(Debug, Clone)]
+pub struct ErrorDetails {
+    pub status: StatusCode,
+    pub message: String,
+}
+
+/// Body format of gateway error responses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// `{"error": ..., "status": ...}` (or the configured template)
+    Json,
+    /// The error message as plain text
+    Text,
+}
+
+impl ErrorFormat {
+    /// Pick the format preferred by a request's `Accept` header
+    ///
+    /// Any `text/*` type (including `text/html`) gets plain text; JSON and
+    /// wildcards get JSON, which is also the default. Among acceptable types
+    /// the highest quality wins, then the first listed.
+    pub fn negotiate(accept: Option<&HeaderValue>) -> Self {
+        let Some(accept) = accept.and_then(|value| value.to_str().ok()) else {
+            return ErrorFormat::Json;
+        };
+
+        let mut best: Option<(f32, ErrorFormat)> = None;
+        for range in accept.split(',') {
+            let mut params = range.split(';');
+            let media_type = params.next().unwrap_or("").trim().to_ascii_lowercase();
+            let quality = params
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            let format = if media_type.starts_with("text/") {
+                ErrorFormat::Text
+            } else if media_type == "*/*"
+                || media_type == "application/*"
+                || media_type == "application/json"
+                || media_type.ends_with("+json")
+            {
+                ErrorFormat::Json
+            } else {
+                continue;
+            };
+
+            if quality > 0.0 && best.is_none_or(|(q, _)| quality > q) {
+                best = Some((quality, format));
+            }
+        }
+
+        best.map_or(ErrorFormat::Json, |(_, format)| format)
+    }
+}
+
+/// Renders gateway error bodies in the format a client asks for
+#[derive(Debug, Clone, Default)]
+pub struct ErrorRenderer {
+    /// JSON body template with `{status}` and `{error}` placeholders
+    template: Option<String>,
+}
+
+impl ErrorRenderer {
+    /// Create a renderer, optionally with a custom JSON body template
+    pub fn new(template: Option<String>) -> Self {
+        Self { template }
+    }
+
+    /// Render the body and content type of an error response
+    pub fn render(&self, details: &ErrorDetails, format: ErrorFormat) -> (&'static str, String) {
+        match (format, &self.template) {
+            (ErrorFormat::Text, _) => (
+                "text/plain; charset=utf-8",
+                format!("{}\n", details.message),
+            ),
+            (ErrorFormat::Json, Some(template)) => {
+                // Escape the message so it can sit inside a JSON string
+                let message = serde_json::to_string(&details.message).unwrap_or_default();
+                let body = template
+                    .replace("{status}", &details.status.as_u16().to_string())
+                    .replace("{error}", &message[1..message.len() - 1]);
+                ("application/json", body)
+            }
+            (ErrorFormat::Json, None) => (
+                "application/json",
+                json!({
+                    "error": details.message,
+                    "status": details.status.as_u16(),
+                })
+                .to_string(),
+            ),
+        }
+    }
+}
+
+/// Middleware rendering gateway error bodies according to the request's
+/// `Accept` header and the configured template
+///
+/// Only responses built from a `GatewayError` are touched; backend responses
+/// pass through unchanged.
+pub async fn error_response_middleware(
+    State(renderer): State<Arc<ErrorRenderer>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let format = ErrorFormat::negotiate(req.headers().get(header::ACCEPT));
+    let response = next.run(req).await;
+
+    let Some(details) = response.extensions().get::<ErrorDetails>().cloned() else {
+        return response;
+    };
+    if format == ErrorFormat::Json && renderer.template.is_none() {
+        return response;
+    }
+
+    let (content_type, body) = renderer.render(&details, format);
+    let (mut parts, _) = response.into_parts();
+    parts
+        .headers
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, body.into())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -123,6 +274,104 @@ mod tests {
             GatewayError::Timeout("test".to_string()).status_code(),
             StatusCode::GATEWAY_TIMEOUT
         );
+        assert_eq!(
+            GatewayError::NotSupported("test".to_string()).status_code(),
+            StatusCode::NOT_IMPLEMENTED
+        );
+    }
+
+    #[test]
+    fn test_negotiate_error_format() {
+        let negotiate =
+            |accept: &str| ErrorFormat::negotiate(Some(&HeaderValue::from_str(accept).unwrap()));
+
+        assert_eq!(ErrorFormat::negotiate(None), ErrorFormat::Json);
+        assert_eq!(negotiate("application/json"), ErrorFormat::Json);
+        assert_eq!(negotiate("*/*"), ErrorFormat::Json);
+        assert_eq!(negotiate("text/plain"), ErrorFormat::Text);
+        assert_eq!(
+            negotiate("text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8"),
+            ErrorFormat::Text
+        );
+        assert_eq!(
+            negotiate("text/plain;q=0.5, application/json"),
+            ErrorFormat::Json
+        );
+        assert_eq!(
+            negotiate("application/json;q=0, text/plain;q=0.1"),
+            ErrorFormat::Text
+        );
+        assert_eq!(negotiate("image/png"), ErrorFormat::Json);
+    }
+
+    async fn error_request(
+        renderer: ErrorRenderer,
+        accept: Option<&str>,
+    ) -> (StatusCode, String, String) {
+        use axum::{body::Body, middleware, routing::get, Router};
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route(
+                "/missing",
+                get(|| async { GatewayError::RouteNotFound("/missing".to_string()) }),
+            )
+            .layer(middleware::from_fn_with_state(
+                Arc::new(renderer),
+                error_response_middleware,
+            ));
+
+        let mut request = axum::http::Request::builder().uri("/missing");
+        if let Some(accept) = accept {
+            request = request.header(header::ACCEPT, accept);
+        }
+        let response = app
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let content_type = response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            content_type,
+            String::from_utf8(body.to_vec()).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_error_response_json_by_default() {
+        let (status, content_type, body) = error_request(ErrorRenderer::default(), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(content_type, "application/json");
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body["error"], "Route not found: /missing");
+        assert_eq!(body["status"], 404);
+    }
+
+    #[tokio::test]
+    async fn test_error_response_plain_text() {
+        let (status, content_type, body) =
+            error_request(ErrorRenderer::default(), Some("text/plain")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(content_type, "text/plain; charset=utf-8");
+        assert_eq!(body, "Route not found: /missing\n");
+    }
+
+    #[tokio::test]
+    async fn test_error_response_template() {
+        let renderer =
+            ErrorRenderer::new(Some(r#"{"code":{status},"message":"{error}"}"#.to_string()));
+        let (_, content_type, body) = error_request(renderer, Some("application/json")).await;
+        assert_eq!(content_type, "application/json");
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body["code"], 404);
+        assert_eq!(body["message"], "Route not found: /missing");
     }
 
     #[test]
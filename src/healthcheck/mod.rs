@@ -1,8 +1,14 @@
+use crate::error::{GatewayError, Result};
 use crate::loadbalancer::backend::Backend;
+use crate::secrets::{resolve_secret_ref, SecretsManager};
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::time::interval;
+use tokio::net::TcpStream;
+use tokio::time::Instant;
 use tracing::{debug, error, info, warn};
 
 /// Health check configuration
@@ -23,15 +29,38 @@ pub struct HealthCheckConfig {
     /// Number of consecutive successes before marking healthy
     #[serde(default = "default_healthy_threshold")]
     pub healthy_threshold: usize,
+    /// How backends are probed
+    #[serde(default)]
+    pub check_type: HealthCheckType,
     /// HTTP path to check (e.g., "/health")
     #[serde(default = "default_path")]
     pub path: String,
     /// Expected HTTP status code
     #[serde(default = "default_expected_status")]
     pub expected_status: u16,
+    /// Text the HTTP response body must also contain (e.g. `"status":"up"`)
+    #[serde(default)]
+    pub expected_body_substring: Option<String>,
+    /// Headers sent with each HTTP probe (values may be `secret://` references)
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
     /// Enable passive health checks (based on request failures)
     #[serde(default = "default_enabled")]
     pub passive_enabled: bool,
+    /// Random jitter as a fraction of the interval (0.0-1.0), applied per backend
+    #[serde(default)]
+    pub interval_jitter: f64,
+}
+
+/// Active health check probe type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthCheckType {
+    /// HTTP GET of `path`, expecting `expected_status`
+    #[default]
+    Http,
+    /// TCP connect to the backend's host and port, for non-HTTP backends
+    Tcp,
 }
 
 fn default_enabled() -> bool {
@@ -70,9 +99,13 @@ impl Default for HealthCheckConfig {
             timeout_secs: default_timeout(),
             unhealthy_threshold: default_unhealthy_threshold(),
             healthy_threshold: default_healthy_threshold(),
+            check_type: HealthCheckType::default(),
             path: default_path(),
             expected_status: default_expected_status(),
+            expected_body_substring: None,
+            headers: HashMap::new(),
             passive_enabled: default_enabled(),
+            interval_jitter: 0.0,
         }
     }
 }
@@ -93,94 +126,193 @@ impl std::fmt::Debug for HealthChecker {
 }
 
 impl HealthChecker {
-    /// Create a new health checker
-    pub fn new(config: HealthCheckConfig) -> Self {
+    /// Create a new health checker, resolving the probe headers
+    pub fn new(config: HealthCheckConfig) -> Result<Self> {
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(config.timeout_secs))
+            .default_headers(probe_headers(&config.headers)?)
             .build()
             .expect("Failed to create health check client");
 
-        Self { config, client }
+        Ok(Self { config, client })
     }
 
     /// Start active health checking for a set of backends
+    ///
+    /// Each backend is probed on its own schedule; with `interval_jitter` set,
+    /// the first probe and every following one are delayed by a fresh random
+    /// fraction of the interval so backends and gateway replicas don't probe
+    /// in lockstep.
     pub fn start_active_checks(&self, backends: Vec<Arc<Backend>>) {
         if !self.config.enabled {
             info!("Active health checks disabled");
             return;
         }
 
-        let config = self.config.clone();
-        let client = self.client.clone();
+        info!(
+            interval_secs = self.config.interval_secs,
+            interval_jitter = self.config.interval_jitter,
+            check_type = ?self.config.check_type,
+            path = %self.config.path,
+            "Started active health checks"
+        );
+
+        for backend in backends {
+            let config = self.config.clone();
+            let client = self.client.clone();
+            // Hold a weak reference so checks stop once the route is replaced (e.g. on reload)
+            let backend = Arc::downgrade(&backend);
+
+            tokio::spawn(async move {
+                let period = Duration::from_secs(config.interval_secs);
+                let mut next_check = Instant::now() + jitter_delay(period, config.interval_jitter);
+
+                // Each probe is scheduled from the previous one rather than a fixed
+                // tick, so the jitter drawn every round actually shifts the schedule
+                loop {
+                    tokio::time::sleep_until(next_check).await;
+                    let Some(backend) = backend.upgrade() else {
+                        debug!("Backend removed, stopping active health checks");
+                        break;
+                    };
+                    Self::check_backend(&client, &config, &backend).await;
+                    next_check += period + jitter_delay(period, config.interval_jitter);
+                }
+            });
+        }
+    }
+
+    /// Probe a single backend and record the result
+    async fn check_backend(
+        client: &reqwest::Client,
+        config: &HealthCheckConfig,
+        backend: &Backend,
+    ) {
+        let success = match config.check_type {
+            HealthCheckType::Http => Self::check_http(client, config, backend).await,
+            HealthCheckType::Tcp => Self::check_tcp(config, backend).await,
+        };
 
-        tokio::spawn(async move {
-            let mut check_interval = interval(Duration::from_secs(config.interval_secs));
+        // Record health check result
+        backend.record_health_check(
+            success,
+            config.unhealthy_threshold,
+            config.healthy_threshold,
+        );
 
-            info!(
-                interval_secs = config.interval_secs,
-                path = %config.path,
-                "Started active health checks"
+        // Log health status changes
+        if !backend.is_healthy() {
+            error!(
+                backend = %backend.url(),
+                "Backend marked unhealthy"
             );
+        }
+    }
 
-            loop {
-                check_interval.tick().await;
-
-                for backend in &backends {
-                    let url = format!("{}{}", backend.url().trim_end_matches('/'), config.path);
-
-                    debug!(url = %url, "Performing health check");
-
-                    let result = client.get(&url).send().await;
-
-                    let success = match result {
-                        Ok(response) => {
-                            let status = response.status();
-                            let success = status.as_u16() == config.expected_status;
-
-                            if success {
-                                debug!(
-                                    backend = %backend.url(),
-                                    status = %status,
-                                    "Health check passed"
-                                );
-                            } else {
-                                warn!(
-                                    backend = %backend.url(),
-                                    status = %status,
-                                    expected = config.expected_status,
-                                    "Health check failed: unexpected status"
-                                );
-                            }
-
-                            success
+    /// Probe a backend with an HTTP GET of the configured path
+    async fn check_http(
+        client: &reqwest::Client,
+        config: &HealthCheckConfig,
+        backend: &Backend,
+    ) -> bool {
+        let url = format!("{}{}", backend.url().trim_end_matches('/'), config.path);
+
+        debug!(url = %url, "Performing health check");
+
+        let result = client.get(&url).send().await;
+
+        match result {
+            Ok(response) => {
+                let status = response.status();
+                if status.as_u16() != config.expected_status {
+                    warn!(
+                        backend = %backend.url(),
+                        status = %status,
+                        expected = config.expected_status,
+                        "Health check failed: unexpected status"
+                    );
+                    return false;
+                }
+
+                // Only read the body when a match is required
+                if let Some(expected) = &config.expected_body_substring {
+                    match response.text().await {
+                        Ok(body) if body.contains(expected.as_str()) => {}
+                        Ok(_) => {
+                            warn!(
+                                backend = %backend.url(),
+                                expected = %expected,
+                                "Health check failed: unexpected body"
+                            );
+                            return false;
                         }
                         Err(e) => {
                             warn!(
                                 backend = %backend.url(),
                                 error = %e,
-                                "Health check failed: request error"
+                                "Health check failed: could not read body"
                             );
-                            false
+                            return false;
                         }
-                    };
-
-                    // Record health check result
-                    backend.record_health_check(
-                        success,
-                        config.unhealthy_threshold,
-                        config.healthy_threshold,
-                    );
-
-                    // Log health status changes
-                    if !backend.is_healthy() {
-                        error!(
-                            backend = %backend.url(),
-                            "Backend marked unhealthy"
-                        );
                     }
                 }
+
+                debug!(
+                    backend = %backend.url(),
+                    status = %status,
+                    "Health check passed"
+                );
+                true
             }
-        });
+            Err(e) => {
+                warn!(
+                    backend = %backend.url(),
+                    error = %e,
+                    "Health check failed: request error"
+                );
+                false
+            }
+        }
+    }
+
+    /// Probe a backend by opening a TCP connection to its host and port
+    ///
+    /// Succeeds if the connection completes within the timeout; the
+    /// connection is closed immediately.
+    async fn check_tcp(config: &HealthCheckConfig, backend: &Backend) -> bool {
+        let Some(address) = tcp_address(backend.url()) else {
+            warn!(
+                backend = %backend.url(),
+                "Health check failed: backend URL has no host and port"
+            );
+            return false;
+        };
+
+        debug!(address = %address, "Performing TCP health check");
+
+        let timeout = Duration::from_secs(config.timeout_secs);
+        match tokio::time::timeout(timeout, TcpStream::connect(&address)).await {
+            Ok(Ok(_)) => {
+                debug!(backend = %backend.url(), "TCP health check passed");
+                true
+            }
+            Ok(Err(e)) => {
+                warn!(
+                    backend = %backend.url(),
+                    error = %e,
+                    "Health check failed: TCP connect error"
+                );
+                false
+            }
+            Err(_) => {
+                warn!(
+                    backend = %backend.url(),
+                    timeout_secs = config.timeout_secs,
+                    "Health check failed: TCP connect timed out"
+                );
+                false
+            }
+        }
     }
 
     /// Perform passive health check based on request result
@@ -207,6 +339,52 @@ impl HealthChecker {
     }
 }
 
+/// Build the headers sent with every HTTP probe
+fn probe_headers(headers: &HashMap<String, String>) -> Result<HeaderMap> {
+    let mut probe_headers = HeaderMap::new();
+    if headers.is_empty() {
+        return Ok(probe_headers);
+    }
+
+    let secrets = SecretsManager::default();
+    for (name, value) in headers {
+        let header_name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+            GatewayError::Config(format!(
+                "health_check.headers: Invalid header name '{}'",
+                name
+            ))
+        })?;
+        let header_value =
+            HeaderValue::from_str(&resolve_secret_ref(value, &secrets)?).map_err(|_| {
+                GatewayError::Config(format!(
+                    "health_check.headers: Invalid value for header '{}'",
+                    name
+                ))
+            })?;
+        probe_headers.insert(header_name, header_value);
+    }
+    Ok(probe_headers)
+}
+
+/// `host:port` to connect to for a backend URL, using the scheme's default port
+fn tcp_address(backend_url: &str) -> Option<String> {
+    let url = url::Url::parse(backend_url).ok()?;
+    Some(format!(
+        "{}:{}",
+        url.host_str()?,
+        url.port_or_known_default()?
+    ))
+}
+
+/// Random delay of up to `jitter` (a fraction of `period`, clamped to 0.0-1.0)
+fn jitter_delay(period: Duration, jitter: f64) -> Duration {
+    let jitter = jitter.clamp(0.0, 1.0);
+    if jitter == 0.0 {
+        return Duration::ZERO;
+    }
+    period.mul_f64(rand::thread_rng().gen_range(0.0..jitter))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,14 +406,14 @@ mod tests {
     #[test]
     fn test_health_checker_creation() {
         let config = HealthCheckConfig::default();
-        let _checker = HealthChecker::new(config);
+        let _checker = HealthChecker::new(config).unwrap();
         // Just verify it doesn't panic
     }
 
     #[test]
     fn test_passive_check() {
         let config = HealthCheckConfig::default();
-        let checker = HealthChecker::new(config);
+        let checker = HealthChecker::new(config).unwrap();
 
         let backend = Arc::new(Backend::new(BackendConfig {
             url: "http://test:3000".to_string(),
@@ -264,16 +442,280 @@ mod tests {
             ..Default::default()
         };
 
-        let checker = HealthChecker::new(config);
+        let checker = HealthChecker::new(config).unwrap();
 
         let backends = vec![Arc::new(Backend::new(BackendConfig {
             url: "http://test:3000".to_string(),
             weight: 1,
         }))];
 
-        checker.start_active_checks(backends);
+        checker.start_active_checks(backends.clone());
 
         // Should not panic and should return immediately
         tokio::time::sleep(Duration::from_millis(100)).await;
     }
+
+    fn tcp_checker_config() -> HealthCheckConfig {
+        HealthCheckConfig {
+            check_type: HealthCheckType::Tcp,
+            timeout_secs: 1,
+            unhealthy_threshold: 1,
+            healthy_threshold: 1,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tcp_check_passes_for_listening_port() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let config = tcp_checker_config();
+        let checker = HealthChecker::new(config.clone()).unwrap();
+        let backend = Backend::new(BackendConfig {
+            // The scheme is irrelevant to a TCP check; only host and port are used
+            url: format!("http://127.0.0.1:{}", port),
+            weight: 1,
+        });
+        backend.record_health_check(false, 1, 1);
+        assert!(!backend.is_healthy());
+
+        HealthChecker::check_backend(&checker.client, &config, &backend).await;
+        assert!(backend.is_healthy());
+    }
+
+    #[tokio::test]
+    async fn test_tcp_check_fails_for_closed_port() {
+        let port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
+        };
+        let config = tcp_checker_config();
+        let checker = HealthChecker::new(config.clone()).unwrap();
+        let backend = Backend::new(BackendConfig {
+            url: format!("http://127.0.0.1:{}", port),
+            weight: 1,
+        });
+        assert!(backend.is_healthy());
+
+        HealthChecker::check_backend(&checker.client, &config, &backend).await;
+        assert!(!backend.is_healthy());
+    }
+
+    #[test]
+    fn test_tcp_address() {
+        assert_eq!(
+            tcp_address("http://db-proxy:5432").as_deref(),
+            Some("db-proxy:5432")
+        );
+        assert_eq!(
+            tcp_address("https://backend").as_deref(),
+            Some("backend:443")
+        );
+        assert_eq!(
+            tcp_address("http://[::1]:8080").as_deref(),
+            Some("[::1]:8080")
+        );
+        assert_eq!(tcp_address("not a url"), None);
+    }
+
+    #[test]
+    fn test_check_type_parsing() {
+        let config: HealthCheckConfig = serde_yaml::from_str("check_type: tcp").unwrap();
+        assert_eq!(config.check_type, HealthCheckType::Tcp);
+        let config: HealthCheckConfig = serde_yaml::from_str("path: /ready").unwrap();
+        assert_eq!(config.check_type, HealthCheckType::Http);
+    }
+
+    async fn check_body(body: &'static str, expected: Option<&str>) -> bool {
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::path("/health"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_string(body))
+            .mount(&mock_server)
+            .await;
+
+        let config = HealthCheckConfig {
+            expected_body_substring: expected.map(str::to_string),
+            ..Default::default()
+        };
+        let checker = HealthChecker::new(config.clone()).unwrap();
+        let backend = Backend::new(BackendConfig {
+            url: mock_server.uri(),
+            weight: 1,
+        });
+        HealthChecker::check_http(&checker.client, &config, &backend).await
+    }
+
+    #[tokio::test]
+    async fn test_http_check_body_match_passes() {
+        assert!(check_body(r#"{"status":"up"}"#, Some(r#""status":"up""#)).await);
+    }
+
+    #[tokio::test]
+    async fn test_http_check_body_mismatch_fails() {
+        assert!(!check_body(r#"{"status":"down"}"#, Some(r#""status":"up""#)).await);
+    }
+
+    #[tokio::test]
+    async fn test_http_check_status_only_ignores_body() {
+        assert!(check_body(r#"{"status":"down"}"#, None).await);
+    }
+
+    #[tokio::test]
+    async fn test_http_check_sends_configured_headers() {
+        let mock_server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::path("/health"))
+            .and(wiremock::matchers::header(
+                "authorization",
+                "Bearer probe-token",
+            ))
+            .and(wiremock::matchers::header("x-probe", "gateway"))
+            .respond_with(wiremock::ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::path("/health"))
+            .respond_with(wiremock::ResponseTemplate::new(401))
+            .mount(&mock_server)
+            .await;
+        let backend = Backend::new(BackendConfig {
+            url: mock_server.uri(),
+            weight: 1,
+        });
+
+        // A bare probe is rejected
+        let config = HealthCheckConfig::default();
+        let checker = HealthChecker::new(config.clone()).unwrap();
+        assert!(!HealthChecker::check_http(&checker.client, &config, &backend).await);
+
+        std::env::set_var("GATEWAY_SECRET_PROBE_TOKEN", "Bearer probe-token");
+        let config = HealthCheckConfig {
+            headers: HashMap::from([
+                (
+                    "Authorization".to_string(),
+                    "secret://probe_token".to_string(),
+                ),
+                ("X-Probe".to_string(), "gateway".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let checker = HealthChecker::new(config.clone()).unwrap();
+        std::env::remove_var("GATEWAY_SECRET_PROBE_TOKEN");
+        assert!(HealthChecker::check_http(&checker.client, &config, &backend).await);
+    }
+
+    #[test]
+    fn test_probe_headers_rejects_invalid_name() {
+        let headers = HashMap::from([("bad header".to_string(), "value".to_string())]);
+        assert!(probe_headers(&headers).is_err());
+    }
+
+    #[test]
+    fn test_jitter_delay_bounds() {
+        let period = Duration::from_secs(10);
+        assert_eq!(jitter_delay(period, 0.0), Duration::ZERO);
+
+        for _ in 0..100 {
+            assert!(jitter_delay(period, 0.5) < Duration::from_secs(5));
+            assert!(jitter_delay(period, 5.0) < period);
+        }
+    }
+
+    /// Responder that records when each probe arrived
+    struct ProbeRecorder(Arc<std::sync::Mutex<Vec<std::time::Instant>>>);
+
+    impl wiremock::Respond for ProbeRecorder {
+        fn respond(&self, _request: &wiremock::Request) -> wiremock::ResponseTemplate {
+            self.0.lock().unwrap().push(std::time::Instant::now());
+            wiremock::ResponseTemplate::new(200)
+        }
+    }
+
+    /// Times of the first `rounds` probes of each of four backends
+    async fn probe_times(
+        interval_jitter: f64,
+        rounds: u64,
+        wait: Duration,
+    ) -> Vec<Vec<std::time::Instant>> {
+        let mut probes = Vec::new();
+        let mut servers = Vec::new();
+        let mut backends = Vec::new();
+        for _ in 0..4 {
+            let backend_probes = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let server = wiremock::MockServer::start().await;
+            wiremock::Mock::given(wiremock::matchers::path("/health"))
+                .respond_with(ProbeRecorder(backend_probes.clone()))
+                .up_to_n_times(rounds)
+                .mount(&server)
+                .await;
+            probes.push(backend_probes);
+            backends.push(Arc::new(Backend::new(BackendConfig {
+                url: server.uri(),
+                weight: 1,
+            })));
+            servers.push(server);
+        }
+
+        let checker = HealthChecker::new(HealthCheckConfig {
+            interval_secs: 1,
+            interval_jitter,
+            ..Default::default()
+        })
+        .unwrap();
+        checker.start_active_checks(backends.clone());
+
+        tokio::time::sleep(wait).await;
+        probes
+            .iter()
+            .map(|probes| probes.lock().unwrap().clone())
+            .collect()
+    }
+
+    async fn first_probe_times(interval_jitter: f64) -> Vec<std::time::Instant> {
+        probe_times(interval_jitter, 1, Duration::from_millis(1200))
+            .await
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    fn probe_spread(probes: &[std::time::Instant]) -> Duration {
+        let first = probes.iter().min().unwrap();
+        let last = probes.iter().max().unwrap();
+        last.duration_since(*first)
+    }
+
+    #[tokio::test]
+    async fn test_jitter_spreads_probes() {
+        let probes = first_probe_times(1.0).await;
+        assert_eq!(probes.len(), 4);
+        assert!(
+            probe_spread(&probes) > Duration::from_millis(50),
+            "probes should be spread over the interval"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_jitter_applies_to_every_round() {
+        // With 0.5 jitter the gap between probes is 1.0-1.5s; a fixed tick would
+        // keep every gap at 1s
+        let probes = probe_times(0.5, 2, Duration::from_millis(2300)).await;
+        let gaps: Vec<Duration> = probes
+            .iter()
+            .map(|times| {
+                assert_eq!(times.len(), 2);
+                times[1].duration_since(times[0])
+            })
+            .collect();
+        assert!(gaps.iter().all(|gap| *gap >= Duration::from_millis(950)));
+        assert!(
+            gaps.iter().any(|gap| *gap > Duration::from_millis(1050)),
+            "later probes should be jittered too: {gaps:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_jitter_probes_together() {
+        let probes = first_probe_times(0.0).await;
+        assert_eq!(probes.len(), 4);
+        assert!(probe_spread(&probes) < Duration::from_millis(500));
+    }
 }
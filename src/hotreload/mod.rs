@@ -1,10 +1,11 @@
 use crate::config::GatewayConfig;
 use crate::error::{GatewayError, Result};
 use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{mpsc, watch, RwLock};
 use tracing::{debug, error, info};
 
 /// Hot reload configuration
@@ -39,6 +40,8 @@ impl Default for HotReloadConfig {
 #[derive(Clone)]
 pub struct ReloadableConfig {
     inner: Arc<RwLock<GatewayConfig>>,
+    /// Incremented after every successful update
+    version: Arc<watch::Sender<u64>>,
 }
 
 impl ReloadableConfig {
@@ -46,9 +49,15 @@ impl ReloadableConfig {
     pub fn new(config: GatewayConfig) -> Self {
         Self {
             inner: Arc::new(RwLock::new(config)),
+            version: Arc::new(watch::channel(0).0),
         }
     }
 
+    /// Get notified whenever the configuration has been updated
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        self.version.subscribe()
+    }
+
     /// Get a read lock on the configuration
     pub async fn read(&self) -> tokio::sync::RwLockReadGuard<'_, GatewayConfig> {
         self.inner.read().await
@@ -61,7 +70,9 @@ impl ReloadableConfig {
 
         let mut config = self.inner.write().await;
         *config = new_config;
+        drop(config);
 
+        self.version.send_modify(|version| *version += 1);
         info!("Configuration updated via hot reload");
         Ok(())
     }
@@ -90,56 +101,15 @@ impl HotReloadService {
 
     /// Start watching the configuration file for changes
     pub async fn start(self) -> Result<()> {
-        let (tx, mut rx) = mpsc::channel(100);
-
-        // Create a watcher
-        let mut watcher: RecommendedWatcher = Watcher::new(
-            move |res: notify::Result<Event>| {
-                if let Ok(event) = res {
-                    // Only care about modify events
-                    if matches!(
-                        event.kind,
-                        notify::EventKind::Modify(_) | notify::EventKind::Create(_)
-                    ) {
-                        let _ = tx.blocking_send(event);
-                    }
-                }
-            },
-            Config::default(),
-        )
-        .map_err(|e| GatewayError::Internal(format!("Failed to create file watcher: {}", e)))?;
-
-        // Watch the config file
-        watcher
-            .watch(&self.config_path, RecursiveMode::NonRecursive)
-            .map_err(|e| {
-                GatewayError::Internal(format!("Failed to watch config file: {}", e))
-            })?;
-
-        info!(
-            path = %self.config_path.display(),
-            debounce_ms = self.debounce_duration.as_millis(),
-            "Hot reload watcher started"
-        );
-
-        // Spawn a task to handle file change events
-        tokio::spawn(async move {
-            let mut last_reload = std::time::Instant::now();
-
-            while let Some(event) = rx.recv().await {
-                debug!("File change event detected: {:?}", event);
-
-                // Debounce: ignore events that are too close together
-                let now = std::time::Instant::now();
-                if now.duration_since(last_reload) < self.debounce_duration {
-                    debug!("Ignoring event due to debounce");
-                    continue;
-                }
-
-                last_reload = now;
+        let paths = vec![self.config_path.clone()];
+        let debounce = self.debounce_duration;
+        let service = Arc::new(self);
 
+        watch_paths(&paths, debounce, move || {
+            let service = service.clone();
+            async move {
                 // Attempt to reload configuration
-                match self.reload_config().await {
+                match service.reload_config().await {
                     Ok(()) => {
                         info!("Configuration reloaded successfully");
                     }
@@ -148,10 +118,13 @@ impl HotReloadService {
                     }
                 }
             }
+        })?;
 
-            // Keep watcher alive
-            drop(watcher);
-        });
+        info!(
+            path = %paths[0].display(),
+            debounce_ms = debounce.as_millis(),
+            "Hot reload watcher started"
+        );
 
         Ok(())
     }
@@ -173,6 +146,67 @@ impl HotReloadService {
     }
 }
 
+/// Watch `paths` for changes and run `on_change` once a burst of events has settled.
+///
+/// Events are debounced on the trailing edge: `on_change` runs after no further
+/// event has arrived for `debounce`, so multi-file updates (e.g. a certificate
+/// and its key) are picked up together.
+pub fn watch_paths<F, Fut>(paths: &[PathBuf], debounce: Duration, on_change: F) -> Result<()>
+where
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    let (tx, mut rx) = mpsc::channel(100);
+
+    // Create a watcher
+    let mut watcher: RecommendedWatcher = Watcher::new(
+        move |res: notify::Result<Event>| {
+            if let Ok(event) = res {
+                // Only care about modify events
+                if matches!(
+                    event.kind,
+                    notify::EventKind::Modify(_) | notify::EventKind::Create(_)
+                ) {
+                    let _ = tx.blocking_send(event);
+                }
+            }
+        },
+        Config::default(),
+    )
+    .map_err(|e| GatewayError::Internal(format!("Failed to create file watcher: {}", e)))?;
+
+    for path in paths {
+        watcher
+            .watch(path, RecursiveMode::NonRecursive)
+            .map_err(|e| {
+                GatewayError::Internal(format!("Failed to watch {}: {}", path.display(), e))
+            })?;
+    }
+
+    // Spawn a task to handle file change events
+    tokio::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            debug!("File change event detected: {:?}", event);
+
+            // Debounce: wait until events stop arriving before acting
+            loop {
+                match tokio::time::timeout(debounce, rx.recv()).await {
+                    Ok(Some(event)) => debug!("Coalescing file change event: {:?}", event),
+                    Ok(None) => return,
+                    Err(_) => break,
+                }
+            }
+
+            on_change().await;
+        }
+
+        // Keep watcher alive
+        drop(watcher);
+    });
+
+    Ok(())
+}
+
 /// Watch a configuration file and reload on changes
 pub async fn watch_config_file<P: AsRef<Path>>(
     config_path: P,
@@ -210,8 +244,10 @@ mod tests {
         let mut new_config = GatewayConfig::default_config();
         new_config.server.port = 9090;
 
+        let updates = reloadable.subscribe();
         let result = reloadable.update(new_config).await;
         assert!(result.is_ok());
+        assert!(updates.has_changed().unwrap());
 
         let read_config = reloadable.read().await;
         assert_eq!(read_config.server.port, 9090);
@@ -239,6 +275,18 @@ mod tests {
             cors: None,
             ip_filter: None,
             cache: None,
+            honor_method_override: None,
+            circuit_breaker: None,
+            fallback: None,
+            mirror: None,
+            canary: None,
+            match_headers: Default::default(),
+            match_query: Default::default(),
+            max_response_bytes: None,
+            answer_options: None,
+            preserve_headers: Vec::new(),
+            enabled: true,
+            disabled_status: 503,
         });
 
         let result = reloadable.update(new_config).await;
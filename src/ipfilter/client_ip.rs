@@ -0,0 +1,244 @@
+//! Client IP resolution behind trusted proxies
+//!
+//! When the direct peer is a trusted proxy (e.g. a load balancer), the client
+//! IP is taken from `X-Forwarded-For`, or the RFC 7239 `Forwarded` header if
+//! that is absent: hops are walked right to left and the first one that isn't
+//! itself a trusted proxy is the client. Headers from untrusted peers are
+//! ignored, since any client can send them.
+
+use super::IpRange;
+use crate::error::{GatewayError, Result};
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header::FORWARDED, Extensions, HeaderMap},
+    middleware::Next,
+    response::Response,
+};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use tracing::debug;
+
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// Resolved client IP, stored in request extensions by `client_ip_middleware`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub IpAddr);
+
+/// Proxies whose forwarding headers are trusted
+#[derive(Debug, Default)]
+pub struct TrustedProxies {
+    ranges: Vec<IpRange>,
+}
+
+impl TrustedProxies {
+    /// Create from a list of IP addresses or CIDR ranges
+    pub fn new(proxies: &[String]) -> Result<Self> {
+        let ranges = proxies
+            .iter()
+            .map(|proxy| {
+                IpRange::parse(proxy).map_err(|e| match e {
+                    GatewayError::Config(msg) => {
+                        GatewayError::Config(format!("server.trusted_proxies: {}", msg))
+                    }
+                    e => e,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { ranges })
+    }
+
+    /// Check if an address belongs to a trusted proxy
+    pub fn is_trusted(&self, ip: &IpAddr) -> bool {
+        self.ranges.iter().any(|range| range.contains(ip))
+    }
+
+    /// Resolve the client IP of a request received from `peer`
+    pub fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        if !self.is_trusted(&peer) {
+            return peer;
+        }
+
+        let hops = if headers.contains_key(X_FORWARDED_FOR) {
+            forwarded_for_hops(headers)
+        } else {
+            forwarded_hops(headers)
+        };
+
+        // The nearest untrusted hop is the client; an unparseable hop
+        // (e.g. `unknown`) ends the chain we can vouch for
+        let mut client = peer;
+        for hop in hops.iter().rev() {
+            match hop {
+                Some(ip) if self.is_trusted(ip) => client = *ip,
+                Some(ip) => return *ip,
+                None => break,
+            }
+        }
+        client
+    }
+}
+
+/// Hops listed in `X-Forwarded-For`, nearest last
+fn forwarded_for_hops(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    headers
+        .get_all(X_FORWARDED_FOR)
+        .iter()
+        .flat_map(|value| value.to_str().unwrap_or_default().split(','))
+        .map(parse_node)
+        .collect()
+}
+
+/// `for=` hops listed in `Forwarded`, nearest last
+fn forwarded_hops(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    headers
+        .get_all(FORWARDED)
+        .iter()
+        .flat_map(|value| value.to_str().unwrap_or_default().split(','))
+        .filter_map(|element| {
+            element.split(';').find_map(|pair| {
+                let (name, value) = pair.split_once('=')?;
+                name.trim()
+                    .eq_ignore_ascii_case("for")
+                    .then(|| parse_node(value))
+            })
+        })
+        .collect()
+}
+
+/// Parse a forwarded node: a bare IP, `ip:port`, `"[v6]:port"` or an
+/// obfuscated identifier (which yields `None`)
+fn parse_node(node: &str) -> Option<IpAddr> {
+    let node = node.trim().trim_matches('"');
+    if let Ok(ip) = node.parse::<IpAddr>() {
+        return Some(ip);
+    }
+    if let Ok(addr) = node.parse::<SocketAddr>() {
+        return Some(addr.ip());
+    }
+    node.strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+        .and_then(|(ip, _)| ip.parse().ok())
+}
+
+/// Client IP of a request: the resolved `ClientIp` if present, otherwise the peer address
+pub fn client_ip(extensions: &Extensions) -> Option<IpAddr> {
+    extensions
+        .get::<ClientIp>()
+        .map(|ClientIp(ip)| *ip)
+        .or_else(|| {
+            extensions
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip())
+        })
+}
+
+/// Middleware resolving the client IP through trusted proxies
+///
+/// Must run outside IP filtering, rate limiting and access logging so they
+/// see the resolved `ClientIp`.
+pub async fn client_ip_middleware(
+    State(trusted_proxies): State<Arc<TrustedProxies>>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    if let Some(ConnectInfo(peer)) = req.extensions().get::<ConnectInfo<SocketAddr>>() {
+        let ip = trusted_proxies.client_ip(peer.ip(), req.headers());
+        if ip != peer.ip() {
+            debug!(peer = %peer.ip(), client_ip = %ip, "Resolved client IP from forwarding headers");
+        }
+        req.extensions_mut().insert(ClientIp(ip));
+    }
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn trusted() -> TrustedProxies {
+        TrustedProxies::new(&["10.0.0.0/8".to_string(), "192.168.1.1".to_string()]).unwrap()
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    fn headers(name: &str, values: &[&str]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for value in values {
+            headers.append(
+                axum::http::HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                HeaderValue::from_str(value).unwrap(),
+            );
+        }
+        headers
+    }
+
+    #[test]
+    fn test_untrusted_peer_ignores_headers() {
+        let headers = headers("x-forwarded-for", &["203.0.113.7"]);
+        assert_eq!(
+            trusted().client_ip(ip("198.51.100.1"), &headers),
+            ip("198.51.100.1")
+        );
+    }
+
+    #[test]
+    fn test_trusted_peer_uses_rightmost_untrusted_hop() {
+        // The leftmost entry is client-supplied and can't be trusted
+        let headers = headers(
+            "x-forwarded-for",
+            &["1.2.3.4, 203.0.113.7", "10.1.1.1, 192.168.1.1"],
+        );
+        assert_eq!(
+            trusted().client_ip(ip("10.0.0.2"), &headers),
+            ip("203.0.113.7")
+        );
+    }
+
+    #[test]
+    fn test_all_hops_trusted() {
+        let headers = headers("x-forwarded-for", &["10.2.2.2, 10.1.1.1"]);
+        assert_eq!(
+            trusted().client_ip(ip("10.0.0.2"), &headers),
+            ip("10.2.2.2")
+        );
+
+        let empty = HeaderMap::new();
+        assert_eq!(trusted().client_ip(ip("10.0.0.2"), &empty), ip("10.0.0.2"));
+    }
+
+    #[test]
+    fn test_unparseable_hop_stops_the_chain() {
+        let headers = headers("x-forwarded-for", &["203.0.113.7, unknown, 10.1.1.1"]);
+        assert_eq!(
+            trusted().client_ip(ip("10.0.0.2"), &headers),
+            ip("10.1.1.1")
+        );
+    }
+
+    #[test]
+    fn test_forwarded_header() {
+        let forwarded = headers(
+            "forwarded",
+            &[r#"for=192.0.2.60;proto=http, For="[2001:db8:cafe::17]:4711";by=10.0.0.1"#],
+        );
+        assert_eq!(
+            trusted().client_ip(ip("10.0.0.2"), &forwarded),
+            ip("2001:db8:cafe::17")
+        );
+
+        let forwarded = headers("forwarded", &[r#"for="198.51.100.4:5123", for=10.3.3.3"#]);
+        assert_eq!(
+            trusted().client_ip(ip("10.0.0.2"), &forwarded),
+            ip("198.51.100.4")
+        );
+    }
+
+    #[test]
+    fn test_invalid_trusted_proxy() {
+        let err = TrustedProxies::new(&["10.0.0.0/40".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("server.trusted_proxies"));
+    }
+}
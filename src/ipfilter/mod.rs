@@ -1,8 +1,13 @@
+pub mod client_ip;
+
+pub use client_ip::{client_ip_middleware, ClientIp, TrustedProxies};
+
 use crate::error::{GatewayError, Result};
+use maxminddb::{geoip2, MaxMindDBError, Reader};
 use serde::{Deserialize, Serialize};
 use std::net::IpAddr;
 use std::str::FromStr;
-use tracing::{debug, warn};
+use tracing::{debug, info, warn};
 
 /// IP filtering configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -16,6 +21,22 @@ pub struct IpFilterConfig {
     /// Default action when IP doesn't match any rule (allow or deny)
     #[serde(default = "default_action")]
     pub default_action: IpFilterAction,
+    /// ISO 3166-1 alpha-2 country codes allowed (requires `geoip`)
+    #[serde(default)]
+    pub allowed_countries: Vec<String>,
+    /// ISO 3166-1 alpha-2 country codes blocked (requires `geoip`)
+    #[serde(default)]
+    pub blocked_countries: Vec<String>,
+    /// GeoIP database used to resolve client countries
+    #[serde(default)]
+    pub geoip: Option<GeoIpConfig>,
+}
+
+/// GeoIP lookup configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeoIpConfig {
+    /// Path to a MaxMind country or city database (`.mmdb`)
+    pub database: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -36,18 +57,85 @@ pub struct IpFilterService {
     config: IpFilterConfig,
     whitelist_ranges: Vec<IpRange>,
     blacklist_ranges: Vec<IpRange>,
+    geoip: Option<GeoIpDatabase>,
+}
+
+/// Opened MaxMind database
+struct GeoIpDatabase {
+    path: String,
+    reader: Reader<Vec<u8>>,
+}
+
+impl GeoIpDatabase {
+    fn open(config: &GeoIpConfig) -> Result<Self> {
+        let reader = Reader::open_readfile(&config.database).map_err(|e| {
+            GatewayError::Config(format!(
+                "ip_filter.geoip.database: failed to open '{}': {}",
+                config.database, e
+            ))
+        })?;
+        info!(
+            path = %config.database,
+            database_type = %reader.metadata.database_type,
+            "Loaded GeoIP database"
+        );
+        Ok(Self {
+            path: config.database.clone(),
+            reader,
+        })
+    }
+
+    /// ISO country code for an IP, if the database knows it
+    fn country(&self, ip: &IpAddr) -> Option<String> {
+        match self.reader.lookup::<geoip2::Country>(*ip) {
+            Ok(record) => record
+                .country
+                .and_then(|country| country.iso_code)
+                .map(str::to_string),
+            Err(MaxMindDBError::AddressNotFoundError(_)) => None,
+            Err(e) => {
+                warn!(ip = %ip, error = %e, "GeoIP lookup failed");
+                None
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for GeoIpDatabase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GeoIpDatabase")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+/// Normalize and validate a list of ISO country codes
+fn parse_country_codes(field: &str, codes: &[String]) -> Result<Vec<String>> {
+    codes
+        .iter()
+        .map(|code| {
+            if code.len() == 2 && code.chars().all(|c| c.is_ascii_alphabetic()) {
+                Ok(code.to_ascii_uppercase())
+            } else {
+                Err(GatewayError::Config(format!(
+                    "ip_filter.{}: invalid ISO country code '{}'",
+                    field, code
+                )))
+            }
+        })
+        .collect()
 }
 
 /// Represents an IP address or CIDR range
 #[derive(Debug, Clone)]
-enum IpRange {
+pub(crate) enum IpRange {
     Single(IpAddr),
     Cidr { network: IpAddr, prefix_len: u8 },
 }
 
 impl IpRange {
     /// Parse an IP or CIDR string into an IpRange
-    fn parse(s: &str) -> Result<Self> {
+    pub(crate) fn parse(s: &str) -> Result<Self> {
         if let Some((network_str, prefix_str)) = s.split_once('/') {
             // CIDR notation
             let network = IpAddr::from_str(network_str).map_err(|e| {
@@ -81,15 +169,14 @@ impl IpRange {
             })
         } else {
             // Single IP address
-            let ip = IpAddr::from_str(s).map_err(|e| {
-                GatewayError::Config(format!("Invalid IP address '{}': {}", s, e))
-            })?;
+            let ip = IpAddr::from_str(s)
+                .map_err(|e| GatewayError::Config(format!("Invalid IP address '{}': {}", s, e)))?;
             Ok(IpRange::Single(ip))
         }
     }
 
     /// Check if an IP address matches this range
-    fn contains(&self, ip: &IpAddr) -> bool {
+    pub(crate) fn contains(&self, ip: &IpAddr) -> bool {
         match self {
             IpRange::Single(range_ip) => ip == range_ip,
             IpRange::Cidr {
@@ -127,7 +214,7 @@ impl IpRange {
 
 impl IpFilterService {
     /// Create a new IP filter service from configuration
-    pub fn new(config: IpFilterConfig) -> Result<Self> {
+    pub fn new(mut config: IpFilterConfig) -> Result<Self> {
         // Parse whitelist ranges
         let whitelist_ranges: Result<Vec<IpRange>> =
             config.whitelist.iter().map(|s| IpRange::parse(s)).collect();
@@ -136,13 +223,36 @@ impl IpFilterService {
         let blacklist_ranges: Result<Vec<IpRange>> =
             config.blacklist.iter().map(|s| IpRange::parse(s)).collect();
 
+        config.allowed_countries =
+            parse_country_codes("allowed_countries", &config.allowed_countries)?;
+        config.blocked_countries =
+            parse_country_codes("blocked_countries", &config.blocked_countries)?;
+        let has_country_rules =
+            !config.allowed_countries.is_empty() || !config.blocked_countries.is_empty();
+
+        let geoip = match &config.geoip {
+            Some(geoip) => Some(GeoIpDatabase::open(geoip)?),
+            None if has_country_rules => {
+                return Err(GatewayError::Config(
+                    "ip_filter: country rules require ip_filter.geoip".to_string(),
+                ));
+            }
+            None => None,
+        };
+
         Ok(Self {
             config,
             whitelist_ranges: whitelist_ranges?,
             blacklist_ranges: blacklist_ranges?,
+            geoip,
         })
     }
 
+    /// ISO country code for an IP, if a GeoIP database is configured and knows it
+    pub fn country(&self, ip: &IpAddr) -> Option<String> {
+        self.geoip.as_ref().and_then(|geoip| geoip.country(ip))
+    }
+
     /// Check if an IP address is allowed
     pub fn is_allowed(&self, ip: &IpAddr) -> bool {
         // Check blacklist first (highest priority)
@@ -153,15 +263,42 @@ impl IpFilterService {
             }
         }
 
-        // If whitelist is not empty, IP must be in whitelist
-        if !self.whitelist_ranges.is_empty() {
-            for range in &self.whitelist_ranges {
-                if range.contains(ip) {
-                    debug!(ip = %ip, "IP allowed by whitelist");
+        // Unknown countries skip the country rules and fall through to the
+        // remaining checks
+        let country = if self.config.allowed_countries.is_empty()
+            && self.config.blocked_countries.is_empty()
+        {
+            None
+        } else {
+            self.country(ip)
+        };
+        if let Some(country) = &country {
+            if self.config.blocked_countries.contains(country) {
+                warn!(ip = %ip, country = %country, "IP blocked by country");
+                return false;
+            }
+        }
+
+        for range in &self.whitelist_ranges {
+            if range.contains(ip) {
+                debug!(ip = %ip, "IP allowed by whitelist");
+                return true;
+            }
+        }
+
+        if let Some(country) = &country {
+            if !self.config.allowed_countries.is_empty() {
+                if self.config.allowed_countries.contains(country) {
+                    debug!(ip = %ip, country = %country, "IP allowed by country");
                     return true;
                 }
+                warn!(ip = %ip, country = %country, "IP country not allowed, denying");
+                return false;
             }
-            // IP not in whitelist
+        }
+
+        // If whitelist is not empty, IP must be in whitelist
+        if !self.whitelist_ranges.is_empty() {
             warn!(ip = %ip, "IP not in whitelist, denying");
             return false;
         }
@@ -181,7 +318,10 @@ impl IpFilterService {
 
     /// Check if filtering is enabled (has any rules)
     pub fn is_enabled(&self) -> bool {
-        !self.whitelist_ranges.is_empty() || !self.blacklist_ranges.is_empty()
+        !self.whitelist_ranges.is_empty()
+            || !self.blacklist_ranges.is_empty()
+            || !self.config.allowed_countries.is_empty()
+            || !self.config.blocked_countries.is_empty()
     }
 }
 
@@ -195,6 +335,7 @@ mod tests {
             whitelist: vec!["192.168.1.1".to_string()],
             blacklist: vec![],
             default_action: IpFilterAction::Deny,
+            ..Default::default()
         };
 
         let service = IpFilterService::new(config).unwrap();
@@ -209,6 +350,7 @@ mod tests {
             whitelist: vec!["192.168.1.0/24".to_string()],
             blacklist: vec![],
             default_action: IpFilterAction::Deny,
+            ..Default::default()
         };
 
         let service = IpFilterService::new(config).unwrap();
@@ -224,6 +366,7 @@ mod tests {
             whitelist: vec!["192.168.1.0/24".to_string()],
             blacklist: vec!["192.168.1.100".to_string()],
             default_action: IpFilterAction::Deny,
+            ..Default::default()
         };
 
         let service = IpFilterService::new(config).unwrap();
@@ -238,6 +381,7 @@ mod tests {
             whitelist: vec![],
             blacklist: vec!["10.0.0.1".to_string(), "10.0.0.0/24".to_string()],
             default_action: IpFilterAction::Allow,
+            ..Default::default()
         };
 
         let service = IpFilterService::new(config).unwrap();
@@ -253,6 +397,7 @@ mod tests {
             whitelist: vec![],
             blacklist: vec![],
             default_action: IpFilterAction::Allow,
+            ..Default::default()
         };
 
         let service = IpFilterService::new(config).unwrap();
@@ -267,6 +412,7 @@ mod tests {
             whitelist: vec![],
             blacklist: vec![],
             default_action: IpFilterAction::Deny,
+            ..Default::default()
         };
 
         let service = IpFilterService::new(config).unwrap();
@@ -281,6 +427,7 @@ mod tests {
             whitelist: vec!["2001:db8::/32".to_string()],
             blacklist: vec![],
             default_action: IpFilterAction::Deny,
+            ..Default::default()
         };
 
         let service = IpFilterService::new(config).unwrap();
@@ -296,6 +443,7 @@ mod tests {
             whitelist: vec!["invalid-ip".to_string()],
             blacklist: vec![],
             default_action: IpFilterAction::Allow,
+            ..Default::default()
         };
 
         assert!(IpFilterService::new(config).is_err());
@@ -307,6 +455,7 @@ mod tests {
             whitelist: vec!["192.168.1.0/33".to_string()], // Invalid prefix for IPv4
             blacklist: vec![],
             default_action: IpFilterAction::Allow,
+            ..Default::default()
         };
 
         assert!(IpFilterService::new(config).is_err());
@@ -318,6 +467,7 @@ mod tests {
             whitelist: vec!["192.168.1.0/24".to_string()],
             blacklist: vec![],
             default_action: IpFilterAction::Allow,
+            ..Default::default()
         };
         assert!(IpFilterService::new(config1).unwrap().is_enabled());
 
@@ -325,6 +475,7 @@ mod tests {
             whitelist: vec![],
             blacklist: vec!["10.0.0.1".to_string()],
             default_action: IpFilterAction::Allow,
+            ..Default::default()
         };
         assert!(IpFilterService::new(config2).unwrap().is_enabled());
 
@@ -332,7 +483,214 @@ mod tests {
             whitelist: vec![],
             blacklist: vec![],
             default_action: IpFilterAction::Allow,
+            ..Default::default()
         };
         assert!(!IpFilterService::new(config3).unwrap().is_enabled());
     }
+
+    /// Write a minimal MaxMind country database mapping CIDRs to ISO codes
+    fn geoip_database(networks: &[(&str, &str)]) -> tempfile::NamedTempFile {
+        enum Record {
+            Empty,
+            Node(usize),
+            Data(usize),
+        }
+
+        fn string(out: &mut Vec<u8>, value: &str) {
+            out.push(0x40 | value.len() as u8);
+            out.extend_from_slice(value.as_bytes());
+        }
+
+        // Search tree over IPv6 keys, with IPv4 networks under ::/96
+        let mut nodes = vec![[Record::Empty, Record::Empty]];
+        let mut data = Vec::new();
+        for (cidr, iso_code) in networks {
+            let (network, prefix_len) = cidr.split_once('/').unwrap();
+            let (bits, prefix_len) = match IpAddr::from_str(network).unwrap() {
+                IpAddr::V4(v4) => (
+                    u32::from_be_bytes(v4.octets()) as u128,
+                    96 + prefix_len.parse::<usize>().unwrap(),
+                ),
+                IpAddr::V6(v6) => (
+                    u128::from_be_bytes(v6.octets()),
+                    prefix_len.parse::<usize>().unwrap(),
+                ),
+            };
+
+            let mut node = 0;
+            for i in 0..prefix_len {
+                let bit = ((bits >> (127 - i)) & 1) as usize;
+                if i == prefix_len - 1 {
+                    nodes[node][bit] = Record::Data(data.len());
+                } else if let Record::Node(next) = nodes[node][bit] {
+                    node = next;
+                } else {
+                    nodes.push([Record::Empty, Record::Empty]);
+                    nodes[node][bit] = Record::Node(nodes.len() - 1);
+                    node = nodes.len() - 1;
+                }
+            }
+
+            // {"country": {"iso_code": <code>}}
+            data.push(0xE1);
+            string(&mut data, "country");
+            data.push(0xE1);
+            string(&mut data, "iso_code");
+            string(&mut data, iso_code);
+        }
+
+        let node_count = nodes.len();
+        let mut db = Vec::new();
+        for node in &nodes {
+            for record in node {
+                let value = match record {
+                    Record::Empty => node_count,
+                    Record::Node(next) => *next,
+                    Record::Data(offset) => node_count + 16 + offset,
+                };
+                db.extend_from_slice(&(value as u32).to_be_bytes()[1..]);
+            }
+        }
+        db.extend_from_slice(&[0; 16]);
+        db.extend_from_slice(&data);
+
+        db.extend_from_slice(b"\xab\xcd\xefMaxMind.com");
+        db.push(0xE9);
+        string(&mut db, "binary_format_major_version");
+        db.extend_from_slice(&[0xA1, 2]);
+        string(&mut db, "binary_format_minor_version");
+        db.push(0xA0);
+        string(&mut db, "build_epoch");
+        db.extend_from_slice(&[0x00, 2]);
+        string(&mut db, "database_type");
+        string(&mut db, "GeoIP2-Country");
+        string(&mut db, "description");
+        db.push(0xE0);
+        string(&mut db, "ip_version");
+        db.extend_from_slice(&[0xA1, 6]);
+        string(&mut db, "languages");
+        db.extend_from_slice(&[0x00, 4]);
+        string(&mut db, "node_count");
+        db.push(0xC4);
+        db.extend_from_slice(&(node_count as u32).to_be_bytes());
+        string(&mut db, "record_size");
+        db.extend_from_slice(&[0xA1, 24]);
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, &db).unwrap();
+        file
+    }
+
+    fn test_geoip_database() -> tempfile::NamedTempFile {
+        geoip_database(&[
+            ("81.2.69.0/24", "GB"),
+            ("175.16.199.0/24", "CN"),
+            ("2001:db8::/32", "DE"),
+        ])
+    }
+
+    fn geoip_config(database: &tempfile::NamedTempFile) -> Option<GeoIpConfig> {
+        Some(GeoIpConfig {
+            database: database.path().to_str().unwrap().to_string(),
+        })
+    }
+
+    #[test]
+    fn test_geoip_country_lookup() {
+        let database = test_geoip_database();
+        let service = IpFilterService::new(IpFilterConfig {
+            geoip: geoip_config(&database),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let country = |ip: &str| service.country(&IpAddr::from_str(ip).unwrap());
+        assert_eq!(country("81.2.69.142").as_deref(), Some("GB"));
+        assert_eq!(country("175.16.199.1").as_deref(), Some("CN"));
+        assert_eq!(country("2001:db8::1").as_deref(), Some("DE"));
+        assert_eq!(country("203.0.113.7"), None);
+    }
+
+    #[test]
+    fn test_blocked_countries() {
+        let database = test_geoip_database();
+        let config = IpFilterConfig {
+            blocked_countries: vec!["cn".to_string()],
+            geoip: geoip_config(&database),
+            ..Default::default()
+        };
+
+        let service = IpFilterService::new(config).unwrap();
+        assert!(service.is_enabled());
+
+        assert!(!service.is_allowed(&IpAddr::from_str("175.16.199.1").unwrap()));
+        assert!(service.is_allowed(&IpAddr::from_str("81.2.69.142").unwrap()));
+        // Unknown countries fall back to the default action
+        assert!(service.is_allowed(&IpAddr::from_str("203.0.113.7").unwrap()));
+    }
+
+    #[test]
+    fn test_allowed_countries() {
+        let database = test_geoip_database();
+        let config = IpFilterConfig {
+            whitelist: vec!["10.0.0.0/8".to_string()],
+            default_action: IpFilterAction::Deny,
+            allowed_countries: vec!["GB".to_string(), "DE".to_string()],
+            geoip: geoip_config(&database),
+            ..Default::default()
+        };
+
+        let service = IpFilterService::new(config).unwrap();
+
+        assert!(service.is_allowed(&IpAddr::from_str("81.2.69.142").unwrap()));
+        assert!(service.is_allowed(&IpAddr::from_str("2001:db8::1").unwrap()));
+        assert!(!service.is_allowed(&IpAddr::from_str("175.16.199.1").unwrap()));
+        // CIDR whitelist still applies alongside the country list
+        assert!(service.is_allowed(&IpAddr::from_str("10.1.2.3").unwrap()));
+        assert!(!service.is_allowed(&IpAddr::from_str("203.0.113.7").unwrap()));
+    }
+
+    #[test]
+    fn test_unknown_country_uses_default_action() {
+        let database = test_geoip_database();
+        let config = |default_action| IpFilterConfig {
+            default_action,
+            allowed_countries: vec!["GB".to_string()],
+            geoip: geoip_config(&database),
+            ..Default::default()
+        };
+        let unknown = IpAddr::from_str("203.0.113.7").unwrap();
+
+        let service = IpFilterService::new(config(IpFilterAction::Allow)).unwrap();
+        assert!(service.is_allowed(&unknown));
+
+        let service = IpFilterService::new(config(IpFilterAction::Deny)).unwrap();
+        assert!(!service.is_allowed(&unknown));
+    }
+
+    #[test]
+    fn test_invalid_geoip_config() {
+        // Country rules need a database
+        let config = IpFilterConfig {
+            blocked_countries: vec!["CN".to_string()],
+            ..Default::default()
+        };
+        assert!(IpFilterService::new(config).is_err());
+
+        let database = test_geoip_database();
+        let config = IpFilterConfig {
+            blocked_countries: vec!["China".to_string()],
+            geoip: geoip_config(&database),
+            ..Default::default()
+        };
+        assert!(IpFilterService::new(config).is_err());
+
+        let config = IpFilterConfig {
+            geoip: Some(GeoIpConfig {
+                database: "/nonexistent/GeoLite2-Country.mmdb".to_string(),
+            }),
+            ..Default::default()
+        };
+        assert!(IpFilterService::new(config).is_err());
+    }
 }
@@ -1,14 +1,18 @@
+pub mod admin;
 pub mod auth;
 pub mod cache;
 pub mod circuit_breaker;
 pub mod config;
 pub mod cors;
+pub mod dns;
 pub mod error;
 pub mod healthcheck;
 pub mod hotreload;
 pub mod ipfilter;
 pub mod loadbalancer;
+pub mod maintenance;
 pub mod metrics;
+pub mod mirror;
 pub mod observability;
 pub mod proxy;
 pub mod rate_limit;
@@ -17,21 +21,44 @@ pub mod secrets;
 pub mod tls;
 pub mod transform;
 
-use crate::config::GatewayConfig;
-use crate::error::Result;
+use crate::config::{GatewayConfig, MetricsExporter};
+use crate::error::{error_response_middleware, ErrorRenderer, Result};
+use crate::hotreload::{watch_config_file, ReloadableConfig};
+use crate::ipfilter::{client_ip_middleware, IpFilterService, TrustedProxies};
 use crate::metrics::{metrics_handler, MetricsService};
-use crate::observability::{request_id_middleware, TracingConfig};
+use crate::observability::{
+    access_log_middleware, request_id_middleware, trace_context_middleware, AccessLogSampler,
+    RequestIdSource, TracingConfig,
+};
 use crate::proxy::{proxy_handler, ProxyState};
+use crate::rate_limit::RateLimitMiddleware;
 use crate::router::Router;
 use axum::{middleware, routing::any, routing::get, Router as AxumRouter};
-use std::net::SocketAddr;
-use std::str::FromStr;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Duration;
 use tower_http::trace::TraceLayer;
-use tracing::info;
+use tracing::{error, info, warn};
+
+/// How often response cache statistics are recorded as gauges
+const CACHE_STATS_INTERVAL: Duration = Duration::from_secs(15);
 
 /// Initialize the gateway server
 pub async fn init_gateway(config: GatewayConfig) -> Result<()> {
+    serve_gateway(config, None).await
+}
+
+/// Initialize the gateway server and, if `hot_reload` is enabled, apply
+/// changes to `config_path` to the running gateway
+pub async fn init_gateway_with_reload<P: AsRef<Path>>(
+    config: GatewayConfig,
+    config_path: P,
+) -> Result<()> {
+    serve_gateway(config, Some(config_path.as_ref().to_path_buf())).await
+}
+
+async fn serve_gateway(config: GatewayConfig, config_path: Option<PathBuf>) -> Result<()> {
     // Validate configuration
     config.validate()?;
 
@@ -41,70 +68,87 @@ pub async fn init_gateway(config: GatewayConfig) -> Result<()> {
         config.server.host, config.server.port
     );
 
-    // Create authentication service if configured
-    let auth_service = if config.auth.is_some() {
-        info!("Initializing authentication service");
-        let service = auth::AuthService::new(config.auth.as_ref()).await?;
-        if service.is_available() {
-            info!("Authentication service initialized successfully");
-            Some(service)
-        } else {
-            info!("No authentication methods configured");
-            None
-        }
-    } else {
-        info!("Authentication not configured");
-        None
-    };
-
-    // Create circuit breaker service if configured
-    let circuit_breaker = if let Some(cb_config) = config.circuit_breaker {
-        info!(
-            failure_threshold = cb_config.failure_threshold,
-            success_threshold = cb_config.success_threshold,
-            timeout_secs = cb_config.timeout_secs,
-            "Initializing circuit breaker service"
-        );
-        Some(circuit_breaker::CircuitBreakerService::new(cb_config))
-    } else {
-        info!("Circuit breaker not configured");
-        None
-    };
-
-    // Create retry executor if configured
-    let retry_executor = if let Some(retry_config) = config.retry {
-        info!(
-            max_retries = retry_config.max_retries,
-            initial_backoff_ms = retry_config.initial_backoff_ms,
-            "Initializing retry executor"
-        );
-        Some(circuit_breaker::RetryExecutor::new(retry_config))
-    } else {
-        info!("Retry logic not configured");
-        None
-    };
+    let auth_service = build_auth_service(&config).await?;
+    let rate_limiter = build_rate_limiter(&config).await?;
+    let circuit_breaker = build_circuit_breaker(&config);
+    let retry_executor = build_retry_executor(&config);
 
     // Create router
-    let router = Router::new(config.routes)?;
+    let router = build_router(&config)?;
     info!("Loaded {} routes", router.routes().len());
 
     // Create proxy state
-    let proxy_state = ProxyState::new(
+    let mut proxy_state = ProxyState::new(
         router,
         Duration::from_secs(config.server.timeout_secs),
         auth_service,
         circuit_breaker,
         retry_executor,
-    );
+    )
+    .with_upstream(config.server.upstream.clone())
+    .with_request_deadline(config.server.enforce_request_deadline)
+    .with_method_override(config.server.honor_method_override)
+    .with_answer_options(config.server.answer_options)
+    .with_health_check_paths(config.server.health_check_paths.clone())
+    .with_maintenance(config.maintenance.clone().unwrap_or_default());
+    proxy_state.set_rate_limiter(rate_limiter);
+    proxy_state.set_ip_filter(build_ip_filter(&config)?);
+
+    // Enable backend DNS caching if configured
+    if let Some(dns_config) = config.dns.clone().filter(|d| d.enabled) {
+        info!(
+            ttl_secs = dns_config.dns_cache_ttl_secs,
+            stale_on_error = dns_config.stale_on_error,
+            "Initializing backend DNS cache"
+        );
+        proxy_state = proxy_state.with_dns_cache(dns::DnsCache::new(dns_config));
+    }
+
+    // Configure TLS for backend connections if configured
+    if let Some(backend_tls) = &config.backend_tls {
+        info!(
+            mtls = backend_tls.client_cert_path.is_some(),
+            "Initializing backend TLS"
+        );
+        proxy_state = proxy_state.with_backend_tls(tls::build_backend_tls_config(backend_tls)?);
+    }
+
+    // Apply configuration file changes to the running gateway
+    if let Some(hot_reload) = config.hot_reload.as_ref().filter(|h| h.enabled) {
+        match &config_path {
+            Some(path) => {
+                let reloadable = ReloadableConfig::new(config.clone());
+                watch_config_file(path, reloadable.clone(), hot_reload.debounce_ms).await?;
+                spawn_config_applier(reloadable, proxy_state.clone());
+            }
+            None => warn!("Hot reload enabled but no configuration file path was given"),
+        }
+    }
+
+    // Start the admin API on its own listener if configured
+    if let Some(admin_config) = config.admin.as_ref().filter(|a| a.enabled) {
+        admin::spawn_admin_server(admin_config, proxy_state.clone()).await?;
+    }
 
     // Initialize metrics service if configured
     let metrics_service = if let Some(obs_config) = &config.observability {
         if let Some(metrics_config) = &obs_config.metrics {
             if metrics_config.enabled {
-                info!("Initializing Prometheus metrics service");
-                let service = MetricsService::new()?;
-                info!("Metrics endpoint enabled at {}", metrics_config.path);
-                Some((service, metrics_config.path.clone()))
+                match metrics_config.exporter {
+                    MetricsExporter::Prometheus => {
+                        info!("Initializing Prometheus metrics service");
+                        let service = MetricsService::with_duration_buckets(
+                            metrics_config.duration_buckets.as_deref(),
+                        )?;
+                        info!("Metrics endpoint enabled at {}", metrics_config.path);
+                        Some((service, metrics_config.path.clone()))
+                    }
+                    MetricsExporter::Statsd => {
+                        info!("Initializing StatsD metrics exporter");
+                        MetricsService::install_statsd(&metrics_config.statsd)?;
+                        None
+                    }
+                }
             } else {
                 info!("Metrics disabled in configuration");
                 None
@@ -116,10 +160,26 @@ pub async fn init_gateway(config: GatewayConfig) -> Result<()> {
         None
     };
 
+    // Periodically report response cache sizes for capacity planning
+    if config
+        .observability
+        .as_ref()
+        .and_then(|o| o.metrics.as_ref())
+        .is_some_and(|m| m.enabled)
+    {
+        proxy_state.spawn_cache_stats_reporter(CACHE_STATS_INTERVAL);
+    }
+
     // Create Axum app
-    let mut app = AxumRouter::new()
-        .route("/*path", any(proxy_handler))
-        .with_state(proxy_state);
+    let mut app = AxumRouter::new().route("/*path", any(proxy_handler));
+
+    // Serve aggregate backend health outside the proxy (and its auth)
+    if let Some(health_path) = &config.server.health_path {
+        info!("Aggregate health endpoint enabled at {}", health_path);
+        app = app.route(health_path, get(admin::aggregate_health_handler));
+    }
+
+    let mut app = app.with_state(proxy_state);
 
     // Add metrics endpoint if configured
     if let Some((metrics_service, metrics_path)) = metrics_service {
@@ -129,9 +189,50 @@ pub async fn init_gateway(config: GatewayConfig) -> Result<()> {
         );
     }
 
+    // Render gateway errors in the format the client accepts
+    app = app.layer(middleware::from_fn_with_state(
+        Arc::new(ErrorRenderer::new(config.server.error_template.clone())),
+        error_response_middleware,
+    ));
+
+    // Log each completed request; runs inside the request ID middleware
+    let access_log = config
+        .observability
+        .as_ref()
+        .and_then(|o| o.access_log.as_ref())
+        .filter(|a| a.enabled);
+    if let Some(access_log) = access_log {
+        app = app.layer(middleware::from_fn_with_state(
+            Arc::new(AccessLogSampler::new(access_log)),
+            access_log_middleware,
+        ));
+    }
+
+    // Resolve client IPs behind trusted proxies before anything reads them
+    if !config.server.trusted_proxies.is_empty() {
+        info!(
+            proxies = config.server.trusted_proxies.len(),
+            "Trusting forwarded client IPs from configured proxies"
+        );
+        let trusted_proxies = Arc::new(TrustedProxies::new(&config.server.trusted_proxies)?);
+        app = app.layer(middleware::from_fn_with_state(
+            trusted_proxies,
+            client_ip_middleware,
+        ));
+    }
+
     // Add middleware layers
+    let request_id_source = config
+        .observability
+        .as_ref()
+        .map(|o| RequestIdSource::new(&o.request_id))
+        .unwrap_or_default();
     app = app
-        .layer(middleware::from_fn(request_id_middleware))
+        .layer(middleware::from_fn_with_state(
+            Arc::new(request_id_source),
+            request_id_middleware,
+        ))
+        .layer(middleware::from_fn(trace_context_middleware))
         .layer(TraceLayer::new_for_http());
 
     // Bind and serve
@@ -140,18 +241,33 @@ pub async fn init_gateway(config: GatewayConfig) -> Result<()> {
     // Check if TLS is configured
     if let Some(tls_config) = config.tls {
         info!("TLS enabled, starting HTTPS server");
-        let tls_server_config = tls::build_tls_config(&tls_config)?;
+        let resolver = std::sync::Arc::new(tls::ReloadableCertResolver::new(&tls_config)?);
+        let tls_server_config = tls::build_tls_config_with_resolver(&tls_config, resolver.clone())?;
+
+        // Pick up rotated certificates without restarting
+        if tls_config.watch_certificates {
+            resolver.watch(Duration::from_millis(tls_config.watch_debounce_ms))?;
+        }
+
         let rustls_config = std::sync::Arc::new(tls_server_config);
 
+        // Bind through std so host names resolve and bind errors surface as IO errors
+        let listener =
+            std::net::TcpListener::bind(&addr).map_err(crate::error::GatewayError::Io)?;
+        listener
+            .set_nonblocking(true)
+            .map_err(crate::error::GatewayError::Io)?;
+
         info!("Gateway ready to accept TLS connections on {}", addr);
 
-        axum_server::bind_rustls(
-            SocketAddr::from_str(&addr).unwrap(),
+        // Use make_service_with_connect_info to extract client IP
+        axum_server::from_tcp_rustls(
+            listener,
             axum_server::tls_rustls::RustlsConfig::from_config(rustls_config),
         )
-            .serve(app.into_make_service_with_connect_info::<std::net::SocketAddr>())
-            .await
-            .map_err(|e| crate::error::GatewayError::Internal(format!("TLS server error: {}", e)))?;
+        .serve(app.into_make_service_with_connect_info::<std::net::SocketAddr>())
+        .await
+        .map_err(|e| crate::error::GatewayError::Internal(format!("TLS server error: {}", e)))?;
     } else {
         info!("TLS not configured, starting HTTP server");
         let listener = tokio::net::TcpListener::bind(&addr)
@@ -172,6 +288,192 @@ pub async fn init_gateway(config: GatewayConfig) -> Result<()> {
     Ok(())
 }
 
+/// Create the authentication service if configured
+async fn build_auth_service(config: &GatewayConfig) -> Result<Option<auth::AuthService>> {
+    if config.auth.is_none() {
+        info!("Authentication not configured");
+        return Ok(None);
+    }
+
+    info!("Initializing authentication service");
+    let service = auth::AuthService::new(config.auth.as_ref()).await?;
+    if service.is_available() {
+        info!("Authentication service initialized successfully");
+        Ok(Some(service))
+    } else {
+        info!("No authentication methods configured");
+        Ok(None)
+    }
+}
+
+/// Create the gateway-wide rate limiter if configured and enabled
+async fn build_rate_limiter(config: &GatewayConfig) -> Result<Option<RateLimitMiddleware>> {
+    let Some(rate_limiting) = config.rate_limiting.as_ref().filter(|r| r.enabled) else {
+        info!("Rate limiting not configured");
+        return Ok(None);
+    };
+
+    info!(
+        limits = rate_limiting.global.len(),
+        algorithm = %rate_limiting.algorithm,
+        distributed = rate_limiting.redis.is_some(),
+        "Initializing rate limiter"
+    );
+    Ok(Some(RateLimitMiddleware::from_config(rate_limiting).await?))
+}
+
+/// Create the gateway-wide IP filter if configured
+fn build_ip_filter(config: &GatewayConfig) -> Result<Option<IpFilterService>> {
+    config
+        .ip_filter
+        .as_ref()
+        .map(|ip_filter| {
+            info!("Initializing global IP filter");
+            IpFilterService::new(ip_filter.clone())
+        })
+        .transpose()
+}
+
+/// Create the global circuit breaker service if configured
+fn build_circuit_breaker(config: &GatewayConfig) -> Option<circuit_breaker::CircuitBreakerService> {
+    let Some(cb_config) = config.circuit_breaker.clone() else {
+        info!("Circuit breaker not configured");
+        return None;
+    };
+
+    info!(
+        failure_threshold = cb_config.failure_threshold,
+        success_threshold = cb_config.success_threshold,
+        timeout_secs = cb_config.timeout_secs,
+        "Initializing circuit breaker service"
+    );
+    Some(circuit_breaker::CircuitBreakerService::new(cb_config))
+}
+
+/// Create the retry executor if configured
+fn build_retry_executor(config: &GatewayConfig) -> Option<circuit_breaker::RetryExecutor> {
+    let Some(retry_config) = config.retry.clone() else {
+        info!("Retry logic not configured");
+        return None;
+    };
+
+    info!(
+        max_retries = retry_config.max_retries,
+        initial_backoff_ms = retry_config.initial_backoff_ms,
+        "Initializing retry executor"
+    );
+    Some(circuit_breaker::RetryExecutor::new(retry_config))
+}
+
+/// Build the routing table; routes without their own CORS policy inherit the global one
+fn build_router(config: &GatewayConfig) -> Result<Router> {
+    let mut routes = config.routes.clone();
+    if let Some(cors) = &config.cors {
+        for route in routes.iter_mut().filter(|route| route.cors.is_none()) {
+            route.cors = Some(cors.clone());
+        }
+    }
+
+    Ok(Router::new(routes)?.with_trailing_slash(config.server.trailing_slash))
+}
+
+/// Whether a configuration section differs between two configs
+fn section_changed<T: Serialize>(previous: &T, current: &T) -> bool {
+    serde_json::to_value(previous).ok() != serde_json::to_value(current).ok()
+}
+
+/// Rebuild the subsystems whose configuration changed and swap them into `state`.
+///
+/// Everything is built before anything is swapped, so a failure leaves the
+/// running configuration untouched.
+async fn apply_config(
+    state: &ProxyState,
+    previous: &GatewayConfig,
+    config: &GatewayConfig,
+) -> Result<()> {
+    let router = if section_changed(&previous.routes, &config.routes)
+        || section_changed(&previous.cors, &config.cors)
+        || previous.server.trailing_slash != config.server.trailing_slash
+    {
+        Some(build_router(config)?)
+    } else {
+        None
+    };
+    let auth_service = if section_changed(&previous.auth, &config.auth) {
+        Some(build_auth_service(config).await?)
+    } else {
+        None
+    };
+    let rate_limiter = if section_changed(&previous.rate_limiting, &config.rate_limiting) {
+        Some(build_rate_limiter(config).await?)
+    } else {
+        None
+    };
+    let ip_filter = if section_changed(&previous.ip_filter, &config.ip_filter) {
+        Some(build_ip_filter(config)?)
+    } else {
+        None
+    };
+
+    if let Some(router) = router {
+        info!("Reloaded {} routes", router.routes().len());
+        state.set_router(router);
+    }
+    if let Some(auth_service) = auth_service {
+        info!("Reloaded authentication service");
+        state.set_auth_service(auth_service);
+    }
+    if let Some(rate_limiter) = rate_limiter {
+        info!("Reloaded rate limiter");
+        state.set_rate_limiter(rate_limiter);
+    }
+    if let Some(ip_filter) = ip_filter {
+        info!("Reloaded global IP filter");
+        state.set_ip_filter(ip_filter);
+    }
+    if section_changed(&previous.circuit_breaker, &config.circuit_breaker) {
+        info!("Reloaded circuit breaker");
+        state.set_circuit_breaker(build_circuit_breaker(config));
+    }
+    if section_changed(&previous.retry, &config.retry) {
+        info!("Reloaded retry executor");
+        state.set_retry_executor(build_retry_executor(config));
+    }
+    if section_changed(&previous.maintenance, &config.maintenance) {
+        let maintenance = config.maintenance.clone().unwrap_or_default();
+        info!(enabled = maintenance.enabled, "Reloaded maintenance mode");
+        state.set_maintenance(maintenance);
+    }
+
+    if section_changed(&previous.server, &config.server)
+        || section_changed(&previous.tls, &config.tls)
+        || section_changed(&previous.backend_tls, &config.backend_tls)
+        || section_changed(&previous.dns, &config.dns)
+        || section_changed(&previous.observability, &config.observability)
+    {
+        warn!("Server, TLS, DNS and observability changes take effect after a restart");
+    }
+
+    Ok(())
+}
+
+/// Apply every configuration update published by `reloadable` to `state`
+fn spawn_config_applier(reloadable: ReloadableConfig, state: ProxyState) {
+    let mut updates = reloadable.subscribe();
+
+    tokio::spawn(async move {
+        let mut applied = reloadable.read().await.clone();
+
+        while updates.changed().await.is_ok() {
+            let config = reloadable.read().await.clone();
+            match apply_config(&state, &applied, &config).await {
+                Ok(()) => applied = config,
+                Err(e) => error!("Failed to apply reloaded configuration: {}", e),
+            }
+        }
+    });
+}
+
 /// Initialize tracing/logging with optional OpenTelemetry support
 pub fn init_tracing(config: Option<&GatewayConfig>) -> Result<()> {
     // Check if OpenTelemetry is configured
@@ -186,5 +488,20 @@ pub fn init_tracing(config: Option<&GatewayConfig>) -> Result<()> {
             sample_rate: t.sample_rate,
         });
 
-    observability::init_tracing(otel_config)
+    let access_log_format = config
+        .and_then(|c| c.observability.as_ref())
+        .and_then(|o| o.access_log.as_ref())
+        .filter(|a| a.enabled)
+        .map(|a| a.format)
+        .unwrap_or_default();
+
+    // Propagation works without exporting spans, so it ignores `enabled`
+    let propagators = config
+        .and_then(|c| c.observability.as_ref())
+        .and_then(|o| o.tracing.as_ref())
+        .map(|t| t.propagators.clone())
+        .unwrap_or_else(config::default_propagators);
+    observability::install_propagator(&propagators);
+
+    observability::init_tracing(otel_config, access_log_format)
 }
@@ -1,10 +1,15 @@
+use crate::error::{GatewayError, Result};
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Backend server configuration
+///
+/// Written as `{url, weight}` or as a URL string with an optional weight
+/// suffix, e.g. `http://host:3000|weight=3`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "BackendConfigRepr")]
 pub struct BackendConfig {
     /// Backend URL
     pub url: String,
@@ -17,6 +22,56 @@ fn default_weight() -> u32 {
     1
 }
 
+/// Accepted configuration forms of a backend
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BackendConfigRepr {
+    Shorthand(String),
+    Full {
+        url: String,
+        #[serde(default = "default_weight")]
+        weight: u32,
+    },
+}
+
+impl TryFrom<BackendConfigRepr> for BackendConfig {
+    type Error = GatewayError;
+
+    fn try_from(repr: BackendConfigRepr) -> Result<Self> {
+        match repr {
+            BackendConfigRepr::Shorthand(value) => value.parse(),
+            BackendConfigRepr::Full { url, weight } => Ok(Self { url, weight }),
+        }
+    }
+}
+
+impl std::str::FromStr for BackendConfig {
+    type Err = GatewayError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let Some((url, options)) = s.split_once('|') else {
+            return Ok(Self {
+                url: s.to_string(),
+                weight: default_weight(),
+            });
+        };
+
+        let weight = options
+            .strip_prefix("weight=")
+            .and_then(|weight| weight.trim().parse().ok())
+            .ok_or_else(|| {
+                GatewayError::Config(format!(
+                    "Invalid backend: {} (expected <url> or <url>|weight=<n>)",
+                    s
+                ))
+            })?;
+        Ok(Self {
+            url: url.trim().to_string(),
+            weight,
+        })
+    }
+}
+
 /// Backend server state
 #[derive(Debug, Clone)]
 pub struct Backend {
@@ -92,6 +147,17 @@ impl Backend {
         self.active_connections.fetch_sub(1, Ordering::Relaxed);
     }
 
+    /// Count an in-flight request until the returned guard is dropped
+    ///
+    /// The count is released on every exit path, including errors and
+    /// cancelled requests, so removed backends drain reliably.
+    pub fn track_connection(&self) -> ConnectionGuard {
+        self.increment_connections();
+        ConnectionGuard {
+            active_connections: self.active_connections.clone(),
+        }
+    }
+
     /// Record a successful request
     pub fn record_success(&self) {
         self.health.total_successes.fetch_add(1, Ordering::Relaxed);
@@ -192,6 +258,18 @@ impl Backend {
     }
 }
 
+/// In-flight request on a backend, released when dropped
+#[derive(Debug)]
+pub struct ConnectionGuard {
+    active_connections: Arc<AtomicUsize>,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.active_connections.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
 /// Health statistics for a backend
 #[derive(Debug, Clone, Serialize)]
 pub struct HealthStats {
@@ -207,6 +285,53 @@ pub struct HealthStats {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_backend_config_shorthand() {
+        let backend: BackendConfig = "http://host:3000|weight=3".parse().unwrap();
+        assert_eq!(backend.url, "http://host:3000");
+        assert_eq!(backend.weight, 3);
+
+        // Without a suffix the default weight applies
+        let backend: BackendConfig = "http://host:3000".parse().unwrap();
+        assert_eq!(backend.url, "http://host:3000");
+        assert_eq!(backend.weight, 1);
+
+        for invalid in [
+            "http://host:3000|weight=",
+            "http://host:3000|weight=-1",
+            "http://host:3000|w=2",
+        ] {
+            assert!(invalid.parse::<BackendConfig>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_backend_config_forms() {
+        let backends: Vec<BackendConfig> = serde_yaml::from_str(
+            r#"
+- http://a:3000|weight=5
+- http://b:3000
+- url: http://c:3000
+  weight: 2
+- url: http://d:3000
+"#,
+        )
+        .unwrap();
+        let parsed: Vec<_> = backends
+            .iter()
+            .map(|b| (b.url.as_str(), b.weight))
+            .collect();
+        assert_eq!(
+            parsed,
+            vec![
+                ("http://a:3000", 5),
+                ("http://b:3000", 1),
+                ("http://c:3000", 2),
+                ("http://d:3000", 1)
+            ]
+        );
+    }
+
     #[test]
     fn test_backend_creation() {
         let config = BackendConfig {
@@ -241,6 +366,23 @@ mod tests {
         assert_eq!(backend.active_connections(), 1);
     }
 
+    #[test]
+    fn test_connection_guard_releases_on_drop() {
+        let backend = Backend::new(BackendConfig {
+            url: "http://localhost:3000".to_string(),
+            weight: 1,
+        });
+
+        let first = backend.track_connection();
+        let second = backend.track_connection();
+        assert_eq!(backend.active_connections(), 2);
+
+        drop(first);
+        assert_eq!(backend.active_connections(), 1);
+        drop(second);
+        assert_eq!(backend.active_connections(), 0);
+    }
+
     #[test]
     fn test_health_tracking() {
         let config = BackendConfig {
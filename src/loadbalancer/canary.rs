@@ -0,0 +1,280 @@
+//! Canary routing across two weighted backend groups
+//!
+//! A route's regular backends form the stable group; a `canary` section adds a
+//! second group that receives `canary_weight` percent of requests. The group is
+//! chosen per request, then the group's own load balancer picks a backend.
+//! With a `sticky` key, clients sending the same header or cookie value always
+//! land in the same group.
+
+use super::backend::{Backend, BackendConfig};
+use super::strategies::LoadBalancingStrategy;
+use super::LoadBalancer;
+use crate::error::{GatewayError, Result};
+use http::HeaderMap;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::sync::Arc;
+
+/// Canary backend group configuration for a route
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanaryConfig {
+    /// Backends of the canary group
+    pub backends: Vec<BackendConfig>,
+    /// Percentage of requests sent to the canary group (0-100)
+    pub canary_weight: f64,
+    /// Keep clients in one group by a request value: `header:<name>` or `cookie:<name>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sticky: Option<StickyKey>,
+}
+
+/// Request value that pins a client to a backend group
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum StickyKey {
+    /// Header with the given name
+    Header(String),
+    /// Cookie with the given name
+    Cookie(String),
+}
+
+impl StickyKey {
+    /// Extract the sticky value from request headers
+    fn value<'a>(&self, headers: &'a HeaderMap) -> Option<&'a str> {
+        match self {
+            StickyKey::Header(name) => headers
+                .get(name.as_str())
+                .and_then(|value| value.to_str().ok())
+                .filter(|value| !value.is_empty()),
+            StickyKey::Cookie(name) => headers
+                .get_all(http::header::COOKIE)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .flat_map(|cookies| cookies.split(';'))
+                .filter_map(|cookie| cookie.trim().split_once('='))
+                .find(|(key, value)| key == name && !value.is_empty())
+                .map(|(_, value)| value),
+        }
+    }
+}
+
+impl std::str::FromStr for StickyKey {
+    type Err = GatewayError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split_once(':') {
+            Some((kind, name)) if !name.is_empty() => match kind.to_lowercase().as_str() {
+                "header" => Ok(StickyKey::Header(name.to_string())),
+                "cookie" => Ok(StickyKey::Cookie(name.to_string())),
+                _ => Err(GatewayError::Config(format!(
+                    "canary.sticky: Invalid sticky key: {}",
+                    s
+                ))),
+            },
+            _ => Err(GatewayError::Config(format!(
+                "canary.sticky: Invalid sticky key: {} (expected header:<name> or cookie:<name>)",
+                s
+            ))),
+        }
+    }
+}
+
+impl TryFrom<String> for StickyKey {
+    type Error = GatewayError;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl From<StickyKey> for String {
+    fn from(key: StickyKey) -> Self {
+        match key {
+            StickyKey::Header(name) => format!("header:{}", name),
+            StickyKey::Cookie(name) => format!("cookie:{}", name),
+        }
+    }
+}
+
+/// Canary backend group and the share of traffic it receives
+#[derive(Debug)]
+pub struct CanaryGroup {
+    /// Load balancer over the canary backends
+    load_balancer: Arc<LoadBalancer>,
+    /// Percentage of requests sent to the canary group
+    weight: f64,
+    /// Request value pinning clients to a group
+    sticky: Option<StickyKey>,
+}
+
+impl CanaryGroup {
+    /// Create a canary group balancing its backends with `strategy`
+    pub fn new(config: CanaryConfig, strategy: LoadBalancingStrategy) -> Result<Self> {
+        if config.backends.is_empty() {
+            return Err(GatewayError::Config(
+                "canary.backends: At least one canary backend is required".to_string(),
+            ));
+        }
+        for (i, backend) in config.backends.iter().enumerate() {
+            if !backend.url.starts_with("http://") && !backend.url.starts_with("https://") {
+                return Err(GatewayError::Config(format!(
+                    "canary.backends[{}].url: Backend URL must start with http:// or https://",
+                    i
+                )));
+            }
+        }
+        if !(0.0..=100.0).contains(&config.canary_weight) {
+            return Err(GatewayError::Config(format!(
+                "canary.canary_weight: Must be between 0 and 100, got {}",
+                config.canary_weight
+            )));
+        }
+
+        Ok(Self {
+            load_balancer: Arc::new(LoadBalancer::new(config.backends, strategy)),
+            weight: config.canary_weight,
+            sticky: config.sticky,
+        })
+    }
+
+    /// Load balancer over the canary backends
+    pub fn load_balancer(&self) -> &Arc<LoadBalancer> {
+        &self.load_balancer
+    }
+
+    /// Percentage of requests sent to the canary group
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+
+    /// Decide whether a request goes to the canary group
+    ///
+    /// Requests carrying the sticky value are bucketed by its hash, so the
+    /// decision is stable across requests and gateway replicas; all others
+    /// are sampled at random.
+    pub fn is_selected(&self, headers: &HeaderMap) -> bool {
+        if self.weight >= 100.0 {
+            return true;
+        }
+        let sample = match self.sticky.as_ref().and_then(|key| key.value(headers)) {
+            Some(value) => (fnv1a(value.as_bytes()) % 10_000) as f64 / 100.0,
+            None => rand::random::<f64>() * 100.0,
+        };
+        sample < self.weight
+    }
+
+    /// Select a canary backend, or `None` if the request belongs to the stable
+    /// group or no canary backend is healthy
+    pub fn select_backend(
+        &self,
+        client_ip: Option<IpAddr>,
+        headers: &HeaderMap,
+    ) -> Option<Arc<Backend>> {
+        if !self.is_selected(headers) {
+            return None;
+        }
+        self.load_balancer.select_backend(client_ip)
+    }
+}
+
+/// 64-bit FNV-1a hash, stable across processes unlike `DefaultHasher`
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loadbalancer::strategies::RoundRobinStrategy;
+    use http::HeaderValue;
+
+    fn canary(weight: f64, sticky: Option<&str>) -> Result<CanaryGroup> {
+        CanaryGroup::new(
+            CanaryConfig {
+                backends: vec![BackendConfig {
+                    url: "http://canary:8080".to_string(),
+                    weight: 1,
+                }],
+                canary_weight: weight,
+                sticky: sticky.map(|key| key.parse().unwrap()),
+            },
+            LoadBalancingStrategy::RoundRobin(RoundRobinStrategy::new()),
+        )
+    }
+
+    #[test]
+    fn test_weight_bounds() {
+        let headers = HeaderMap::new();
+        let all = canary(100.0, None).unwrap();
+        assert!((0..1000).all(|_| all.is_selected(&headers)));
+
+        let none = canary(0.0, None).unwrap();
+        assert!((0..1000).all(|_| !none.is_selected(&headers)));
+    }
+
+    #[test]
+    fn test_random_split() {
+        let group = canary(20.0, None).unwrap();
+        let headers = HeaderMap::new();
+        let selected = (0..10_000).filter(|_| group.is_selected(&headers)).count();
+        assert!((1700..=2300).contains(&selected), "selected {}", selected);
+    }
+
+    #[test]
+    fn test_sticky_header() {
+        let group = canary(30.0, Some("header:X-User-Id")).unwrap();
+
+        let mut selected = 0;
+        for user in 0..5_000 {
+            let mut headers = HeaderMap::new();
+            headers.insert("x-user-id", HeaderValue::from(user));
+            let first = group.is_selected(&headers);
+            assert!((0..5).all(|_| group.is_selected(&headers) == first));
+            selected += usize::from(first);
+        }
+        assert!((1250..=1750).contains(&selected), "selected {}", selected);
+    }
+
+    #[test]
+    fn test_sticky_cookie() {
+        let group = canary(50.0, Some("cookie:session")).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            http::header::COOKIE,
+            HeaderValue::from_static("theme=dark; session=abc123"),
+        );
+        let first = group.is_selected(&headers);
+        assert!((0..100).all(|_| group.is_selected(&headers) == first));
+    }
+
+    #[test]
+    fn test_unhealthy_canary_yields_none() {
+        let group = canary(100.0, None).unwrap();
+        let headers = HeaderMap::new();
+        assert!(group.select_backend(None, &headers).is_some());
+
+        group.load_balancer().backends()[0].mark_unhealthy();
+        assert!(group.select_backend(None, &headers).is_none());
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let err = canary(120.0, None).unwrap_err();
+        assert!(err.to_string().contains("canary.canary_weight"));
+
+        assert!("query:user".parse::<StickyKey>().is_err());
+        assert!("header:".parse::<StickyKey>().is_err());
+
+        let err = CanaryGroup::new(
+            CanaryConfig {
+                backends: vec![],
+                canary_weight: 10.0,
+                sticky: None,
+            },
+            LoadBalancingStrategy::LeastConnections,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("canary.backends"));
+    }
+}
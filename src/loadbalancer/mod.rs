@@ -1,16 +1,21 @@
 pub mod backend;
+pub mod canary;
 pub mod strategies;
 
+use arc_swap::ArcSwap;
 use backend::{Backend, BackendConfig};
 use std::net::IpAddr;
 use std::sync::Arc;
 use strategies::LoadBalancingStrategy;
 
 /// Load balancer for distributing requests across multiple backends
-#[derive(Debug, Clone)]
+///
+/// Backends can be added and removed at runtime; selection works on a
+/// snapshot of the backend list, so it never blocks on updates.
+#[derive(Debug)]
 pub struct LoadBalancer {
     /// Available backends
-    backends: Vec<Arc<Backend>>,
+    backends: ArcSwap<Vec<Arc<Backend>>>,
     /// Load balancing strategy
     strategy: LoadBalancingStrategy,
 }
@@ -23,32 +28,78 @@ impl LoadBalancer {
             .map(|config| Arc::new(Backend::new(config)))
             .collect();
 
-        Self { backends, strategy }
+        Self {
+            backends: ArcSwap::from_pointee(backends),
+            strategy,
+        }
     }
 
     /// Select a backend for the request
     pub fn select_backend(&self, client_ip: Option<IpAddr>) -> Option<Arc<Backend>> {
-        self.strategy.select(&self.backends, client_ip).cloned()
+        self.strategy
+            .select(&self.backends.load(), client_ip)
+            .cloned()
+    }
+
+    /// Get a snapshot of all backends
+    pub fn backends(&self) -> Arc<Vec<Arc<Backend>>> {
+        self.backends.load_full()
     }
 
-    /// Get all backends
-    pub fn backends(&self) -> &[Arc<Backend>] {
-        &self.backends
+    /// Add a backend, returning it unless a backend with the same URL already exists
+    pub fn add_backend(&self, config: BackendConfig) -> Option<Arc<Backend>> {
+        let backend = Arc::new(Backend::new(config));
+        let mut added = false;
+
+        self.backends.rcu(|backends| {
+            added = !backends.iter().any(|b| b.url() == backend.url());
+            let mut updated = Vec::clone(backends);
+            if added {
+                updated.push(backend.clone());
+            }
+            updated
+        });
+
+        added.then_some(backend)
+    }
+
+    /// Remove the backend with the given URL so it is no longer selected
+    ///
+    /// Requests already sent to the backend keep their reference and release
+    /// their connection count when they finish, so the returned backend's
+    /// `active_connections` drains to zero.
+    pub fn remove_backend(&self, url: &str) -> Option<Arc<Backend>> {
+        let mut removed = None;
+
+        self.backends.rcu(|backends| {
+            let mut updated = Vec::clone(backends);
+            removed = updated
+                .iter()
+                .position(|b| b.url() == url)
+                .map(|index| updated.remove(index));
+            updated
+        });
+
+        removed
     }
 
     /// Get healthy backend count
     pub fn healthy_count(&self) -> usize {
-        self.backends.iter().filter(|b| b.is_healthy()).count()
+        self.backends
+            .load()
+            .iter()
+            .filter(|b| b.is_healthy())
+            .count()
     }
 
     /// Get total backend count
     pub fn total_count(&self) -> usize {
-        self.backends.len()
+        self.backends.load().len()
     }
 
     /// Check if any backend is healthy
     pub fn has_healthy_backend(&self) -> bool {
-        self.backends.iter().any(|b| b.is_healthy())
+        self.backends.load().iter().any(|b| b.is_healthy())
     }
 }
 
@@ -158,7 +209,7 @@ mod tests {
         let lb = LoadBalancer::new(configs, strategy);
 
         // Mark all backends as unhealthy
-        for backend in lb.backends() {
+        for backend in lb.backends().iter() {
             backend.mark_unhealthy();
         }
 
@@ -184,4 +235,119 @@ mod tests {
         let backend = lb.select_backend(None).unwrap();
         assert!(backend.url() != "http://backend-0");
     }
+
+    #[test]
+    fn test_add_and_remove_backend() {
+        let strategy = LoadBalancingStrategy::RoundRobin(RoundRobinStrategy::new());
+        let lb = LoadBalancer::new(create_test_configs(2), strategy);
+
+        let added = lb.add_backend(BackendConfig {
+            url: "http://backend-2".to_string(),
+            weight: 1,
+        });
+        assert_eq!(added.unwrap().url(), "http://backend-2");
+        assert_eq!(lb.total_count(), 3);
+
+        // Duplicate URLs are rejected
+        let duplicate = lb.add_backend(BackendConfig {
+            url: "http://backend-0".to_string(),
+            weight: 5,
+        });
+        assert!(duplicate.is_none());
+        assert_eq!(lb.total_count(), 3);
+
+        let removed = lb.remove_backend("http://backend-0").unwrap();
+        assert_eq!(removed.url(), "http://backend-0");
+        assert!(lb.remove_backend("http://backend-0").is_none());
+        for _ in 0..10 {
+            assert_ne!(lb.select_backend(None).unwrap().url(), "http://backend-0");
+        }
+    }
+
+    #[test]
+    fn test_removed_backend_drains_connections() {
+        let strategy = LoadBalancingStrategy::RoundRobin(RoundRobinStrategy::new());
+        let lb = LoadBalancer::new(create_test_configs(2), strategy);
+
+        let backend = lb.select_backend(None).unwrap();
+        let in_flight = backend.track_connection();
+
+        let removed = lb.remove_backend(backend.url()).unwrap();
+        assert_eq!(removed.active_connections(), 1);
+
+        drop(in_flight);
+        assert_eq!(removed.active_connections(), 0);
+    }
+
+    #[test]
+    fn test_concurrent_adds_are_not_lost() {
+        let strategy = LoadBalancingStrategy::RoundRobin(RoundRobinStrategy::new());
+        let lb = Arc::new(LoadBalancer::new(vec![], strategy));
+
+        let handles: Vec<_> = (0..8)
+            .map(|thread| {
+                let lb = lb.clone();
+                std::thread::spawn(move || {
+                    for i in 0..50 {
+                        lb.add_backend(BackendConfig {
+                            url: format!("http://backend-{}-{}", thread, i),
+                            weight: 1,
+                        });
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(lb.total_count(), 400);
+    }
+
+    #[test]
+    fn test_selection_during_concurrent_updates() {
+        let strategy = LoadBalancingStrategy::LeastConnections;
+        let lb = Arc::new(LoadBalancer::new(create_test_configs(1), strategy));
+        let done = Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+        let selectors: Vec<_> = (0..4)
+            .map(|_| {
+                let lb = lb.clone();
+                let done = done.clone();
+                std::thread::spawn(move || {
+                    let mut selected = 0;
+                    loop {
+                        // backend-0 is never removed, so selection always succeeds
+                        let backend = lb.select_backend(None).unwrap();
+                        let _in_flight = backend.track_connection();
+                        selected += 1;
+                        if done.load(std::sync::atomic::Ordering::Relaxed) {
+                            return selected;
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        let mut churned = Vec::new();
+        for i in 0..200 {
+            let url = format!("http://dynamic-{}", i);
+            churned.push(
+                lb.add_backend(BackendConfig {
+                    url: url.clone(),
+                    weight: 1,
+                })
+                .unwrap(),
+            );
+            lb.remove_backend(&url).unwrap();
+        }
+        done.store(true, std::sync::atomic::Ordering::Relaxed);
+
+        for selector in selectors {
+            assert!(selector.join().unwrap() > 0);
+        }
+        assert_eq!(lb.total_count(), 1);
+        assert!(churned.iter().all(|b| b.active_connections() == 0));
+        assert_eq!(lb.backends()[0].active_connections(), 0);
+    }
 }
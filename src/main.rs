@@ -1,4 +1,4 @@
-use gateway::{config::GatewayConfig, init_gateway, init_tracing};
+use gateway::{config::GatewayConfig, init_gateway_with_reload, init_tracing};
 use std::env;
 use std::process;
 
@@ -26,7 +26,7 @@ async fn main() {
     }
 
     // Start the gateway
-    if let Err(e) = init_gateway(config).await {
+    if let Err(e) = init_gateway_with_reload(config, &config_path).await {
         eprintln!("Gateway error: {}", e);
         process::exit(1);
     }
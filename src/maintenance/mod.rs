@@ -0,0 +1,186 @@
+use crate::error::{GatewayError, Result};
+use axum::{
+    body::Body,
+    http::{header, HeaderValue, Response, StatusCode},
+};
+use serde::{Deserialize, Serialize};
+
+/// Maintenance mode configuration
+///
+/// While enabled, proxied requests are answered with a fixed response instead
+/// of being forwarded. The health and admin endpoints are served outside the
+/// proxy and stay up.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaintenanceConfig {
+    /// Answer matching requests with the maintenance response
+    #[serde(default)]
+    pub enabled: bool,
+    /// Status code of the maintenance response
+    #[serde(default = "default_status")]
+    pub status: u16,
+    /// Body of the maintenance response
+    #[serde(default = "default_body")]
+    pub body: String,
+    /// Content type of the maintenance response body
+    #[serde(default = "default_content_type")]
+    pub content_type: String,
+    /// Value of the Retry-After header, in seconds
+    #[serde(default)]
+    pub retry_after_secs: Option<u64>,
+    /// Path prefixes put under maintenance (all paths if empty)
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Path prefixes that stay live during maintenance
+    #[serde(default)]
+    pub allow_paths: Vec<String>,
+}
+
+fn default_status() -> u16 {
+    503
+}
+
+fn default_body() -> String {
+    r#"{"error":"Service is down for maintenance, please try again later","status":503}"#
+        .to_string()
+}
+
+fn default_content_type() -> String {
+    "application/json".to_string()
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            status: default_status(),
+            body: default_body(),
+            content_type: default_content_type(),
+            retry_after_secs: None,
+            paths: Vec::new(),
+            allow_paths: Vec::new(),
+        }
+    }
+}
+
+impl MaintenanceConfig {
+    pub fn validate(&self) -> Result<()> {
+        if StatusCode::from_u16(self.status).is_err() {
+            return Err(GatewayError::Config(format!(
+                "maintenance.status: Invalid status code: {}",
+                self.status
+            )));
+        }
+        if HeaderValue::from_str(&self.content_type).is_err() {
+            return Err(GatewayError::Config(format!(
+                "maintenance.content_type: Invalid content type: {}",
+                self.content_type
+            )));
+        }
+        for (field, paths) in [("paths", &self.paths), ("allow_paths", &self.allow_paths)] {
+            if let Some(path) = paths.iter().find(|p| !p.starts_with('/')) {
+                return Err(GatewayError::Config(format!(
+                    "maintenance.{}: Path must start with '/': {}",
+                    field, path
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether requests for `path` get the maintenance response
+    pub fn applies_to(&self, path: &str) -> bool {
+        self.enabled
+            && (self.paths.is_empty() || self.paths.iter().any(|p| has_prefix(path, p)))
+            && !self.allow_paths.iter().any(|p| has_prefix(path, p))
+    }
+
+    /// Build the maintenance response
+    pub fn response(&self) -> Response<Body> {
+        let mut response = Response::new(Body::from(self.body.clone()));
+        *response.status_mut() =
+            StatusCode::from_u16(self.status).unwrap_or(StatusCode::SERVICE_UNAVAILABLE);
+        if let Ok(content_type) = HeaderValue::from_str(&self.content_type) {
+            response
+                .headers_mut()
+                .insert(header::CONTENT_TYPE, content_type);
+        }
+        if let Some(retry_after) = self.retry_after_secs {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+        }
+        response
+    }
+}
+
+/// Whether `path` is `prefix` or lies below it
+fn has_prefix(path: &str, prefix: &str) -> bool {
+    match path.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/') || prefix.ends_with('/'),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn maintenance(paths: &[&str], allow_paths: &[&str]) -> MaintenanceConfig {
+        MaintenanceConfig {
+            enabled: true,
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+            allow_paths: allow_paths.iter().map(|p| p.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_disabled_applies_to_nothing() {
+        let config = MaintenanceConfig::default();
+        assert!(!config.applies_to("/api/users"));
+    }
+
+    #[test]
+    fn test_applies_to_all_paths_by_default() {
+        let config = maintenance(&[], &["/api/status"]);
+        assert!(config.applies_to("/api/users"));
+        assert!(config.applies_to("/"));
+        assert!(!config.applies_to("/api/status"));
+        assert!(!config.applies_to("/api/status/db"));
+        assert!(config.applies_to("/api/statuses"));
+    }
+
+    #[test]
+    fn test_applies_to_selected_paths() {
+        let config = maintenance(&["/api/orders"], &["/api/orders/health"]);
+        assert!(config.applies_to("/api/orders"));
+        assert!(config.applies_to("/api/orders/42"));
+        assert!(!config.applies_to("/api/orders/health"));
+        assert!(!config.applies_to("/api/users"));
+    }
+
+    #[test]
+    fn test_response() {
+        let config = MaintenanceConfig {
+            retry_after_secs: Some(120),
+            ..maintenance(&[], &[])
+        };
+        let response = config.response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(response.headers()[header::RETRY_AFTER], "120");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(MaintenanceConfig::default().validate().is_ok());
+        assert!(MaintenanceConfig {
+            status: 1000,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+        assert!(maintenance(&["api"], &[]).validate().is_err());
+        assert!(maintenance(&[], &["health"]).validate().is_err());
+    }
+}
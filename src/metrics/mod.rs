@@ -1,4 +1,6 @@
+use crate::config::StatsdConfig;
 use crate::error::{GatewayError, Result};
+use crate::loadbalancer::backend::HealthStats;
 use axum::{
     body::Body,
     extract::State,
@@ -6,9 +8,10 @@ use axum::{
     response::IntoResponse,
 };
 use metrics::{counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram};
-use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use metrics_exporter_statsd::StatsdBuilder;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tracing::{debug, info};
 
 /// Metrics service for collecting and exposing Prometheus metrics
@@ -20,7 +23,13 @@ pub struct MetricsService {
 impl MetricsService {
     /// Create a new metrics service
     pub fn new() -> Result<Self> {
-        let handle = PrometheusBuilder::new().install_recorder().map_err(|e| {
+        Self::with_duration_buckets(None)
+    }
+
+    /// Create a new metrics service, using `buckets` (in seconds) for the
+    /// request and backend duration histograms
+    pub fn with_duration_buckets(buckets: Option<&[f64]>) -> Result<Self> {
+        let handle = Self::builder(buckets)?.install_recorder().map_err(|e| {
             GatewayError::Internal(format!("Failed to install metrics recorder: {}", e))
         })?;
 
@@ -34,6 +43,49 @@ impl MetricsService {
         })
     }
 
+    /// Prometheus exporter builder with the duration histogram buckets applied
+    fn builder(buckets: Option<&[f64]>) -> Result<PrometheusBuilder> {
+        let mut builder = PrometheusBuilder::new();
+        if let Some(buckets) = buckets {
+            for metric in [
+                "gateway_request_duration_seconds",
+                "gateway_backend_duration_seconds",
+            ] {
+                builder = builder
+                    .set_buckets_for_metric(Matcher::Full(metric.to_string()), buckets)
+                    .map_err(|e| {
+                        GatewayError::Config(format!(
+                            "observability.metrics.duration_buckets: {}",
+                            e
+                        ))
+                    })?;
+            }
+        }
+        Ok(builder)
+    }
+
+    /// Install a recorder pushing metrics to a StatsD agent over UDP
+    ///
+    /// There is nothing to scrape, so no service is returned; the `record_*`
+    /// functions report through whichever recorder is installed.
+    pub fn install_statsd(config: &StatsdConfig) -> Result<()> {
+        let recorder = StatsdBuilder::from(config.host.as_str(), config.port)
+            .build(config.prefix.as_deref())
+            .map_err(|e| GatewayError::Config(format!("observability.metrics.statsd: {}", e)))?;
+        metrics::set_global_recorder(recorder).map_err(|e| {
+            GatewayError::Internal(format!("Failed to install metrics recorder: {}", e))
+        })?;
+
+        Self::register_metrics();
+
+        info!(
+            host = %config.host,
+            port = config.port,
+            "StatsD metrics exporter initialized successfully"
+        );
+        Ok(())
+    }
+
     /// Register all metrics with descriptions
     fn register_metrics() {
         // Request metrics
@@ -49,6 +101,18 @@ impl MetricsService {
             "gateway_requests_errors_total",
             "Total number of HTTP requests that resulted in errors"
         );
+        describe_histogram!(
+            "gateway_request_size_bytes",
+            "HTTP request body sizes in bytes"
+        );
+        describe_histogram!(
+            "gateway_response_size_bytes",
+            "HTTP response body sizes in bytes"
+        );
+        describe_counter!(
+            "gateway_client_disconnects_total",
+            "Total number of requests abandoned by the client before the response was ready"
+        );
 
         // Backend metrics
         describe_counter!(
@@ -61,12 +125,24 @@ impl MetricsService {
         );
         describe_histogram!(
             "gateway_backend_duration_seconds",
-            "Backend request latencies in seconds"
+            "Time spent waiting on backends in seconds, including retries"
         );
         describe_gauge!(
             "gateway_backend_healthy",
             "Backend health status (1 = healthy, 0 = unhealthy)"
         );
+        describe_counter!(
+            "gateway_backend_total_successes",
+            "Total successful requests observed by backend health tracking"
+        );
+        describe_counter!(
+            "gateway_backend_total_failures",
+            "Total failed requests observed by backend health tracking"
+        );
+        describe_gauge!(
+            "gateway_backend_consecutive_failures",
+            "Current run of consecutive failures for a backend"
+        );
 
         // Circuit breaker metrics
         describe_gauge!(
@@ -93,11 +169,51 @@ impl MetricsService {
             "gateway_auth_failures_total",
             "Total number of authentication failures"
         );
+        describe_counter!(
+            "gateway_jwks_fetch_total",
+            "Total number of JWKS key set fetches, by result"
+        );
+
+        // Cache metrics
+        describe_counter!(
+            "gateway_cache_hits_total",
+            "Total number of requests served from the response cache (fresh or stale)"
+        );
+        describe_counter!(
+            "gateway_cache_misses_total",
+            "Total number of cache lookups that went to the backend"
+        );
+        describe_counter!(
+            "gateway_cache_refreshes_total",
+            "Total number of background refreshes of stale cache entries, by backend status"
+        );
+        describe_gauge!(
+            "gateway_cache_entries",
+            "Number of entries in a route's response cache"
+        );
+        describe_gauge!(
+            "gateway_cache_weighted_size",
+            "Weighted size of a route's response cache, reported periodically"
+        );
+
+        // Mirror metrics
+        describe_counter!(
+            "gateway_mirror_dropped_total",
+            "Total number of mirrored requests dropped because the mirror was saturated"
+        );
 
         // Rate limiting metrics
         describe_counter!(
             "gateway_rate_limit_exceeded_total",
-            "Total number of requests rejected due to rate limiting"
+            "Total number of requests rejected due to rate limiting, by limit dimension"
+        );
+        describe_counter!(
+            "gateway_rate_limit_fallback_total",
+            "Total number of rate limit checks served locally after a Redis error"
+        );
+        describe_counter!(
+            "gateway_rate_limit_exempt_total",
+            "Total number of requests that skipped rate limiting via an exemption"
         );
 
         debug!("All metrics registered with descriptions");
@@ -140,6 +256,34 @@ pub fn record_request(method: &str, path: &str, status: u16, duration: f64) {
     }
 }
 
+/// Record a request abandoned by its client
+pub fn record_client_disconnect(method: &str, path: &str) {
+    let labels = [
+        ("method", method.to_string()),
+        ("path", sanitize_path(path)),
+    ];
+    counter!("gateway_client_disconnects_total", &labels).increment(1);
+}
+
+/// Record the size of a request body
+pub fn record_request_size(method: &str, path: &str, bytes: u64) {
+    let labels = [
+        ("method", method.to_string()),
+        ("path", sanitize_path(path)),
+    ];
+    histogram!("gateway_request_size_bytes", &labels).record(bytes as f64);
+}
+
+/// Record the size of a response body
+pub fn record_response_size(method: &str, path: &str, status: u16, bytes: u64) {
+    let labels = [
+        ("method", method.to_string()),
+        ("path", sanitize_path(path)),
+        ("status", status.to_string()),
+    ];
+    histogram!("gateway_response_size_bytes", &labels).record(bytes as f64);
+}
+
 /// Record a backend request metric
 pub fn record_backend_request(backend: &str, method: &str, status: u16, duration: f64) {
     let labels = [
@@ -162,6 +306,17 @@ pub fn record_backend_health(backend: &str, healthy: bool) {
     gauge!("gateway_backend_healthy", &labels).set(if healthy { 1.0 } else { 0.0 });
 }
 
+/// Record a backend's health statistics
+///
+/// The totals are already cumulative, so the counters are set to them rather
+/// than incremented.
+pub fn record_backend_stats(backend: &str, stats: &HealthStats) {
+    let labels = [("backend", backend.to_string())];
+    counter!("gateway_backend_total_successes", &labels).absolute(stats.total_successes);
+    counter!("gateway_backend_total_failures", &labels).absolute(stats.total_failures);
+    gauge!("gateway_backend_consecutive_failures", &labels).set(stats.consecutive_failures as f64);
+}
+
 /// Record circuit breaker state
 /// State: 0 = Closed, 1 = Open, 2 = HalfOpen
 pub fn record_circuit_breaker_state(backend: &str, state: u8) {
@@ -195,15 +350,73 @@ pub fn record_auth_attempt(method: &str, success: bool) {
     }
 }
 
-/// Record rate limit exceeded
-pub fn record_rate_limit_exceeded(identifier: &str, route: &str) {
+/// Record a JWKS key set fetch
+pub fn record_jwks_fetch(url: &str, success: bool) {
+    let result = if success { "success" } else { "failure" };
+    let labels = [("url", url.to_string()), ("result", result.to_string())];
+    counter!("gateway_jwks_fetch_total", &labels).increment(1);
+}
+
+/// Record a response served from the cache
+pub fn record_cache_hit(route: &str) {
+    let labels = [("route", route.to_string())];
+    counter!("gateway_cache_hits_total", &labels).increment(1);
+}
+
+/// Record a cache lookup that missed
+pub fn record_cache_miss(route: &str) {
+    let labels = [("route", route.to_string())];
+    counter!("gateway_cache_misses_total", &labels).increment(1);
+}
+
+/// Record a background refresh of a stale cache entry by the backend status
+pub fn record_cache_refresh(route: &str, status: u16) {
+    let labels = [("route", route.to_string()), ("status", status.to_string())];
+    counter!("gateway_cache_refreshes_total", &labels).increment(1);
+}
+
+/// Record a mirrored request dropped because the mirror was saturated
+pub fn record_mirror_dropped(route: &str) {
+    let labels = [("route", route.to_string())];
+    counter!("gateway_mirror_dropped_total", &labels).increment(1);
+}
+
+/// Record the number of entries in a route's cache
+pub fn record_cache_entries(route: &str, count: u64) {
+    let labels = [("route", route.to_string())];
+    gauge!("gateway_cache_entries", &labels).set(count as f64);
+}
+
+/// Record the periodically reported statistics of a route's cache
+pub fn record_cache_stats(route: &str, entry_count: u64, weighted_size: u64) {
+    record_cache_entries(route, entry_count);
+    let labels = [("route", route.to_string())];
+    gauge!("gateway_cache_weighted_size", &labels).set(weighted_size as f64);
+}
+
+/// Record a request rejected by the limit on `dimension` (e.g. `ip`, `api_key`)
+///
+/// The client identifier is not a label: it may be an API key, and IPs,
+/// users and header values would make the series count unbounded.
+pub fn record_rate_limit_exceeded(route: &str, dimension: &str) {
     let labels = [
-        ("identifier", identifier.to_string()),
-        ("route", route.to_string()),
+        ("route", sanitize_path(route)),
+        ("dimension", dimension.to_string()),
     ];
     counter!("gateway_rate_limit_exceeded_total", &labels).increment(1);
 }
 
+/// Record a rate limit check that fell back to the local limiter
+pub fn record_rate_limit_fallback() {
+    counter!("gateway_rate_limit_fallback_total").increment(1);
+}
+
+/// Record a request that skipped rate limiting, by what exempted it (`ip` or `api_key`)
+pub fn record_rate_limit_exempt(reason: &str) {
+    let labels = [("reason", reason.to_string())];
+    counter!("gateway_rate_limit_exempt_total", &labels).increment(1);
+}
+
 /// Sanitize path for metrics to avoid cardinality explosion
 /// Replaces path parameters with placeholders
 fn sanitize_path(path: &str) -> String {
@@ -254,11 +467,17 @@ fn is_likely_id(segment: &str) -> bool {
 }
 
 /// Timer for measuring request duration
+///
+/// The request duration runs from creation to `record`; the backend duration
+/// only covers the time spent waiting on the backend, as reported through
+/// `set_backend_duration`.
 pub struct Timer {
     start: Instant,
     method: String,
     path: String,
     backend: Option<String>,
+    backend_duration: Option<Duration>,
+    cache_refresh: bool,
 }
 
 impl Timer {
@@ -269,6 +488,8 @@ impl Timer {
             method,
             path,
             backend: None,
+            backend_duration: None,
+            cache_refresh: false,
         }
     }
 
@@ -277,13 +498,33 @@ impl Timer {
         self.backend = Some(backend);
     }
 
+    /// Set how long the backend took to respond, including retries
+    pub fn set_backend_duration(&mut self, duration: Duration) {
+        self.backend_duration = Some(duration);
+    }
+
+    /// Mark the request as a background cache refresh issued by the gateway
+    pub fn set_cache_refresh(&mut self) {
+        self.cache_refresh = true;
+    }
+
     /// Record the elapsed time with the given status code
+    ///
+    /// Backend metrics are only recorded once the backend was called. Cache
+    /// refreshes are not client requests and only record backend metrics.
     pub fn record(self, status: u16) {
-        let duration = self.start.elapsed().as_secs_f64();
-        record_request(&self.method, &self.path, status, duration);
+        if !self.cache_refresh {
+            let duration = self.start.elapsed().as_secs_f64();
+            record_request(&self.method, &self.path, status, duration);
+        }
 
-        if let Some(backend) = &self.backend {
-            record_backend_request(backend, &self.method, status, duration);
+        if let (Some(backend), Some(backend_duration)) = (&self.backend, self.backend_duration) {
+            record_backend_request(
+                backend,
+                &self.method,
+                status,
+                backend_duration.as_secs_f64(),
+            );
         }
     }
 
@@ -336,6 +577,57 @@ mod tests {
         assert_eq!(timer.backend, Some("http://backend:3000".to_string()));
     }
 
+    #[test]
+    fn test_timer_records_backend_duration_only_when_called() {
+        let recorder = MetricsService::builder(None).unwrap().build_recorder();
+        let handle = recorder.handle();
+
+        metrics::with_local_recorder(&recorder, || {
+            // Rejected before the backend was called
+            let mut timer = Timer::new("GET".to_string(), "/api/data".to_string());
+            timer.set_backend("http://backend:3000".to_string());
+            timer.record(503);
+
+            let mut timer = Timer::new("GET".to_string(), "/api/data".to_string());
+            timer.set_backend("http://backend:3000".to_string());
+            timer.set_backend_duration(Duration::from_millis(250));
+            timer.record(200);
+        });
+
+        let rendered = handle.render();
+        assert!(rendered.contains(
+            r#"gateway_backend_duration_seconds_sum{backend="http://backend:3000",method="GET",status="200"} 0.25"#
+        ));
+        assert!(rendered
+            .contains(r#"gateway_requests_total{method="GET",path="/api/data",status="503"} 1"#));
+        assert!(!rendered
+            .lines()
+            .any(|line| line.starts_with("gateway_backend") && line.contains(r#"status="503""#)));
+    }
+
+    #[test]
+    fn test_timer_skips_request_metrics_for_cache_refresh() {
+        let recorder = MetricsService::builder(None).unwrap().build_recorder();
+        let handle = recorder.handle();
+
+        metrics::with_local_recorder(&recorder, || {
+            let mut timer = Timer::new("GET".to_string(), "/api/data".to_string());
+            timer.set_backend("http://backend:3000".to_string());
+            timer.set_backend_duration(Duration::from_millis(10));
+            timer.set_cache_refresh();
+            timer.record(200);
+            record_cache_refresh("/api/*path", 200);
+        });
+
+        let rendered = handle.render();
+        assert!(!rendered.contains("gateway_requests_total"));
+        assert!(rendered.contains(
+            r#"gateway_backend_requests_total{backend="http://backend:3000",method="GET",status="200"} 1"#
+        ));
+        assert!(rendered
+            .contains(r#"gateway_cache_refreshes_total{route="/api/*path",status="200"} 1"#));
+    }
+
     #[tokio::test]
     async fn test_metrics_service_creation() {
         // This test may fail if metrics recorder is already installed
@@ -355,6 +647,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_duration_buckets_rendered() {
+        let recorder = MetricsService::builder(Some(&[0.001, 0.005, 0.01]))
+            .unwrap()
+            .build_recorder();
+        let handle = recorder.handle();
+
+        metrics::with_local_recorder(&recorder, || {
+            record_request("GET", "/api/test", 200, 0.003);
+            record_backend_request("http://backend:3000", "GET", 200, 0.002);
+        });
+
+        let rendered = handle.render();
+        for metric in [
+            "gateway_request_duration_seconds_bucket",
+            "gateway_backend_duration_seconds_bucket",
+        ] {
+            let boundaries: Vec<&str> = rendered
+                .lines()
+                .filter(|line| line.starts_with(metric))
+                .filter_map(|line| line.split("le=\"").nth(1)?.split('"').next())
+                .collect();
+            assert_eq!(boundaries, ["0.001", "0.005", "0.01", "+Inf"], "{rendered}");
+        }
+    }
+
+    #[test]
+    fn test_record_size_functions() {
+        let recorder = MetricsService::builder(None).unwrap().build_recorder();
+        let handle = recorder.handle();
+
+        metrics::with_local_recorder(&recorder, || {
+            record_request_size("POST", "/api/users/12345", 0);
+            record_request_size("POST", "/api/users/12345", 2_048);
+            record_response_size("POST", "/api/users/12345", 201, 512);
+            record_response_size("GET", "/api/files", 200, 50 * 1024 * 1024);
+        });
+
+        let rendered = handle.render();
+        assert!(rendered.contains(
+            r#"gateway_request_size_bytes_count{method="POST",path="/api/users/:id"} 2"#
+        ));
+        assert!(rendered.contains(
+            r#"gateway_request_size_bytes_sum{method="POST",path="/api/users/:id"} 2048"#
+        ));
+        assert!(rendered.contains(
+            r#"gateway_response_size_bytes_sum{method="GET",path="/api/files",status="200"} 52428800"#
+        ));
+    }
+
     #[test]
     fn test_record_functions_dont_panic() {
         // These functions should not panic even if recorder isn't installed
@@ -365,6 +707,55 @@ mod tests {
         record_circuit_breaker_transition("http://backend:3000", "closed", "open");
         record_active_connections("http://backend:3000", 5);
         record_auth_attempt("jwt", true);
-        record_rate_limit_exceeded("127.0.0.1", "/api/test");
+        record_jwks_fetch("https://idp.example.com/jwks.json", false);
+        record_rate_limit_exceeded("/api/test", "ip");
+        record_rate_limit_exempt("ip");
+        record_request_size("POST", "/api/test", 1024);
+        record_response_size("GET", "/api/test", 200, 4096);
+        record_cache_hit("/api/test");
+        record_cache_miss("/api/test");
+        record_cache_entries("/api/test", 10);
+        record_cache_stats("/api/test", 10, 10);
+        record_mirror_dropped("/api/test");
+        record_backend_stats(
+            "http://backend:3000",
+            &HealthStats {
+                is_healthy: true,
+                consecutive_successes: 0,
+                consecutive_failures: 0,
+                total_successes: 0,
+                total_failures: 0,
+                active_connections: 0,
+            },
+        );
+    }
+
+    #[test]
+    fn test_record_backend_stats() {
+        let recorder = MetricsService::builder(None).unwrap().build_recorder();
+        let handle = recorder.handle();
+        let stats = HealthStats {
+            is_healthy: false,
+            consecutive_successes: 0,
+            consecutive_failures: 3,
+            total_successes: 42,
+            total_failures: 7,
+            active_connections: 1,
+        };
+
+        metrics::with_local_recorder(&recorder, || {
+            record_backend_stats("http://backend:3000", &stats);
+            // Re-recording the same snapshot must not double count
+            record_backend_stats("http://backend:3000", &stats);
+        });
+
+        let rendered = handle.render();
+        assert!(rendered
+            .contains(r#"gateway_backend_total_successes{backend="http://backend:3000"} 42"#));
+        assert!(
+            rendered.contains(r#"gateway_backend_total_failures{backend="http://backend:3000"} 7"#)
+        );
+        assert!(rendered
+            .contains(r#"gateway_backend_consecutive_failures{backend="http://backend:3000"} 3"#));
     }
 }
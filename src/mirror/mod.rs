@@ -0,0 +1,153 @@
+//! Traffic mirroring (shadowing)
+//!
+//! A sampled share of a route's requests is copied to a mirror backend.
+//! Mirrored requests are fire-and-forget: their responses are discarded and
+//! their failures never reach the client. A slow mirror backend cannot pile
+//! up requests: once `max_in_flight` are pending, further copies are dropped.
+
+use crate::error::{GatewayError, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Mirror configuration for a route
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MirrorConfig {
+    /// Backend URL that receives copies of requests
+    pub backend: String,
+    /// Percentage of requests to mirror (0-100)
+    #[serde(default = "default_percentage")]
+    pub percentage: f64,
+    /// Mirrored requests pending at once before further ones are dropped
+    #[serde(default = "default_max_in_flight")]
+    pub max_in_flight: usize,
+}
+
+fn default_percentage() -> f64 {
+    100.0
+}
+
+fn default_max_in_flight() -> usize {
+    100
+}
+
+/// Decides which requests are mirrored and where to
+#[derive(Debug)]
+pub struct MirrorService {
+    config: MirrorConfig,
+    in_flight: Arc<Semaphore>,
+}
+
+impl MirrorService {
+    /// Create a new mirror service from configuration
+    pub fn new(config: MirrorConfig) -> Result<Self> {
+        url::Url::parse(&config.backend).map_err(|e| {
+            GatewayError::Config(format!(
+                "mirror.backend: Invalid URL '{}': {}",
+                config.backend, e
+            ))
+        })?;
+        if !(0.0..=100.0).contains(&config.percentage) {
+            return Err(GatewayError::Config(format!(
+                "mirror.percentage: Must be between 0 and 100, got {}",
+                config.percentage
+            )));
+        }
+        if config.max_in_flight == 0 {
+            return Err(GatewayError::Config(
+                "mirror.max_in_flight: Must be > 0".to_string(),
+            ));
+        }
+
+        let in_flight = Arc::new(Semaphore::new(config.max_in_flight));
+        Ok(Self { config, in_flight })
+    }
+
+    /// Base URL of the mirror backend
+    pub fn backend(&self) -> &str {
+        &self.config.backend
+    }
+
+    /// Sample whether the current request should be mirrored
+    pub fn should_mirror(&self) -> bool {
+        self.config.percentage >= 100.0 || rand::random::<f64>() * 100.0 < self.config.percentage
+    }
+
+    /// Reserve a slot for a mirrored request, held until it completes, or
+    /// `None` when `max_in_flight` requests are already pending
+    pub fn try_acquire(&self) -> Option<OwnedSemaphorePermit> {
+        self.in_flight.clone().try_acquire_owned().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mirror(percentage: f64) -> Result<MirrorService> {
+        MirrorService::new(MirrorConfig {
+            backend: "http://shadow:8080".to_string(),
+            percentage,
+            max_in_flight: default_max_in_flight(),
+        })
+    }
+
+    #[test]
+    fn test_sampling_bounds() {
+        let always = mirror(100.0).unwrap();
+        assert!((0..1000).all(|_| always.should_mirror()));
+
+        let never = mirror(0.0).unwrap();
+        assert!((0..1000).all(|_| !never.should_mirror()));
+    }
+
+    #[test]
+    fn test_sampling_rate() {
+        let quarter = mirror(25.0).unwrap();
+        let mirrored = (0..10_000).filter(|_| quarter.should_mirror()).count();
+        assert!((2000..3000).contains(&mirrored), "{}", mirrored);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        assert!(mirror(-1.0).is_err());
+        assert!(mirror(100.5).is_err());
+        assert!(MirrorService::new(MirrorConfig {
+            backend: "not a url".to_string(),
+            percentage: 10.0,
+            max_in_flight: 10,
+        })
+        .is_err());
+        assert!(MirrorService::new(MirrorConfig {
+            backend: "http://shadow:8080".to_string(),
+            percentage: 10.0,
+            max_in_flight: 0,
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_in_flight_limit() {
+        let service = MirrorService::new(MirrorConfig {
+            backend: "http://shadow:8080".to_string(),
+            percentage: 100.0,
+            max_in_flight: 2,
+        })
+        .unwrap();
+
+        let first = service.try_acquire().unwrap();
+        let _second = service.try_acquire().unwrap();
+        assert!(service.try_acquire().is_none());
+
+        // A completed mirror frees its slot
+        drop(first);
+        assert!(service.try_acquire().is_some());
+    }
+
+    #[test]
+    fn test_percentage_defaults_to_all() {
+        let config: MirrorConfig = serde_yaml::from_str("backend: http://shadow:8080").unwrap();
+        assert_eq!(config.percentage, 100.0);
+        assert_eq!(config.max_in_flight, 100);
+    }
+}
@@ -0,0 +1,283 @@
+//! Zipkin B3 trace context propagation
+//!
+//! Supports the single `b3` header (`{trace_id}-{span_id}-{sampled}-{parent_span_id}`)
+//! and the multi-header `X-B3-*` encoding. A missing sampling decision is
+//! treated as not sampled, as with W3C trace flags.
+
+use opentelemetry::{
+    propagation::{text_map_propagator::FieldIter, Extractor, Injector, TextMapPropagator},
+    trace::{SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState},
+    Context,
+};
+use std::sync::OnceLock;
+
+const B3_SINGLE_HEADER: &str = "b3";
+const B3_TRACE_ID_HEADER: &str = "x-b3-traceid";
+const B3_SPAN_ID_HEADER: &str = "x-b3-spanid";
+const B3_SAMPLED_HEADER: &str = "x-b3-sampled";
+const B3_FLAGS_HEADER: &str = "x-b3-flags";
+
+/// B3 header encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum B3Encoding {
+    /// Single `b3` header
+    SingleHeader,
+    /// `X-B3-TraceId`, `X-B3-SpanId` and `X-B3-Sampled` headers
+    MultipleHeader,
+}
+
+/// Propagator for Zipkin B3 headers
+///
+/// Extraction accepts either encoding; injection writes the configured one.
+#[derive(Debug, Clone)]
+pub struct B3Propagator {
+    encoding: B3Encoding,
+}
+
+impl B3Propagator {
+    pub fn new(encoding: B3Encoding) -> Self {
+        Self { encoding }
+    }
+
+    fn extract_single(&self, extractor: &dyn Extractor) -> Option<SpanContext> {
+        let header = extractor.get(B3_SINGLE_HEADER)?.trim();
+        let parts: Vec<&str> = header.split('-').collect();
+        // A lone sampling decision carries no trace to continue
+        if parts.len() < 2 || parts.len() > 4 {
+            return None;
+        }
+
+        let trace_id = parse_trace_id(parts[0])?;
+        let span_id = parse_span_id(parts[1])?;
+        let trace_flags = match parts.get(2) {
+            Some(sampled) => parse_sampled(sampled)?,
+            None => TraceFlags::NOT_SAMPLED,
+        };
+        if let Some(parent_span_id) = parts.get(3) {
+            parse_span_id(parent_span_id)?;
+        }
+
+        Some(remote_span_context(trace_id, span_id, trace_flags))
+    }
+
+    fn extract_multiple(&self, extractor: &dyn Extractor) -> Option<SpanContext> {
+        let trace_id = parse_trace_id(extractor.get(B3_TRACE_ID_HEADER)?.trim())?;
+        let span_id = parse_span_id(extractor.get(B3_SPAN_ID_HEADER)?.trim())?;
+
+        // The debug flag implies sampling
+        let trace_flags = if extractor.get(B3_FLAGS_HEADER).map(str::trim) == Some("1") {
+            TraceFlags::SAMPLED
+        } else {
+            match extractor.get(B3_SAMPLED_HEADER).map(str::trim) {
+                Some("1") | Some("true") => TraceFlags::SAMPLED,
+                Some("0") | Some("false") | None => TraceFlags::NOT_SAMPLED,
+                Some(_) => return None,
+            }
+        };
+
+        Some(remote_span_context(trace_id, span_id, trace_flags))
+    }
+}
+
+impl TextMapPropagator for B3Propagator {
+    fn inject_context(&self, cx: &Context, injector: &mut dyn Injector) {
+        let span = cx.span();
+        let span_context = span.span_context();
+        if !span_context.is_valid() {
+            return;
+        }
+
+        let sampled = if span_context.is_sampled() { "1" } else { "0" };
+        match self.encoding {
+            B3Encoding::SingleHeader => injector.set(
+                B3_SINGLE_HEADER,
+                format!(
+                    "{}-{}-{}",
+                    span_context.trace_id(),
+                    span_context.span_id(),
+                    sampled
+                ),
+            ),
+            B3Encoding::MultipleHeader => {
+                injector.set(B3_TRACE_ID_HEADER, span_context.trace_id().to_string());
+                injector.set(B3_SPAN_ID_HEADER, span_context.span_id().to_string());
+                injector.set(B3_SAMPLED_HEADER, sampled.to_string());
+            }
+        }
+    }
+
+    fn extract_with_context(&self, cx: &Context, extractor: &dyn Extractor) -> Context {
+        self.extract_single(extractor)
+            .or_else(|| self.extract_multiple(extractor))
+            .map(|span_context| cx.with_remote_span_context(span_context))
+            .unwrap_or_else(|| cx.clone())
+    }
+
+    fn fields(&self) -> FieldIter<'_> {
+        static SINGLE_FIELDS: OnceLock<[String; 1]> = OnceLock::new();
+        static MULTIPLE_FIELDS: OnceLock<[String; 4]> = OnceLock::new();
+        match self.encoding {
+            B3Encoding::SingleHeader => {
+                FieldIter::new(SINGLE_FIELDS.get_or_init(|| [B3_SINGLE_HEADER.to_string()]))
+            }
+            B3Encoding::MultipleHeader => FieldIter::new(MULTIPLE_FIELDS.get_or_init(|| {
+                [
+                    B3_TRACE_ID_HEADER.to_string(),
+                    B3_SPAN_ID_HEADER.to_string(),
+                    B3_SAMPLED_HEADER.to_string(),
+                    B3_FLAGS_HEADER.to_string(),
+                ]
+            })),
+        }
+    }
+}
+
+/// Parse a 64- or 128-bit lowercase hex trace ID
+fn parse_trace_id(value: &str) -> Option<TraceId> {
+    if !matches!(value.len(), 16 | 32) || !is_lower_hex(value) {
+        return None;
+    }
+    TraceId::from_hex(value)
+        .ok()
+        .filter(|id| *id != TraceId::INVALID)
+}
+
+/// Parse a 64-bit lowercase hex span ID
+fn parse_span_id(value: &str) -> Option<SpanId> {
+    if value.len() != 16 || !is_lower_hex(value) {
+        return None;
+    }
+    SpanId::from_hex(value)
+        .ok()
+        .filter(|id| *id != SpanId::INVALID)
+}
+
+/// Parse the single-header sampling state (`d` is debug, which implies sampling)
+fn parse_sampled(value: &str) -> Option<TraceFlags> {
+    match value {
+        "1" | "d" => Some(TraceFlags::SAMPLED),
+        "0" => Some(TraceFlags::NOT_SAMPLED),
+        _ => None,
+    }
+}
+
+fn is_lower_hex(value: &str) -> bool {
+    value
+        .chars()
+        .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+}
+
+fn remote_span_context(trace_id: TraceId, span_id: SpanId, flags: TraceFlags) -> SpanContext {
+    SpanContext::new(trace_id, span_id, flags, true, TraceState::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const TRACE_ID: &str = "80f198ee56343ba864fe8b2a57d3eff7";
+    const SPAN_ID: &str = "e457b5a2e4d86bd1";
+
+    fn extract(headers: &[(&str, &str)]) -> SpanContext {
+        let carrier: HashMap<String, String> = headers
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let cx = B3Propagator::new(B3Encoding::SingleHeader)
+            .extract_with_context(&Context::new(), &carrier);
+        let span = cx.span();
+        span.span_context().clone()
+    }
+
+    #[test]
+    fn test_extract_single_header() {
+        let span_context = extract(&[("b3", &format!("{TRACE_ID}-{SPAN_ID}-1-05e3ac9a4f6e3b90"))]);
+        assert!(span_context.is_valid());
+        assert!(span_context.is_remote());
+        assert!(span_context.is_sampled());
+        assert_eq!(span_context.trace_id().to_string(), TRACE_ID);
+        assert_eq!(span_context.span_id().to_string(), SPAN_ID);
+    }
+
+    #[test]
+    fn test_extract_single_header_sampling_states() {
+        let not_sampled = extract(&[("b3", &format!("{TRACE_ID}-{SPAN_ID}-0"))]);
+        assert!(not_sampled.is_valid());
+        assert!(!not_sampled.is_sampled());
+
+        let debug = extract(&[("b3", &format!("{TRACE_ID}-{SPAN_ID}-d"))]);
+        assert!(debug.is_sampled());
+
+        let deferred = extract(&[("b3", &format!("{TRACE_ID}-{SPAN_ID}"))]);
+        assert!(deferred.is_valid());
+        assert!(!deferred.is_sampled());
+
+        // A bare sampling decision has no trace to continue
+        assert!(!extract(&[("b3", "1")]).is_valid());
+    }
+
+    #[test]
+    fn test_extract_multiple_headers() {
+        let span_context = extract(&[
+            ("x-b3-traceid", "64fe8b2a57d3eff7"),
+            ("x-b3-spanid", SPAN_ID),
+            ("x-b3-sampled", "1"),
+        ]);
+        assert!(span_context.is_valid());
+        assert!(span_context.is_sampled());
+        // 64-bit trace IDs are left-padded
+        assert_eq!(
+            span_context.trace_id().to_string(),
+            "000000000000000064fe8b2a57d3eff7"
+        );
+
+        let debug = extract(&[
+            ("x-b3-traceid", TRACE_ID),
+            ("x-b3-spanid", SPAN_ID),
+            ("x-b3-flags", "1"),
+        ]);
+        assert!(debug.is_sampled());
+    }
+
+    #[test]
+    fn test_extract_rejects_malformed_headers() {
+        assert!(!extract(&[("b3", &format!("{TRACE_ID}-xyz-1"))]).is_valid());
+        assert!(
+            !extract(&[("b3", &format!("{}-{SPAN_ID}-1", TRACE_ID.to_uppercase()))]).is_valid()
+        );
+        assert!(!extract(&[("b3", &format!("{TRACE_ID}-{SPAN_ID}-2"))]).is_valid());
+        assert!(!extract(&[("x-b3-traceid", TRACE_ID)]).is_valid());
+        assert!(!extract(&[]).is_valid());
+    }
+
+    #[test]
+    fn test_inject_round_trip() {
+        let span_context = SpanContext::new(
+            TraceId::from_hex(TRACE_ID).unwrap(),
+            SpanId::from_hex(SPAN_ID).unwrap(),
+            TraceFlags::SAMPLED,
+            true,
+            TraceState::default(),
+        );
+        let cx = Context::new().with_remote_span_context(span_context);
+
+        let mut single = HashMap::new();
+        B3Propagator::new(B3Encoding::SingleHeader).inject_context(&cx, &mut single);
+        assert_eq!(single["b3"], format!("{TRACE_ID}-{SPAN_ID}-1"));
+
+        let mut multiple = HashMap::new();
+        B3Propagator::new(B3Encoding::MultipleHeader).inject_context(&cx, &mut multiple);
+        assert_eq!(multiple["x-b3-traceid"], TRACE_ID);
+        assert_eq!(multiple["x-b3-spanid"], SPAN_ID);
+        assert_eq!(multiple["x-b3-sampled"], "1");
+        assert!(!multiple.contains_key("b3"));
+    }
+
+    #[test]
+    fn test_inject_skips_invalid_context() {
+        let mut carrier = HashMap::new();
+        B3Propagator::new(B3Encoding::SingleHeader).inject_context(&Context::new(), &mut carrier);
+        assert!(carrier.is_empty());
+    }
+}
@@ -1,20 +1,41 @@
-use axum::{body::Body, extract::Request, http::HeaderMap, middleware::Next, response::Response};
-use opentelemetry::{global, KeyValue};
+pub mod b3;
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use opentelemetry::{global, propagation::TextMapPropagator, KeyValue};
 use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_sdk::{
+    propagation::{BaggagePropagator, TextMapCompositePropagator, TraceContextPropagator},
     trace::{self, RandomIdGenerator, Sampler},
     Resource,
 };
-use std::time::SystemTime;
-use tracing::{info, Span};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use tracing::{info, Span, Subscriber};
+use tracing_subscriber::{
+    filter::filter_fn, fmt::MakeWriter, layer::SubscriberExt, registry::LookupSpan,
+    util::SubscriberInitExt, EnvFilter, Layer,
+};
 use uuid::Uuid;
 
+use crate::config::{
+    AccessLogConfig, AccessLogFormat, RequestIdConfig, RequestIdFormat, TracePropagator,
+};
 use crate::error::{GatewayError, Result};
+use crate::ipfilter::client_ip::client_ip;
+use b3::{B3Encoding, B3Propagator};
 
 pub const REQUEST_ID_HEADER: &str = "x-request-id";
 pub const TRACE_ID_HEADER: &str = "x-trace-id";
 
+/// Tracing target of access log events
+pub const ACCESS_LOG_TARGET: &str = "gateway::access";
+
 /// Configuration for OpenTelemetry tracing
 #[derive(Debug, Clone)]
 pub struct TracingConfig {
@@ -75,58 +96,134 @@ pub fn init_telemetry(config: TracingConfig) -> Result<opentelemetry_sdk::trace:
 }
 
 /// Initialize tracing with optional OpenTelemetry support
-pub fn init_tracing(otel_config: Option<TracingConfig>) -> Result<()> {
-    if let Some(config) = otel_config {
-        // Initialize with OpenTelemetry
-        let tracer = init_telemetry(config)?;
-        let telemetry_layer = tracing_opentelemetry::layer().with_tracer(tracer);
-
-        let env_filter = EnvFilter::try_from_default_env()
-            .unwrap_or_else(|_| "gateway=debug,tower_http=debug".into());
-
-        tracing_subscriber::registry()
-            .with(telemetry_layer)
-            .with(env_filter)
-            .with(
-                tracing_subscriber::fmt::layer()
-                    .with_target(false)
-                    .compact(),
-            )
-            .init();
-
+///
+/// With JSON access logs, access log events are written by their own layer
+/// and left out of the regular log output.
+pub fn init_tracing(
+    otel_config: Option<TracingConfig>,
+    access_log_format: AccessLogFormat,
+) -> Result<()> {
+    let with_otel = otel_config.is_some();
+    let telemetry_layer = match otel_config {
+        Some(config) => {
+            let tracer = init_telemetry(config)?;
+            Some(tracing_opentelemetry::layer().with_tracer(tracer))
+        }
+        None => None,
+    };
+
+    let env_filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| "gateway=debug,tower_http=debug".into());
+
+    let json_access_log = access_log_format == AccessLogFormat::Json;
+    let fmt_layer = tracing_subscriber::fmt::layer()
+        .with_target(false)
+        .compact()
+        .with_filter(filter_fn(move |metadata| {
+            !(json_access_log && metadata.target() == ACCESS_LOG_TARGET)
+        }));
+
+    tracing_subscriber::registry()
+        .with(telemetry_layer)
+        .with(env_filter)
+        .with(fmt_layer)
+        .with(json_access_log.then(|| json_access_log_layer(std::io::stdout)))
+        .init();
+
+    if with_otel {
         info!("Tracing initialized with OpenTelemetry support");
     } else {
-        // Initialize without OpenTelemetry (basic tracing)
-        let env_filter = EnvFilter::try_from_default_env()
-            .unwrap_or_else(|_| "gateway=debug,tower_http=debug".into());
-
-        tracing_subscriber::fmt()
-            .with_env_filter(env_filter)
-            .with_target(false)
-            .compact()
-            .init();
-
         info!("Tracing initialized without OpenTelemetry");
     }
 
     Ok(())
 }
 
+/// Layer writing each access log event as one flat JSON object per line
+pub fn json_access_log_layer<S, W>(writer: W) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    tracing_subscriber::fmt::layer()
+        .json()
+        .flatten_event(true)
+        .with_current_span(false)
+        .with_span_list(false)
+        .with_writer(writer)
+        .with_filter(filter_fn(|metadata| metadata.target() == ACCESS_LOG_TARGET))
+}
+
 /// Shutdown OpenTelemetry gracefully
 pub fn shutdown_telemetry() {
     info!("Shutting down OpenTelemetry");
     global::shutdown_tracer_provider();
 }
 
+/// Alphabet of nanoid request IDs (URL-safe)
+const NANOID_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789_-";
+
+/// Length of nanoid request IDs
+const NANOID_LENGTH: usize = 21;
+
+/// Where request IDs come from: incoming headers, else a generated ID
+#[derive(Debug, Clone)]
+pub struct RequestIdSource {
+    headers: Vec<HeaderName>,
+    format: RequestIdFormat,
+}
+
+impl RequestIdSource {
+    pub fn new(config: &RequestIdConfig) -> Self {
+        Self {
+            headers: config
+                .headers
+                .iter()
+                .filter_map(|name| HeaderName::from_bytes(name.as_bytes()).ok())
+                .collect(),
+            format: config.format,
+        }
+    }
+
+    /// The first non-empty request ID header, or a newly generated ID
+    pub fn request_id(&self, headers: &HeaderMap) -> String {
+        self.headers
+            .iter()
+            .filter_map(|name| headers.get(name)?.to_str().ok())
+            .find(|value| !value.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| generate_request_id(self.format))
+    }
+}
+
+impl Default for RequestIdSource {
+    fn default() -> Self {
+        Self::new(&RequestIdConfig::default())
+    }
+}
+
+/// Generate a request ID in the given format
+pub fn generate_request_id(format: RequestIdFormat) -> String {
+    match format {
+        RequestIdFormat::UuidV4 => Uuid::new_v4().to_string(),
+        RequestIdFormat::UuidV7 => Uuid::now_v7().to_string(),
+        RequestIdFormat::Nanoid => (0..NANOID_LENGTH)
+            .map(|_| NANOID_ALPHABET[(rand::random::<u8>() & 63) as usize] as char)
+            .collect(),
+    }
+}
+
 /// Middleware to add request ID to requests
-pub async fn request_id_middleware(mut req: Request, next: Next) -> Response {
-    // Check if request already has a request ID
-    let request_id = req
-        .headers()
-        .get(REQUEST_ID_HEADER)
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.to_string())
-        .unwrap_or_else(|| Uuid::new_v4().to_string());
+///
+/// The ID is read from the configured headers or generated, and always
+/// returned in the `x-request-id` response header.
+pub async fn request_id_middleware(
+    State(source): State<Arc<RequestIdSource>>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let request_id = source.request_id(req.headers());
 
     // Add request ID to tracing span
     Span::current().record("request_id", &request_id);
@@ -148,12 +245,95 @@ pub async fn request_id_middleware(mut req: Request, next: Next) -> Response {
     response
 }
 
+/// Backend that served a request, set on the response by the proxy
+#[derive(Debug, Clone)]
+pub struct UpstreamBackend(pub String);
+
+/// Decides which completed requests get an access log event
+///
+/// Errors and slow requests are always logged; successful (2xx/3xx) requests
+/// are sampled at `sample_rate`.
+#[derive(Debug, Clone)]
+pub struct AccessLogSampler {
+    sample_rate: f64,
+    slow_threshold: Option<Duration>,
+}
+
+impl AccessLogSampler {
+    pub fn new(config: &AccessLogConfig) -> Self {
+        Self {
+            sample_rate: config.sample_rate,
+            slow_threshold: config.slow_threshold_ms.map(Duration::from_millis),
+        }
+    }
+
+    /// Whether a request finishing with `status` after `latency` is logged
+    pub fn should_log(&self, status: StatusCode, latency: Duration) -> bool {
+        if !(status.is_success() || status.is_redirection()) {
+            return true;
+        }
+        if self.slow_threshold.is_some_and(|t| latency >= t) {
+            return true;
+        }
+        self.sample_rate >= 1.0 || rand::random::<f64>() < self.sample_rate
+    }
+}
+
+impl Default for AccessLogSampler {
+    fn default() -> Self {
+        Self {
+            sample_rate: 1.0,
+            slow_threshold: None,
+        }
+    }
+}
+
+/// Middleware emitting a single access log event per completed request
+///
+/// Must run inside `request_id_middleware` to pick up the request ID.
+pub async fn access_log_middleware(
+    State(sampler): State<Arc<AccessLogSampler>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let start = Instant::now();
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+    let request_id = get_request_id(&req);
+    let client_ip = client_ip(req.extensions()).map(|ip| ip.to_string());
+
+    let response = next.run(req).await;
+
+    let latency = start.elapsed();
+    if !sampler.should_log(response.status(), latency) {
+        return response;
+    }
+
+    let backend = response.extensions().get::<UpstreamBackend>();
+    info!(
+        target: ACCESS_LOG_TARGET,
+        method = %method,
+        path = %path,
+        status = response.status().as_u16(),
+        latency_ms = latency.as_secs_f64() * 1000.0,
+        backend = backend.map(|b| b.0.as_str()).unwrap_or("-"),
+        client_ip = client_ip.as_deref().unwrap_or("-"),
+        request_id = request_id.as_deref().unwrap_or("-"),
+        "access"
+    );
+
+    response
+}
+
+/// Trace context of a request, carrying the gateway's server span
+#[derive(Debug, Clone)]
+pub struct RequestTraceContext(pub opentelemetry::Context);
+
 /// Middleware to add trace context propagation
-pub async fn trace_context_middleware(req: Request, next: Next) -> Response {
+pub async fn trace_context_middleware(mut req: Request, next: Next) -> Response {
     use opentelemetry::trace::{SpanKind, TraceContextExt, Tracer};
-    use opentelemetry::Context;
 
-    // Extract trace context from headers if present
+    // Extract trace context (and baggage) from headers if present
     let parent_context = extract_trace_context(req.headers());
 
     // Create a new span
@@ -164,26 +344,55 @@ pub async fn trace_context_middleware(req: Request, next: Next) -> Response {
         .with_start_time(SystemTime::now())
         .start_with_context(&tracer, &parent_context);
 
-    let cx = Context::current_with_span(span);
-
-    // Attach context to current scope
-    let _guard = cx.attach();
+    // Keep the context so the proxy continues the trace to the backend
+    let cx = parent_context.with_span(span);
+    req.extensions_mut().insert(RequestTraceContext(cx.clone()));
 
-    // Process request
     let response = next.run(req).await;
 
+    // End the span once the response is ready; the request (and its copy of
+    // the context) is dropped as soon as the proxy reads the body
+    let span = cx.span();
+    span.set_attribute(KeyValue::new(
+        "http.status_code",
+        i64::from(response.status().as_u16()),
+    ));
+    span.end();
+
     response
 }
 
-/// Extract trace context from headers (W3C Trace Context format)
-fn extract_trace_context(headers: &HeaderMap) -> opentelemetry::Context {
-    use opentelemetry::propagation::TextMapPropagator;
-    use opentelemetry_sdk::propagation::TraceContextPropagator;
+/// Build a propagator handling each of `propagators`, in order
+pub fn build_propagator(propagators: &[TracePropagator]) -> TextMapCompositePropagator {
+    let propagators = propagators
+        .iter()
+        .map(|propagator| -> Box<dyn TextMapPropagator + Send + Sync> {
+            match propagator {
+                TracePropagator::TraceContext => Box::new(TraceContextPropagator::new()),
+                TracePropagator::Baggage => Box::new(BaggagePropagator::new()),
+                TracePropagator::B3 => Box::new(B3Propagator::new(B3Encoding::SingleHeader)),
+                TracePropagator::B3Multi => Box::new(B3Propagator::new(B3Encoding::MultipleHeader)),
+            }
+        })
+        .collect();
+    TextMapCompositePropagator::new(propagators)
+}
+
+/// Install the global propagator used to extract and inject trace context
+pub fn install_propagator(propagators: &[TracePropagator]) {
+    global::set_text_map_propagator(build_propagator(propagators));
+}
 
-    let propagator = TraceContextPropagator::new();
-    let context = propagator.extract(&HeaderMapCarrier(headers));
+/// Extract trace context from headers using the global propagator
+fn extract_trace_context(headers: &HeaderMap) -> opentelemetry::Context {
+    global::get_text_map_propagator(|propagator| propagator.extract(&HeaderMapCarrier(headers)))
+}
 
-    context
+/// Write the trace context into outgoing request headers using the global propagator
+pub fn inject_trace_context(cx: &opentelemetry::Context, headers: &mut HeaderMap) {
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(cx, &mut HeaderMapInjector(headers))
+    });
 }
 
 /// Carrier for extracting trace context from HeaderMap
@@ -199,6 +408,20 @@ impl<'a> opentelemetry::propagation::Extractor for HeaderMapCarrier<'a> {
     }
 }
 
+/// Carrier for injecting trace context into a HeaderMap
+struct HeaderMapInjector<'a>(&'a mut HeaderMap);
+
+impl<'a> opentelemetry::propagation::Injector for HeaderMapInjector<'a> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
+
 /// Request ID extension type
 #[derive(Debug, Clone)]
 pub struct RequestId(pub String);
@@ -211,7 +434,9 @@ pub fn get_request_id(req: &Request<Body>) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::extract::ConnectInfo;
     use axum::http::HeaderValue;
+    use std::net::SocketAddr;
 
     #[test]
     fn test_tracing_config_default() {
@@ -253,6 +478,88 @@ mod tests {
         assert_eq!(request_id.0, "test-id");
     }
 
+    #[test]
+    fn test_request_id_source_reads_headers_in_order() {
+        let source = RequestIdSource::new(&RequestIdConfig {
+            headers: vec!["x-amzn-trace-id".to_string(), "x-request-id".to_string()],
+            format: RequestIdFormat::UuidV4,
+        });
+
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_static("req-123"));
+        assert_eq!(source.request_id(&headers), "req-123");
+
+        headers.insert("x-amzn-trace-id", HeaderValue::from_static("Root=1-abc"));
+        assert_eq!(source.request_id(&headers), "Root=1-abc");
+
+        headers.insert("x-amzn-trace-id", HeaderValue::from_static(""));
+        assert_eq!(source.request_id(&headers), "req-123");
+    }
+
+    #[test]
+    fn test_generated_request_id_formats() {
+        let v4 = Uuid::parse_str(&generate_request_id(RequestIdFormat::UuidV4)).unwrap();
+        assert_eq!(v4.get_version_num(), 4);
+
+        let v7 = Uuid::parse_str(&generate_request_id(RequestIdFormat::UuidV7)).unwrap();
+        assert_eq!(v7.get_version_num(), 7);
+
+        let nanoid = generate_request_id(RequestIdFormat::Nanoid);
+        assert_eq!(nanoid.len(), NANOID_LENGTH);
+        assert!(
+            nanoid.bytes().all(|b| NANOID_ALPHABET.contains(&b)),
+            "{nanoid}"
+        );
+        assert_ne!(nanoid, generate_request_id(RequestIdFormat::Nanoid));
+    }
+
+    #[test]
+    fn test_uuid_v7_request_ids_sort_by_creation() {
+        let ids: Vec<String> = (0..100)
+            .map(|_| generate_request_id(RequestIdFormat::UuidV7))
+            .collect();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]), "{ids:?}");
+    }
+
+    #[tokio::test]
+    async fn test_request_id_middleware_returns_alternate_header_id() {
+        use axum::{middleware, routing::get, Router};
+        use tower::ServiceExt;
+
+        let source = RequestIdSource::new(&RequestIdConfig {
+            headers: vec!["x-correlation-id".to_string()],
+            format: RequestIdFormat::Nanoid,
+        });
+        let app = Router::new()
+            .route(
+                "/",
+                get(|req: Request| async move {
+                    req.extensions().get::<RequestId>().unwrap().0.clone()
+                }),
+            )
+            .layer(middleware::from_fn_with_state(
+                Arc::new(source),
+                request_id_middleware,
+            ));
+
+        let request = axum::http::Request::builder()
+            .uri("/")
+            .header("x-correlation-id", "corr-42")
+            .header(REQUEST_ID_HEADER, "ignored")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.headers()[REQUEST_ID_HEADER], "corr-42");
+
+        let request = axum::http::Request::builder()
+            .uri("/")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let generated = response.headers()[REQUEST_ID_HEADER].to_str().unwrap();
+        assert_eq!(generated.len(), NANOID_LENGTH);
+    }
+
     #[test]
     fn test_header_map_carrier() {
         use axum::http::HeaderValue;
@@ -271,4 +578,163 @@ mod tests {
         assert!(keys.contains(&"traceparent"));
         assert!(keys.contains(&"tracestate"));
     }
+
+    #[derive(Clone, Default)]
+    struct CapturedLogs(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for CapturedLogs {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_access_log_emits_single_json_event() {
+        use axum::{middleware, response::IntoResponse, routing::get, Router};
+        use tower::ServiceExt;
+
+        let logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::registry().with(json_access_log_layer(logs.clone()));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let app = Router::new()
+            .route(
+                "/api/users",
+                get(|| async {
+                    info!("Not an access log event");
+                    let mut response = "ok".into_response();
+                    response
+                        .extensions_mut()
+                        .insert(UpstreamBackend("http://localhost:3001".to_string()));
+                    response
+                }),
+            )
+            .layer(middleware::from_fn_with_state(
+                Arc::new(AccessLogSampler::default()),
+                access_log_middleware,
+            ))
+            .layer(middleware::from_fn_with_state(
+                Arc::new(RequestIdSource::default()),
+                request_id_middleware,
+            ));
+
+        let mut request = axum::http::Request::builder()
+            .uri("/api/users")
+            .header(REQUEST_ID_HEADER, "req-123")
+            .body(Body::empty())
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([10, 0, 0, 7], 54321))));
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), 200);
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 1, "{output}");
+
+        let event: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(event["target"], ACCESS_LOG_TARGET);
+        assert_eq!(event["method"], "GET");
+        assert_eq!(event["path"], "/api/users");
+        assert_eq!(event["status"], 200);
+        assert_eq!(event["backend"], "http://localhost:3001");
+        assert_eq!(event["client_ip"], "10.0.0.7");
+        assert_eq!(event["request_id"], "req-123");
+        assert!(event["latency_ms"].as_f64().unwrap() >= 0.0);
+    }
+
+    fn sampler(sample_rate: f64, slow_threshold_ms: Option<u64>) -> AccessLogSampler {
+        AccessLogSampler::new(&AccessLogConfig {
+            enabled: true,
+            format: AccessLogFormat::Json,
+            sample_rate,
+            slow_threshold_ms,
+        })
+    }
+
+    #[test]
+    fn test_access_log_sampler_always_logs_errors() {
+        let sampler = sampler(0.0, None);
+        let fast = Duration::from_millis(1);
+
+        for status in [400, 404, 429, 500, 502, 504] {
+            let status = StatusCode::from_u16(status).unwrap();
+            assert!(sampler.should_log(status, fast), "{status}");
+        }
+        assert!(!sampler.should_log(StatusCode::OK, fast));
+        assert!(!sampler.should_log(StatusCode::MOVED_PERMANENTLY, fast));
+    }
+
+    #[test]
+    fn test_access_log_sampler_always_logs_slow_requests() {
+        let sampler = sampler(0.0, Some(500));
+
+        assert!(!sampler.should_log(StatusCode::OK, Duration::from_millis(499)));
+        assert!(sampler.should_log(StatusCode::OK, Duration::from_millis(500)));
+        assert!(sampler.should_log(StatusCode::OK, Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_access_log_sampler_samples_successes() {
+        let fast = Duration::from_millis(1);
+        let logged = |sampler: &AccessLogSampler| {
+            (0..10_000)
+                .filter(|_| sampler.should_log(StatusCode::OK, fast))
+                .count()
+        };
+
+        assert_eq!(logged(&sampler(1.0, None)), 10_000);
+        assert_eq!(logged(&sampler(0.0, None)), 0);
+
+        let sampled = logged(&sampler(0.1, None));
+        assert!((700..1_300).contains(&sampled), "{sampled}");
+    }
+
+    #[tokio::test]
+    async fn test_access_log_middleware_skips_unsampled_requests() {
+        use axum::{middleware, routing::get, Router};
+        use tower::ServiceExt;
+
+        let logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::registry().with(json_access_log_layer(logs.clone()));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let app = Router::new()
+            .route("/ok", get(|| async { "ok" }))
+            .route("/fail", get(|| async { StatusCode::BAD_GATEWAY }))
+            .layer(middleware::from_fn_with_state(
+                Arc::new(sampler(0.0, None)),
+                access_log_middleware,
+            ));
+
+        for uri in ["/ok", "/fail", "/ok"] {
+            let request = axum::http::Request::builder()
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap();
+            app.clone().oneshot(request).await.unwrap();
+        }
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 1, "{output}");
+
+        let event: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(event["path"], "/fail");
+        assert_eq!(event["status"], 502);
+    }
 }
@@ -1,30 +1,77 @@
-use crate::auth::AuthService;
-use crate::cache::CacheKey;
-use crate::circuit_breaker::{CircuitBreakerService, RetryExecutor};
+use crate::auth::middleware::AuthExtension;
+use crate::auth::{AuthRequest, AuthService};
+use crate::cache::{
+    has_request_directive, CacheFill, CacheKey, CacheLookup, CachedResponse, RefreshGuard,
+};
+use crate::circuit_breaker::{
+    parse_retry_after, CircuitBreakerService, RetryExecutor, RetryLogContext,
+};
+use crate::config::{default_health_check_paths, UpstreamConfig};
+use crate::cors::CorsService;
+use crate::dns::DnsCache;
 use crate::error::{GatewayError, Result};
+use crate::ipfilter::{ClientIp, IpFilterService};
+use crate::maintenance::MaintenanceConfig;
 use crate::metrics;
-use crate::router::Router;
+use crate::observability::{
+    get_request_id, inject_trace_context, RequestTraceContext, UpstreamBackend,
+};
+use crate::rate_limit::RateLimitMiddleware;
+use crate::router::{Route, Router};
+use crate::transform::TemplateContext;
 use axum::{
-    body::Body,
+    body::{Body, HttpBody},
     extract::{ConnectInfo, State},
-    http::{HeaderMap, Method, Request, Response},
-    response::IntoResponse,
+    http::{
+        header::{ALLOW, CONTENT_LENGTH, CONTENT_TYPE, RETRY_AFTER, WWW_AUTHENTICATE},
+        HeaderMap, HeaderValue, Method, Request, Response, StatusCode,
+    },
+    response::{IntoResponse, Redirect},
 };
-use bytes::Bytes;
-use http_body_util::BodyExt;
+use arc_swap::{ArcSwap, ArcSwapOption};
+use bytes::{Bytes, BytesMut};
+use http_body_util::{BodyExt, LengthLimitError, Limited};
+use std::future::Future;
 use std::net::SocketAddr;
+use std::pin::Pin;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tracing::{debug, info, warn};
 
 /// Proxy handler state
+///
+/// The router, auth service, rate limiter, circuit breaker, retry executor and
+/// global IP filter can be swapped
+/// at runtime (e.g. on config reload); each request works on a snapshot.
 #[derive(Clone)]
 pub struct ProxyState {
-    pub router: Arc<Router>,
+    pub router: Arc<ArcSwap<Router>>,
     pub client: reqwest::Client,
-    pub auth_service: Option<Arc<AuthService>>,
-    pub circuit_breaker: Option<Arc<CircuitBreakerService>>,
-    pub retry_executor: Option<Arc<RetryExecutor>>,
+    pub auth_service: Arc<ArcSwapOption<AuthService>>,
+    /// Gateway-wide rate limits, checked after authentication
+    pub rate_limiter: Arc<ArcSwapOption<RateLimitMiddleware>>,
+    pub circuit_breaker: Arc<ArcSwapOption<CircuitBreakerService>>,
+    pub retry_executor: Arc<ArcSwapOption<RetryExecutor>>,
+    /// Gateway-wide IP filter, checked before any route filter
+    pub ip_filter: Arc<ArcSwapOption<IpFilterService>>,
+    /// Default backend request timeout
+    pub timeout: Duration,
+    /// Gateway-managed DNS cache for backend hostnames
+    pub dns_cache: Option<Arc<DnsCache>>,
+    /// TLS configuration for backend connections (CA bundle, client identity)
+    pub backend_tls: Option<Arc<rustls::ClientConfig>>,
+    /// Connection pool and keep-alive settings for backend connections
+    pub upstream: UpstreamConfig,
+    /// Whether to honor and propagate the X-Request-Deadline header
+    pub enforce_request_deadline: bool,
+    /// Whether to honor X-HTTP-Method-Override on POST requests by default
+    pub honor_method_override: bool,
+    /// Whether to answer OPTIONS requests at the gateway by default
+    pub answer_options: bool,
+    /// Maintenance mode, toggled by configuration or the admin API
+    pub maintenance: Arc<ArcSwap<MaintenanceConfig>>,
+    /// Request paths that bypass authentication
+    pub health_check_paths: Arc<[String]>,
 }
 
 impl ProxyState {
@@ -36,34 +83,453 @@ impl ProxyState {
         circuit_breaker: Option<CircuitBreakerService>,
         retry_executor: Option<RetryExecutor>,
     ) -> Self {
-        let client = reqwest::Client::builder()
-            .timeout(timeout)
-            .build()
-            .expect("Failed to create HTTP client");
+        Self {
+            router: Arc::new(ArcSwap::from_pointee(router)),
+            client: build_client(timeout, &UpstreamConfig::default(), None, None),
+            auth_service: Arc::new(ArcSwapOption::from_pointee(auth_service)),
+            rate_limiter: Arc::new(ArcSwapOption::empty()),
+            circuit_breaker: Arc::new(ArcSwapOption::from_pointee(circuit_breaker)),
+            retry_executor: Arc::new(ArcSwapOption::from_pointee(retry_executor)),
+            ip_filter: Arc::new(ArcSwapOption::empty()),
+            timeout,
+            dns_cache: None,
+            backend_tls: None,
+            upstream: UpstreamConfig::default(),
+            enforce_request_deadline: false,
+            honor_method_override: false,
+            answer_options: false,
+            maintenance: Arc::new(ArcSwap::from_pointee(MaintenanceConfig::default())),
+            health_check_paths: default_health_check_paths().into(),
+        }
+    }
+
+    /// Resolve backend hostnames through the given DNS cache
+    pub fn with_dns_cache(mut self, dns_cache: DnsCache) -> Self {
+        let dns_cache = Arc::new(dns_cache);
+        self.client = build_client(
+            self.timeout,
+            &self.upstream,
+            Some(&dns_cache),
+            self.backend_tls.as_deref(),
+        );
+        self.dns_cache = Some(dns_cache);
+        self
+    }
+
+    /// Connect to HTTPS backends with the given TLS client configuration
+    pub fn with_backend_tls(mut self, tls_config: rustls::ClientConfig) -> Self {
+        self.client = build_client(
+            self.timeout,
+            &self.upstream,
+            self.dns_cache.as_ref(),
+            Some(&tls_config),
+        );
+        self.backend_tls = Some(Arc::new(tls_config));
+        self
+    }
 
+    /// Tune the connection pool and keep-alive of backend connections
+    pub fn with_upstream(mut self, upstream: UpstreamConfig) -> Self {
+        self.client = build_client(
+            self.timeout,
+            &upstream,
+            self.dns_cache.as_ref(),
+            self.backend_tls.as_deref(),
+        );
+        self.upstream = upstream;
+        self
+    }
+
+    /// Honor and propagate the X-Request-Deadline header
+    pub fn with_request_deadline(mut self, enabled: bool) -> Self {
+        self.enforce_request_deadline = enabled;
+        self
+    }
+
+    /// Honor X-HTTP-Method-Override for routes that don't configure it
+    pub fn with_method_override(mut self, enabled: bool) -> Self {
+        self.honor_method_override = enabled;
+        self
+    }
+
+    /// Answer OPTIONS for routes that don't configure it instead of forwarding
+    pub fn with_answer_options(mut self, enabled: bool) -> Self {
+        self.answer_options = enabled;
+        self
+    }
+
+    /// Bypass authentication for the given request paths
+    pub fn with_health_check_paths(mut self, paths: Vec<String>) -> Self {
+        self.health_check_paths = paths.into();
+        self
+    }
+
+    /// Apply a gateway-wide IP filter to every request
+    pub fn with_ip_filter(self, ip_filter: IpFilterService) -> Self {
+        self.set_ip_filter(Some(ip_filter));
+        self
+    }
+
+    /// Rate limit every request once it has been authenticated
+    pub fn with_rate_limiter(self, rate_limiter: RateLimitMiddleware) -> Self {
+        self.set_rate_limiter(Some(rate_limiter));
+        self
+    }
+
+    /// Answer requests with a maintenance response while enabled
+    pub fn with_maintenance(self, maintenance: MaintenanceConfig) -> Self {
+        self.set_maintenance(maintenance);
+        self
+    }
+
+    /// Replace the maintenance mode settings
+    pub fn set_maintenance(&self, maintenance: MaintenanceConfig) {
+        self.maintenance.store(Arc::new(maintenance));
+    }
+
+    /// Replace the routing table; in-flight requests finish on the old one
+    pub fn set_router(&self, router: Router) {
+        self.router.store(Arc::new(router));
+    }
+
+    /// Replace the authentication service
+    pub fn set_auth_service(&self, auth_service: Option<AuthService>) {
+        self.auth_service.store(auth_service.map(Arc::new));
+    }
+
+    /// Replace the rate limiter, discarding its counters
+    pub fn set_rate_limiter(&self, rate_limiter: Option<RateLimitMiddleware>) {
+        self.rate_limiter.store(rate_limiter.map(Arc::new));
+    }
+
+    /// Replace the global circuit breaker, discarding its recorded state
+    pub fn set_circuit_breaker(&self, circuit_breaker: Option<CircuitBreakerService>) {
+        self.circuit_breaker.store(circuit_breaker.map(Arc::new));
+    }
+
+    /// Replace the retry executor
+    pub fn set_retry_executor(&self, retry_executor: Option<RetryExecutor>) {
+        self.retry_executor.store(retry_executor.map(Arc::new));
+    }
+
+    /// Replace the gateway-wide IP filter
+    pub fn set_ip_filter(&self, ip_filter: Option<IpFilterService>) {
+        self.ip_filter.store(ip_filter.map(Arc::new));
+    }
+
+    /// Record the statistics of every route's response cache
+    pub async fn record_cache_stats(&self) {
+        let router = self.router.load_full();
+        for (path, route) in router.entries() {
+            let Some(cache) = &route.cache else {
+                continue;
+            };
+            // Redis-backed caches don't know their size and are left out
+            if let Some(stats) = cache.current_stats().await {
+                metrics::record_cache_stats(path, stats.entry_count, stats.weighted_size);
+            }
+        }
+    }
+
+    /// Record cache statistics every `interval` in the background, following
+    /// routing table swaps
+    pub fn spawn_cache_stats_reporter(&self, interval: Duration) {
+        let state = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                state.record_cache_stats().await;
+            }
+        });
+    }
+}
+
+/// Header used by legacy clients to tunnel the real method through POST
+pub const METHOD_OVERRIDE_HEADER: &str = "x-http-method-override";
+
+/// Methods a POST request may be overridden to
+const METHOD_OVERRIDE_ALLOWLIST: [Method; 3] = [Method::PUT, Method::PATCH, Method::DELETE];
+
+/// Get the overridden method for a POST request carrying X-HTTP-Method-Override
+///
+/// Returns `None` for other methods or override targets outside the allowlist.
+pub fn method_override(method: &Method, headers: &HeaderMap) -> Option<Method> {
+    if method != Method::POST {
+        return None;
+    }
+
+    let value = headers.get(METHOD_OVERRIDE_HEADER)?.to_str().ok()?.trim();
+    METHOD_OVERRIDE_ALLOWLIST
+        .iter()
+        .find(|allowed| allowed.as_str().eq_ignore_ascii_case(value))
+        .cloned()
+}
+
+/// Header carrying the caller's request deadline (epoch-ms or relative ms)
+pub const REQUEST_DEADLINE_HEADER: &str = "x-request-deadline";
+
+/// Values at or above this are treated as absolute epoch milliseconds
+/// (roughly September 2001), smaller values as relative milliseconds
+const ABSOLUTE_DEADLINE_THRESHOLD_MS: u64 = 1_000_000_000_000;
+
+/// Request deadline parsed from the X-Request-Deadline header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestDeadline {
+    /// Time remaining until the deadline
+    pub remaining: Duration,
+    /// Whether the caller sent an absolute epoch-ms deadline
+    pub absolute: bool,
+}
+
+impl RequestDeadline {
+    /// Parse the deadline header relative to the current epoch time in milliseconds
+    pub fn from_headers(headers: &HeaderMap, now_ms: u64) -> Option<Self> {
+        let value: u64 = headers
+            .get(REQUEST_DEADLINE_HEADER)?
+            .to_str()
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+
+        if value >= ABSOLUTE_DEADLINE_THRESHOLD_MS {
+            Some(Self {
+                remaining: Duration::from_millis(value.saturating_sub(now_ms)),
+                absolute: true,
+            })
+        } else {
+            Some(Self {
+                remaining: Duration::from_millis(value),
+                absolute: false,
+            })
+        }
+    }
+
+    /// Whether the deadline has already passed
+    pub fn is_expired(&self) -> bool {
+        self.remaining.is_zero()
+    }
+
+    /// Clamp a request timeout to the time remaining before the deadline
+    pub fn clamp_timeout(&self, timeout: Duration) -> Duration {
+        timeout.min(self.remaining)
+    }
+
+    /// Header value to propagate to the backend for the given effective timeout
+    pub fn propagated_value(&self, timeout: Duration, now_ms: u64) -> String {
+        let timeout_ms = timeout.as_millis() as u64;
+        if self.absolute {
+            (now_ms + timeout_ms).to_string()
+        } else {
+            timeout_ms.to_string()
+        }
+    }
+}
+
+/// Current time as epoch milliseconds
+fn now_epoch_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Build the HTTP client used for backend requests
+fn build_client(
+    timeout: Duration,
+    upstream: &UpstreamConfig,
+    dns_cache: Option<&Arc<DnsCache>>,
+    tls_config: Option<&rustls::ClientConfig>,
+) -> reqwest::Client {
+    let mut builder = reqwest::Client::builder()
+        .timeout(timeout)
+        .pool_idle_timeout(upstream.pool_idle_timeout_secs.map(Duration::from_secs))
+        .tcp_keepalive(upstream.tcp_keepalive_secs.map(Duration::from_secs));
+
+    if let Some(max_idle) = upstream.pool_max_idle_per_host {
+        builder = builder.pool_max_idle_per_host(max_idle);
+    }
+
+    if upstream.http2_prior_knowledge {
+        builder = builder.http2_prior_knowledge();
+    }
+
+    if let Some(dns_cache) = dns_cache {
+        builder = builder.dns_resolver(dns_cache.clone());
+    }
+
+    if let Some(tls_config) = tls_config {
+        builder = builder.use_preconfigured_tls(tls_config.clone());
+    }
+
+    builder.build().expect("Failed to create HTTP client")
+}
+
+/// Response to serve instead of a 503 while the route's circuit is open
+///
+/// A stale cached response is preferred when the fallback allows it, then
+/// the static fallback body. Returns `None` if neither is available.
+async fn circuit_open_fallback(
+    route: &Route,
+    method: &Method,
+    path: &str,
+    query: Option<&str>,
+    request_headers: &HeaderMap,
+) -> Option<Response<Body>> {
+    let fallback = route.fallback.as_ref()?;
+
+    if fallback.serve_stale {
+        if let Some(cache) = &route.cache {
+            let cache_key = CacheKey::new(
+                method.to_string(),
+                path.to_string(),
+                query.map(|q| q.to_string()),
+                request_headers,
+                cache.key_headers(),
+            );
+            if let Some(cached) = cache.get_stale(&cache_key, request_headers).await {
+                return Some(cached.to_stale_response());
+            }
+        }
+    }
+
+    let body = fallback.body.clone()?;
+    Response::builder()
+        .status(fallback.status)
+        .header(CONTENT_TYPE, &fallback.content_type)
+        .body(Body::from(body))
+        .ok()
+}
+
+/// Records requests whose client went away before the response was ready
+///
+/// Hyper drops the handler future when the client connection closes, which
+/// cancels the in-flight backend request and any pending retries with it;
+/// nothing on that path may be spawned off. If this guard is dropped before
+/// `finish` is called, the request was abandoned.
+struct DisconnectGuard {
+    method: Method,
+    path: String,
+    start: Instant,
+    finished: bool,
+}
+
+impl DisconnectGuard {
+    fn new(req: &Request<Body>) -> Self {
         Self {
-            router: Arc::new(router),
-            client,
-            auth_service: auth_service.map(Arc::new),
-            circuit_breaker: circuit_breaker.map(Arc::new),
-            retry_executor: retry_executor.map(Arc::new),
+            method: req.method().clone(),
+            path: req.uri().path().to_string(),
+            start: Instant::now(),
+            finished: false,
+        }
+    }
+
+    fn finish(mut self, response: Response<Body>) -> Response<Body> {
+        self.finished = true;
+        response
+    }
+}
+
+impl Drop for DisconnectGuard {
+    fn drop(&mut self) {
+        if !self.finished {
+            info!(
+                method = %self.method,
+                path = %self.path,
+                elapsed_ms = self.start.elapsed().as_millis() as u64,
+                "Client disconnected, aborting backend request"
+            );
+            metrics::record_client_disconnect(self.method.as_str(), &self.path);
         }
     }
 }
 
 /// Main proxy handler that forwards requests to backend services
+///
+/// Routes with a CORS configuration have preflight requests answered here
+/// and CORS headers added to every response, including errors. If the client
+/// disconnects first, the backend request and its retries are abandoned.
 #[axum::debug_handler]
 pub async fn proxy_handler(
     State(state): State<ProxyState>,
     connect_info: Option<ConnectInfo<SocketAddr>>,
     req: Request<Body>,
-) -> Result<impl IntoResponse> {
-    let method = req.method().clone();
+) -> Response<Body> {
+    let guard = DisconnectGuard::new(&req);
+    let response = handle_request(state, connect_info, req).await;
+    guard.finish(response)
+}
+
+/// Answer CORS preflights and forward everything else
+async fn handle_request(
+    state: ProxyState,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    req: Request<Body>,
+) -> Response<Body> {
+    let router = state.router.load_full();
+    let Some(cors) = router
+        .find_route(req.uri().path())
+        .and_then(|route| route.cors.clone())
+    else {
+        return forward_request(state, router, connect_info, req)
+            .await
+            .into_response();
+    };
+
+    if CorsService::is_preflight(req.method(), req.headers()) {
+        debug!(path = %req.uri().path(), "Answering CORS preflight");
+        return cors.preflight_response(req.headers());
+    }
+
+    let request_headers = req.headers().clone();
+    let mut response = forward_request(state, router, connect_info, req)
+        .await
+        .into_response();
+    cors.apply_response_headers(&request_headers, response.headers_mut());
+    response
+}
+
+/// Route, authenticate and forward a request to its backend
+async fn forward_request(
+    state: ProxyState,
+    router: Arc<Router>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    mut req: Request<Body>,
+) -> Result<Response<Body>> {
     let uri = req.uri().clone();
     let path = uri.path();
     let query = uri.query();
-    let client_ip = connect_info
-        .map(|ConnectInfo(addr)| addr.ip())
+
+    let maintenance = state.maintenance.load();
+    if maintenance.applies_to(path) {
+        debug!(path = %path, "Answering request in maintenance mode");
+        return Ok(maintenance.response());
+    }
+
+    // Signatures cover the method the client actually sent
+    let original_method = req.method().clone();
+
+    // Treat tunneled POST requests as the overridden method if the route allows it
+    let overridden = method_override(req.method(), req.headers()).filter(|_| {
+        router.find_route(path).is_some_and(|route| {
+            route
+                .honor_method_override
+                .unwrap_or(state.honor_method_override)
+        })
+    });
+    if let Some(overridden) = overridden {
+        debug!(method = %overridden, "Honoring method override");
+        *req.method_mut() = overridden;
+        req.headers_mut().remove(METHOD_OVERRIDE_HEADER);
+    }
+    let method = req.method().clone();
+    // Prefer the client IP resolved through trusted proxies
+    let client_ip = req
+        .extensions()
+        .get::<ClientIp>()
+        .map(|ClientIp(ip)| *ip)
+        .or_else(|| connect_info.map(|ConnectInfo(addr)| addr.ip()))
         .unwrap_or_else(|| "127.0.0.1".parse().unwrap());
 
     // Start metrics timer
@@ -76,19 +542,88 @@ pub async fn proxy_handler(
         "Incoming request"
     );
 
+    // Enforce the caller's deadline if configured
+    let mut request_timeout = state.timeout;
+    let mut request_deadline = None;
+    let mut deadline_at = None;
+    if state.enforce_request_deadline {
+        if let Some(deadline) = RequestDeadline::from_headers(req.headers(), now_epoch_ms()) {
+            if deadline.is_expired() {
+                warn!(path = %path, "Request deadline already exceeded");
+                timer.record(504);
+                return Err(GatewayError::Timeout(
+                    "Request deadline exceeded".to_string(),
+                ));
+            }
+            request_timeout = deadline.clamp_timeout(request_timeout);
+            debug!(
+                timeout_ms = request_timeout.as_millis() as u64,
+                "Request timeout clamped to deadline"
+            );
+            request_deadline = Some(deadline);
+            deadline_at = Some(Instant::now() + deadline.remaining);
+        }
+    }
+
     // Check for health check bypass
-    if is_health_check_path(path) {
+    let is_health_check = is_health_check_path(&state.health_check_paths, path);
+    if is_health_check {
         debug!("Health check path detected, bypassing authentication");
     }
 
+    // Check the gateway-wide IP filter before any route-specific one
+    if let Some(ip_filter) = state.ip_filter.load_full() {
+        if !ip_filter.is_allowed(&client_ip) {
+            warn!(ip = %client_ip, "IP address blocked by global filter");
+            timer.record(403);
+            return Err(GatewayError::Forbidden(format!(
+                "Access denied for IP: {}",
+                client_ip
+            )));
+        }
+    }
+
+    // Paths off by a trailing slash are redirected under the `redirect` policy
+    if let Some(location) = router.redirect_location(path, query) {
+        debug!(path = %path, location = %location, "Redirecting to the route's path");
+        timer.record(StatusCode::PERMANENT_REDIRECT.as_u16());
+        return Ok(Redirect::permanent(&location).into_response());
+    }
+
+    // Answer OPTIONS with the route's methods rather than forwarding it
+    if method == Method::OPTIONS {
+        if let Some(route_match) = router
+            .match_options_route(path, req.headers(), query)
+            .ok()
+            .filter(|m| m.route.answer_options.unwrap_or(state.answer_options))
+        {
+            debug!(path = %path, "Answering OPTIONS request");
+            timer.record(StatusCode::NO_CONTENT.as_u16());
+            return Ok(options_response(&route_match.route));
+        }
+    }
+
     // Match the route
-    let route_match = state.router.match_route(path, &method)?;
+    let route_match = router.match_route(path, &method, req.headers(), query)?;
 
     debug!(
         params = ?route_match.params,
         "Route matched"
     );
 
+    // Disabled routes still match, so they can be switched off without
+    // falling through to another route
+    if !route_match.route.enabled {
+        debug!(path = %path, route = %route_match.route.path, "Route is disabled");
+        let status = route_match.route.disabled_status;
+        timer.record(status.as_u16());
+        return Err(if status == StatusCode::NOT_FOUND {
+            GatewayError::RouteNotFound(path.to_string())
+        } else {
+            GatewayError::RouteDisabled(route_match.route.path.clone())
+        });
+    }
+
     // Check IP filtering if configured
     if let Some(ip_filter) = &route_match.route.ip_filter {
         if !ip_filter.is_allowed(&client_ip) {
@@ -103,12 +638,37 @@ pub async fn proxy_handler(
     }
 
     // Perform authentication if required and not a health check
-    if !is_health_check_path(path) {
+    let mut token_query_param = None;
+    if !is_health_check {
         if let Some(route_auth) = &route_match.route.auth {
             if route_auth.required {
-                if let Some(auth_service) = &state.auth_service {
-                    let headers = req.headers();
-                    match auth_service.authenticate(headers, route_auth).await {
+                if let Some(auth_service) = state.auth_service.load_full() {
+                    // Signatures cover the body, so buffer it before it is
+                    // consumed for forwarding and hand it back to the request
+                    let body = match auth_service.body_limit(req.headers(), route_auth) {
+                        Some(limit) => match buffer_body(req, limit).await {
+                            Ok((buffered, bytes)) => {
+                                req = buffered;
+                                Some(bytes)
+                            }
+                            Err(e) => {
+                                if matches!(e, GatewayError::PayloadTooLarge(_)) {
+                                    warn!(limit, "Signed request body exceeds the buffering limit");
+                                    timer.record(413);
+                                }
+                                return Err(e);
+                            }
+                        },
+                        None => None,
+                    };
+                    let auth_request = AuthRequest {
+                        method: &original_method,
+                        path_and_query: uri.path_and_query().map_or(path, |pq| pq.as_str()),
+                        query,
+                        headers: req.headers(),
+                        body: body.as_deref(),
+                    };
+                    match auth_service.authenticate(&auth_request, route_auth).await {
                         Ok(auth_result) => {
                             info!(
                                 user_id = %auth_result.user_id,
@@ -120,13 +680,25 @@ pub async fn proxy_handler(
                                 &format!("{:?}", auth_result.method),
                                 true,
                             );
+                            token_query_param =
+                                auth_service.token_query_param().map(str::to_string);
+                            req.extensions_mut().insert(AuthExtension {
+                                auth_result: Some(auth_result),
+                            });
                         }
                         Err(e) => {
                             warn!(error = %e, "Authentication failed");
                             // Record failed authentication
                             metrics::record_auth_attempt("unknown", false);
                             timer.record(401);
-                            return Err(e);
+                            // Tell the client which schemes the route accepts
+                            let mut response = e.into_response();
+                            if response.status() == StatusCode::UNAUTHORIZED {
+                                for challenge in auth_service.challenges(route_auth) {
+                                    response.headers_mut().append(WWW_AUTHENTICATE, challenge);
+                                }
+                            }
+                            return Ok(response);
                         }
                     }
                 } else {
@@ -139,9 +711,31 @@ pub async fn proxy_handler(
         }
     }
 
-    // Check cache if configured
+    // Rate limits run after authentication so they can key on the caller;
+    // background cache refreshes are the gateway's own and aren't counted
+    if !is_health_check && req.extensions().get::<CacheRefresh>().is_none() {
+        if let Some(rate_limiter) = state.rate_limiter.load_full() {
+            match rate_limiter.apply_authenticated(req).await {
+                Ok(limited) => req = limited,
+                Err(response) => {
+                    timer.record(response.status().as_u16());
+                    return Ok(response);
+                }
+            }
+        }
+    }
+
+    // Check cache if configured; background refreshes and clients sending
+    // `Cache-Control: no-cache` always go to the backend
     let request_headers = req.headers().clone();  // Clone headers for cache key before consuming req
-    if let Some(cache) = &route_match.route.cache {
+    let is_refresh = req.extensions().get::<CacheRefresh>().is_some();
+    if is_refresh {
+        timer.set_cache_refresh();
+    }
+    let bypass_cache = is_refresh || has_request_directive(&request_headers, "no-cache");
+    // Held while this request fills a missing cache entry for concurrent ones
+    let mut _fill_guard = None;
+    if let Some(cache) = route_match.route.cache.as_ref().filter(|_| !bypass_cache) {
         let cache_key = CacheKey::new(
             method.to_string(),
             path.to_string(),
@@ -150,22 +744,72 @@ pub async fn proxy_handler(
             cache.key_headers(),
         );
 
-        if let Some(cached_response) = cache.get(&cache_key).await {
-            debug!(
-                method = %method,
-                path = %path,
-                "Returning cached response"
-            );
-            timer.record(cached_response.status.as_u16());
-            return Ok(cached_response.to_response());
+        // Clients revalidating a GET or HEAD they already hold get a bodiless 304
+        let not_modified = |cached: &CachedResponse| {
+            matches!(method, Method::GET | Method::HEAD) && cached.is_not_modified(&request_headers)
+        };
+
+        let mut lookup = cache.get_or_stale(&cache_key, &request_headers).await;
+
+        // On a miss, only one of the concurrent identical requests goes to the
+        // backend; the rest wait for it and are served what it cached
+        if matches!(lookup, CacheLookup::Miss) && cache.is_cacheable_method(method.as_str()) {
+            match cache.join_fill(&cache_key) {
+                CacheFill::Leader(guard) => _fill_guard = Some(guard),
+                CacheFill::Follower(waiter) => {
+                    debug!(method = %method, path = %path, "Waiting for in-flight cache fill");
+                    waiter.wait().await;
+                    lookup = cache.get_or_stale(&cache_key, &request_headers).await;
+                }
+            }
+        }
+        match lookup {
+            CacheLookup::Fresh(cached_response) => {
+                metrics::record_cache_hit(&route_match.route.path);
+                debug!(
+                    method = %method,
+                    path = %path,
+                    "Returning cached response"
+                );
+                let response = if not_modified(&cached_response) {
+                    cached_response.to_not_modified_response()
+                } else {
+                    cached_response.to_response()
+                };
+                timer.record(response.status().as_u16());
+                return Ok(response);
+            }
+            CacheLookup::Stale { response: cached_response, key } => {
+                metrics::record_cache_hit(&route_match.route.path);
+                if let Some(guard) = cache.try_begin_refresh(&key) {
+                    spawn_cache_refresh(
+                        state.clone(),
+                        router.clone(),
+                        connect_info,
+                        &req,
+                        &route_match.route.path,
+                        guard,
+                    );
+                }
+                let mut response = if not_modified(&cached_response) {
+                    cached_response.to_not_modified_response()
+                } else {
+                    cached_response.to_response()
+                };
+                response
+                    .headers_mut()
+                    .insert("X-Cache", HeaderValue::from_static("STALE"));
+                timer.record(response.status().as_u16());
+                return Ok(response);
+            }
+            CacheLookup::Miss => metrics::record_cache_miss(&route_match.route.path),
         }
     }
 
-    // Select backend using load balancer
+    // Select backend from the stable or canary group
     let backend = match route_match
         .route
-        .load_balancer
-        .select_backend(Some(client_ip))
+        .select_backend(Some(client_ip), &request_headers)
     {
         Some(backend) => backend,
         None => {
@@ -196,34 +840,50 @@ pub async fn proxy_handler(
         path.to_string()
     };
 
+    // The query-string token is a credential for the gateway only
+    let backend_query = match &token_query_param {
+        Some(name) => query.and_then(|q| remove_query_param(q, name)),
+        None => query.map(str::to_string),
+    };
+
     // Apply query parameter transformation if configured
     let transformed_query = if let Some(transform) = &route_match.route.transform {
-        if let Some(q) = query {
-            Some(transform.transform_query_params(q))
-        } else {
-            None
-        }
+        backend_query.map(|q| transform.transform_query_params(&q))
     } else {
-        query.map(|q| q.to_string())
+        backend_query
     };
 
     // Build backend URL with transformations
-    let mut backend_url = route_match.build_backend_url(backend.url(), &transformed_path);
+    let mut backend_url = route_match.build_backend_url(backend.url(), &transformed_path)?;
     if let Some(q) = transformed_query.as_ref() {
-        backend_url.push('?');
-        backend_url.push_str(q);
+        append_query(&mut backend_url, q);
     }
 
     debug!(backend_url = %backend_url, "Forwarding to backend");
 
     let backend_url_for_cb = backend.url().to_string();
 
-    // Check circuit breaker
-    if let Some(circuit_breaker) = &state.circuit_breaker {
+    // Check circuit breaker, preferring the route's own breaker
+    let circuit_breaker = route_match
+        .route
+        .circuit_breaker
+        .clone()
+        .or_else(|| state.circuit_breaker.load_full());
+    if let Some(circuit_breaker) = &circuit_breaker {
         if !circuit_breaker.can_proceed(&backend_url_for_cb).await {
-            warn!(backend = %backend_url_for_cb, "Circuit breaker open, rejecting request");
             // Record circuit breaker state as open
             metrics::record_circuit_breaker_state(&backend_url_for_cb, 1);
+
+            if let Some(response) =
+                circuit_open_fallback(&route_match.route, &method, path, query, &request_headers)
+                    .await
+            {
+                warn!(backend = %backend_url_for_cb, "Circuit breaker open, serving fallback");
+                timer.record(response.status().as_u16());
+                return Ok(response);
+            }
+
+            warn!(backend = %backend_url_for_cb, "Circuit breaker open, rejecting request");
             timer.record(503);
             return Err(GatewayError::CircuitBreakerOpen(format!(
                 "Circuit breaker is open for backend: {}",
@@ -232,8 +892,8 @@ pub async fn proxy_handler(
         }
     }
 
-    // Track connection for least connections strategy
-    backend.increment_connections();
+    // Track connection for least connections strategy; released on every exit path
+    let connection = backend.track_connection();
 
     // Record active connections
     metrics::record_active_connections(backend.url(), backend.active_connections() as i64);
@@ -241,10 +901,31 @@ pub async fn proxy_handler(
     // Collect request body and headers for potential retries
     let method_for_request = req.method().clone();
     let mut headers_for_request = req.headers().clone();
+    let request_id = get_request_id(&req);
 
     // Apply request header transformations if configured
     if let Some(transform) = &route_match.route.transform {
-        transform.transform_request_headers(&mut headers_for_request)?;
+        let context = TemplateContext {
+            request_id: request_id.as_deref(),
+            client_ip,
+            method: method.as_str(),
+            path,
+            params: &route_match.params,
+        };
+        transform.transform_request_headers(&mut headers_for_request, &context)?;
+    }
+
+    // Continue the caller's trace at the backend
+    if let Some(RequestTraceContext(cx)) = req.extensions().get::<RequestTraceContext>() {
+        inject_trace_context(cx, &mut headers_for_request);
+    }
+
+    // Propagate the (possibly reduced) deadline to the backend
+    if let Some(deadline) = &request_deadline {
+        let value = deadline.propagated_value(request_timeout, now_epoch_ms());
+        if let Ok(value) = value.parse() {
+            headers_for_request.insert(REQUEST_DEADLINE_HEADER, value);
+        }
     }
 
     let body_bytes = req
@@ -253,54 +934,146 @@ pub async fn proxy_handler(
         .await
         .map_err(|e| GatewayError::Proxy(format!("Failed to read request body: {}", e)))?
         .to_bytes();
+    metrics::record_request_size(method.as_str(), path, body_bytes.len() as u64);
+
+    // Apply JSON body transformations; only configured routes pay for parsing
+    let body_bytes = match &route_match.route.transform {
+        Some(transform) if transform.has_body_transform() => {
+            transform.transform_request_body(&mut headers_for_request, body_bytes)
+        }
+        _ => body_bytes,
+    };
+
+    // Shadow a sample of traffic to the mirror backend; its outcome is ignored
+    if let Some(mirror) = route_match
+        .route
+        .mirror
+        .as_ref()
+        .filter(|m| m.should_mirror())
+    {
+        if let Some(permit) = mirror.try_acquire() {
+            let mut mirror_url =
+                route_match.build_backend_url(mirror.backend(), &transformed_path)?;
+            if let Some(q) = transformed_query.as_ref() {
+                append_query(&mut mirror_url, q);
+            }
+            let mirrored = mirror_request(
+                state.client.clone(),
+                method_for_request.clone(),
+                headers_for_request.clone(),
+                body_bytes.clone(),
+                mirror_url,
+                request_timeout,
+                BackendOptions::for_route(&route_match.route),
+            );
+            tokio::spawn(async move {
+                mirrored.await;
+                // Frees the mirror's in-flight slot
+                drop(permit);
+            });
+        } else {
+            debug!(route = %route_match.route.path, "Mirror saturated, dropping mirrored request");
+            metrics::record_mirror_dropped(&route_match.route.path);
+        }
+    }
 
     // Forward the request with retry logic if configured
-    let response: Result<Response<Body>> = if let Some(retry_executor) = &state.retry_executor {
+    let backend_options = BackendOptions::for_route(&route_match.route);
+    let backend_start = Instant::now();
+    let (mut response, attempts) = if let Some(retry_executor) = state.retry_executor.load_full() {
         let client = state.client.clone();
         let backend_url_clone = backend_url.clone();
         let method_clone = method_for_request.clone();
         let headers_clone = headers_for_request.clone();
         let body_clone = body_bytes.clone();
+        let log_context = RetryLogContext {
+            request_id: request_id.clone(),
+            backend: backend.url().to_string(),
+        };
+        let retry_on_status = retry_executor.config().retry_on_status.clone();
 
-        retry_executor
-            .execute_with_predicate(
+        let (result, attempts) = retry_executor
+            .execute_for_request(
+                &log_context,
+                deadline_at,
                 || {
                     let client = client.clone();
                     let backend_url = backend_url_clone.clone();
                     let method = method_clone.clone();
                     let headers = headers_clone.clone();
                     let body = body_clone.clone();
-                    async move { send_request(client, method, headers, body, &backend_url).await }
+                    let retry_on_status = &retry_on_status;
+                    let backend_options = &backend_options;
+                    // Retries only get the time left before the deadline
+                    let timeout = deadline_at.map_or(request_timeout, |deadline_at| {
+                        request_timeout.min(deadline_at.saturating_duration_since(Instant::now()))
+                    });
+                    async move {
+                        let response = send_request(
+                            client,
+                            method,
+                            headers,
+                            body,
+                            &backend_url,
+                            timeout,
+                            backend_options,
+                        )
+                        .await
+                        .map_err(AttemptFailure::Error)?;
+                        if retry_on_status.contains(&response.status().as_u16()) {
+                            Err(AttemptFailure::Status(response))
+                        } else {
+                            Ok(response)
+                        }
+                    }
                 },
-                |e| {
-                    // Only retry on timeout or connection errors
-                    matches!(e, GatewayError::Timeout(_) | GatewayError::Backend(_))
+                AttemptFailure::is_retryable,
+                |failure| match failure {
+                    AttemptFailure::Status(response) => response
+                        .headers()
+                        .get(RETRY_AFTER)
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| parse_retry_after(value, SystemTime::now())),
+                    AttemptFailure::Error(_) => None,
                 },
             )
-            .await
+            .await;
+
+        // The last retryable response is still the backend's answer
+        let result = match result {
+            Ok(response) | Err(AttemptFailure::Status(response)) => Ok(response),
+            Err(AttemptFailure::Error(e)) => Err(e),
+        };
+        (result, attempts)
     } else {
-        send_request(
+        let response = send_request(
             state.client.clone(),
             method_for_request,
             headers_for_request,
             body_bytes,
             &backend_url,
+            request_timeout,
+            &backend_options,
         )
-        .await
+        .await;
+        (response, 1)
     };
 
+    let backend_elapsed = backend_start.elapsed();
+    timer.set_backend_duration(backend_elapsed);
+
     // Decrement connection counter
-    backend.decrement_connections();
+    drop(connection);
 
     // Record active connections after decrement
     metrics::record_active_connections(backend.url(), backend.active_connections() as i64);
 
     // Record result in circuit breaker
-    if let Some(circuit_breaker) = &state.circuit_breaker {
+    if let Some(circuit_breaker) = &circuit_breaker {
         match &response {
             Ok(resp) => {
-                // Consider 5xx status codes as failures
-                if resp.status().is_server_error() {
+                // Consider configured status codes (all 5xx by default) as failures
+                if circuit_breaker.is_failure_status(resp.status().as_u16()) {
                     circuit_breaker.record_failure(&backend_url_for_cb).await;
                     metrics::record_circuit_breaker_state(&backend_url_for_cb, 0);
                 } else {
@@ -313,6 +1086,10 @@ pub async fn proxy_handler(
                     circuit_breaker.record_timeout(&backend_url_for_cb).await;
                     metrics::record_circuit_breaker_state(&backend_url_for_cb, 0);
                 }
+                // The backend answered; only the gateway's size limit rejected it
+                GatewayError::ResponseTooLarge(_) => {
+                    circuit_breaker.record_ignored(&backend_url_for_cb).await;
+                }
                 _ => {
                     circuit_breaker.record_failure(&backend_url_for_cb).await;
                     metrics::record_circuit_breaker_state(&backend_url_for_cb, 0);
@@ -321,22 +1098,45 @@ pub async fn proxy_handler(
         }
     }
 
-    // Passive health check
-    if let Some(health_checker) = &route_match.route.health_checker {
+    // Passive health check, which oversized responses don't count towards either
+    if let Some(health_checker) = route_match
+        .route
+        .health_checker
+        .as_ref()
+        .filter(|_| !matches!(response, Err(GatewayError::ResponseTooLarge(_))))
+    {
         let success = response.is_ok() && !response.as_ref().unwrap().status().is_server_error();
         health_checker.passive_check(&backend, success);
 
         // Update backend health metric
         metrics::record_backend_health(backend.url(), backend.is_healthy());
     }
+    metrics::record_backend_stats(backend.url(), &backend.health_stats());
+
+    // Rewrite the status before it is logged, recorded in metrics and cached;
+    // the circuit breaker and health checks above see the backend's own status
+    if let (Ok(resp), Some(transform)) = (response.as_mut(), &route_match.route.transform) {
+        *resp.status_mut() = transform.transform_status(resp.status());
+    }
 
     // Record final metrics and log result
     let final_status = match &response {
         Ok(resp) => {
             let status_code = resp.status().as_u16();
+            // Backend bodies are buffered, so the size hint is exact
+            if let Some(size) = resp.body().size_hint().exact().or_else(|| {
+                resp.headers()
+                    .get(CONTENT_LENGTH)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse().ok())
+            }) {
+                metrics::record_response_size(method.as_str(), path, status_code, size);
+            }
             info!(
+                request_id = request_id.as_deref().unwrap_or("-"),
                 status = %resp.status(),
                 backend = %backend.url(),
+                attempts,
                 latency_ms = timer.elapsed() * 1000.0,
                 "Request completed"
             );
@@ -352,8 +1152,10 @@ pub async fn proxy_handler(
                 _ => 502,
             };
             warn!(
+                request_id = request_id.as_deref().unwrap_or("-"),
                 error = %e,
                 backend = %backend.url(),
+                attempts,
                 latency_ms = timer.elapsed() * 1000.0,
                 "Request failed"
             );
@@ -362,6 +1164,7 @@ pub async fn proxy_handler(
     };
 
     // Record metrics with timer
+    let total_elapsed = Duration::from_secs_f64(timer.elapsed());
     timer.record(final_status);
 
     // Apply response transformations and caching if successful
@@ -371,6 +1174,7 @@ pub async fn proxy_handler(
     if let Some(transform) = &route_match.route.transform {
         let headers = final_response.headers_mut();
         transform.transform_response_headers(headers)?;
+        transform.add_timing_headers(headers, total_elapsed, backend_elapsed);
     }
 
     // Store in cache if configured and response is cacheable
@@ -396,6 +1200,7 @@ pub async fn proxy_handler(
         cache
             .put(
                 cache_key,
+                &request_headers,
                 parts.status,
                 parts.headers.clone(),
                 body_bytes.clone(),
@@ -406,9 +1211,205 @@ pub async fn proxy_handler(
         final_response = Response::from_parts(parts, Body::from(body_bytes));
     }
 
+    // Report the serving backend in the access log
+    final_response
+        .extensions_mut()
+        .insert(UpstreamBackend(backend.url().to_string()));
+
     Ok(final_response)
 }
 
+/// Marks a request issued by the gateway to refresh a stale cache entry
+#[derive(Debug, Clone, Copy)]
+struct CacheRefresh;
+
+/// Re-send a request in the background so its stale cache entry is replaced
+///
+/// The refresh goes through the normal forwarding path, skipping the cache
+/// lookup, and the guard is held until it completes. It is counted in
+/// `gateway_cache_refreshes_total` rather than as a client request.
+fn spawn_cache_refresh(
+    state: ProxyState,
+    router: Arc<Router>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    req: &Request<Body>,
+    route: &str,
+    guard: RefreshGuard,
+) {
+    let mut refresh = Request::new(Body::empty());
+    *refresh.method_mut() = req.method().clone();
+    *refresh.uri_mut() = req.uri().clone();
+    *refresh.headers_mut() = req.headers().clone();
+    refresh.extensions_mut().insert(CacheRefresh);
+    if let Some(client_ip) = req.extensions().get::<ClientIp>() {
+        refresh.extensions_mut().insert(*client_ip);
+    }
+
+    // Boxed so the recursive call into `forward_request` has a nameable type
+    let forward: Pin<Box<dyn Future<Output = Result<Response<Body>>> + Send>> =
+        Box::pin(forward_request(state, router, connect_info, refresh));
+    let route = route.to_string();
+    tokio::spawn(async move {
+        match forward.await {
+            Ok(response) => {
+                metrics::record_cache_refresh(&route, response.status().as_u16());
+                debug!(status = %response.status(), "Refreshed stale cache entry");
+            }
+            Err(e) => {
+                metrics::record_cache_refresh(&route, e.status_code().as_u16());
+                warn!(error = %e, "Failed to refresh stale cache entry");
+            }
+        }
+        drop(guard);
+    });
+}
+
+/// Empty `204` listing a route's methods in the `Allow` header
+fn options_response(route: &Route) -> Response<Body> {
+    let allow = route
+        .allowed_methods()
+        .iter()
+        .map(Method::as_str)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = StatusCode::NO_CONTENT;
+    if let Ok(allow) = HeaderValue::from_str(&allow) {
+        response.headers_mut().insert(ALLOW, allow);
+    }
+    response
+}
+
+/// Append a request's query string to a backend URL, which may already have
+/// one from a URL template
+fn append_query(url: &mut String, query: &str) {
+    url.push(if url.contains('?') { '&' } else { '?' });
+    url.push_str(query);
+}
+
+/// Remove every occurrence of a parameter from a query string, keeping the
+/// rest as sent; `None` if nothing is left
+fn remove_query_param(query: &str, name: &str) -> Option<String> {
+    let kept = query
+        .split('&')
+        .filter(|pair| {
+            url::form_urlencoded::parse(pair.as_bytes())
+                .next()
+                .is_none_or(|(key, _)| key != name)
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+    (!kept.is_empty()).then_some(kept)
+}
+
+/// Send a copy of a request to a mirror backend, logging the outcome
+async fn mirror_request(
+    client: reqwest::Client,
+    method: Method,
+    headers: HeaderMap,
+    body_bytes: Bytes,
+    mirror_url: String,
+    timeout: Duration,
+    options: BackendOptions,
+) {
+    match send_request(
+        client,
+        method,
+        headers,
+        body_bytes,
+        &mirror_url,
+        timeout,
+        &options,
+    )
+    .await
+    {
+        Ok(response) => {
+            debug!(mirror_url = %mirror_url, status = %response.status(), "Mirrored request")
+        }
+        Err(e) => debug!(mirror_url = %mirror_url, error = %e, "Mirrored request failed"),
+    }
+}
+
+/// Read a request body of at most `limit` bytes
+///
+/// Returns the request with the body restored, so it can still be forwarded.
+async fn buffer_body(req: Request<Body>, limit: usize) -> Result<(Request<Body>, Bytes)> {
+    let (parts, body) = req.into_parts();
+    let bytes = match Limited::new(body, limit).collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(e) if e.downcast_ref::<LengthLimitError>().is_some() => {
+            return Err(GatewayError::PayloadTooLarge(format!(
+                "Request body exceeds {} bytes",
+                limit
+            )));
+        }
+        Err(e) => {
+            return Err(GatewayError::Proxy(format!(
+                "Failed to read request body: {}",
+                e
+            )));
+        }
+    };
+    Ok((Request::from_parts(parts, Body::from(bytes.clone())), bytes))
+}
+
+/// Why a retried backend attempt didn't succeed
+enum AttemptFailure {
+    /// The request failed outright
+    Error(GatewayError),
+    /// The backend answered with a status configured for retries
+    Status(Response<Body>),
+}
+
+impl AttemptFailure {
+    /// Whether another attempt may be made after this failure
+    fn is_retryable(&self) -> bool {
+        match self {
+            // Only retry on timeout or connection errors
+            AttemptFailure::Error(e) => {
+                matches!(e, GatewayError::Timeout(_) | GatewayError::Backend(_))
+            }
+            // A backend shedding load must not see its requests multiplied, so
+            // its 429 (and Retry-After) is passed straight to the client
+            AttemptFailure::Status(response) => response.status() != StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+}
+
+impl std::fmt::Display for AttemptFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttemptFailure::Error(e) => e.fmt(f),
+            AttemptFailure::Status(response) => {
+                write!(f, "Backend responded with {}", response.status())
+            }
+        }
+    }
+}
+
+/// Route settings applied to requests sent to a backend
+#[derive(Debug, Clone, Default)]
+struct BackendOptions {
+    /// Largest response body accepted, in bytes
+    max_response_bytes: Option<usize>,
+    /// Hop-by-hop request headers forwarded anyway (lowercase)
+    preserve_headers: Vec<String>,
+}
+
+impl BackendOptions {
+    fn for_route(route: &Route) -> Self {
+        Self {
+            max_response_bytes: route.max_response_bytes,
+            preserve_headers: route.preserve_headers.clone(),
+        }
+    }
+
+    /// Whether a request header is forwarded to the backend
+    fn forwards_header(&self, name: &str) -> bool {
+        !is_hop_by_hop_header(name) || self.preserve_headers.iter().any(|h| h == name)
+    }
+}
+
 /// Send request to the backend service
 async fn send_request(
     client: reqwest::Client,
@@ -416,17 +1417,18 @@ async fn send_request(
     headers: HeaderMap,
     body_bytes: Bytes,
     backend_url: &str,
+    timeout: Duration,
+    options: &BackendOptions,
 ) -> Result<Response<Body>> {
     // Build the backend request
     let mut backend_req = client
         .request(method.clone(), backend_url)
+        .timeout(timeout)
         .body(body_bytes.to_vec());
 
-    // Forward headers (excluding hop-by-hop headers)
+    // Forward headers (excluding hop-by-hop headers the route doesn't preserve)
     for (name, value) in headers.iter() {
-        let name_str = name.as_str();
-        // Skip hop-by-hop headers
-        if !is_hop_by_hop_header(name_str) {
+        if options.forwards_header(name.as_str()) {
             backend_req = backend_req.header(name, value);
         }
     }
@@ -455,10 +1457,7 @@ async fn send_request(
     }
 
     // Get response body
-    let body_bytes = backend_response
-        .bytes()
-        .await
-        .map_err(|e| GatewayError::Backend(format!("Failed to read backend response: {}", e)))?;
+    let body_bytes = read_response_body(backend_response, options.max_response_bytes).await?;
 
     let response = response_builder
         .body(Body::from(body_bytes))
@@ -467,8 +1466,41 @@ async fn send_request(
     Ok(response)
 }
 
+/// Buffer a backend response body, giving up once it exceeds `limit` bytes
+async fn read_response_body(
+    mut backend_response: reqwest::Response,
+    limit: Option<usize>,
+) -> Result<Bytes> {
+    let read_error = |e: reqwest::Error| {
+        GatewayError::Backend(format!("Failed to read backend response: {}", e))
+    };
+    let Some(limit) = limit else {
+        return backend_response.bytes().await.map_err(read_error);
+    };
+    let too_large = || {
+        GatewayError::ResponseTooLarge(format!("Backend response exceeds the {} byte limit", limit))
+    };
+
+    // Reject up front when the backend announces an oversized body
+    if backend_response
+        .content_length()
+        .is_some_and(|length| length > limit as u64)
+    {
+        return Err(too_large());
+    }
+
+    let mut body = BytesMut::new();
+    while let Some(chunk) = backend_response.chunk().await.map_err(read_error)? {
+        if body.len() + chunk.len() > limit {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body.freeze())
+}
+
 /// Check if a header is a hop-by-hop header that should not be forwarded
-fn is_hop_by_hop_header(name: &str) -> bool {
+pub(crate) fn is_hop_by_hop_header(name: &str) -> bool {
     matches!(
         name.to_lowercase().as_str(),
         "connection"
@@ -483,11 +1515,8 @@ fn is_hop_by_hop_header(name: &str) -> bool {
 }
 
 /// Check if a path is a health check endpoint that should bypass authentication
-fn is_health_check_path(path: &str) -> bool {
-    matches!(
-        path,
-        "/health" | "/healthz" | "/ready" | "/readiness" | "/ping"
-    )
+fn is_health_check_path(health_check_paths: &[String], path: &str) -> bool {
+    health_check_paths.iter().any(|p| p == path)
 }
 
 #[cfg(test)]
@@ -523,6 +1552,18 @@ mod tests {
             cors: None,
             ip_filter: None,
             cache: None,
+            honor_method_override: None,
+            circuit_breaker: None,
+            fallback: None,
+            mirror: None,
+            canary: None,
+            match_headers: Default::default(),
+            match_query: Default::default(),
+            max_response_bytes: None,
+            answer_options: None,
+            preserve_headers: Vec::new(),
+            enabled: true,
+            disabled_status: 503,
         }];
 
         let _router = Router::new(routes).unwrap();
@@ -531,14 +1572,344 @@ mod tests {
         // State created successfully - just testing that creation doesn't panic
     }
 
+    #[test]
+    fn test_request_deadline_relative() {
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_DEADLINE_HEADER, "250".parse().unwrap());
+
+        let deadline = RequestDeadline::from_headers(&headers, 1_700_000_000_000).unwrap();
+        assert!(!deadline.absolute);
+        assert_eq!(deadline.remaining, Duration::from_millis(250));
+        assert_eq!(
+            deadline.clamp_timeout(Duration::from_secs(30)),
+            Duration::from_millis(250)
+        );
+        assert_eq!(
+            deadline.clamp_timeout(Duration::from_millis(100)),
+            Duration::from_millis(100)
+        );
+        assert_eq!(
+            deadline.propagated_value(Duration::from_millis(250), 1_700_000_000_000),
+            "250"
+        );
+    }
+
+    #[test]
+    fn test_request_deadline_absolute() {
+        let now_ms = 1_700_000_000_000;
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            REQUEST_DEADLINE_HEADER,
+            (now_ms + 2_000).to_string().parse().unwrap(),
+        );
+
+        let deadline = RequestDeadline::from_headers(&headers, now_ms).unwrap();
+        assert!(deadline.absolute);
+        assert_eq!(deadline.remaining, Duration::from_secs(2));
+        assert!(!deadline.is_expired());
+        assert_eq!(
+            deadline.propagated_value(Duration::from_secs(1), now_ms),
+            (now_ms + 1_000).to_string()
+        );
+    }
+
+    #[test]
+    fn test_request_deadline_expired() {
+        let now_ms = 1_700_000_000_000;
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            REQUEST_DEADLINE_HEADER,
+            (now_ms - 1).to_string().parse().unwrap(),
+        );
+        assert!(RequestDeadline::from_headers(&headers, now_ms)
+            .unwrap()
+            .is_expired());
+
+        headers.insert(REQUEST_DEADLINE_HEADER, "0".parse().unwrap());
+        assert!(RequestDeadline::from_headers(&headers, now_ms)
+            .unwrap()
+            .is_expired());
+    }
+
+    #[test]
+    fn test_request_deadline_missing_or_invalid() {
+        let mut headers = HeaderMap::new();
+        assert!(RequestDeadline::from_headers(&headers, 0).is_none());
+
+        headers.insert(REQUEST_DEADLINE_HEADER, "soon".parse().unwrap());
+        assert!(RequestDeadline::from_headers(&headers, 0).is_none());
+    }
+
+    #[test]
+    fn test_remove_query_param() {
+        assert_eq!(
+            remove_query_param("q=a%20b&access_token=abc&page=2", "access_token").as_deref(),
+            Some("q=a%20b&page=2")
+        );
+        assert_eq!(
+            remove_query_param("access%5Ftoken=abc&access_token=def", "access_token"),
+            None
+        );
+        assert_eq!(
+            remove_query_param("page=2", "access_token").as_deref(),
+            Some("page=2")
+        );
+    }
+
+    #[test]
+    fn test_method_override() {
+        let mut headers = HeaderMap::new();
+        headers.insert(METHOD_OVERRIDE_HEADER, "delete".parse().unwrap());
+        assert_eq!(
+            method_override(&Method::POST, &headers),
+            Some(Method::DELETE)
+        );
+
+        // Only POST requests can be overridden
+        assert_eq!(method_override(&Method::GET, &headers), None);
+
+        // Targets outside the allowlist are ignored
+        headers.insert(METHOD_OVERRIDE_HEADER, "CONNECT".parse().unwrap());
+        assert_eq!(method_override(&Method::POST, &headers), None);
+
+        assert_eq!(method_override(&Method::POST, &HeaderMap::new()), None);
+    }
+
     #[test]
     fn test_is_health_check_path() {
-        assert!(is_health_check_path("/health"));
-        assert!(is_health_check_path("/healthz"));
-        assert!(is_health_check_path("/ready"));
-        assert!(is_health_check_path("/readiness"));
-        assert!(is_health_check_path("/ping"));
-        assert!(!is_health_check_path("/api/users"));
-        assert!(!is_health_check_path("/healthy"));
+        let paths = default_health_check_paths();
+        assert!(is_health_check_path(&paths, "/health"));
+        assert!(is_health_check_path(&paths, "/healthz"));
+        assert!(is_health_check_path(&paths, "/ready"));
+        assert!(is_health_check_path(&paths, "/readiness"));
+        assert!(is_health_check_path(&paths, "/ping"));
+        assert!(!is_health_check_path(&paths, "/api/users"));
+        assert!(!is_health_check_path(&paths, "/healthy"));
+
+        let paths = vec!["/status".to_string()];
+        assert!(is_health_check_path(&paths, "/status"));
+        assert!(!is_health_check_path(&paths, "/health"));
+    }
+
+    #[test]
+    fn test_disconnect_guard_counts_abandoned_requests() {
+        let recorder = metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        let request = || {
+            Request::builder()
+                .uri("/api/users/12345")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        ::metrics::with_local_recorder(&recorder, || {
+            // Dropped before finishing, as when hyper cancels the handler
+            drop(DisconnectGuard::new(&request()));
+            DisconnectGuard::new(&request()).finish(Response::new(Body::empty()));
+        });
+
+        let rendered = handle.render();
+        assert!(
+            rendered.contains(
+                r#"gateway_client_disconnects_total{method="GET",path="/api/users/:id"} 1"#
+            ),
+            "{rendered}"
+        );
+    }
+
+    /// Backend answering every HTTP/1.1 request with `200 ok` on kept-alive
+    /// connections, counting the connections it accepts
+    async fn counting_backend() -> (String, Arc<std::sync::atomic::AtomicUsize>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let connections = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let accepted = connections.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                accepted.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    while matches!(stream.read(&mut buf).await, Ok(n) if n > 0) {
+                        let response = "HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok";
+                        if stream.write_all(response.as_bytes()).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        (url, connections)
+    }
+
+    async fn connections_for_two_requests(upstream: &UpstreamConfig) -> usize {
+        let (url, connections) = counting_backend().await;
+        let client = build_client(Duration::from_secs(5), upstream, None, None);
+        for _ in 0..2 {
+            let response = client.get(&url).send().await.unwrap();
+            assert_eq!(response.text().await.unwrap(), "ok");
+        }
+        connections.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn test_upstream_pool_reuses_connections() {
+        assert_eq!(
+            connections_for_two_requests(&UpstreamConfig::default()).await,
+            1
+        );
+
+        // Without idle connections every request opens a new one
+        let no_idle = UpstreamConfig {
+            pool_max_idle_per_host: Some(0),
+            ..UpstreamConfig::default()
+        };
+        assert_eq!(connections_for_two_requests(&no_idle).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_upstream_http2_prior_knowledge() {
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let preface = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 24];
+            stream.read_exact(&mut buf).await.unwrap();
+            buf
+        });
+
+        let upstream = UpstreamConfig {
+            http2_prior_knowledge: true,
+            tcp_keepalive_secs: Some(30),
+            ..UpstreamConfig::default()
+        };
+        let client = build_client(Duration::from_millis(500), &upstream, None, None);
+        // The backend never answers; only the connection preface matters
+        let _ = client.get(&url).send().await;
+
+        assert_eq!(&preface.await.unwrap(), b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n");
+    }
+
+    /// Backend streaming a chunked body of `chunks` 1 KiB chunks without
+    /// announcing its length
+    async fn chunked_backend(chunks: usize) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    let _ = stream.read(&mut buf).await;
+                    let mut response =
+                        "HTTP/1.1 200 OK\r\ntransfer-encoding: chunked\r\n\r\n".to_string();
+                    for _ in 0..chunks {
+                        response.push_str(&format!("400\r\n{}\r\n", "x".repeat(1024)));
+                    }
+                    response.push_str("0\r\n\r\n");
+                    let _ = stream.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        url
+    }
+
+    async fn send_with_limit(url: &str, limit: Option<usize>) -> Result<Response<Body>> {
+        let options = BackendOptions {
+            max_response_bytes: limit,
+            ..Default::default()
+        };
+        send_request(
+            reqwest::Client::new(),
+            Method::GET,
+            HeaderMap::new(),
+            Bytes::new(),
+            url,
+            Duration::from_secs(5),
+            &options,
+        )
+        .await
+    }
+
+    /// Parse the `_sum` of a histogram series from rendered Prometheus output
+    fn histogram_sum(rendered: &str, metric: &str) -> f64 {
+        rendered
+            .lines()
+            .find(|line| line.starts_with(&format!("{}_sum", metric)))
+            .and_then(|line| line.rsplit(' ').next())
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_else(|| panic!("{metric} not rendered:\n{rendered}"))
+    }
+
+    #[tokio::test]
+    async fn test_backend_duration_excludes_gateway_overhead() {
+        use futures::stream;
+        use tower::ServiceExt;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(50)))
+            .mount(&mock_server)
+            .await;
+
+        let config = crate::config::GatewayConfig::from_yaml(&format!(
+            "server: {{}}\nroutes:\n  - path: /upload\n    backend: {}\n",
+            mock_server.uri()
+        ))
+        .unwrap();
+        let state = ProxyState::new(
+            Router::new(config.routes).unwrap(),
+            Duration::from_secs(5),
+            None,
+            None,
+            None,
+        );
+        let app = axum::Router::new()
+            .route("/*path", axum::routing::any(proxy_handler))
+            .with_state(state);
+
+        // The request body trickles in, delaying the gateway before the backend call
+        let body = Body::from_stream(stream::once(async {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            Ok::<_, std::io::Error>(Bytes::from("payload"))
+        }));
+        let request = Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .body(body)
+            .unwrap();
+
+        let recorder = metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        let response = {
+            let _guard = ::metrics::set_default_local_recorder(&recorder);
+            app.oneshot(request).await.unwrap()
+        };
+        assert_eq!(response.status(), 200);
+
+        let rendered = handle.render();
+        let total = histogram_sum(&rendered, "gateway_request_duration_seconds");
+        let backend = histogram_sum(&rendered, "gateway_backend_duration_seconds");
+        assert!(total >= 0.25, "total {total}");
+        assert!((0.05..0.2).contains(&backend), "backend {backend}");
+    }
+
+    #[tokio::test]
+    async fn test_response_size_limit_on_streamed_body() {
+        let url = chunked_backend(8).await;
+
+        let response = send_with_limit(&url, Some(8 * 1024)).await.unwrap();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body.len(), 8 * 1024);
+        assert!(send_with_limit(&url, None).await.is_ok());
+
+        let err = send_with_limit(&url, Some(4 * 1024)).await.unwrap_err();
+        assert!(matches!(err, GatewayError::ResponseTooLarge(_)), "{err}");
+        assert_eq!(err.status_code(), http::StatusCode::BAD_GATEWAY);
     }
 }
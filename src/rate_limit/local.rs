@@ -1,3 +1,4 @@
+use super::redis::RateLimitAlgorithm;
 use super::types::{RateLimitConfig, RateLimitKey, RateLimitResult};
 use dashmap::DashMap;
 use governor::{
@@ -8,39 +9,88 @@ use governor::{
 use std::num::NonZeroU32;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::time::Instant;
 use tracing::{debug, warn};
 
-/// Local (in-memory) rate limiter using token bucket algorithm
+/// Local (in-memory) rate limiter using token bucket algorithm (or GCRA / leaky bucket)
 pub struct LocalRateLimiter {
     /// Map of rate limiters per key
     #[allow(clippy::type_complexity)]
     limiters: Arc<DashMap<String, Arc<GovernorRateLimiter<NotKeyed, InMemoryState, DefaultClock>>>>,
+    /// Theoretical arrival time per key (GCRA)
+    gcra_tats: Arc<DashMap<String, Instant>>,
+    /// Time the last queued request drains, per key (leaky bucket)
+    leaky_drains: Arc<DashMap<String, Instant>>,
     /// Default configuration
     config: RateLimitConfig,
+    /// Algorithm to use
+    algorithm: RateLimitAlgorithm,
 }
 
 impl LocalRateLimiter {
     /// Create a new local rate limiter
     pub fn new(config: RateLimitConfig) -> Self {
+        Self::with_algorithm(config, RateLimitAlgorithm::TokenBucket)
+    }
+
+    /// Create a new local rate limiter using the given algorithm
+    ///
+    /// GCRA and the leaky bucket have dedicated local implementations; other
+    /// algorithms use the token bucket.
+    pub fn with_algorithm(config: RateLimitConfig, algorithm: RateLimitAlgorithm) -> Self {
         Self {
             limiters: Arc::new(DashMap::new()),
+            gcra_tats: Arc::new(DashMap::new()),
+            leaky_drains: Arc::new(DashMap::new()),
             config,
+            algorithm,
         }
     }
 
     /// Check if a request is allowed
     pub async fn check_rate_limit(&self, key: &RateLimitKey) -> RateLimitResult {
+        self.check_rate_limit_with_config(key, &self.config).await
+    }
+
+    /// Check if a request is allowed using a specific configuration
+    ///
+    /// The limiter for a key is created from the configuration seen on its first request.
+    pub async fn check_rate_limit_with_config(
+        &self,
+        key: &RateLimitKey,
+        config: &RateLimitConfig,
+    ) -> RateLimitResult {
+        match self.algorithm {
+            RateLimitAlgorithm::Gcra => return self.check_gcra(key, config),
+            RateLimitAlgorithm::LeakyBucket => return self.check_leaky_bucket(key, config),
+            _ => {}
+        }
+
         let redis_key = key.to_redis_key();
 
-        // Get or create rate limiter for this key
+        // Get or create rate limiter for this key, falling back to the
+        // default configuration if this one can't form a quota
         let limiter = self
             .limiters
             .entry(redis_key.clone())
-            .or_insert_with(|| {
+            .or_try_insert_with(|| {
                 debug!("Creating new rate limiter for key: {}", redis_key);
-                Arc::new(self.create_limiter())
+                Self::create_limiter(config)
+                    .or_else(|| {
+                        warn!(
+                            "Invalid rate limit for key: {}, using the default limit",
+                            redis_key
+                        );
+                        Self::create_limiter(&self.config)
+                    })
+                    .map(Arc::new)
+                    .ok_or(())
             })
-            .clone();
+            .map(|limiter| limiter.clone());
+        let Ok(limiter) = limiter else {
+            warn!("No valid rate limit for key: {}, not limiting", redis_key);
+            return RateLimitResult::allowed(0, config.requests, config.window_secs);
+        };
 
         // Check the rate limit
         match limiter.check() {
@@ -50,34 +100,96 @@ impl LocalRateLimiter {
                 debug!("Rate limit check passed for key: {}", redis_key);
 
                 RateLimitResult::allowed(
-                    (self.config.requests / 2) as i64, // Conservative estimate
-                    self.config.requests,
-                    self.config.window_secs,
+                    (config.requests / 2) as i64, // Conservative estimate
+                    config.requests,
+                    config.window_secs,
                 )
             }
             Err(_) => {
                 // Request denied - use the window duration as retry_after
                 warn!("Rate limit exceeded for key: {}", redis_key);
 
-                RateLimitResult::denied(self.config.requests, self.config.window_secs)
+                RateLimitResult::denied(config.requests, config.window_secs)
             }
         }
     }
 
+    /// Check a request using GCRA, tracking one theoretical arrival time per key
+    fn check_gcra(&self, key: &RateLimitKey, config: &RateLimitConfig) -> RateLimitResult {
+        let redis_key = key.to_redis_key();
+        let emission_interval = config.emission_interval();
+        let burst_window = emission_interval * config.burst_size();
+        let now = Instant::now();
+
+        let mut tat = self.gcra_tats.entry(redis_key.clone()).or_insert(now);
+        let new_tat = (*tat).max(now) + emission_interval;
+        let allow_at = new_tat.checked_sub(burst_window).unwrap_or(now);
+
+        if now < allow_at {
+            warn!("Rate limit exceeded for key: {} (gcra)", redis_key);
+            return RateLimitResult::denied(config.requests, ceil_secs(allow_at - now));
+        }
+
+        *tat = new_tat;
+        let remaining = ((now - allow_at).as_nanos() / emission_interval.as_nanos().max(1)) as i64;
+        debug!("GCRA check passed for key: {}", redis_key);
+
+        RateLimitResult::allowed(remaining, config.requests, ceil_secs(new_tat - now))
+    }
+
+    /// Check a request using a leaky bucket, queueing it behind earlier requests
+    ///
+    /// Admitted requests are spaced one emission interval apart: the result
+    /// carries how long the request must wait for its slot. Once `burst_size`
+    /// requests are waiting the bucket is full and the request is rejected.
+    fn check_leaky_bucket(&self, key: &RateLimitKey, config: &RateLimitConfig) -> RateLimitResult {
+        let redis_key = key.to_redis_key();
+        let interval = config.emission_interval();
+        let capacity = config.burst_size();
+        let now = Instant::now();
+
+        let mut drain_at = self.leaky_drains.entry(redis_key.clone()).or_insert(now);
+        let next = (*drain_at).max(now);
+        let wait = next - now;
+        let queued = (wait.as_nanos() / interval.as_nanos().max(1)) as u32;
+
+        if queued >= capacity {
+            warn!("Rate limit exceeded for key: {} (leaky bucket)", redis_key);
+            let retry_after = wait.saturating_sub(interval * capacity) + Duration::from_millis(1);
+            return RateLimitResult::denied(config.requests, ceil_secs(retry_after));
+        }
+
+        *drain_at = next + interval;
+        debug!(
+            "Leaky bucket queued request for key: {} (delay {:?})",
+            redis_key, wait
+        );
+
+        RateLimitResult::allowed(
+            (capacity - queued - 1) as i64,
+            config.requests,
+            ceil_secs(wait + interval),
+        )
+        .with_delay(wait)
+    }
+
     /// Create a new governor rate limiter
-    fn create_limiter(&self) -> GovernorRateLimiter<NotKeyed, InMemoryState, DefaultClock> {
-        let quota = if let Some(burst) = self.config.burst {
+    ///
+    /// Returns None if the configuration has a zero limit, window or burst.
+    fn create_limiter(
+        config: &RateLimitConfig,
+    ) -> Option<GovernorRateLimiter<NotKeyed, InMemoryState, DefaultClock>> {
+        let quota = if let Some(burst) = config.burst {
             // Create quota with custom burst
-            Quota::with_period(Duration::from_secs(self.config.window_secs))
-                .unwrap()
-                .allow_burst(NonZeroU32::new(burst).unwrap())
+            Quota::with_period(Duration::from_secs(config.window_secs))?
+                .allow_burst(NonZeroU32::new(burst)?)
         } else {
             // Create quota with default burst (same as limit)
-            Quota::per_second(NonZeroU32::new(self.config.requests).unwrap())
-                .allow_burst(NonZeroU32::new(self.config.requests).unwrap())
+            let requests = NonZeroU32::new(config.requests)?;
+            Quota::per_second(requests).allow_burst(requests)
         };
 
-        GovernorRateLimiter::direct(quota)
+        Some(GovernorRateLimiter::direct(quota))
     }
 
     /// Get the number of active limiters (for testing/monitoring)
@@ -89,9 +201,16 @@ impl LocalRateLimiter {
     #[cfg(test)]
     pub fn clear(&self) {
         self.limiters.clear();
+        self.gcra_tats.clear();
+        self.leaky_drains.clear();
     }
 }
 
+/// Round a duration up to whole seconds (at least one)
+fn ceil_secs(duration: Duration) -> u64 {
+    (duration.as_millis() as u64).div_ceil(1000).max(1)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,6 +239,41 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_invalid_override_uses_default_limit() {
+        let config = RateLimitConfig {
+            dimension: RateLimitDimension::ApiKey,
+            requests: 2,
+            window_secs: 60,
+            burst: None,
+        };
+        let limiter = LocalRateLimiter::new(config.clone());
+        let key = RateLimitKey::new(RateLimitDimension::ApiKey, "tier-key".to_string());
+
+        for invalid in [
+            RateLimitConfig {
+                requests: 0,
+                ..config.clone()
+            },
+            RateLimitConfig {
+                burst: Some(0),
+                ..config.clone()
+            },
+            RateLimitConfig {
+                window_secs: 0,
+                burst: Some(5),
+                ..config.clone()
+            },
+        ] {
+            limiter.clear();
+            // Falls back to the 2-request default instead of panicking
+            for expect_allowed in [true, true, false] {
+                let result = limiter.check_rate_limit_with_config(&key, &invalid).await;
+                assert_eq!(result.allowed, expect_allowed, "{:?}", invalid);
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_local_rate_limiter_denies_over_limit() {
         let config = RateLimitConfig {
@@ -204,4 +358,102 @@ mod tests {
         let result = limiter.check_rate_limit(&key).await;
         assert!(result.allowed);
     }
+
+    fn gcra_limiter(requests: u32, window_secs: u64) -> LocalRateLimiter {
+        let config = RateLimitConfig {
+            dimension: RateLimitDimension::Ip,
+            requests,
+            window_secs,
+            burst: None,
+        };
+        LocalRateLimiter::with_algorithm(config, RateLimitAlgorithm::Gcra)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_local_gcra_burst_and_retry_after() {
+        // 10 requests per 60s: one request every 6s, burst of 10
+        let limiter = gcra_limiter(10, 60);
+        let key = RateLimitKey::new(RateLimitDimension::Ip, "192.168.1.1".to_string());
+
+        for i in 0..10 {
+            let result = limiter.check_rate_limit(&key).await;
+            assert!(result.allowed, "Request {} should be allowed", i);
+            assert_eq!(result.remaining, 9 - i);
+        }
+
+        let result = limiter.check_rate_limit(&key).await;
+        assert!(!result.allowed);
+        assert_eq!(result.retry_after, Some(6));
+
+        // After 4s the next request is still 2s away
+        tokio::time::advance(Duration::from_secs(4)).await;
+        let result = limiter.check_rate_limit(&key).await;
+        assert_eq!(result.retry_after, Some(2));
+
+        // Exactly one emission interval later a single request is allowed again
+        tokio::time::advance(Duration::from_secs(2)).await;
+        assert!(limiter.check_rate_limit(&key).await.allowed);
+        assert!(!limiter.check_rate_limit(&key).await.allowed);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_local_gcra_reset_after() {
+        let limiter = gcra_limiter(10, 60);
+        let key = RateLimitKey::new(RateLimitDimension::Ip, "192.168.1.2".to_string());
+
+        // One request leaves the bucket one emission interval from full
+        let result = limiter.check_rate_limit(&key).await;
+        assert_eq!(result.reset_after, 6);
+
+        for _ in 0..4 {
+            limiter.check_rate_limit(&key).await;
+        }
+        let result = limiter.check_rate_limit(&key).await;
+        assert_eq!(result.remaining, 4);
+        assert_eq!(result.reset_after, 36);
+
+        // Fully replenished after reset_after
+        tokio::time::advance(Duration::from_secs(36)).await;
+        let result = limiter.check_rate_limit(&key).await;
+        assert_eq!(result.remaining, 9);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_local_leaky_bucket_smooths_burst() {
+        // 10 requests per 10s with room for 5 queued: one request leaves every 1s
+        let config = RateLimitConfig {
+            dimension: RateLimitDimension::Ip,
+            requests: 10,
+            window_secs: 10,
+            burst: Some(5),
+        };
+        let limiter = LocalRateLimiter::with_algorithm(config, RateLimitAlgorithm::LeakyBucket);
+        let key = RateLimitKey::new(RateLimitDimension::Ip, "192.168.1.1".to_string());
+
+        // A burst is admitted but spread out at the drain rate
+        for i in 0..5u64 {
+            let result = limiter.check_rate_limit(&key).await;
+            assert!(result.allowed, "Request {} should be allowed", i);
+            assert_eq!(result.delay, Duration::from_secs(i));
+            assert_eq!(result.remaining, 4 - i as i64);
+        }
+
+        // The bucket is full
+        let result = limiter.check_rate_limit(&key).await;
+        assert!(!result.allowed);
+        assert_eq!(result.retry_after, Some(1));
+
+        // Draining one request makes room for exactly one more
+        tokio::time::advance(Duration::from_secs(1)).await;
+        let result = limiter.check_rate_limit(&key).await;
+        assert!(result.allowed);
+        assert_eq!(result.delay, Duration::from_secs(4));
+        assert!(!limiter.check_rate_limit(&key).await.allowed);
+
+        // An idle bucket drains completely and admits without delay
+        tokio::time::advance(Duration::from_secs(10)).await;
+        let result = limiter.check_rate_limit(&key).await;
+        assert_eq!(result.delay, Duration::ZERO);
+        assert_eq!(result.remaining, 4);
+    }
 }
@@ -140,6 +140,108 @@ end
 return {allowed, math.max(0, remaining), math.max(1, ttl)}
 "#;
 
+/// Lua script for GCRA (generic cell rate algorithm) rate limiting
+///
+/// GCRA behaves like a leaky bucket but stores only the theoretical arrival
+/// time (TAT) of the next request, which also gives an exact retry time.
+///
+/// KEYS[1] = the rate limit key
+/// ARGV[1] = emission interval (milliseconds per request)
+/// ARGV[2] = burst size
+/// ARGV[3] = current timestamp (milliseconds)
+///
+/// Returns: [allowed (0/1), remaining requests, reset_after (ms), retry_after (ms)]
+pub const GCRA_SCRIPT: &str = r#"
+local key = KEYS[1]
+local emission_interval = tonumber(ARGV[1])
+local burst = tonumber(ARGV[2])
+local now = tonumber(ARGV[3])
+
+local burst_window = emission_interval * burst
+
+local tat = tonumber(redis.call('GET', key))
+if tat == nil or tat < now then
+    tat = now
+end
+
+local new_tat = tat + emission_interval
+local allow_at = new_tat - burst_window
+
+if now < allow_at then
+    -- Denied: the request would arrive before its allowed time
+    return {0, 0, math.ceil(tat - now), math.ceil(allow_at - now)}
+end
+
+redis.call('SET', key, new_tat, 'PX', math.ceil(new_tat - now))
+
+local remaining = math.floor((now - allow_at) / emission_interval)
+return {1, remaining, math.ceil(new_tat - now), 0}
+"#;
+
+/// Lua script for leaky bucket rate limiting
+///
+/// The bucket is a queue drained at a constant rate. Each request takes the
+/// next drain slot and waits for it, so admitted requests leave evenly spaced
+/// instead of in bursts. A request is rejected when the queue is full.
+///
+/// KEYS[1] = the rate limit key
+/// ARGV[1] = drain interval (milliseconds per request)
+/// ARGV[2] = capacity (requests that may wait in the queue)
+/// ARGV[3] = current timestamp (milliseconds)
+///
+/// Returns: [allowed (0/1), remaining, reset_after (ms), delay (allowed) or retry_after (denied) (ms)]
+pub const LEAKY_BUCKET_SCRIPT: &str = r#"
+local key = KEYS[1]
+local interval = tonumber(ARGV[1])
+local capacity = tonumber(ARGV[2])
+local now = tonumber(ARGV[3])
+
+-- When the last queued request leaves the bucket
+local drain_at = tonumber(redis.call('GET', key))
+if drain_at == nil or drain_at < now then
+    drain_at = now
+end
+
+local wait = drain_at - now
+local queued = math.floor(wait / interval)
+
+if queued >= capacity then
+    -- Full: a slot frees up once the queue drains below capacity
+    return {0, 0, math.ceil(wait), math.ceil(wait - capacity * interval) + 1}
+end
+
+local new_drain_at = drain_at + interval
+redis.call('SET', key, new_drain_at, 'PX', math.ceil(new_drain_at - now))
+
+return {1, capacity - queued - 1, math.ceil(new_drain_at - now), math.ceil(wait)}
+"#;
+
+/// Lua script for calendar quotas
+///
+/// Only requests within the quota are counted, so clients retrying after
+/// being denied don't inflate the period's usage.
+///
+/// KEYS[1] = the quota key for the current period
+/// ARGV[1] = requests allowed in the period
+/// ARGV[2] = end of the period (Unix seconds)
+///
+/// Returns: [allowed (0/1), requests used in the period]
+pub const QUOTA_SCRIPT: &str = r#"
+local key = KEYS[1]
+local limit = tonumber(ARGV[1])
+local period_end = tonumber(ARGV[2])
+
+local used = tonumber(redis.call('GET', key) or '0')
+if used >= limit then
+    return {0, used}
+end
+
+used = redis.call('INCR', key)
+redis.call('EXPIREAT', key, period_end)
+
+return {1, used}
+"#;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,5 +258,14 @@ mod tests {
 
         assert!(FIXED_WINDOW_SCRIPT.contains("INCR"));
         assert!(FIXED_WINDOW_SCRIPT.contains("EXPIRE"));
+
+        assert!(GCRA_SCRIPT.contains("tat"));
+        assert!(GCRA_SCRIPT.contains("PX"));
+
+        assert!(LEAKY_BUCKET_SCRIPT.contains("drain_at"));
+        assert!(LEAKY_BUCKET_SCRIPT.contains("PX"));
+
+        assert!(QUOTA_SCRIPT.contains("INCR"));
+        assert!(QUOTA_SCRIPT.contains("EXPIREAT"));
     }
 }
@@ -1,31 +1,183 @@
+use super::quota::{QuotaResult, QuotaService};
+use super::redis::RateLimitAlgorithm;
 use super::service::RateLimiterService;
-use super::types::{RateLimitConfig, RateLimitDimension, RateLimitKey};
+use super::types::{
+    RateLimitConfig, RateLimitDimension, RateLimitExemptConfig, RateLimitKey, RateLimitResult,
+};
+use crate::auth::api_key::hashing;
+use crate::auth::middleware::AuthExtension;
+use crate::auth::AuthMethodType;
+use crate::config::GlobalRateLimitConfig;
+use crate::error::GatewayError;
+use crate::ipfilter::client_ip::client_ip;
+use crate::ipfilter::IpRange;
+use crate::metrics;
 use axum::{
     extract::{ConnectInfo, Request},
-    http::{HeaderMap, HeaderValue, StatusCode},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
-use std::net::SocketAddr;
+use std::collections::HashSet;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
-use tracing::{debug, warn};
+use tracing::debug;
+
+/// Client IPs and API keys that bypass rate limiting
+#[derive(Debug, Default)]
+pub struct RateLimitExemptions {
+    ranges: Vec<IpRange>,
+    /// SHA-256 digests of the exempt API keys
+    api_keys: HashSet<String>,
+}
+
+impl RateLimitExemptions {
+    /// Create from the configured IP addresses, CIDR ranges and API keys
+    pub fn new(config: &RateLimitExemptConfig) -> crate::error::Result<Self> {
+        let ranges = config
+            .ips
+            .iter()
+            .map(|ip| {
+                IpRange::parse(ip).map_err(|e| match e {
+                    GatewayError::Config(msg) => {
+                        GatewayError::Config(format!("rate_limiting.exempt.ips: {}", msg))
+                    }
+                    e => e,
+                })
+            })
+            .collect::<crate::error::Result<_>>()?;
+        Ok(Self {
+            ranges,
+            api_keys: config
+                .api_keys
+                .iter()
+                .map(|key| hashing::hash_key(key))
+                .collect(),
+        })
+    }
+
+    /// What exempts a request from rate limiting (`ip` or `api_key`), if anything
+    ///
+    /// `api_key` is the digest of the authenticated API key, so a key is only
+    /// exempt once it has been validated.
+    pub fn exemption(
+        &self,
+        client_ip: Option<IpAddr>,
+        api_key: Option<&str>,
+    ) -> Option<&'static str> {
+        if client_ip.is_some_and(|ip| self.ranges.iter().any(|range| range.contains(&ip))) {
+            Some("ip")
+        } else if api_key.is_some_and(|key| self.api_keys.contains(key)) {
+            Some("api_key")
+        } else {
+            None
+        }
+    }
+}
 
 /// Rate limiting middleware state
 #[derive(Clone)]
 pub struct RateLimitMiddleware {
     /// The rate limiter service
     service: Arc<RateLimiterService>,
-    /// Rate limit configurations
+    /// Rate limit configurations, all of which must pass
     configs: Vec<RateLimitConfig>,
+    /// Custom JSON body template for 429 responses
+    response_body: Option<String>,
+    /// Requests that skip every limit
+    exemptions: Arc<RateLimitExemptions>,
+    /// Calendar quota counted per API key
+    quota: Option<Arc<QuotaService>>,
 }
 
 impl RateLimitMiddleware {
     /// Create a new rate limiting middleware
     pub fn new(service: Arc<RateLimiterService>, configs: Vec<RateLimitConfig>) -> Self {
-        Self { service, configs }
+        Self {
+            service,
+            configs,
+            response_body: None,
+            exemptions: Arc::new(RateLimitExemptions::default()),
+            quota: None,
+        }
+    }
+
+    /// Create from the `rate_limiting` settings, counting in Redis when configured
+    pub async fn from_config(config: &GlobalRateLimitConfig) -> crate::error::Result<Self> {
+        let algorithm: RateLimitAlgorithm = config.algorithm.parse()?;
+        // Every check carries its own limit; the service default is unused
+        let default = config.global.first().cloned().unwrap_or(RateLimitConfig {
+            dimension: RateLimitDimension::Ip,
+            requests: u32::MAX,
+            window_secs: 1,
+            burst: None,
+        });
+        let service = match &config.redis {
+            Some(redis) => RateLimiterService::from_redis_config(default, redis, algorithm)
+                .await
+                .map_err(|e| GatewayError::Config(format!("Failed to connect to Redis: {}", e)))?,
+            None => RateLimiterService::local_with_algorithm(default, algorithm),
+        };
+        let mut middleware = Self::new(Arc::new(service), config.global.clone())
+            .with_exemptions(RateLimitExemptions::new(&config.exempt)?);
+        if let Some(quota) = &config.quota {
+            let mut quota = QuotaService::new(quota.clone())?;
+            if let Some(redis) = &config.redis {
+                quota = quota.with_redis(&redis.url)?;
+            }
+            middleware = middleware.with_quota(quota);
+        }
+        Ok(middleware)
+    }
+
+    /// Count requests with an API key against a calendar quota
+    ///
+    /// The quota is checked after the rate limits, so throttled requests
+    /// don't use it up.
+    pub fn with_quota(mut self, quota: QuotaService) -> Self {
+        self.quota = Some(Arc::new(quota));
+        self
+    }
+
+    /// Never limit requests matching `exemptions`
+    pub fn with_exemptions(mut self, exemptions: RateLimitExemptions) -> Self {
+        self.exemptions = Arc::new(exemptions);
+        self
+    }
+
+    /// Use a custom JSON body for 429 responses
+    ///
+    /// `{limit}`, `{remaining}`, `{reset_after}` and `{retry_after}` are
+    /// replaced with the values of the denied check.
+    pub fn with_response_body(mut self, template: impl Into<String>) -> Self {
+        self.response_body = Some(template.into());
+        self
+    }
+
+    /// Apply rate limiting keyed on the caller authenticated for `request`
+    ///
+    /// Reads the `AuthExtension` left by authentication. API keys are
+    /// identified by their SHA-256 digest so raw keys never become limiter
+    /// keys; other methods are limited by user id.
+    pub async fn apply_authenticated(&self, request: Request) -> Result<Request, Response> {
+        let (user_id, api_key) = match request
+            .extensions()
+            .get::<AuthExtension>()
+            .and_then(|ext| ext.auth_result.as_ref())
+        {
+            Some(auth) if auth.method == AuthMethodType::ApiKey => {
+                (None, Some(hashing::hash_key(&auth.user_id)))
+            }
+            Some(auth) => (Some(auth.user_id.clone()), None),
+            None => (None, None),
+        };
+        self.apply(request, user_id, api_key).await
     }
 
     /// Apply rate limiting to a request
+    ///
+    /// `api_key` identifies the caller's API key for the `apikey` dimension;
+    /// see [`apply_authenticated`](Self::apply_authenticated).
     pub async fn apply(
         &self,
         request: Request,
@@ -34,14 +186,28 @@ impl RateLimitMiddleware {
     ) -> Result<Request, Response> {
         let path = request.uri().path().to_string();
 
-        // Extract client IP
-        let client_ip = request
-            .extensions()
-            .get::<ConnectInfo<SocketAddr>>()
-            .map(|ci| ci.0.ip().to_string())
+        // Extract client IP, resolved through trusted proxies if configured
+        let client_ip = client_ip(request.extensions());
+        if let Some(reason) = self.exemptions.exemption(client_ip, api_key.as_deref()) {
+            debug!(path = %path, reason, "Request exempt from rate limiting");
+            metrics::record_rate_limit_exempt(reason);
+            return Ok(request);
+        }
+        let headers = request.headers();
+        let client_ip = client_ip
+            .map(|ip| ip.to_string())
             .unwrap_or_else(|| "unknown".to_string());
 
-        // Check rate limits for each configured dimension
+        // Per-key override when the request authenticated via API key
+        let key_override = request
+            .extensions()
+            .get::<AuthExtension>()
+            .and_then(|ext| ext.auth_result.as_ref())
+            .filter(|auth| auth.method == AuthMethodType::ApiKey)
+            .and_then(|auth| auth.rate_limit.clone());
+
+        // Check every configured dimension; any one of them can reject
+        let mut checks = Vec::with_capacity(self.configs.len());
         for config in &self.configs {
             let key = self.create_rate_limit_key(
                 &config.dimension,
@@ -49,29 +215,45 @@ impl RateLimitMiddleware {
                 user_id.as_deref(),
                 api_key.as_deref(),
                 &path,
+                headers,
             );
 
             if let Some(key) = key {
-                let result = self.service.check_rate_limit(&key).await;
-
-                if !result.allowed {
-                    warn!(
-                        "Rate limit exceeded for dimension {:?}, identifier: {}",
-                        config.dimension, key.identifier
-                    );
-
-                    return Err(create_rate_limit_response(
-                        result.limit,
-                        result.remaining,
-                        result.reset_after,
-                        result.retry_after,
-                    ));
-                }
+                let config = match (&config.dimension, &key_override) {
+                    (RateLimitDimension::ApiKey, Some(key_override)) => {
+                        config.with_override(key_override)
+                    }
+                    _ => config.clone(),
+                };
+                checks.push((key, config));
+            }
+        }
 
-                debug!(
-                    "Rate limit check passed for dimension {:?}, remaining: {}",
-                    config.dimension, result.remaining
-                );
+        let results = self.service.check_each_rate_limit(&checks).await;
+        for ((key, _), result) in checks.iter().zip(&results) {
+            if !result.allowed {
+                metrics::record_rate_limit_exceeded(&path, key.dimension.label());
+            }
+        }
+
+        if let Some(result) = RateLimitResult::most_restrictive(results) {
+            if !result.allowed {
+                return Err(create_rate_limit_response(
+                    &result,
+                    self.response_body.as_deref(),
+                ));
+            }
+            // A leaky bucket releases queued requests at its drain rate
+            if !result.delay.is_zero() {
+                debug!(path = %path, delay_ms = result.delay.as_millis() as u64, "Delaying request to smooth its rate");
+                tokio::time::sleep(result.delay).await;
+            }
+        }
+
+        if let (Some(quota), Some(api_key)) = (&self.quota, api_key.as_deref()) {
+            let result = quota.check(api_key).await;
+            if !result.allowed {
+                return Err(create_quota_response(&result));
             }
         }
 
@@ -86,6 +268,7 @@ impl RateLimitMiddleware {
         user_id: Option<&str>,
         api_key: Option<&str>,
         path: &str,
+        headers: &HeaderMap,
     ) -> Option<RateLimitKey> {
         match dimension {
             RateLimitDimension::Ip => Some(RateLimitKey::new(
@@ -103,22 +286,31 @@ impl RateLimitMiddleware {
                 client_ip.to_string(),
                 path.to_string(),
             )),
+            // An absent or non-UTF-8 header falls into the shared empty-value bucket
+            RateLimitDimension::Header(name) => {
+                let value = headers
+                    .get(name.as_str())
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or_default();
+                Some(RateLimitKey::new(
+                    RateLimitDimension::Header(name.to_ascii_lowercase()),
+                    value.to_string(),
+                ))
+            }
         }
     }
 }
 
 /// Create a 429 Too Many Requests response with rate limit headers
-fn create_rate_limit_response(
-    limit: u32,
-    remaining: i64,
-    reset_after: u64,
-    retry_after: Option<u64>,
-) -> Response {
+fn create_rate_limit_response(result: &RateLimitResult, body_template: Option<&str>) -> Response {
+    let remaining = result.remaining.max(0);
+    let retry_after = result.retry_after.unwrap_or(result.reset_after);
+
     let mut headers = HeaderMap::new();
 
     headers.insert(
         "X-RateLimit-Limit",
-        HeaderValue::from_str(&limit.to_string()).unwrap(),
+        HeaderValue::from_str(&result.limit.to_string()).unwrap(),
     );
     headers.insert(
         "X-RateLimit-Remaining",
@@ -126,52 +318,77 @@ fn create_rate_limit_response(
     );
     headers.insert(
         "X-RateLimit-Reset",
-        HeaderValue::from_str(&reset_after.to_string()).unwrap(),
+        HeaderValue::from_str(&result.reset_after.to_string()).unwrap(),
+    );
+    headers.insert(
+        header::RETRY_AFTER,
+        HeaderValue::from_str(&retry_after.to_string()).unwrap(),
+    );
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
     );
 
-    if let Some(retry) = retry_after {
-        headers.insert(
-            "Retry-After",
-            HeaderValue::from_str(&retry.to_string()).unwrap(),
-        );
-    }
+    let body = match body_template {
+        Some(template) => template
+            .replace("{limit}", &result.limit.to_string())
+            .replace("{remaining}", &remaining.to_string())
+            .replace("{reset_after}", &result.reset_after.to_string())
+            .replace("{retry_after}", &retry_after.to_string()),
+        None => serde_json::json!({
+            "error": "Rate limit exceeded",
+            "status": 429,
+            "limit": result.limit,
+            "remaining": remaining,
+            "reset_after": result.reset_after,
+            "retry_after": retry_after,
+        })
+        .to_string(),
+    };
+
+    (StatusCode::TOO_MANY_REQUESTS, headers, body).into_response()
+}
+
+/// Create a 429 Too Many Requests response for an exhausted quota
+fn create_quota_response(result: &QuotaResult) -> Response {
+    let mut headers = HeaderMap::new();
+    headers.insert("X-Quota-Limit", HeaderValue::from(result.limit));
+    headers.insert("X-Quota-Remaining", HeaderValue::from(result.remaining));
+    headers.insert("X-Quota-Reset", HeaderValue::from(result.reset_after));
+    headers.insert(header::RETRY_AFTER, HeaderValue::from(result.reset_after));
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
 
     let body = serde_json::json!({
-        "error": "Rate limit exceeded",
+        "error": "Quota exceeded",
         "status": 429,
-        "limit": limit,
-        "remaining": remaining,
-        "reset_after": reset_after,
-        "retry_after": retry_after,
-    });
+        "limit": result.limit,
+        "remaining": result.remaining,
+        "reset_after": result.reset_after,
+    })
+    .to_string();
 
-    (StatusCode::TOO_MANY_REQUESTS, headers, body.to_string()).into_response()
+    (StatusCode::TOO_MANY_REQUESTS, headers, body).into_response()
 }
 
 /// Axum middleware function for rate limiting
+///
+/// Must run after authentication, which leaves the caller in an `AuthExtension`.
 pub async fn rate_limit_middleware(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
-    headers: HeaderMap,
     mut request: Request,
     next: Next,
 ) -> Response {
     // Store the client IP in request extensions
     request.extensions_mut().insert(ConnectInfo(addr));
 
-    // Extract user ID from request extensions (set by auth middleware)
-    let user_id = request.extensions().get::<String>().map(|s| s.to_string());
-
-    // Extract API key from headers
-    let api_key = headers
-        .get("X-API-Key")
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.to_string());
-
     // Get rate limiter from request extensions and clone it
     let rate_limiter = request.extensions().get::<RateLimitMiddleware>().cloned();
 
     if let Some(limiter) = rate_limiter {
-        match limiter.apply(request, user_id, api_key).await {
+        match limiter.apply_authenticated(request).await {
             Ok(req) => next.run(req).await,
             Err(response) => response,
         }
@@ -209,7 +426,12 @@ pub fn add_rate_limit_headers(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::auth::AuthResult;
+    use crate::ipfilter::ClientIp;
+    use crate::rate_limit::quota::{QuotaConfig, QuotaPeriod};
     use crate::rate_limit::service::RateLimiterService;
+    use crate::rate_limit::types::RateLimitOverride;
+    use std::collections::HashMap;
 
     #[test]
     fn test_create_rate_limit_key() {
@@ -230,6 +452,7 @@ mod tests {
             None,
             None,
             "/api/test",
+            &HeaderMap::new(),
         );
         assert!(key.is_some());
         assert_eq!(key.unwrap().identifier, "192.168.1.1");
@@ -241,6 +464,7 @@ mod tests {
             None,
             None,
             "/api/test",
+            &HeaderMap::new(),
         );
         assert!(key.is_none());
 
@@ -251,6 +475,7 @@ mod tests {
             Some("user123"),
             None,
             "/api/test",
+            &HeaderMap::new(),
         );
         assert!(key.is_some());
         assert_eq!(key.unwrap().identifier, "user123");
@@ -262,6 +487,7 @@ mod tests {
             None,
             None,
             "/api/test",
+            &HeaderMap::new(),
         );
         assert!(key.is_some());
         let key = key.unwrap();
@@ -269,15 +495,426 @@ mod tests {
         assert_eq!(key.route, Some("/api/test".to_string()));
     }
 
+    fn api_key_request(tier: Option<RateLimitOverride>) -> Request {
+        let mut request = Request::builder()
+            .uri("/api/test")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        request.extensions_mut().insert(AuthExtension {
+            auth_result: Some(AuthResult {
+                user_id: "api-key-user".to_string(),
+                method: AuthMethodType::ApiKey,
+                metadata: HashMap::new(),
+                rate_limit: tier,
+            }),
+        });
+        request
+    }
+
+    async fn allowed_requests(
+        middleware: &RateLimitMiddleware,
+        api_key: &str,
+        tier: Option<RateLimitOverride>,
+    ) -> u32 {
+        let mut allowed = 0;
+        for _ in 0..20 {
+            let request = api_key_request(tier.clone());
+            if middleware
+                .apply(request, None, Some(api_key.to_string()))
+                .await
+                .is_err()
+            {
+                break;
+            }
+            allowed += 1;
+        }
+        allowed
+    }
+
+    #[tokio::test]
+    async fn test_api_key_rate_limit_override() {
+        let config = RateLimitConfig {
+            dimension: RateLimitDimension::ApiKey,
+            requests: 3,
+            window_secs: 60,
+            burst: None,
+        };
+        let service = RateLimiterService::local_only(config.clone());
+        let middleware = RateLimitMiddleware::new(Arc::new(service), vec![config]);
+
+        let free = RateLimitOverride {
+            requests: 2,
+            window_secs: 60,
+            burst: None,
+        };
+        let pro = RateLimitOverride {
+            requests: 10,
+            window_secs: 60,
+            burst: None,
+        };
+
+        assert_eq!(
+            allowed_requests(&middleware, "free-key", Some(free)).await,
+            2
+        );
+        assert_eq!(
+            allowed_requests(&middleware, "pro-key", Some(pro)).await,
+            10
+        );
+
+        // Keys without an override fall back to the route limit
+        assert_eq!(allowed_requests(&middleware, "plain-key", None).await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_multiple_dimensions_all_enforced() {
+        let ip_config = RateLimitConfig {
+            dimension: RateLimitDimension::Ip,
+            requests: 100,
+            window_secs: 60,
+            burst: None,
+        };
+        let key_config = RateLimitConfig {
+            dimension: RateLimitDimension::ApiKey,
+            requests: 3,
+            window_secs: 60,
+            burst: None,
+        };
+        let service = RateLimiterService::local_only(ip_config.clone());
+        let middleware = RateLimitMiddleware::new(Arc::new(service), vec![ip_config, key_config]);
+
+        // The IP limit passes but the API key limit trips
+        assert_eq!(allowed_requests(&middleware, "key-a", None).await, 3);
+
+        // Another key from the same IP has its own API key budget
+        assert_eq!(allowed_requests(&middleware, "key-b", None).await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_header_dimension_buckets() {
+        let config = RateLimitConfig {
+            dimension: RateLimitDimension::Header("X-Tenant-ID".to_string()),
+            requests: 2,
+            window_secs: 60,
+            burst: None,
+        };
+        let service = RateLimiterService::local_only(config.clone());
+        let middleware = RateLimitMiddleware::new(Arc::new(service), vec![config]);
+
+        let allowed = |tenant: Option<&'static str>| {
+            let middleware = middleware.clone();
+            async move {
+                let mut allowed = 0;
+                for _ in 0..5 {
+                    let mut request = Request::builder().uri("/api/test");
+                    if let Some(tenant) = tenant {
+                        request = request.header("x-tenant-id", tenant);
+                    }
+                    let request = request.body(axum::body::Body::empty()).unwrap();
+                    if middleware.apply(request, None, None).await.is_err() {
+                        break;
+                    }
+                    allowed += 1;
+                }
+                allowed
+            }
+        };
+
+        // Each tenant has its own budget
+        assert_eq!(allowed(Some("acme")).await, 2);
+        assert_eq!(allowed(Some("globex")).await, 2);
+        assert_eq!(allowed(Some("acme")).await, 0);
+
+        // Requests without the header share one default bucket
+        assert_eq!(allowed(None).await, 2);
+        assert_eq!(allowed(None).await, 0);
+        assert_eq!(allowed(Some("")).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_exempt_requests_never_limited() {
+        let config = RateLimitConfig {
+            dimension: RateLimitDimension::Ip,
+            requests: 2,
+            window_secs: 60,
+            burst: None,
+        };
+        let exemptions = RateLimitExemptions::new(&RateLimitExemptConfig {
+            ips: vec!["10.0.0.0/8".to_string()],
+            api_keys: vec!["partner-key".to_string()],
+        })
+        .unwrap();
+        let service = RateLimiterService::local_only(config.clone());
+        let middleware =
+            RateLimitMiddleware::new(Arc::new(service), vec![config]).with_exemptions(exemptions);
+
+        let allowed = |ip: [u8; 4], api_key: Option<&'static str>| {
+            let middleware = middleware.clone();
+            async move {
+                let mut allowed = 0;
+                for _ in 0..10 {
+                    let mut request = Request::builder()
+                        .uri("/api/test")
+                        .body(axum::body::Body::empty())
+                        .unwrap();
+                    request.extensions_mut().insert(ClientIp(IpAddr::from(ip)));
+                    let api_key = api_key.map(hashing::hash_key);
+                    if middleware.apply(request, None, api_key).await.is_err() {
+                        break;
+                    }
+                    allowed += 1;
+                }
+                allowed
+            }
+        };
+
+        assert_eq!(allowed([10, 1, 2, 3], None).await, 10);
+        assert_eq!(allowed([203, 0, 113, 9], None).await, 2);
+        // Exempt keys bypass the limit their IP has already exhausted
+        assert_eq!(allowed([203, 0, 113, 9], Some("partner-key")).await, 10);
+        assert_eq!(allowed([203, 0, 113, 9], Some("other-key")).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_denial_recorded_with_dimension() {
+        let ip_config = RateLimitConfig {
+            dimension: RateLimitDimension::Ip,
+            requests: 1,
+            window_secs: 60,
+            burst: None,
+        };
+        let key_config = RateLimitConfig {
+            dimension: RateLimitDimension::ApiKey,
+            requests: 100,
+            window_secs: 60,
+            burst: None,
+        };
+        let service = RateLimiterService::local_only(ip_config.clone());
+        let middleware = RateLimitMiddleware::new(Arc::new(service), vec![ip_config, key_config]);
+
+        let recorder = metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        let _guard = ::metrics::set_default_local_recorder(&recorder);
+
+        for expect_allowed in [true, false] {
+            let mut request = Request::builder()
+                .uri("/api/users/12345")
+                .body(axum::body::Body::empty())
+                .unwrap();
+            request
+                .extensions_mut()
+                .insert(ClientIp(IpAddr::from([203, 0, 113, 9])));
+            let result = middleware
+                .apply(request, None, Some("key-a".to_string()))
+                .await;
+            assert_eq!(result.is_ok(), expect_allowed);
+        }
+
+        // Only the IP limit tripped
+        let rendered = handle.render();
+        assert!(
+            rendered.contains(
+                r#"gateway_rate_limit_exceeded_total{route="/api/users/:id",dimension="ip"} 1"#
+            ),
+            "{rendered}"
+        );
+        assert!(!rendered.contains(r#"dimension="api_key""#), "{rendered}");
+        assert!(!rendered.contains("203.0.113.9"), "{rendered}");
+    }
+
+    #[tokio::test]
+    async fn test_denial_metrics_omit_api_key() {
+        let config = RateLimitConfig {
+            dimension: RateLimitDimension::ApiKey,
+            requests: 1,
+            window_secs: 60,
+            burst: None,
+        };
+        let service = RateLimiterService::local_only(config.clone());
+        let middleware = RateLimitMiddleware::new(Arc::new(service), vec![config]);
+
+        let recorder = metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        let _guard = ::metrics::set_default_local_recorder(&recorder);
+
+        for expect_allowed in [true, false] {
+            let request = Request::builder()
+                .uri("/api/orders")
+                .body(axum::body::Body::empty())
+                .unwrap();
+            let result = middleware
+                .apply(request, None, Some("sk_live_secret123".to_string()))
+                .await;
+            assert_eq!(result.is_ok(), expect_allowed);
+        }
+
+        let rendered = handle.render();
+        assert!(
+            rendered.contains(
+                r#"gateway_rate_limit_exceeded_total{route="/api/orders",dimension="api_key"} 1"#
+            ),
+            "{rendered}"
+        );
+        assert!(!rendered.contains("sk_live_secret123"), "{rendered}");
+        assert!(!rendered.contains("identifier"), "{rendered}");
+    }
+
+    #[tokio::test]
+    async fn test_quota_exhausted_response() {
+        let config = RateLimitConfig {
+            dimension: RateLimitDimension::Ip,
+            requests: 100,
+            window_secs: 60,
+            burst: None,
+        };
+        let quota = QuotaService::new(QuotaConfig {
+            requests: 2,
+            period: QuotaPeriod::Monthly,
+            utc_offset: "UTC".to_string(),
+        })
+        .unwrap();
+        let service = RateLimiterService::local_only(config.clone());
+        let middleware =
+            RateLimitMiddleware::new(Arc::new(service), vec![config]).with_quota(quota);
+
+        let send = |api_key: Option<&str>| {
+            let request = Request::builder()
+                .uri("/api/test")
+                .body(axum::body::Body::empty())
+                .unwrap();
+            middleware.apply(request, None, api_key.map(str::to_string))
+        };
+
+        assert!(send(Some("metered-key")).await.is_ok());
+        assert!(send(Some("metered-key")).await.is_ok());
+        let response = send(Some("metered-key")).await.unwrap_err();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(header_u64(&response, "X-Quota-Limit"), 2);
+        assert_eq!(header_u64(&response, "X-Quota-Remaining"), 0);
+        let reset = header_u64(&response, "X-Quota-Reset");
+        assert!(reset > 0 && reset <= 31 * 24 * 3600);
+        assert_eq!(header_u64(&response, "Retry-After"), reset);
+
+        // Requests without an API key have no quota
+        for _ in 0..5 {
+            assert!(send(None).await.is_ok());
+        }
+    }
+
+    #[test]
+    fn test_invalid_exempt_ip() {
+        let err = RateLimitExemptions::new(&RateLimitExemptConfig {
+            ips: vec!["10.0.0.0/40".to_string()],
+            api_keys: vec![],
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("rate_limiting.exempt.ips"));
+    }
+
     #[test]
     fn test_rate_limit_response() {
-        let response = create_rate_limit_response(100, 0, 30, Some(30));
+        let response = create_rate_limit_response(&RateLimitResult::denied(100, 30), None);
 
         assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
 
         let headers = response.headers();
         assert_eq!(headers.get("X-RateLimit-Limit").unwrap(), "100");
         assert_eq!(headers.get("X-RateLimit-Remaining").unwrap(), "0");
+        assert_eq!(headers.get("X-RateLimit-Reset").unwrap(), "30");
         assert_eq!(headers.get("Retry-After").unwrap(), "30");
+        assert_eq!(headers.get("Content-Type").unwrap(), "application/json");
+    }
+
+    #[test]
+    fn test_rate_limit_response_retry_after_defaults_to_reset() {
+        let result = RateLimitResult {
+            allowed: false,
+            remaining: -1,
+            limit: 10,
+            reset_after: 12,
+            retry_after: None,
+            delay: std::time::Duration::ZERO,
+        };
+        let response = create_rate_limit_response(&result, None);
+
+        let headers = response.headers();
+        assert_eq!(headers.get("Retry-After").unwrap(), "12");
+        assert_eq!(headers.get("X-RateLimit-Remaining").unwrap(), "0");
+    }
+
+    async fn denied_response(middleware: &RateLimitMiddleware) -> Response {
+        for _ in 0..10 {
+            let request = Request::builder()
+                .uri("/api/test")
+                .body(axum::body::Body::empty())
+                .unwrap();
+            if let Err(response) = middleware.apply(request, None, None).await {
+                return response;
+            }
+        }
+        panic!("request was never rate limited");
+    }
+
+    fn header_u64(response: &Response, name: &str) -> u64 {
+        response
+            .headers()
+            .get(name)
+            .unwrap_or_else(|| panic!("missing {} header", name))
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_denied_request_headers() {
+        let config = RateLimitConfig {
+            dimension: RateLimitDimension::Ip,
+            requests: 2,
+            window_secs: 60,
+            burst: None,
+        };
+        let service = RateLimiterService::local_only(config.clone());
+        let middleware = RateLimitMiddleware::new(Arc::new(service), vec![config]);
+
+        let response = denied_response(&middleware).await;
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(header_u64(&response, "X-RateLimit-Limit"), 2);
+        assert_eq!(header_u64(&response, "X-RateLimit-Remaining"), 0);
+        assert_eq!(header_u64(&response, "X-RateLimit-Reset"), 60);
+        assert_eq!(header_u64(&response, "Retry-After"), 60);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["status"], 429);
+        assert_eq!(body["retry_after"], 60);
+    }
+
+    #[tokio::test]
+    async fn test_custom_response_body() {
+        let config = RateLimitConfig {
+            dimension: RateLimitDimension::Ip,
+            requests: 1,
+            window_secs: 30,
+            burst: None,
+        };
+        let service = RateLimiterService::local_only(config.clone());
+        let middleware = RateLimitMiddleware::new(Arc::new(service), vec![config])
+            .with_response_body(r#"{"code":"SLOW_DOWN","limit":{limit},"retry_in":{retry_after}}"#);
+
+        let response = denied_response(&middleware).await;
+        assert_eq!(header_u64(&response, "Retry-After"), 30);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"code": "SLOW_DOWN", "limit": 1, "retry_in": 30})
+        );
     }
 }
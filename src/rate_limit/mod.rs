@@ -9,7 +9,9 @@
 //!
 //! # Features
 //!
-//! - Multiple rate limiting dimensions (IP, User, API Key, Route)
+//! - Multiple rate limiting dimensions (IP, User, API Key, Route, Header)
+//! - Exemptions for trusted IP ranges and API keys
+//! - Daily or monthly quotas per API key that reset at calendar boundaries
 //! - Graceful fallback from Redis to local rate limiting
 //! - Rate limit headers in responses (`X-RateLimit-*`)
 //! - Configurable per route
@@ -43,12 +45,19 @@
 pub mod local;
 pub mod lua_scripts;
 pub mod middleware;
+pub mod quota;
 pub mod redis;
 pub mod service;
 pub mod types;
 
 // Re-export commonly used types
-pub use middleware::{add_rate_limit_headers, rate_limit_middleware, RateLimitMiddleware};
+pub use middleware::{
+    add_rate_limit_headers, rate_limit_middleware, RateLimitExemptions, RateLimitMiddleware,
+};
+pub use quota::{QuotaConfig, QuotaPeriod, QuotaResult, QuotaService};
 pub use redis::RateLimitAlgorithm;
 pub use service::RateLimiterService;
-pub use types::{RateLimitConfig, RateLimitDimension, RateLimitKey, RateLimitResult};
+pub use types::{
+    RateLimitConfig, RateLimitDimension, RateLimitExemptConfig, RateLimitKey, RateLimitOverride,
+    RateLimitResult,
+};
@@ -0,0 +1,399 @@
+//! Calendar quotas per API key
+//!
+//! Unlike rate limits, which use rolling windows, a quota counts an API key's
+//! requests over a calendar period (a day or a month) and resets at the
+//! period boundary, e.g. "1M requests/month". Boundaries fall at midnight at
+//! a fixed UTC offset; named time zones are not supported, so boundaries don't
+//! follow daylight saving changes. Only allowed requests are counted. Keys are
+//! identified by their SHA-256 digest. Usage is counted in memory, or in Redis
+//! under a key per period that expires at the boundary so every replica
+//! shares it.
+
+use super::lua_scripts::QUOTA_SCRIPT;
+use crate::error::{GatewayError, Result};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveTime, Utc};
+use dashmap::DashMap;
+use redis::{aio::ConnectionManager, Script};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use tracing::{debug, warn};
+
+/// Connection and command timeout; a slow Redis must not stall requests
+const REDIS_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Calendar period after which quota usage resets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuotaPeriod {
+    /// Resets at midnight every day
+    Daily,
+    /// Resets at midnight on the first of every month
+    Monthly,
+}
+
+impl QuotaPeriod {
+    /// Start and end of the period containing `now`, with boundaries at
+    /// midnight in `offset`
+    pub fn bounds(self, now: DateTime<Utc>, offset: FixedOffset) -> (DateTime<Utc>, DateTime<Utc>) {
+        let today = now.with_timezone(&offset).date_naive();
+        let (start, end) = match self {
+            QuotaPeriod::Daily => (today, today + chrono::Days::new(1)),
+            QuotaPeriod::Monthly => {
+                let start = today.with_day(1).expect("every month has a first day");
+                (start, start + chrono::Months::new(1))
+            }
+        };
+        let midnight = |date: NaiveDate| {
+            date.and_time(NaiveTime::MIN)
+                .and_local_timezone(offset)
+                .single()
+                .expect("fixed offsets have no gaps")
+                .with_timezone(&Utc)
+        };
+        (midnight(start), midnight(end))
+    }
+}
+
+/// Quota configuration, applied to every API key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaConfig {
+    /// Requests allowed per API key in each period
+    pub requests: u64,
+    /// Calendar period after which usage resets
+    pub period: QuotaPeriod,
+    /// Fixed UTC offset of the period boundaries: `UTC` or `+HH:MM` / `-HH:MM`
+    #[serde(default = "default_utc_offset")]
+    pub utc_offset: String,
+}
+
+fn default_utc_offset() -> String {
+    "UTC".to_string()
+}
+
+/// Parse a `UTC`, `+HH:MM` or `-HH:MM` offset
+fn parse_offset(utc_offset: &str) -> Result<FixedOffset> {
+    if utc_offset.eq_ignore_ascii_case("utc") || utc_offset == "Z" {
+        return Ok(FixedOffset::east_opt(0).expect("zero offset is valid"));
+    }
+    let invalid = || {
+        GatewayError::Config(format!(
+            "rate_limiting.quota.utc_offset: Invalid UTC offset: {} (expected UTC or +HH:MM)",
+            utc_offset
+        ))
+    };
+    let (sign, rest) = if let Some(rest) = utc_offset.strip_prefix('+') {
+        (1, rest)
+    } else if let Some(rest) = utc_offset.strip_prefix('-') {
+        (-1, rest)
+    } else if utc_offset.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return Err(GatewayError::Config(format!(
+            "rate_limiting.quota.utc_offset: Named time zones such as {} are not supported, use a fixed offset such as +05:30",
+            utc_offset
+        )));
+    } else {
+        return Err(invalid());
+    };
+    let (hours, minutes) = rest.split_once(':').ok_or_else(invalid)?;
+    let hours: i32 = hours.parse().map_err(|_| invalid())?;
+    let minutes: i32 = minutes.parse().map_err(|_| invalid())?;
+    if minutes >= 60 {
+        return Err(invalid());
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).ok_or_else(invalid)
+}
+
+/// Outcome of counting a request against an API key's quota
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaResult {
+    /// Whether the request is within the quota
+    pub allowed: bool,
+    /// Requests allowed per period
+    pub limit: u64,
+    /// Requests left in the current period
+    pub remaining: u64,
+    /// Seconds until the quota resets
+    pub reset_after: u64,
+}
+
+/// Counts API key usage against a calendar quota
+pub struct QuotaService {
+    config: QuotaConfig,
+    offset: FixedOffset,
+    /// Usage per API key when counting locally: (period start, requests)
+    local: DashMap<String, (i64, u64)>,
+    /// Latest period counted locally; usage from earlier ones is dropped
+    period_start: AtomicI64,
+    /// Redis client when usage is shared across replicas
+    redis: Option<redis::Client>,
+    /// Connected on first use so startup does not depend on Redis being up
+    connection: OnceCell<ConnectionManager>,
+}
+
+impl std::fmt::Debug for QuotaService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QuotaService")
+            .field("config", &self.config)
+            .field("redis", &self.redis.is_some())
+            .finish()
+    }
+}
+
+impl QuotaService {
+    /// Create a quota service counting usage in memory
+    pub fn new(config: QuotaConfig) -> Result<Self> {
+        if config.requests == 0 {
+            return Err(GatewayError::Config(
+                "rate_limiting.quota.requests: Quota requests must be > 0".to_string(),
+            ));
+        }
+        let offset = parse_offset(&config.utc_offset)?;
+
+        Ok(Self {
+            config,
+            offset,
+            local: DashMap::new(),
+            period_start: AtomicI64::new(i64::MIN),
+            redis: None,
+            connection: OnceCell::new(),
+        })
+    }
+
+    /// Count usage in Redis so every replica shares it
+    ///
+    /// Redis errors fall back to counting in memory, so an outage relaxes
+    /// quotas to per-replica counts rather than rejecting traffic.
+    pub fn with_redis(mut self, redis_url: &str) -> Result<Self> {
+        let client = redis::Client::open(redis_url).map_err(|e| {
+            GatewayError::Config(format!("rate_limiting.redis.url: Invalid Redis URL: {}", e))
+        })?;
+        self.redis = Some(client);
+        Ok(self)
+    }
+
+    /// Count a request by `api_key` against its quota
+    ///
+    /// `api_key` is the key's digest, so raw keys are never stored. Denied
+    /// requests are not counted, so usage never exceeds the quota.
+    pub async fn check(&self, api_key: &str) -> QuotaResult {
+        self.check_at(api_key, Utc::now()).await
+    }
+
+    /// Count a request by `api_key` made at `now` against its quota
+    pub async fn check_at(&self, api_key: &str, now: DateTime<Utc>) -> QuotaResult {
+        let (start, end) = self.config.period.bounds(now, self.offset);
+
+        let (allowed, used) = match self.count_redis(api_key, start, end).await {
+            Some(Ok(counted)) => counted,
+            Some(Err(e)) => {
+                warn!(error = %e, "Redis quota check failed, counting locally");
+                self.count_local(api_key, start)
+            }
+            None => self.count_local(api_key, start),
+        };
+
+        let reset_after = (end - now).num_seconds().max(0) as u64;
+        debug!(used, limit = self.config.requests, allowed, "Quota check");
+        QuotaResult {
+            allowed,
+            limit: self.config.requests,
+            remaining: self.config.requests.saturating_sub(used),
+            reset_after,
+        }
+    }
+
+    /// Count a request in memory if it is within the quota, returning
+    /// whether it is and the period's usage so far
+    fn count_local(&self, api_key: &str, start: DateTime<Utc>) -> (bool, u64) {
+        let start = start.timestamp();
+        if self.period_start.fetch_max(start, Ordering::Relaxed) < start {
+            self.local.retain(|_, usage| usage.0 >= start);
+        }
+
+        let mut usage = self.local.entry(api_key.to_string()).or_insert((start, 0));
+        if usage.0 != start {
+            *usage = (start, 0);
+        }
+        if usage.1 >= self.config.requests {
+            return (false, usage.1);
+        }
+        usage.1 += 1;
+        (true, usage.1)
+    }
+
+    /// Count a request in Redis, or `None` when usage is counted locally
+    async fn count_redis(
+        &self,
+        api_key: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Option<redis::RedisResult<(bool, u64)>> {
+        let client = self.redis.as_ref()?;
+        let key = format!("gateway:quota:{}:{}", api_key, start.timestamp());
+        let result = async {
+            let mut connection = self
+                .connection
+                .get_or_try_init(|| {
+                    ConnectionManager::new_with_backoff_and_timeouts(
+                        client.clone(),
+                        2,
+                        100,
+                        1,
+                        REDIS_TIMEOUT,
+                        REDIS_TIMEOUT,
+                    )
+                })
+                .await?
+                .clone();
+            let (allowed, used): (i64, u64) = Script::new(QUOTA_SCRIPT)
+                .key(&key)
+                .arg(self.config.requests)
+                .arg(end.timestamp())
+                .invoke_async(&mut connection)
+                .await?;
+            Ok((allowed == 1, used))
+        };
+        Some(result.await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn quota(requests: u64, period: QuotaPeriod, utc_offset: &str) -> QuotaService {
+        QuotaService::new(QuotaConfig {
+            requests,
+            period,
+            utc_offset: utc_offset.to_string(),
+        })
+        .unwrap()
+    }
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_period_bounds() {
+        let utc = parse_offset("UTC").unwrap();
+        let (start, end) = QuotaPeriod::Monthly.bounds(at(2024, 12, 15, 10, 0), utc);
+        assert_eq!(start, at(2024, 12, 1, 0, 0));
+        assert_eq!(end, at(2025, 1, 1, 0, 0));
+
+        let (start, end) = QuotaPeriod::Daily.bounds(at(2024, 2, 28, 23, 59), utc);
+        assert_eq!(start, at(2024, 2, 28, 0, 0));
+        assert_eq!(end, at(2024, 2, 29, 0, 0));
+
+        // 20:00 UTC is already the next day at +05:30
+        let ist = parse_offset("+05:30").unwrap();
+        let (start, end) = QuotaPeriod::Daily.bounds(at(2024, 3, 1, 20, 0), ist);
+        assert_eq!(start, at(2024, 3, 1, 18, 30));
+        assert_eq!(end, at(2024, 3, 2, 18, 30));
+    }
+
+    #[tokio::test]
+    async fn test_counts_and_denies_at_limit() {
+        let quota = quota(3, QuotaPeriod::Monthly, "UTC");
+        let now = at(2024, 5, 31, 12, 0);
+
+        for remaining in [2, 1, 0] {
+            let result = quota.check_at("key-a", now).await;
+            assert!(result.allowed);
+            assert_eq!(result.remaining, remaining);
+        }
+
+        let denied = quota.check_at("key-a", now).await;
+        assert!(!denied.allowed);
+        assert_eq!(denied.remaining, 0);
+        assert_eq!(denied.reset_after, 12 * 3600);
+
+        // Denied requests are not counted
+        assert!(!quota.check_at("key-a", now).await.allowed);
+        assert_eq!(quota.local.get("key-a").unwrap().1, 3);
+
+        // Other keys have their own quota
+        assert!(quota.check_at("key-b", now).await.allowed);
+    }
+
+    #[tokio::test]
+    async fn test_resets_at_boundary() {
+        let quota = quota(2, QuotaPeriod::Daily, "-05:00");
+        // 04:59 UTC is still the previous day at -05:00
+        let before = at(2024, 6, 10, 4, 59);
+        assert!(quota.check_at("key-a", before).await.allowed);
+        assert!(quota.check_at("key-a", before).await.allowed);
+        let denied = quota.check_at("key-a", before).await;
+        assert!(!denied.allowed);
+        assert_eq!(denied.reset_after, 60);
+
+        let after = at(2024, 6, 10, 5, 0);
+        let result = quota.check_at("key-a", after).await;
+        assert!(result.allowed);
+        assert_eq!(result.remaining, 1);
+    }
+
+    #[tokio::test]
+    async fn test_drops_usage_from_earlier_periods() {
+        let quota = quota(5, QuotaPeriod::Daily, "UTC");
+        assert!(
+            quota
+                .check_at("key-a", at(2024, 6, 10, 12, 0))
+                .await
+                .allowed
+        );
+        assert!(
+            quota
+                .check_at("key-b", at(2024, 6, 11, 12, 0))
+                .await
+                .allowed
+        );
+
+        assert!(quota.local.get("key-a").is_none());
+        assert_eq!(quota.local.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let config = |utc_offset: &str| QuotaConfig {
+            requests: 10,
+            period: QuotaPeriod::Daily,
+            utc_offset: utc_offset.to_string(),
+        };
+        for utc_offset in ["+5", "+05:75", "+25:00", "05:00"] {
+            let err = QuotaService::new(config(utc_offset)).unwrap_err();
+            assert!(err
+                .to_string()
+                .contains("rate_limiting.quota.utc_offset: Invalid UTC offset"));
+        }
+        let err = QuotaService::new(config("Europe/Berlin")).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Named time zones such as Europe/Berlin are not supported"));
+
+        let err = QuotaService::new(QuotaConfig {
+            requests: 0,
+            period: QuotaPeriod::Monthly,
+            utc_offset: default_utc_offset(),
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("rate_limiting.quota.requests"));
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_quota() {
+        // Requires a running Redis instance
+        let quota = quota(2, QuotaPeriod::Daily, "UTC")
+            .with_redis("redis://127.0.0.1:6379")
+            .unwrap();
+        let key = format!("test-key-{}", uuid::Uuid::new_v4());
+        let now = Utc::now();
+
+        assert!(quota.check_at(&key, now).await.allowed);
+        assert!(quota.check_at(&key, now).await.allowed);
+        assert!(!quota.check_at(&key, now).await.allowed);
+    }
+}
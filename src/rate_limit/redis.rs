@@ -1,8 +1,12 @@
-use super::lua_scripts::{FIXED_WINDOW_SCRIPT, SLIDING_WINDOW_SCRIPT, TOKEN_BUCKET_SCRIPT};
+use super::lua_scripts::{
+    FIXED_WINDOW_SCRIPT, GCRA_SCRIPT, LEAKY_BUCKET_SCRIPT, SLIDING_WINDOW_SCRIPT,
+    TOKEN_BUCKET_SCRIPT,
+};
 use super::types::{RateLimitConfig, RateLimitKey, RateLimitResult};
+use crate::error::GatewayError;
 use redis::{aio::ConnectionManager, Script};
-use std::time::SystemTime;
-use tracing::{debug, error, warn};
+use std::time::{Duration, SystemTime};
+use tracing::{debug, warn};
 
 /// Redis-backed distributed rate limiter
 pub struct RedisRateLimiter {
@@ -23,6 +27,28 @@ pub enum RateLimitAlgorithm {
     SlidingWindow,
     /// Fixed window (simpler, less accurate)
     FixedWindow,
+    /// Generic cell rate algorithm (leaky bucket with exact retry times)
+    Gcra,
+    /// Leaky bucket queue (requests wait and leave at a constant rate)
+    LeakyBucket,
+}
+
+impl std::str::FromStr for RateLimitAlgorithm {
+    type Err = GatewayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "token_bucket" => Ok(RateLimitAlgorithm::TokenBucket),
+            "sliding_window" => Ok(RateLimitAlgorithm::SlidingWindow),
+            "fixed_window" => Ok(RateLimitAlgorithm::FixedWindow),
+            "gcra" => Ok(RateLimitAlgorithm::Gcra),
+            "leaky_bucket" => Ok(RateLimitAlgorithm::LeakyBucket),
+            _ => Err(GatewayError::Config(format!(
+                "Unsupported rate limit algorithm: {}",
+                s
+            ))),
+        }
+    }
 }
 
 impl RedisRateLimiter {
@@ -43,63 +69,87 @@ impl RedisRateLimiter {
     }
 
     /// Check if a request is allowed
-    pub async fn check_rate_limit(&mut self, key: &RateLimitKey) -> RateLimitResult {
+    ///
+    /// Redis errors are returned to the caller, which decides whether to
+    /// deny the request or fall back to local rate limiting.
+    pub async fn check_rate_limit(
+        &mut self,
+        key: &RateLimitKey,
+    ) -> Result<RateLimitResult, redis::RedisError> {
+        let config = self.config.clone();
+        self.check_rate_limit_with_config(key, &config).await
+    }
+
+    /// Check if a request is allowed using a specific configuration
+    pub async fn check_rate_limit_with_config(
+        &mut self,
+        key: &RateLimitKey,
+        config: &RateLimitConfig,
+    ) -> Result<RateLimitResult, redis::RedisError> {
         let redis_key = key.to_redis_key();
 
         match self.algorithm {
-            RateLimitAlgorithm::TokenBucket => self.check_token_bucket(&redis_key).await,
-            RateLimitAlgorithm::SlidingWindow => self.check_sliding_window(&redis_key).await,
-            RateLimitAlgorithm::FixedWindow => self.check_fixed_window(&redis_key).await,
+            RateLimitAlgorithm::TokenBucket => self.check_token_bucket(&redis_key, config).await,
+            RateLimitAlgorithm::SlidingWindow => {
+                self.check_sliding_window(&redis_key, config).await
+            }
+            RateLimitAlgorithm::FixedWindow => self.check_fixed_window(&redis_key, config).await,
+            RateLimitAlgorithm::Gcra => self.check_gcra(&redis_key, config).await,
+            RateLimitAlgorithm::LeakyBucket => self.check_leaky_bucket(&redis_key, config).await,
         }
     }
 
     /// Check rate limit using token bucket algorithm
-    async fn check_token_bucket(&mut self, key: &str) -> RateLimitResult {
+    async fn check_token_bucket(
+        &mut self,
+        key: &str,
+        config: &RateLimitConfig,
+    ) -> Result<RateLimitResult, redis::RedisError> {
         let now = SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)
             .unwrap()
             .as_secs();
 
-        let refill_rate = self.config.requests as f64 / self.config.window_secs as f64;
+        let refill_rate = config.requests as f64 / config.window_secs as f64;
 
         let script = Script::new(TOKEN_BUCKET_SCRIPT);
 
-        match script
+        let result = script
             .key(key)
-            .arg(self.config.requests)
+            .arg(config.requests)
             .arg(refill_rate)
             .arg(now)
-            .arg(self.config.window_secs)
+            .arg(config.window_secs)
             .invoke_async::<_, Vec<i64>>(&mut self.connection)
-            .await
-        {
-            Ok(result) => {
-                let allowed = result[0] == 1;
-                let remaining = result[1];
-                let reset_after = result[2] as u64;
-
-                debug!(
-                    "Token bucket check for key {}: allowed={}, remaining={}, reset_after={}",
-                    key, allowed, remaining, reset_after
-                );
-
-                if allowed {
-                    RateLimitResult::allowed(remaining, self.config.requests, reset_after)
-                } else {
-                    warn!("Rate limit exceeded for key: {} (token bucket)", key);
-                    RateLimitResult::denied(self.config.requests, reset_after)
-                }
-            }
-            Err(e) => {
-                error!("Redis error during rate limit check: {}", e);
-                // On error, deny the request for safety
-                RateLimitResult::denied(self.config.requests, self.config.window_secs)
-            }
+            .await?;
+
+        let allowed = result[0] == 1;
+        let remaining = result[1];
+        let reset_after = result[2] as u64;
+
+        debug!(
+            "Token bucket check for key {}: allowed={}, remaining={}, reset_after={}",
+            key, allowed, remaining, reset_after
+        );
+
+        if allowed {
+            Ok(RateLimitResult::allowed(
+                remaining,
+                config.requests,
+                reset_after,
+            ))
+        } else {
+            warn!("Rate limit exceeded for key: {} (token bucket)", key);
+            Ok(RateLimitResult::denied(config.requests, reset_after))
         }
     }
 
     /// Check rate limit using sliding window algorithm
-    async fn check_sliding_window(&mut self, key: &str) -> RateLimitResult {
+    async fn check_sliding_window(
+        &mut self,
+        key: &str,
+        config: &RateLimitConfig,
+    ) -> Result<RateLimitResult, redis::RedisError> {
         let now = SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)
             .unwrap()
@@ -107,70 +157,157 @@ impl RedisRateLimiter {
 
         let script = Script::new(SLIDING_WINDOW_SCRIPT);
 
-        match script
+        let result = script
             .key(key)
-            .arg(self.config.requests)
-            .arg(self.config.window_secs)
+            .arg(config.requests)
+            .arg(config.window_secs)
             .arg(now)
             .invoke_async::<_, Vec<i64>>(&mut self.connection)
-            .await
-        {
-            Ok(result) => {
-                let allowed = result[0] == 1;
-                let remaining = result[1];
-                let reset_after = result[2] as u64;
-
-                debug!(
-                    "Sliding window check for key {}: allowed={}, remaining={}, reset_after={}",
-                    key, allowed, remaining, reset_after
-                );
-
-                if allowed {
-                    RateLimitResult::allowed(remaining, self.config.requests, reset_after)
-                } else {
-                    warn!("Rate limit exceeded for key: {} (sliding window)", key);
-                    RateLimitResult::denied(self.config.requests, reset_after)
-                }
-            }
-            Err(e) => {
-                error!("Redis error during rate limit check: {}", e);
-                RateLimitResult::denied(self.config.requests, self.config.window_secs)
-            }
+            .await?;
+
+        let allowed = result[0] == 1;
+        let remaining = result[1];
+        let reset_after = result[2] as u64;
+
+        debug!(
+            "Sliding window check for key {}: allowed={}, remaining={}, reset_after={}",
+            key, allowed, remaining, reset_after
+        );
+
+        if allowed {
+            Ok(RateLimitResult::allowed(
+                remaining,
+                config.requests,
+                reset_after,
+            ))
+        } else {
+            warn!("Rate limit exceeded for key: {} (sliding window)", key);
+            Ok(RateLimitResult::denied(config.requests, reset_after))
         }
     }
 
     /// Check rate limit using fixed window algorithm
-    async fn check_fixed_window(&mut self, key: &str) -> RateLimitResult {
+    async fn check_fixed_window(
+        &mut self,
+        key: &str,
+        config: &RateLimitConfig,
+    ) -> Result<RateLimitResult, redis::RedisError> {
         let script = Script::new(FIXED_WINDOW_SCRIPT);
 
-        match script
+        let result = script
             .key(key)
-            .arg(self.config.requests)
-            .arg(self.config.window_secs)
+            .arg(config.requests)
+            .arg(config.window_secs)
             .invoke_async::<_, Vec<i64>>(&mut self.connection)
-            .await
-        {
-            Ok(result) => {
-                let allowed = result[0] == 1;
-                let remaining = result[1];
-                let reset_after = result[2] as u64;
-
-                debug!(
-                    "Fixed window check for key {}: allowed={}, remaining={}, reset_after={}",
-                    key, allowed, remaining, reset_after
-                );
-
-                if allowed {
-                    RateLimitResult::allowed(remaining, self.config.requests, reset_after)
-                } else {
-                    warn!("Rate limit exceeded for key: {} (fixed window)", key);
-                    RateLimitResult::denied(self.config.requests, reset_after)
-                }
-            }
-            Err(e) => {
-                error!("Redis error during rate limit check: {}", e);
-                RateLimitResult::denied(self.config.requests, self.config.window_secs)
-            }
+            .await?;
+
+        let allowed = result[0] == 1;
+        let remaining = result[1];
+        let reset_after = result[2] as u64;
+
+        debug!(
+            "Fixed window check for key {}: allowed={}, remaining={}, reset_after={}",
+            key, allowed, remaining, reset_after
+        );
+
+        if allowed {
+            Ok(RateLimitResult::allowed(
+                remaining,
+                config.requests,
+                reset_after,
+            ))
+        } else {
+            warn!("Rate limit exceeded for key: {} (fixed window)", key);
+            Ok(RateLimitResult::denied(config.requests, reset_after))
+        }
+    }
+
+    /// Check rate limit using the GCRA algorithm
+    async fn check_gcra(
+        &mut self,
+        key: &str,
+        config: &RateLimitConfig,
+    ) -> Result<RateLimitResult, redis::RedisError> {
+        let now_ms = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        let script = Script::new(GCRA_SCRIPT);
+
+        let result = script
+            .key(key)
+            .arg(config.emission_interval().as_millis() as u64)
+            .arg(config.burst_size())
+            .arg(now_ms)
+            .invoke_async::<_, Vec<i64>>(&mut self.connection)
+            .await?;
+
+        let allowed = result[0] == 1;
+        let remaining = result[1];
+        let reset_after = millis_to_secs(result[2]);
+
+        debug!(
+            "GCRA check for key {}: allowed={}, remaining={}, reset_after={}",
+            key, allowed, remaining, reset_after
+        );
+
+        if allowed {
+            Ok(RateLimitResult::allowed(
+                remaining,
+                config.requests,
+                reset_after,
+            ))
+        } else {
+            warn!("Rate limit exceeded for key: {} (gcra)", key);
+            Ok(RateLimitResult::denied(
+                config.requests,
+                millis_to_secs(result[3]),
+            ))
+        }
+    }
+
+    /// Check rate limit using the leaky bucket algorithm
+    async fn check_leaky_bucket(
+        &mut self,
+        key: &str,
+        config: &RateLimitConfig,
+    ) -> Result<RateLimitResult, redis::RedisError> {
+        let now_ms = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        let script = Script::new(LEAKY_BUCKET_SCRIPT);
+
+        let result = script
+            .key(key)
+            .arg(config.emission_interval().as_millis() as u64)
+            .arg(config.burst_size())
+            .arg(now_ms)
+            .invoke_async::<_, Vec<i64>>(&mut self.connection)
+            .await?;
+
+        let allowed = result[0] == 1;
+        let remaining = result[1];
+        let reset_after = millis_to_secs(result[2]);
+
+        debug!(
+            "Leaky bucket check for key {}: allowed={}, remaining={}, delay_ms={}",
+            key, allowed, remaining, result[3]
+        );
+
+        if allowed {
+            Ok(
+                RateLimitResult::allowed(remaining, config.requests, reset_after)
+                    .with_delay(Duration::from_millis(result[3].max(0) as u64)),
+            )
+        } else {
+            warn!("Rate limit exceeded for key: {} (leaky bucket)", key);
+            Ok(RateLimitResult::denied(
+                config.requests,
+                millis_to_secs(result[3]),
+            ))
         }
     }
 
@@ -180,6 +317,11 @@ impl RedisRateLimiter {
     }
 }
 
+/// Round milliseconds up to whole seconds (at least one)
+fn millis_to_secs(ms: i64) -> u64 {
+    ((ms.max(0) as u64).div_ceil(1000)).max(1)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -215,12 +357,12 @@ mod tests {
 
         // First requests should be allowed
         for _ in 0..10 {
-            let result = limiter.check_rate_limit(&key).await;
+            let result = limiter.check_rate_limit(&key).await.unwrap();
             assert!(result.allowed);
         }
 
         // 11th request should be denied
-        let result = limiter.check_rate_limit(&key).await;
+        let result = limiter.check_rate_limit(&key).await.unwrap();
         assert!(!result.allowed);
     }
 
@@ -238,12 +380,12 @@ mod tests {
 
         // First requests should be allowed
         for _ in 0..10 {
-            let result = limiter.check_rate_limit(&key).await;
+            let result = limiter.check_rate_limit(&key).await.unwrap();
             assert!(result.allowed);
         }
 
         // 11th request should be denied
-        let result = limiter.check_rate_limit(&key).await;
+        let result = limiter.check_rate_limit(&key).await.unwrap();
         assert!(!result.allowed);
     }
 
@@ -261,15 +403,94 @@ mod tests {
 
         // First requests should be allowed
         for i in 0..10 {
-            let result = limiter.check_rate_limit(&key).await;
+            let result = limiter.check_rate_limit(&key).await.unwrap();
             assert!(result.allowed, "Request {} should be allowed", i);
         }
 
         // 11th request should be denied
-        let result = limiter.check_rate_limit(&key).await;
+        let result = limiter.check_rate_limit(&key).await.unwrap();
         assert!(!result.allowed);
     }
 
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_gcra() {
+        let mut limiter = create_test_limiter(RateLimitAlgorithm::Gcra)
+            .await
+            .expect("Failed to connect to Redis");
+
+        let key = RateLimitKey::new(
+            RateLimitDimension::Ip,
+            format!("test-gcra-{}", rand::random::<u32>()),
+        );
+
+        // Burst of 10 requests is allowed
+        for i in 0..10 {
+            let result = limiter.check_rate_limit(&key).await.unwrap();
+            assert!(result.allowed, "Request {} should be allowed", i);
+        }
+
+        // 11th request is denied with a retry time of one emission interval (6s)
+        let result = limiter.check_rate_limit(&key).await.unwrap();
+        assert!(!result.allowed);
+        assert_eq!(result.retry_after, Some(6));
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_leaky_bucket() {
+        let mut limiter = create_test_limiter(RateLimitAlgorithm::LeakyBucket)
+            .await
+            .expect("Failed to connect to Redis");
+
+        let key = RateLimitKey::new(
+            RateLimitDimension::Ip,
+            format!("test-leaky-{}", rand::random::<u32>()),
+        );
+
+        // A burst of 10 is queued and spaced one drain interval (6s) apart
+        for i in 0..10u64 {
+            let result = limiter.check_rate_limit(&key).await.unwrap();
+            assert!(result.allowed, "Request {} should be allowed", i);
+            let expected = Duration::from_secs(6 * i);
+            assert!(
+                result.delay <= expected && result.delay + Duration::from_secs(1) > expected,
+                "Request {} delayed {:?}",
+                i,
+                result.delay
+            );
+        }
+
+        // The queue is full, so the 11th request is rejected
+        let result = limiter.check_rate_limit(&key).await.unwrap();
+        assert!(!result.allowed);
+    }
+
+    #[test]
+    fn test_parse_algorithm() {
+        assert!(matches!(
+            "gcra".parse::<RateLimitAlgorithm>().unwrap(),
+            RateLimitAlgorithm::Gcra
+        ));
+        assert!(matches!(
+            "Sliding_Window".parse::<RateLimitAlgorithm>().unwrap(),
+            RateLimitAlgorithm::SlidingWindow
+        ));
+        assert!(matches!(
+            "leaky_bucket".parse::<RateLimitAlgorithm>().unwrap(),
+            RateLimitAlgorithm::LeakyBucket
+        ));
+        assert!("leaky".parse::<RateLimitAlgorithm>().is_err());
+    }
+
+    #[test]
+    fn test_millis_to_secs() {
+        assert_eq!(millis_to_secs(0), 1);
+        assert_eq!(millis_to_secs(1000), 1);
+        assert_eq!(millis_to_secs(1001), 2);
+        assert_eq!(millis_to_secs(-5), 1);
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_redis_connection() {
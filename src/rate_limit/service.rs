@@ -1,9 +1,11 @@
 use super::local::LocalRateLimiter;
 use super::redis::{RateLimitAlgorithm, RedisRateLimiter};
 use super::types::{RateLimitConfig, RateLimitKey, RateLimitResult};
+use crate::config::RateLimitRedisConfig;
+use crate::metrics;
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 
 /// Rate limiter service that handles both local and distributed rate limiting
 pub struct RateLimiterService {
@@ -13,6 +15,10 @@ pub struct RateLimiterService {
     redis: Option<Arc<Mutex<RedisRateLimiter>>>,
     /// Whether to use Redis as primary
     use_redis_primary: bool,
+    /// Fall back to local limits on Redis errors instead of denying
+    fail_open: bool,
+    /// Default configuration
+    config: RateLimitConfig,
 }
 
 impl RateLimiterService {
@@ -20,9 +26,23 @@ impl RateLimiterService {
     pub fn local_only(config: RateLimitConfig) -> Self {
         info!("Initializing local-only rate limiter");
         Self {
-            local: Arc::new(LocalRateLimiter::new(config)),
+            local: Arc::new(LocalRateLimiter::new(config.clone())),
             redis: None,
             use_redis_primary: false,
+            fail_open: false,
+            config,
+        }
+    }
+
+    /// Create a new local-only rate limiter service using the given algorithm
+    pub fn local_with_algorithm(config: RateLimitConfig, algorithm: RateLimitAlgorithm) -> Self {
+        info!(algorithm = ?algorithm, "Initializing local-only rate limiter");
+        Self {
+            local: Arc::new(LocalRateLimiter::with_algorithm(config.clone(), algorithm)),
+            redis: None,
+            use_redis_primary: false,
+            fail_open: false,
+            config,
         }
     }
 
@@ -34,16 +54,20 @@ impl RateLimiterService {
     ) -> Result<Self, redis::RedisError> {
         info!("Initializing rate limiter with Redis backend");
 
-        let mut redis_limiter = RedisRateLimiter::new(redis_url, config.clone(), algorithm).await?;
+        let mut redis_limiter =
+            RedisRateLimiter::new(redis_url, config.clone(), algorithm.clone()).await?;
+        let local = Arc::new(LocalRateLimiter::with_algorithm(config.clone(), algorithm));
 
         // Test Redis connection
         match redis_limiter.ping().await {
             Ok(_) => {
                 info!("Redis connection successful, using Redis as primary rate limiter");
                 Ok(Self {
-                    local: Arc::new(LocalRateLimiter::new(config)),
+                    local,
                     redis: Some(Arc::new(Mutex::new(redis_limiter))),
                     use_redis_primary: true,
+                    fail_open: false,
+                    config,
                 })
             }
             Err(e) => {
@@ -52,54 +76,126 @@ impl RateLimiterService {
                     e
                 );
                 Ok(Self {
-                    local: Arc::new(LocalRateLimiter::new(config)),
+                    local,
                     redis: Some(Arc::new(Mutex::new(redis_limiter))),
                     use_redis_primary: false,
+                    fail_open: false,
+                    config,
                 })
             }
         }
     }
 
+    /// Create a rate limiter service from the `rate_limiting.redis` settings
+    ///
+    /// An unreachable primary is an error unless `fail_open` is set, in which
+    /// case the service starts with local limits only. Checks are atomic
+    /// scripts that update the counters, so they always run on the primary.
+    pub async fn from_redis_config(
+        config: RateLimitConfig,
+        redis: &RateLimitRedisConfig,
+        algorithm: RateLimitAlgorithm,
+    ) -> Result<Self, redis::RedisError> {
+        match Self::with_redis(config.clone(), &redis.url, algorithm.clone()).await {
+            Ok(service) => Ok(service.with_fail_open(redis.fail_open)),
+            Err(e) if redis.fail_open => {
+                warn!(
+                    "Redis unavailable: {}, failing open to the local rate limiter",
+                    e
+                );
+                Ok(Self::local_with_algorithm(config, algorithm).with_fail_open(true))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Fall back to local rate limiting when a Redis check fails
+    ///
+    /// By default a Redis error denies the request. With `fail_open` the
+    /// request is checked against the in-memory limiter instead, so a Redis
+    /// outage degrades to per-instance limits rather than rejecting traffic.
+    pub fn with_fail_open(mut self, fail_open: bool) -> Self {
+        self.fail_open = fail_open;
+        self
+    }
+
     /// Check if a request is allowed based on rate limiting
     pub async fn check_rate_limit(&self, key: &RateLimitKey) -> RateLimitResult {
+        self.check_rate_limit_with_config(key, &self.config).await
+    }
+
+    /// Check if a request is allowed using a specific configuration
+    /// (e.g. a per-API-key override)
+    pub async fn check_rate_limit_with_config(
+        &self,
+        key: &RateLimitKey,
+        config: &RateLimitConfig,
+    ) -> RateLimitResult {
         if self.use_redis_primary {
             if let Some(redis) = &self.redis {
-                match self.check_with_fallback(redis, key).await {
-                    Some(result) => result,
-                    None => {
-                        // Redis failed, use local
-                        warn!("Redis rate limit check failed, using local fallback");
-                        self.local.check_rate_limit(key).await
+                let result = redis
+                    .lock()
+                    .await
+                    .check_rate_limit_with_config(key, config)
+                    .await;
+
+                return match result {
+                    Ok(result) => result,
+                    Err(e) if self.fail_open => {
+                        warn!("Redis rate limit check failed: {}, using local fallback", e);
+                        metrics::record_rate_limit_fallback();
+                        self.local.check_rate_limit_with_config(key, config).await
                     }
-                }
-            } else {
-                // No Redis configured, use local
-                self.local.check_rate_limit(key).await
+                    Err(e) => {
+                        error!("Redis rate limit check failed: {}, denying request", e);
+                        RateLimitResult::denied(config.requests, config.window_secs)
+                    }
+                };
             }
-        } else {
-            // Use local rate limiter
-            self.local.check_rate_limit(key).await
         }
+
+        // Use local rate limiter
+        self.local.check_rate_limit_with_config(key, config).await
     }
 
-    /// Check rate limit with Redis and handle errors
-    async fn check_with_fallback(
+    /// Check several limits for one request with AND semantics
+    ///
+    /// Every limit is evaluated, so each dimension counts the request even
+    /// when another one rejects it. The combined result is denied if any
+    /// limit is exceeded and carries the longest `reset_after` among the
+    /// denials. Returns `None` when no limits apply.
+    pub async fn check_rate_limits(
         &self,
-        redis: &Arc<Mutex<RedisRateLimiter>>,
-        key: &RateLimitKey,
+        checks: &[(RateLimitKey, RateLimitConfig)],
     ) -> Option<RateLimitResult> {
-        let mut redis_guard = redis.lock().await;
-        let result = redis_guard.check_rate_limit(key).await;
-
-        // Check if the result indicates a Redis error
-        // (we treat deny as a valid result, not an error)
-        if result.allowed || result.remaining == 0 {
-            Some(result)
-        } else {
-            // This might be an error condition
-            debug!("Redis returned unexpected result, falling back to local");
-            None
+        RateLimitResult::most_restrictive(self.check_each_rate_limit(checks).await)
+    }
+
+    /// Check every limit, returning one result per check in order
+    pub async fn check_each_rate_limit(
+        &self,
+        checks: &[(RateLimitKey, RateLimitConfig)],
+    ) -> Vec<RateLimitResult> {
+        let mut results = Vec::with_capacity(checks.len());
+
+        for (key, config) in checks {
+            let result = self.check_rate_limit_with_config(key, config).await;
+            if result.allowed {
+                debug!(
+                    "Rate limit check passed for dimension {:?}, remaining: {}",
+                    key.dimension, result.remaining
+                );
+            } else {
+                warn!(
+                    "Rate limit exceeded for dimension {:?}, identifier: {}",
+                    key.dimension,
+                    key.log_identifier()
+                );
+            }
+            results.push(result);
         }
+
+        results
     }
 
     /// Check if Redis is available
@@ -118,6 +214,58 @@ impl RateLimiterService {
 mod tests {
     use super::*;
     use crate::rate_limit::types::RateLimitDimension;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// Start a fake Redis that answers PING but fails every other command
+    async fn spawn_failing_redis() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(serve_failing_redis(socket));
+            }
+        });
+
+        format!("redis://{}", addr)
+    }
+
+    async fn serve_failing_redis(socket: TcpStream) {
+        let (reader, mut writer) = socket.into_split();
+        let mut reader = BufReader::new(reader);
+        let mut line = String::new();
+
+        loop {
+            // Each command is an array of bulk strings: *<argc>, then $<len> <arg>
+            line.clear();
+            if reader.read_line(&mut line).await.unwrap_or(0) == 0 {
+                return;
+            }
+            let argc: usize = line.trim().trim_start_matches('*').parse().unwrap_or(0);
+
+            let mut command = String::new();
+            for i in 0..argc {
+                line.clear();
+                reader.read_line(&mut line).await.unwrap();
+                let len: usize = line.trim().trim_start_matches('$').parse().unwrap();
+                let mut arg = vec![0; len + 2];
+                reader.read_exact(&mut arg).await.unwrap();
+                if i == 0 {
+                    command = String::from_utf8_lossy(&arg[..len]).to_uppercase();
+                }
+            }
+
+            let reply = if command == "PING" {
+                "+PONG\r\n"
+            } else {
+                "-ERR simulated outage\r\n"
+            };
+            if writer.write_all(reply.as_bytes()).await.is_err() {
+                return;
+            }
+        }
+    }
 
     #[tokio::test]
     async fn test_local_only_service() {
@@ -173,6 +321,171 @@ mod tests {
         assert!(result.allowed);
     }
 
+    #[tokio::test]
+    async fn test_check_rate_limits_and_semantics() {
+        let ip_config = RateLimitConfig {
+            dimension: RateLimitDimension::Ip,
+            requests: 100,
+            window_secs: 60,
+            burst: None,
+        };
+        let key_config = RateLimitConfig {
+            dimension: RateLimitDimension::ApiKey,
+            requests: 2,
+            window_secs: 30,
+            burst: None,
+        };
+        let service = RateLimiterService::local_only(ip_config.clone());
+
+        let checks = vec![
+            (
+                RateLimitKey::new(RateLimitDimension::Ip, "192.168.1.1".to_string()),
+                ip_config,
+            ),
+            (
+                RateLimitKey::new(RateLimitDimension::ApiKey, "key-1".to_string()),
+                key_config,
+            ),
+        ];
+
+        for _ in 0..2 {
+            let result = service.check_rate_limits(&checks).await.unwrap();
+            assert!(result.allowed);
+        }
+
+        // The IP limit still has room but the API key limit is exhausted
+        let result = service.check_rate_limits(&checks).await.unwrap();
+        assert!(!result.allowed);
+        assert_eq!(result.limit, 2);
+        assert_eq!(result.reset_after, 30);
+
+        assert!(service.check_rate_limits(&[]).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_check_rate_limits_longest_reset_wins() {
+        let short = RateLimitConfig {
+            dimension: RateLimitDimension::Ip,
+            requests: 1,
+            window_secs: 10,
+            burst: None,
+        };
+        let long = RateLimitConfig {
+            dimension: RateLimitDimension::Route,
+            requests: 1,
+            window_secs: 120,
+            burst: None,
+        };
+        let service = RateLimiterService::local_only(short.clone());
+
+        let checks = vec![
+            (
+                RateLimitKey::new(RateLimitDimension::Ip, "10.0.0.1".to_string()),
+                short,
+            ),
+            (
+                RateLimitKey::with_route(
+                    RateLimitDimension::Route,
+                    "10.0.0.1".to_string(),
+                    "/api".to_string(),
+                ),
+                long,
+            ),
+        ];
+
+        assert!(service.check_rate_limits(&checks).await.unwrap().allowed);
+
+        let result = service.check_rate_limits(&checks).await.unwrap();
+        assert!(!result.allowed);
+        assert_eq!(result.reset_after, 120);
+    }
+
+    #[tokio::test]
+    async fn test_redis_errors_fall_back_to_local() {
+        let config = RateLimitConfig {
+            dimension: RateLimitDimension::Ip,
+            requests: 3,
+            window_secs: 60,
+            burst: None,
+        };
+        let redis_url = spawn_failing_redis().await;
+
+        let service =
+            RateLimiterService::with_redis(config, &redis_url, RateLimitAlgorithm::SlidingWindow)
+                .await
+                .expect("Failed to connect to fake Redis")
+                .with_fail_open(true);
+        assert!(service.is_redis_available());
+
+        let key = RateLimitKey::new(RateLimitDimension::Ip, "192.168.1.1".to_string());
+
+        // Redis fails every check, so the local limits apply
+        for _ in 0..3 {
+            let result = service.check_rate_limit(&key).await;
+            assert!(result.allowed);
+        }
+        let result = service.check_rate_limit(&key).await;
+        assert!(!result.allowed);
+    }
+
+    #[tokio::test]
+    async fn test_redis_errors_deny_without_fail_open() {
+        let config = RateLimitConfig {
+            dimension: RateLimitDimension::Ip,
+            requests: 3,
+            window_secs: 60,
+            burst: None,
+        };
+        let redis_url = spawn_failing_redis().await;
+
+        let service =
+            RateLimiterService::with_redis(config, &redis_url, RateLimitAlgorithm::SlidingWindow)
+                .await
+                .expect("Failed to connect to fake Redis");
+
+        let key = RateLimitKey::new(RateLimitDimension::Ip, "192.168.1.1".to_string());
+        let result = service.check_rate_limit(&key).await;
+        assert!(!result.allowed);
+        assert_eq!(result.retry_after, Some(60));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_primary_fails_open_when_configured() {
+        let config = RateLimitConfig {
+            dimension: RateLimitDimension::Ip,
+            requests: 3,
+            window_secs: 60,
+            burst: None,
+        };
+        let redis = |fail_open| RateLimitRedisConfig {
+            url: "redis://127.0.0.1:1".to_string(),
+            fail_open,
+        };
+
+        let result = RateLimiterService::from_redis_config(
+            config.clone(),
+            &redis(false),
+            RateLimitAlgorithm::SlidingWindow,
+        )
+        .await;
+        assert!(result.is_err());
+
+        let service = RateLimiterService::from_redis_config(
+            config,
+            &redis(true),
+            RateLimitAlgorithm::SlidingWindow,
+        )
+        .await
+        .unwrap();
+        assert!(!service.is_redis_available());
+
+        let key = RateLimitKey::new(RateLimitDimension::Ip, "192.168.1.1".to_string());
+        for _ in 0..3 {
+            assert!(service.check_rate_limit(&key).await.allowed);
+        }
+        assert!(!service.check_rate_limit(&key).await.allowed);
+    }
+
     #[tokio::test]
     #[ignore] // Requires Redis
     async fn test_redis_service() {
@@ -1,9 +1,13 @@
+use crate::auth::api_key::hashing;
+use crate::error::GatewayError;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 /// Rate limit dimension - what to rate limit by
+///
+/// Written as `ip`, `user`, `apikey`, `route` or `header:<name>`.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
-#[serde(rename_all = "lowercase")]
+#[serde(try_from = "String", into = "String")]
 pub enum RateLimitDimension {
     /// Rate limit by IP address
     Ip,
@@ -13,9 +17,75 @@ pub enum RateLimitDimension {
     ApiKey,
     /// Rate limit by route path
     Route,
+    /// Rate limit by the value of the named request header (e.g. a tenant id)
+    ///
+    /// Requests without the header share a single bucket.
+    Header(String),
+}
+
+impl std::str::FromStr for RateLimitDimension {
+    type Err = GatewayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ip" => Ok(RateLimitDimension::Ip),
+            "user" => Ok(RateLimitDimension::User),
+            "apikey" => Ok(RateLimitDimension::ApiKey),
+            "route" => Ok(RateLimitDimension::Route),
+            _ => match s.split_once(':') {
+                Some((kind, name))
+                    if kind.eq_ignore_ascii_case("header")
+                        && http::HeaderName::from_bytes(name.as_bytes()).is_ok() =>
+                {
+                    Ok(RateLimitDimension::Header(name.to_string()))
+                }
+                _ => Err(GatewayError::Config(format!(
+                    "Invalid rate limit dimension: {} (expected ip, user, apikey, route or header:<name>)",
+                    s
+                ))),
+            },
+        }
+    }
+}
+
+impl RateLimitDimension {
+    /// Metric label naming the kind of dimension
+    pub fn label(&self) -> &'static str {
+        match self {
+            RateLimitDimension::Ip => "ip",
+            RateLimitDimension::User => "user",
+            RateLimitDimension::ApiKey => "api_key",
+            RateLimitDimension::Route => "route",
+            RateLimitDimension::Header(_) => "header",
+        }
+    }
+}
+
+impl TryFrom<String> for RateLimitDimension {
+    type Error = GatewayError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<RateLimitDimension> for String {
+    fn from(dimension: RateLimitDimension) -> Self {
+        match dimension {
+            RateLimitDimension::Ip => "ip".to_string(),
+            RateLimitDimension::User => "user".to_string(),
+            RateLimitDimension::ApiKey => "apikey".to_string(),
+            RateLimitDimension::Route => "route".to_string(),
+            RateLimitDimension::Header(name) => format!("header:{}", name),
+        }
+    }
 }
 
 /// Rate limit configuration
+///
+/// A route may list several configurations; they are combined with AND
+/// semantics. Every limit is checked and the request is rejected if any
+/// one of them is exceeded.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RateLimitConfig {
     /// Dimension to rate limit by
@@ -39,6 +109,66 @@ impl RateLimitConfig {
     pub fn burst_size(&self) -> u32 {
         self.burst.unwrap_or(self.requests)
     }
+
+    /// GCRA emission interval: the time between evenly spaced requests
+    pub fn emission_interval(&self) -> Duration {
+        self.window() / self.requests.max(1)
+    }
+
+    /// Apply a per-key override, keeping the dimension of this config
+    pub fn with_override(&self, rate_limit: &RateLimitOverride) -> Self {
+        Self {
+            dimension: self.dimension.clone(),
+            requests: rate_limit.requests,
+            window_secs: rate_limit.window_secs,
+            burst: rate_limit.burst,
+        }
+    }
+}
+
+/// Requests that bypass rate limiting entirely (e.g. internal monitoring)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitExemptConfig {
+    /// Client IP addresses or CIDR ranges that are never limited
+    #[serde(default)]
+    pub ips: Vec<String>,
+    /// API keys that are never limited, matched once the key has authenticated
+    #[serde(default)]
+    pub api_keys: Vec<String>,
+}
+
+/// Per-API-key rate limit override (e.g. for tiered keys)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitOverride {
+    /// Maximum number of requests allowed
+    pub requests: u32,
+    /// Time window for the limit (in seconds)
+    pub window_secs: u64,
+    /// Burst size (if different from requests)
+    #[serde(default)]
+    pub burst: Option<u32>,
+}
+
+impl RateLimitOverride {
+    /// Check that the override describes a usable limit
+    pub fn validate(&self) -> Result<(), GatewayError> {
+        if self.requests == 0 {
+            return Err(GatewayError::Config(
+                "rate_limit.requests: Rate limit requests must be > 0".to_string(),
+            ));
+        }
+        if self.window_secs == 0 {
+            return Err(GatewayError::Config(
+                "rate_limit.window_secs: Rate limit window must be > 0".to_string(),
+            ));
+        }
+        if self.burst == Some(0) {
+            return Err(GatewayError::Config(
+                "rate_limit.burst: Rate limit burst must be > 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// Rate limit result
@@ -54,6 +184,8 @@ pub struct RateLimitResult {
     pub reset_after: u64,
     /// Retry after duration (for 429 responses)
     pub retry_after: Option<u64>,
+    /// How long an allowed request waits for its turn (leaky bucket)
+    pub delay: Duration,
 }
 
 impl RateLimitResult {
@@ -65,9 +197,16 @@ impl RateLimitResult {
             limit,
             reset_after,
             retry_after: None,
+            delay: Duration::ZERO,
         }
     }
 
+    /// Hold an allowed request for `delay` before it proceeds
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
     /// Create a denied result
     pub fn denied(limit: u32, retry_after: u64) -> Self {
         Self {
@@ -76,8 +215,52 @@ impl RateLimitResult {
             limit,
             reset_after: retry_after,
             retry_after: Some(retry_after),
+            delay: Duration::ZERO,
         }
     }
+
+    /// Combine the results of several limits checked for the same request
+    ///
+    /// The request is denied if any result is denied. Among denied results
+    /// the one with the longest wait wins; if all are allowed, the one with
+    /// the fewest remaining requests does, waiting out the longest delay
+    /// among them. Returns `None` for no results.
+    pub fn most_restrictive(results: impl IntoIterator<Item = Self>) -> Option<Self> {
+        let mut delay = Duration::ZERO;
+        results
+            .into_iter()
+            .inspect(|result| delay = delay.max(result.delay))
+            .reduce(|current, next| match (current.allowed, next.allowed) {
+                (true, false) => next,
+                (false, true) => current,
+                (false, false) => {
+                    if next.wait_secs() > current.wait_secs() {
+                        next
+                    } else {
+                        current
+                    }
+                }
+                (true, true) => {
+                    if next.remaining < current.remaining {
+                        next
+                    } else {
+                        current
+                    }
+                }
+            })
+            .map(|result| {
+                if result.allowed {
+                    result.with_delay(delay)
+                } else {
+                    result
+                }
+            })
+    }
+
+    /// Seconds a client must wait before the limit admits it again
+    fn wait_secs(&self) -> u64 {
+        self.retry_after.unwrap_or(self.reset_after)
+    }
 }
 
 /// Rate limit key components
@@ -110,13 +293,24 @@ impl RateLimitKey {
         }
     }
 
+    /// Identifier safe to log: API keys are reduced to a digest prefix
+    pub fn log_identifier(&self) -> String {
+        match self.dimension {
+            RateLimitDimension::ApiKey => {
+                format!("sha256:{}", &hashing::hash_key(&self.identifier)[..12])
+            }
+            _ => self.identifier.clone(),
+        }
+    }
+
     /// Convert to a Redis key
     pub fn to_redis_key(&self) -> String {
-        let dim = match self.dimension {
-            RateLimitDimension::Ip => "ip",
-            RateLimitDimension::User => "user",
-            RateLimitDimension::ApiKey => "apikey",
-            RateLimitDimension::Route => "route",
+        let dim = match &self.dimension {
+            RateLimitDimension::Ip => "ip".to_string(),
+            RateLimitDimension::User => "user".to_string(),
+            RateLimitDimension::ApiKey => "apikey".to_string(),
+            RateLimitDimension::Route => "route".to_string(),
+            RateLimitDimension::Header(name) => format!("header:{}", name.to_ascii_lowercase()),
         };
 
         if let Some(route) = &self.route {
@@ -145,6 +339,51 @@ mod tests {
             key_with_route.to_redis_key(),
             "gateway:ratelimit:user:user123:/api/users"
         );
+
+        let header_key = RateLimitKey::new(
+            RateLimitDimension::Header("X-Tenant-ID".to_string()),
+            "acme".to_string(),
+        );
+        assert_eq!(
+            header_key.to_redis_key(),
+            "gateway:ratelimit:header:x-tenant-id:acme"
+        );
+    }
+
+    #[test]
+    fn test_log_identifier_hides_api_keys() {
+        let key = RateLimitKey::new(RateLimitDimension::ApiKey, "sk_live_secret123".to_string());
+        let logged = key.log_identifier();
+        assert!(logged.starts_with("sha256:"));
+        assert!(!logged.contains("secret"));
+        assert_eq!(logged.len(), "sha256:".len() + 12);
+
+        let key = RateLimitKey::new(RateLimitDimension::Ip, "192.168.1.1".to_string());
+        assert_eq!(key.log_identifier(), "192.168.1.1");
+    }
+
+    #[test]
+    fn test_dimension_parsing() {
+        let config: RateLimitConfig = serde_yaml::from_str(
+            "{dimension: \"header:X-Tenant-ID\", requests: 10, window_secs: 60}",
+        )
+        .unwrap();
+        assert_eq!(
+            config.dimension,
+            RateLimitDimension::Header("X-Tenant-ID".to_string())
+        );
+        assert_eq!(
+            "apikey".parse::<RateLimitDimension>().unwrap(),
+            RateLimitDimension::ApiKey
+        );
+        assert_eq!(
+            String::from(RateLimitDimension::Header("X-Tenant-ID".to_string())),
+            "header:X-Tenant-ID"
+        );
+
+        assert!("header:".parse::<RateLimitDimension>().is_err());
+        assert!("header:X Tenant".parse::<RateLimitDimension>().is_err());
+        assert!("cookie:session".parse::<RateLimitDimension>().is_err());
     }
 
     #[test]
@@ -160,6 +399,26 @@ mod tests {
         assert_eq!(config.window(), Duration::from_secs(60));
     }
 
+    #[test]
+    fn test_rate_limit_config_with_override() {
+        let config = RateLimitConfig {
+            dimension: RateLimitDimension::ApiKey,
+            requests: 10,
+            window_secs: 60,
+            burst: Some(20),
+        };
+        let pro = RateLimitOverride {
+            requests: 1000,
+            window_secs: 60,
+            burst: None,
+        };
+
+        let overridden = config.with_override(&pro);
+        assert_eq!(overridden.dimension, RateLimitDimension::ApiKey);
+        assert_eq!(overridden.requests, 1000);
+        assert_eq!(overridden.burst_size(), 1000);
+    }
+
     #[test]
     fn test_rate_limit_result() {
         let allowed = RateLimitResult::allowed(50, 100, 30);
@@ -172,4 +431,46 @@ mod tests {
         assert_eq!(denied.remaining, 0);
         assert_eq!(denied.retry_after, Some(30));
     }
+
+    #[test]
+    fn test_most_restrictive_denied_wins() {
+        let combined = RateLimitResult::most_restrictive([
+            RateLimitResult::allowed(99, 100, 60),
+            RateLimitResult::denied(10, 5),
+            RateLimitResult::denied(1000, 42),
+            RateLimitResult::allowed(5, 1000, 60),
+        ])
+        .unwrap();
+
+        assert!(!combined.allowed);
+        assert_eq!(combined.limit, 1000);
+        assert_eq!(combined.reset_after, 42);
+    }
+
+    #[test]
+    fn test_most_restrictive_all_allowed() {
+        let combined = RateLimitResult::most_restrictive([
+            RateLimitResult::allowed(99, 100, 60),
+            RateLimitResult::allowed(3, 1000, 30),
+        ])
+        .unwrap();
+
+        assert!(combined.allowed);
+        assert_eq!(combined.remaining, 3);
+        assert_eq!(combined.limit, 1000);
+
+        assert!(RateLimitResult::most_restrictive([]).is_none());
+    }
+
+    #[test]
+    fn test_most_restrictive_waits_longest_delay() {
+        let combined = RateLimitResult::most_restrictive([
+            RateLimitResult::allowed(3, 10, 60).with_delay(Duration::from_secs(2)),
+            RateLimitResult::allowed(1, 100, 60),
+        ])
+        .unwrap();
+
+        assert_eq!(combined.remaining, 1);
+        assert_eq!(combined.delay, Duration::from_secs(2));
+    }
 }
@@ -1,55 +1,248 @@
 use crate::cache::CacheService;
-use crate::config::{RouteAuthConfig, RouteConfig};
-use crate::cors::CorsConfig;
+use crate::circuit_breaker::{CircuitBreakerService, FallbackConfig};
+use crate::config::{RouteAuthConfig, RouteConfig, TrailingSlash, ValueMatch};
+use crate::cors::CorsService;
 use crate::error::{GatewayError, Result};
 use crate::healthcheck::HealthChecker;
 use crate::ipfilter::IpFilterService;
+use crate::loadbalancer::backend::Backend;
+use crate::loadbalancer::canary::CanaryGroup;
 use crate::loadbalancer::strategies::{
     LoadBalancingStrategy, RoundRobinStrategy, WeightedStrategy,
 };
 use crate::loadbalancer::LoadBalancer;
+use crate::mirror::MirrorService;
 use crate::transform::TransformService;
-use http::Method;
+use http::header::HeaderName;
+use http::{HeaderMap, Method, StatusCode};
 use matchit::Router as MatchitRouter;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use regex::Regex;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+/// Characters left unencoded in path parameter values filled into backend
+/// URL templates (RFC 3986 unreserved characters)
+const COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
 /// Route information
 #[derive(Debug, Clone)]
 pub struct Route {
+    /// Configured path pattern (e.g., "/api/users/:id")
+    pub path: String,
     /// Load balancer for multiple backends (or single backend)
     pub load_balancer: Arc<LoadBalancer>,
+    /// Canary backend group receiving a share of traffic
+    pub canary: Option<Arc<CanaryGroup>>,
     /// Health checker for this route
     pub health_checker: Option<Arc<HealthChecker>>,
-    /// Allowed HTTP methods (empty means all methods allowed)
+    /// Allowed HTTP methods (empty means all but TRACE and CONNECT)
     pub methods: Vec<Method>,
+    /// Header and query parameter predicates that must all hold for this route to match
+    pub predicates: Vec<RequestPredicate>,
     /// Whether to strip the prefix when forwarding
     pub strip_prefix: bool,
     /// Route description
     pub description: String,
+    /// Whether requests are proxied; disabled routes answer `disabled_status`
+    pub enabled: bool,
+    /// Status returned while the route is disabled
+    pub disabled_status: StatusCode,
     /// Authentication configuration
     pub auth: Option<RouteAuthConfig>,
     /// Request/response transformation service
     pub transform: Option<Arc<TransformService>>,
-    /// CORS configuration
-    pub cors: Option<CorsConfig>,
+    /// CORS service
+    pub cors: Option<Arc<CorsService>>,
     /// IP filter service
     pub ip_filter: Option<Arc<IpFilterService>>,
     /// Cache service
     pub cache: Option<Arc<CacheService>>,
+    /// Honor X-HTTP-Method-Override (None uses the global setting)
+    pub honor_method_override: Option<bool>,
+    /// Answer OPTIONS at the gateway (None uses the global setting)
+    pub answer_options: Option<bool>,
+    /// Largest backend response body accepted, in bytes
+    pub max_response_bytes: Option<usize>,
+    /// Hop-by-hop request headers forwarded to the backend (lowercase)
+    pub preserve_headers: Vec<String>,
+    /// Circuit breaker for this route (None uses the global breaker)
+    pub circuit_breaker: Option<Arc<CircuitBreakerService>>,
+    /// Response served while the circuit is open
+    pub fallback: Option<FallbackConfig>,
+    /// Traffic mirror for this route
+    pub mirror: Option<Arc<MirrorService>>,
+}
+
+impl Route {
+    /// Select a backend for a request
+    ///
+    /// Requests assigned to the canary group use a canary backend; if none is
+    /// healthy they fall back to the stable backends.
+    pub fn select_backend(
+        &self,
+        client_ip: Option<std::net::IpAddr>,
+        headers: &HeaderMap,
+    ) -> Option<Arc<Backend>> {
+        self.canary
+            .as_ref()
+            .and_then(|canary| canary.select_backend(client_ip, headers))
+            .or_else(|| self.load_balancer.select_backend(client_ip))
+    }
+
+    /// Check whether the route accepts a request method
+    ///
+    /// Routes without explicit methods accept everything except `TRACE` and
+    /// `CONNECT`, which are only proxied when listed.
+    pub fn allows_method(&self, method: &Method) -> bool {
+        if self.methods.is_empty() {
+            method != Method::TRACE && method != Method::CONNECT
+        } else {
+            self.methods.contains(method)
+        }
+    }
+
+    /// Methods to advertise in an `Allow` header for this route
+    ///
+    /// OPTIONS is always included since the gateway answers it itself.
+    pub fn allowed_methods(&self) -> Vec<Method> {
+        let mut methods = if self.methods.is_empty() {
+            vec![
+                Method::GET,
+                Method::HEAD,
+                Method::POST,
+                Method::PUT,
+                Method::PATCH,
+                Method::DELETE,
+            ]
+        } else {
+            self.methods.clone()
+        };
+        if !methods.contains(&Method::OPTIONS) {
+            methods.push(Method::OPTIONS);
+        }
+        methods
+    }
+
+    /// Check whether a request satisfies all of this route's predicates
+    pub fn matches_request(&self, headers: &HeaderMap, query: &[(String, String)]) -> bool {
+        self.predicates
+            .iter()
+            .all(|predicate| predicate.matches(headers, query))
+    }
+}
+
+/// Compiled header or query parameter predicate of a route
+#[derive(Debug, Clone)]
+pub struct RequestPredicate {
+    /// Request value to inspect
+    target: PredicateTarget,
+    /// Expected value
+    value: ValueMatcher,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum PredicateTarget {
+    Header(String),
+    Query(String),
+}
+
+#[derive(Debug, Clone)]
+enum ValueMatcher {
+    Exact(String),
+    Regex(Regex),
+}
+
+impl RequestPredicate {
+    /// Compile a header predicate from configuration
+    pub fn header(name: &str, value: &ValueMatch) -> Result<Self> {
+        let name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+            GatewayError::Config(format!("match_headers: Invalid header name: {}", name))
+        })?;
+        let value = ValueMatcher::new(value, &format!("match_headers.{}", name))?;
+        Ok(Self {
+            target: PredicateTarget::Header(name.as_str().to_string()),
+            value,
+        })
+    }
+
+    /// Compile a query parameter predicate from configuration
+    pub fn query(name: &str, value: &ValueMatch) -> Result<Self> {
+        let value = ValueMatcher::new(value, &format!("match_query.{}", name))?;
+        Ok(Self {
+            target: PredicateTarget::Query(name.to_string()),
+            value,
+        })
+    }
+
+    /// Check whether any value of the header or query parameter matches
+    pub fn matches(&self, headers: &HeaderMap, query: &[(String, String)]) -> bool {
+        match &self.target {
+            PredicateTarget::Header(name) => headers
+                .get_all(name.as_str())
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .any(|value| self.value.matches(value)),
+            PredicateTarget::Query(name) => query
+                .iter()
+                .any(|(key, value)| key == name && self.value.matches(value)),
+        }
+    }
+
+    /// Comparable form, used to detect routes with identical predicates
+    fn key(&self) -> (PredicateTarget, bool, &str) {
+        match &self.value {
+            ValueMatcher::Exact(value) => (self.target.clone(), false, value),
+            ValueMatcher::Regex(regex) => (self.target.clone(), true, regex.as_str()),
+        }
+    }
+}
+
+impl ValueMatcher {
+    fn new(value: &ValueMatch, field: &str) -> Result<Self> {
+        match value {
+            ValueMatch::Exact(value) => Ok(ValueMatcher::Exact(value.clone())),
+            // Anchor so a pattern cannot match part of a longer value
+            ValueMatch::Regex { regex } => Regex::new(&format!("^(?:{})$", regex))
+                .map(ValueMatcher::Regex)
+                .map_err(|e| {
+                    GatewayError::Config(format!("{}: Invalid regex '{}': {}", field, regex, e))
+                }),
+        }
+    }
+
+    fn matches(&self, value: &str) -> bool {
+        match self {
+            ValueMatcher::Exact(expected) => value == expected,
+            ValueMatcher::Regex(regex) => regex.is_match(value),
+        }
+    }
 }
 
 /// Gateway router for matching incoming requests to backend services
+///
+/// Several routes may share a path when their header or query predicates
+/// differ; the matcher holds them most specific first.
 #[derive(Debug, Clone)]
 pub struct Router {
     /// Path-based router using matchit
-    matcher: MatchitRouter<Route>,
+    matcher: MatchitRouter<Vec<Route>>,
+    /// Configured paths and their routes, in configuration order
+    entries: Vec<(String, Route)>,
+    /// How paths differing from a route only by a trailing slash match
+    trailing_slash: TrailingSlash,
 }
 
 impl Router {
     /// Create a new router from route configurations
     pub fn new(routes: Vec<RouteConfig>) -> Result<Self> {
-        let mut matcher = MatchitRouter::new();
+        let mut candidates: Vec<(String, Vec<Route>)> = Vec::new();
+        let mut entries = Vec::new();
 
         for route_config in routes {
             let methods = if route_config.methods.is_empty() {
@@ -65,9 +258,36 @@ impl Router {
                     .collect::<Result<Vec<_>>>()?
             };
 
+            let predicates = route_config
+                .match_headers
+                .iter()
+                .map(|(name, value)| RequestPredicate::header(name, value))
+                .chain(
+                    route_config
+                        .match_query
+                        .iter()
+                        .map(|(name, value)| RequestPredicate::query(name, value)),
+                )
+                .collect::<Result<Vec<_>>>()?;
+
             // Get backends for this route
             let backend_configs = route_config.get_backends()?;
 
+            // Backend URL templates may only use the route's path parameters
+            let params = path_params(&route_config.path);
+            let canary_backends = route_config.canary.iter().flat_map(|c| &c.backends);
+            for backend in backend_configs.iter().chain(canary_backends) {
+                if let Some(unknown) = placeholders(&backend.url)
+                    .into_iter()
+                    .find(|name| !params.contains(name))
+                {
+                    return Err(GatewayError::InvalidRoute(format!(
+                        "Backend {} uses {{{}}}, which is not a path parameter of {}",
+                        backend.url, unknown, route_config.path
+                    )));
+                }
+            }
+
             // Determine load balancing strategy
             let strategy = if let Some(lb_config) = &route_config.load_balancer {
                 parse_strategy(&lb_config.strategy)?
@@ -79,13 +299,34 @@ impl Router {
             // Create load balancer
             let load_balancer = Arc::new(LoadBalancer::new(backend_configs.clone(), strategy));
 
+            // Create canary group if configured, balanced with the same strategy
+            let canary = route_config
+                .canary
+                .map(|config| -> Result<_> {
+                    let strategy = match &route_config.load_balancer {
+                        Some(lb_config) => parse_strategy(&lb_config.strategy)?,
+                        None => LoadBalancingStrategy::RoundRobin(RoundRobinStrategy::new()),
+                    };
+                    CanaryGroup::new(config, strategy)
+                })
+                .transpose()?
+                .map(Arc::new);
+
             // Create health checker if configured
-            let health_checker = route_config.health_check.as_ref().map(|hc_config| {
-                let checker = Arc::new(HealthChecker::new(hc_config.clone()));
-                // Start active health checks
-                checker.start_active_checks(load_balancer.backends().to_vec());
-                checker
-            });
+            let health_checker = route_config
+                .health_check
+                .as_ref()
+                .map(|hc_config| -> Result<_> {
+                    let checker = Arc::new(HealthChecker::new(hc_config.clone())?);
+                    // Start active health checks
+                    let mut backends = load_balancer.backends().to_vec();
+                    if let Some(canary) = &canary {
+                        backends.extend(canary.load_balancer().backends().iter().cloned());
+                    }
+                    checker.start_active_checks(backends);
+                    Ok(checker)
+                })
+                .transpose()?;
 
             // Create transform service if configured
             let transform = route_config
@@ -103,53 +344,233 @@ impl Router {
                 .transpose()?
                 .map(Arc::new);
 
+            // Create CORS service if configured
+            let cors = route_config
+                .cors
+                .map(CorsService::new)
+                .transpose()?
+                .map(Arc::new);
+
             // Create cache service if configured
             let cache = route_config
                 .cache
                 .as_ref()
                 .filter(|c| c.enabled)
-                .map(|config| Arc::new(CacheService::new(config.clone())));
+                .map(|config| CacheService::new(config.clone()))
+                .transpose()?
+                .map(Arc::new);
+
+            // Create traffic mirror if configured
+            let mirror = route_config
+                .mirror
+                .map(MirrorService::new)
+                .transpose()?
+                .map(Arc::new);
+
+            // Create a route-specific circuit breaker if configured
+            let circuit_breaker = route_config
+                .circuit_breaker
+                .map(|config| Arc::new(CircuitBreakerService::new(config)));
 
             let route = Route {
+                path: route_config.path.clone(),
                 load_balancer,
+                canary,
                 health_checker,
                 methods,
+                predicates,
                 strip_prefix: route_config.strip_prefix,
                 description: route_config.description,
+                enabled: route_config.enabled,
+                disabled_status: StatusCode::from_u16(route_config.disabled_status)
+                    .unwrap_or(StatusCode::SERVICE_UNAVAILABLE),
                 auth: route_config.auth,
                 transform,
-                cors: route_config.cors,
+                cors,
                 ip_filter,
                 cache,
+                honor_method_override: route_config.honor_method_override,
+                answer_options: route_config.answer_options,
+                max_response_bytes: route_config.max_response_bytes,
+                preserve_headers: route_config
+                    .preserve_headers
+                    .iter()
+                    .map(|name| name.to_ascii_lowercase())
+                    .collect(),
+                circuit_breaker,
+                fallback: route_config.fallback,
+                mirror,
             };
 
             // Convert path syntax from :param to {param} and *path to {*path}
             let matchit_path = convert_path_syntax(&route_config.path);
 
-            matcher.insert(&matchit_path, route).map_err(|e| {
+            // Routes sharing a path must be told apart by their predicates
+            match candidates
+                .iter_mut()
+                .find(|(path, _)| *path == matchit_path)
+            {
+                Some((_, routes)) => {
+                    if let Some(existing) = routes
+                        .iter()
+                        .find(|r| same_predicates(&r.predicates, &route.predicates))
+                    {
+                        return Err(GatewayError::InvalidRoute(format!(
+                            "Failed to insert route: {} has the same path and predicates as {}",
+                            route_config.path, existing.path
+                        )));
+                    }
+                    routes.push(route.clone());
+                }
+                None => candidates.push((matchit_path, vec![route.clone()])),
+            }
+            entries.push((route_config.path, route));
+        }
+
+        let mut matcher = MatchitRouter::new();
+        for (matchit_path, mut routes) in candidates {
+            // Most predicates first; ties keep configuration order
+            routes.sort_by_key(|route| std::cmp::Reverse(route.predicates.len()));
+            matcher.insert(&matchit_path, routes).map_err(|e| {
                 GatewayError::InvalidRoute(format!("Failed to insert route: {}", e))
             })?;
         }
 
-        Ok(Self { matcher })
+        Ok(Self {
+            matcher,
+            entries,
+            trailing_slash: TrailingSlash::default(),
+        })
+    }
+
+    /// Set the policy for paths differing from a route only by a trailing slash
+    pub fn with_trailing_slash(mut self, trailing_slash: TrailingSlash) -> Self {
+        self.trailing_slash = trailing_slash;
+        self
     }
 
-    /// Match a request path and method to a route
-    pub fn match_route(&self, path: &str, method: &Method) -> Result<RouteMatch> {
+    /// Path to look up for a request path under the trailing-slash policy
+    ///
+    /// Returns the trailing-slash variant of the path when only that variant
+    /// has a route and the policy is not strict, otherwise the path itself.
+    fn resolve_path<'a>(&self, path: &'a str) -> Cow<'a, str> {
+        if self.trailing_slash == TrailingSlash::Strict || self.matcher.at(path).is_ok() {
+            return Cow::Borrowed(path);
+        }
+
+        let variant = match path.strip_suffix('/') {
+            Some("") => return Cow::Borrowed(path),
+            Some(trimmed) => trimmed.to_string(),
+            None => format!("{}/", path),
+        };
+        if self.matcher.at(&variant).is_ok() {
+            Cow::Owned(variant)
+        } else {
+            Cow::Borrowed(path)
+        }
+    }
+
+    /// Find the route for a path without checking the method or predicates
+    ///
+    /// Among routes sharing the path, the least specific one is returned.
+    pub fn find_route(&self, path: &str) -> Option<&Route> {
+        self.matcher
+            .at(&self.resolve_path(path))
+            .ok()
+            .and_then(|matched| matched.value.last())
+    }
+
+    /// Where to permanently redirect a request path under the `redirect`
+    /// trailing-slash policy
+    ///
+    /// Returns the route's form of the path, keeping the query, when the path
+    /// only matches a route after adding or removing its trailing slash.
+    pub fn redirect_location(&self, path: &str, query: Option<&str>) -> Option<String> {
+        if self.trailing_slash != TrailingSlash::Redirect {
+            return None;
+        }
+        match self.resolve_path(path) {
+            Cow::Owned(canonical) => Some(match query {
+                Some(query) => format!("{}?{}", canonical, query),
+                None => canonical,
+            }),
+            Cow::Borrowed(_) => None,
+        }
+    }
+
+    /// Match a request path, method, headers and query to a route
+    ///
+    /// When several routes share the path, the most specific one whose
+    /// predicates all hold wins, so a route without `match_headers` or
+    /// `match_query` acts as the fallback.
+    ///
+    /// A path matching a route only after adding or removing its trailing
+    /// slash matches that route under the `ignore` policy, but not under
+    /// `redirect`, where `redirect_location` gives the route's form instead.
+    pub fn match_route(
+        &self,
+        path: &str,
+        method: &Method,
+        headers: &HeaderMap,
+        query: Option<&str>,
+    ) -> Result<RouteMatch> {
+        self.match_request(path, Some(method), headers, query)
+    }
+
+    /// Match an OPTIONS request like `match_route`, but to routes whatever
+    /// methods they allow, since the gateway can answer OPTIONS itself
+    pub fn match_options_route(
+        &self,
+        path: &str,
+        headers: &HeaderMap,
+        query: Option<&str>,
+    ) -> Result<RouteMatch> {
+        self.match_request(path, None, headers, query)
+    }
+
+    /// Match a request to a route allowing `method`, or any route if `None`
+    fn match_request(
+        &self,
+        path: &str,
+        method: Option<&Method>,
+        headers: &HeaderMap,
+        query: Option<&str>,
+    ) -> Result<RouteMatch> {
+        let lookup_path = self.resolve_path(path);
+        if matches!(lookup_path, Cow::Owned(_)) && self.trailing_slash == TrailingSlash::Redirect {
+            return Err(GatewayError::RouteNotFound(path.to_string()));
+        }
+
         let matched = self
             .matcher
-            .at(path)
+            .at(&lookup_path)
             .map_err(|_| GatewayError::RouteNotFound(path.to_string()))?;
 
-        let route = matched.value;
+        let query: Vec<(String, String)> = query
+            .map(|q| {
+                url::form_urlencoded::parse(q.as_bytes())
+                    .into_owned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mut routes = matched
+            .value
+            .iter()
+            .filter(|route| route.matches_request(headers, &query))
+            .peekable();
+        if routes.peek().is_none() {
+            return Err(GatewayError::RouteNotFound(path.to_string()));
+        }
 
-        // Check if method is allowed (empty methods means all methods are allowed)
-        if !route.methods.is_empty() && !route.methods.contains(method) {
+        let Some(route) =
+            routes.find(|route| method.is_none_or(|method| route.allows_method(method)))
+        else {
             return Err(GatewayError::InvalidMethod(format!(
                 "Method {} not allowed for path {}",
-                method, path
+                method.unwrap_or(&Method::OPTIONS),
+                path
             )));
-        }
+        };
 
         // Extract path parameters
         let params: HashMap<String, String> = matched
@@ -165,11 +586,16 @@ impl Router {
         })
     }
 
-    /// Get all routes in the router
+    /// Get all configured route paths
     pub fn routes(&self) -> Vec<String> {
-        // This is a simple implementation that doesn't expose internal matchit structure
-        // In a real implementation, you might want to store route paths separately
-        vec![]
+        self.entries.iter().map(|(path, _)| path.clone()).collect()
+    }
+
+    /// Iterate over configured route paths and their routes
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &Route)> {
+        self.entries
+            .iter()
+            .map(|(path, route)| (path.as_str(), route))
     }
 }
 
@@ -184,8 +610,17 @@ pub struct RouteMatch {
     pub matched_path: String,
 }
 
+/// Whether two routes have equivalent predicates
+fn same_predicates(a: &[RequestPredicate], b: &[RequestPredicate]) -> bool {
+    let mut a: Vec<_> = a.iter().map(RequestPredicate::key).collect();
+    let mut b: Vec<_> = b.iter().map(RequestPredicate::key).collect();
+    a.sort();
+    b.sort();
+    a == b
+}
+
 /// Parse load balancing strategy from string
-fn parse_strategy(strategy: &str) -> Result<LoadBalancingStrategy> {
+pub(crate) fn parse_strategy(strategy: &str) -> Result<LoadBalancingStrategy> {
     match strategy.to_lowercase().as_str() {
         "round_robin" | "roundrobin" => Ok(LoadBalancingStrategy::RoundRobin(RoundRobinStrategy::new())),
         "least_connections" | "leastconnections" => Ok(LoadBalancingStrategy::LeastConnections),
@@ -198,6 +633,38 @@ fn parse_strategy(strategy: &str) -> Result<LoadBalancingStrategy> {
     }
 }
 
+/// Names of the `:param` and `*param` segments of a route path
+fn path_params(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter_map(|segment| {
+            let name = segment
+                .strip_prefix(':')
+                .or_else(|| segment.strip_prefix('*'))?;
+            let end = name
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(name.len());
+            Some(&name[..end])
+        })
+        .collect()
+}
+
+/// Names of the catch-all (`*name`) parameters of a route path
+fn wildcard_params(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('*'))
+        .collect()
+}
+
+/// Names of the `{param}` placeholders in a backend URL template
+fn placeholders(template: &str) -> Vec<&str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+        .filter(|name| !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_'))
+        .collect()
+}
+
 /// Convert path syntax from Express-style (:param, *path) to matchit syntax ({param}, {*path})
 fn convert_path_syntax(path: &str) -> String {
     let mut result = String::new();
@@ -239,19 +706,89 @@ fn convert_path_syntax(path: &str) -> String {
 
 impl RouteMatch {
     /// Build the backend URL for a given backend
-    pub fn build_backend_url(&self, backend_url: &str, original_path: &str) -> String {
+    ///
+    /// A backend URL with `{param}` placeholders is a complete template: the
+    /// placeholders are filled from the path parameters and the request path
+    /// is not appended. Values are percent-encoded for the part of the URL
+    /// they land in, so they cannot add path segments or query parameters.
+    pub fn build_backend_url(&self, backend_url: &str, original_path: &str) -> Result<String> {
+        if !placeholders(backend_url).is_empty() {
+            return self.fill_template(backend_url);
+        }
+
         if self.route.strip_prefix {
             // If strip_prefix is true, we need to remove the matched portion
             // and append the remaining path to the backend
             let remaining = original_path
                 .strip_prefix(&self.matched_path)
                 .unwrap_or(original_path);
-            format!("{}{}", backend_url.trim_end_matches('/'), remaining)
+            Ok(format!(
+                "{}{}",
+                backend_url.trim_end_matches('/'),
+                remaining
+            ))
         } else {
             // Otherwise, just append the full path
-            format!("{}{}", backend_url.trim_end_matches('/'), original_path)
+            Ok(format!(
+                "{}{}",
+                backend_url.trim_end_matches('/'),
+                original_path
+            ))
         }
     }
+
+    /// Fill the `{param}` placeholders of a backend URL template
+    fn fill_template(&self, template: &str) -> Result<String> {
+        let (mut path, mut query) = match template.split_once('?') {
+            Some((path, query)) => (path.to_string(), Some(query.to_string())),
+            None => (template.to_string(), None),
+        };
+        let wildcards = wildcard_params(&self.route.path);
+
+        for (name, value) in &self.params {
+            let placeholder = format!("{{{}}}", name);
+            // Decode first so pre-encoded separators (`%2F`, `%2E%2E`) are
+            // checked and re-encoded like literal ones
+            let value = percent_decode_str(value).decode_utf8_lossy();
+
+            if path.contains(&placeholder) {
+                let encoded = if wildcards.contains(&name.as_str()) {
+                    // A catch-all keeps its segments, but may not leave them
+                    let segments = value
+                        .split('/')
+                        .map(|segment| {
+                            if segment == "." || segment == ".." {
+                                return Err(GatewayError::Http(format!(
+                                    "Path parameter {} contains a dot segment",
+                                    name
+                                )));
+                            }
+                            Ok(utf8_percent_encode(segment, COMPONENT).to_string())
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    segments.join("/")
+                } else if value == "." || value == ".." {
+                    return Err(GatewayError::Http(format!(
+                        "Path parameter {} is a dot segment",
+                        name
+                    )));
+                } else {
+                    utf8_percent_encode(&value, COMPONENT).to_string()
+                };
+                path = path.replace(&placeholder, &encoded);
+            }
+
+            if let Some(query) = &mut query {
+                let encoded = utf8_percent_encode(&value, COMPONENT).to_string();
+                *query = query.replace(&placeholder, &encoded);
+            }
+        }
+
+        Ok(match query {
+            Some(query) => format!("{}?{}", path, query),
+            None => path,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -276,6 +813,18 @@ mod tests {
                 cors: None,
                 ip_filter: None,
                 cache: None,
+                honor_method_override: None,
+                max_response_bytes: None,
+                answer_options: None,
+                preserve_headers: Vec::new(),
+                circuit_breaker: None,
+                fallback: None,
+                mirror: None,
+                canary: None,
+                match_headers: Default::default(),
+                match_query: Default::default(),
+                enabled: true,
+                disabled_status: 503,
             },
             RouteConfig {
                 path: "/api/orders/:id".to_string(),
@@ -292,6 +841,18 @@ mod tests {
                 cors: None,
                 ip_filter: None,
                 cache: None,
+                honor_method_override: None,
+                max_response_bytes: None,
+                answer_options: None,
+                preserve_headers: Vec::new(),
+                circuit_breaker: None,
+                fallback: None,
+                mirror: None,
+                canary: None,
+                match_headers: Default::default(),
+                match_query: Default::default(),
+                enabled: true,
+                disabled_status: 503,
             },
             RouteConfig {
                 path: "/v1/products/*path".to_string(),
@@ -308,6 +869,18 @@ mod tests {
                 cors: None,
                 ip_filter: None,
                 cache: None,
+                honor_method_override: None,
+                max_response_bytes: None,
+                answer_options: None,
+                preserve_headers: Vec::new(),
+                circuit_breaker: None,
+                fallback: None,
+                mirror: None,
+                canary: None,
+                match_headers: Default::default(),
+                match_query: Default::default(),
+                enabled: true,
+                disabled_status: 503,
             },
         ]
     }
@@ -319,12 +892,25 @@ mod tests {
         // Router created successfully - just testing that creation doesn't panic
     }
 
+    #[test]
+    fn test_routes_lists_configured_paths() {
+        let router = Router::new(create_test_routes()).unwrap();
+
+        assert_eq!(
+            router.routes(),
+            vec!["/api/users", "/api/orders/:id", "/v1/products/*path"]
+        );
+        let (path, route) = router.entries().nth(1).unwrap();
+        assert_eq!(path, "/api/orders/:id");
+        assert_eq!(route.description, "Order service");
+    }
+
     #[test]
     fn test_exact_match() {
         let routes = create_test_routes();
         let router = Router::new(routes).unwrap();
 
-        let result = router.match_route("/api/users", &Method::GET);
+        let result = router.match_route("/api/users", &Method::GET, &HeaderMap::new(), None);
         assert!(result.is_ok());
 
         let route_match = result.unwrap();
@@ -343,7 +929,7 @@ mod tests {
         let routes = create_test_routes();
         let router = Router::new(routes).unwrap();
 
-        let result = router.match_route("/api/orders/123", &Method::GET);
+        let result = router.match_route("/api/orders/123", &Method::GET, &HeaderMap::new(), None);
         assert!(result.is_ok());
 
         let route_match = result.unwrap();
@@ -361,7 +947,12 @@ mod tests {
         let routes = create_test_routes();
         let router = Router::new(routes).unwrap();
 
-        let result = router.match_route("/v1/products/electronics/phones", &Method::GET);
+        let result = router.match_route(
+            "/v1/products/electronics/phones",
+            &Method::GET,
+            &HeaderMap::new(),
+            None,
+        );
         assert!(result.is_ok());
 
         let route_match = result.unwrap();
@@ -379,13 +970,19 @@ mod tests {
         let router = Router::new(routes).unwrap();
 
         // GET is allowed for /api/users
-        assert!(router.match_route("/api/users", &Method::GET).is_ok());
+        assert!(router
+            .match_route("/api/users", &Method::GET, &HeaderMap::new(), None)
+            .is_ok());
 
         // POST is allowed for /api/users
-        assert!(router.match_route("/api/users", &Method::POST).is_ok());
+        assert!(router
+            .match_route("/api/users", &Method::POST, &HeaderMap::new(), None)
+            .is_ok());
 
         // DELETE is not allowed for /api/users
-        assert!(router.match_route("/api/users", &Method::DELETE).is_err());
+        assert!(router
+            .match_route("/api/users", &Method::DELETE, &HeaderMap::new(), None)
+            .is_err());
     }
 
     #[test]
@@ -393,7 +990,7 @@ mod tests {
         let routes = create_test_routes();
         let router = Router::new(routes).unwrap();
 
-        let result = router.match_route("/nonexistent", &Method::GET);
+        let result = router.match_route("/nonexistent", &Method::GET, &HeaderMap::new(), None);
         assert!(result.is_err());
     }
 
@@ -410,6 +1007,7 @@ mod tests {
 
         let route_match = RouteMatch {
             route: Route {
+                path: "/api/users".to_string(),
                 load_balancer,
                 health_checker: None,
                 methods: vec![],
@@ -420,12 +1018,25 @@ mod tests {
                 cors: None,
                 ip_filter: None,
                 cache: None,
+                honor_method_override: None,
+                max_response_bytes: None,
+                answer_options: None,
+                preserve_headers: Vec::new(),
+                circuit_breaker: None,
+                fallback: None,
+                mirror: None,
+                canary: None,
+                predicates: Vec::new(),
+                enabled: true,
+                disabled_status: StatusCode::SERVICE_UNAVAILABLE,
             },
             params: HashMap::new(),
             matched_path: "/api/users".to_string(),
         };
 
-        let url = route_match.build_backend_url("http://localhost:3000", "/api/users/123");
+        let url = route_match
+            .build_backend_url("http://localhost:3000", "/api/users/123")
+            .unwrap();
         assert_eq!(url, "http://localhost:3000/api/users/123");
     }
 
@@ -442,6 +1053,7 @@ mod tests {
 
         let route_match = RouteMatch {
             route: Route {
+                path: "/api/users".to_string(),
                 load_balancer,
                 health_checker: None,
                 methods: vec![],
@@ -452,16 +1064,141 @@ mod tests {
                 cors: None,
                 ip_filter: None,
                 cache: None,
+                honor_method_override: None,
+                max_response_bytes: None,
+                answer_options: None,
+                preserve_headers: Vec::new(),
+                circuit_breaker: None,
+                fallback: None,
+                mirror: None,
+                canary: None,
+                predicates: Vec::new(),
+                enabled: true,
+                disabled_status: StatusCode::SERVICE_UNAVAILABLE,
             },
             params: HashMap::new(),
             matched_path: "/v1/products".to_string(),
         };
 
-        let url =
-            route_match.build_backend_url("http://localhost:3000", "/v1/products/electronics");
+        let url = route_match
+            .build_backend_url("http://localhost:3000", "/v1/products/electronics")
+            .unwrap();
         assert_eq!(url, "http://localhost:3000/electronics");
     }
 
+    fn template_router(path: &str, backend: &str) -> Result<Router> {
+        let config = crate::config::GatewayConfig::from_yaml(&format!(
+            "server: {{}}\nroutes:\n  - path: \"{}\"\n    backend: \"{}\"\n",
+            path, backend
+        ))
+        .unwrap();
+        Router::new(config.routes)
+    }
+
+    #[test]
+    fn test_build_backend_url_template() {
+        let router = template_router(
+            "/users/:id/files/*file",
+            "http://svc:8080/avatars/{file}?user={id}",
+        )
+        .unwrap();
+        let route_match = router
+            .match_route(
+                "/users/42/files/me.png",
+                &Method::GET,
+                &HeaderMap::new(),
+                None,
+            )
+            .unwrap();
+
+        let url = route_match
+            .build_backend_url(
+                "http://svc:8080/avatars/{file}?user={id}",
+                "/users/42/files/me.png",
+            )
+            .unwrap();
+        assert_eq!(url, "http://svc:8080/avatars/me.png?user=42");
+
+        // Backends without placeholders still get the request path appended
+        let url = route_match
+            .build_backend_url("http://localhost:3000", "/users/42/files/me.png")
+            .unwrap();
+        assert_eq!(url, "http://localhost:3000/users/42/files/me.png");
+    }
+
+    /// Fill `template` for a request to `path` on a `/users/:id/files/*file` route
+    fn fill(template: &str, path: &str) -> Result<String> {
+        let router = template_router("/users/:id/files/*file", template).unwrap();
+        router
+            .match_route(path, &Method::GET, &HeaderMap::new(), None)
+            .unwrap()
+            .build_backend_url(template, path)
+    }
+
+    #[test]
+    fn test_backend_template_query_injection() {
+        let template = "http://svc/avatars?user={id}&file={file}";
+        assert_eq!(
+            fill(template, "/users/42&role=admin/files/a.png").unwrap(),
+            "http://svc/avatars?user=42%26role%3Dadmin&file=a.png"
+        );
+        assert_eq!(
+            fill(template, "/users/42%23frag/files/a.png").unwrap(),
+            "http://svc/avatars?user=42%23frag&file=a.png"
+        );
+        assert_eq!(
+            fill(template, "/users/42/files/dir/a.png").unwrap(),
+            "http://svc/avatars?user=42&file=dir%2Fa.png"
+        );
+    }
+
+    #[test]
+    fn test_backend_template_path_injection() {
+        let template = "http://svc/users/{id}/files/{file}";
+        // `?` and `#` can't start a query or fragment
+        assert_eq!(
+            fill(template, "/users/42%3Fadmin=1/files/a%23b.png").unwrap(),
+            "http://svc/users/42%3Fadmin%3D1/files/a%23b.png"
+        );
+        // An encoded slash stays inside a plain parameter's segment
+        assert_eq!(
+            fill(template, "/users/42%2F..%2Fadmin/files/a.png").unwrap(),
+            "http://svc/users/42%2F..%2Fadmin/files/a.png"
+        );
+        // A catch-all keeps its own segments
+        assert_eq!(
+            fill(template, "/users/42/files/docs/a b.png").unwrap(),
+            "http://svc/users/42/files/docs/a%20b.png"
+        );
+    }
+
+    #[test]
+    fn test_backend_template_rejects_dot_segments() {
+        let template = "http://svc/users/{id}/files/{file}";
+        for path in [
+            "/users/42/files/../../admin",
+            "/users/42/files/docs/%2E%2E/secret",
+            "/users/42/files/..%2F..%2Fadmin",
+            "/users/../files/a.png",
+        ] {
+            let err = fill(template, path).unwrap_err();
+            assert!(matches!(err, GatewayError::Http(_)), "{path}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_backend_template_unknown_param() {
+        let err =
+            template_router("/users/:id/avatar", "http://svc/avatars?user={user_id}").unwrap_err();
+        assert!(err.to_string().contains("{user_id}"), "{err}");
+
+        assert_eq!(path_params("/users/:id/files/*rest"), vec!["id", "rest"]);
+        assert_eq!(
+            placeholders("http://svc/{a}/x?b={b_2}&c={}"),
+            vec!["a", "b_2"]
+        );
+    }
+
     #[test]
     fn test_empty_methods_allows_all() {
         let routes = vec![RouteConfig {
@@ -479,15 +1216,331 @@ mod tests {
             cors: None,
             ip_filter: None,
             cache: None,
+            honor_method_override: None,
+            max_response_bytes: None,
+            answer_options: None,
+            preserve_headers: Vec::new(),
+            circuit_breaker: None,
+            fallback: None,
+            mirror: None,
+            canary: None,
+            match_headers: Default::default(),
+            match_query: Default::default(),
+            enabled: true,
+            disabled_status: 503,
         }];
 
         let router = Router::new(routes).unwrap();
 
         // All methods should be allowed
-        assert!(router.match_route("/api/test", &Method::GET).is_ok());
-        assert!(router.match_route("/api/test", &Method::POST).is_ok());
-        assert!(router.match_route("/api/test", &Method::DELETE).is_ok());
-        assert!(router.match_route("/api/test", &Method::PUT).is_ok());
+        assert!(router
+            .match_route("/api/test", &Method::GET, &HeaderMap::new(), None)
+            .is_ok());
+        assert!(router
+            .match_route("/api/test", &Method::POST, &HeaderMap::new(), None)
+            .is_ok());
+        assert!(router
+            .match_route("/api/test", &Method::DELETE, &HeaderMap::new(), None)
+            .is_ok());
+        assert!(router
+            .match_route("/api/test", &Method::PUT, &HeaderMap::new(), None)
+            .is_ok());
+
+        // ...except TRACE and CONNECT
+        for method in [Method::TRACE, Method::CONNECT] {
+            assert!(matches!(
+                router.match_route("/api/test", &method, &HeaderMap::new(), None),
+                Err(GatewayError::InvalidMethod(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_trace_allowed_when_listed() {
+        let config = crate::config::GatewayConfig::from_yaml(
+            r#"
+server: {}
+routes:
+  - path: "/debug"
+    backend: "http://localhost:3000"
+    methods: ["GET", "TRACE"]
+"#,
+        )
+        .unwrap();
+        // The opt-in must survive startup validation, not just routing
+        config.validate().unwrap();
+        let router = Router::new(config.routes).unwrap();
+
+        assert!(router
+            .match_route("/debug", &Method::TRACE, &HeaderMap::new(), None)
+            .is_ok());
+        assert!(router
+            .match_route("/debug", &Method::CONNECT, &HeaderMap::new(), None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_canary_falls_back_to_stable_backends() {
+        let config = crate::config::GatewayConfig::from_yaml(
+            r#"
+server: {}
+routes:
+  - path: "/api/orders"
+    backend: "http://stable:3000"
+    canary:
+      canary_weight: 100
+      backends:
+        - url: "http://canary:3000"
+"#,
+        )
+        .unwrap();
+        let router = Router::new(config.routes).unwrap();
+        let route = router.find_route("/api/orders").unwrap();
+        let headers = http::HeaderMap::new();
+
+        let backend = route.select_backend(None, &headers).unwrap();
+        assert_eq!(backend.url(), "http://canary:3000");
+
+        let canary = route.canary.as_ref().unwrap();
+        canary.load_balancer().backends()[0].mark_unhealthy();
+        let backend = route.select_backend(None, &headers).unwrap();
+        assert_eq!(backend.url(), "http://stable:3000");
+    }
+
+    fn header_routes() -> Router {
+        let config = crate::config::GatewayConfig::from_yaml(
+            r#"
+server: {}
+routes:
+  - path: "/api/users"
+    backend: "http://users-v1:3000"
+  - path: "/api/users"
+    backend: "http://users-v2:3000"
+    match_headers:
+      X-API-Version: 2
+  - path: "/api/users"
+    backend: "http://users-beta:3000"
+    match_headers:
+      X-API-Version: 2
+      X-Beta:
+        regex: "yes|true"
+  - path: "/api/orders"
+    backend: "http://orders-v3:3000"
+    match_headers:
+      x-api-version:
+        regex: "3(\\.\\d+)?"
+"#,
+        )
+        .unwrap();
+        Router::new(config.routes).unwrap()
+    }
+
+    fn backend_for(router: &Router, path: &str, headers: &[(&str, &str)]) -> Result<String> {
+        let mut header_map = HeaderMap::new();
+        for (name, value) in headers {
+            header_map.append(
+                HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                value.parse().unwrap(),
+            );
+        }
+        let route_match = router.match_route(path, &Method::GET, &header_map, None)?;
+        let backend = route_match
+            .route
+            .load_balancer
+            .select_backend(None)
+            .unwrap();
+        Ok(backend.url().to_string())
+    }
+
+    #[test]
+    fn test_header_match() {
+        let router = header_routes();
+        assert_eq!(
+            backend_for(&router, "/api/users", &[("X-API-Version", "2")]).unwrap(),
+            "http://users-v2:3000"
+        );
+        assert_eq!(
+            backend_for(&router, "/api/orders", &[("X-API-Version", "3.1")]).unwrap(),
+            "http://orders-v3:3000"
+        );
+    }
+
+    #[test]
+    fn test_header_mismatch_falls_back() {
+        let router = header_routes();
+        assert_eq!(
+            backend_for(&router, "/api/users", &[]).unwrap(),
+            "http://users-v1:3000"
+        );
+        assert_eq!(
+            backend_for(&router, "/api/users", &[("X-API-Version", "3")]).unwrap(),
+            "http://users-v1:3000"
+        );
+
+        // Without a header-less route there is nothing to fall back to
+        let err = backend_for(&router, "/api/orders", &[("X-API-Version", "31")]).unwrap_err();
+        assert!(matches!(err, GatewayError::RouteNotFound(_)));
+    }
+
+    #[test]
+    fn test_most_specific_header_match_wins() {
+        let router = header_routes();
+        assert_eq!(
+            backend_for(
+                &router,
+                "/api/users",
+                &[("X-API-Version", "2"), ("X-Beta", "yes")]
+            )
+            .unwrap(),
+            "http://users-beta:3000"
+        );
+        // The regex must match the whole value
+        assert_eq!(
+            backend_for(
+                &router,
+                "/api/users",
+                &[("X-API-Version", "2"), ("X-Beta", "yesno")]
+            )
+            .unwrap(),
+            "http://users-v2:3000"
+        );
+        // The least specific route serves path-only lookups
+        assert_eq!(router.find_route("/api/users").unwrap().predicates.len(), 0);
+    }
+
+    #[test]
+    fn test_duplicate_route_predicates_rejected() {
+        let config = crate::config::GatewayConfig::from_yaml(
+            r#"
+server: {}
+routes:
+  - path: "/api/users"
+    backend: "http://users-v2:3000"
+    match_headers:
+      X-API-Version: "2"
+  - path: "/api/users"
+    backend: "http://users-v2b:3000"
+    match_headers:
+      x-api-version: "2"
+"#,
+        )
+        .unwrap();
+        let err = Router::new(config.routes).unwrap_err();
+        assert!(err.to_string().contains("same path and predicates"));
+    }
+
+    #[test]
+    fn test_query_param_match() {
+        let config = crate::config::GatewayConfig::from_yaml(
+            r#"
+server: {}
+routes:
+  - path: "/api/search"
+    backend: "http://search:3000"
+  - path: "/api/search"
+    backend: "http://search-beta:3000"
+    match_query:
+      beta: true
+"#,
+        )
+        .unwrap();
+        let router = Router::new(config.routes).unwrap();
+        let backend_for = |query: Option<&str>| {
+            let route_match = router
+                .match_route("/api/search", &Method::GET, &HeaderMap::new(), query)
+                .unwrap();
+            let backend = route_match
+                .route
+                .load_balancer
+                .select_backend(None)
+                .unwrap();
+            backend.url().to_string()
+        };
+
+        assert_eq!(
+            backend_for(Some("q=rust&beta=true")),
+            "http://search-beta:3000"
+        );
+        assert_eq!(backend_for(Some("q=rust&beta=false")), "http://search:3000");
+        assert_eq!(backend_for(Some("q=rust")), "http://search:3000");
+        assert_eq!(backend_for(None), "http://search:3000");
+    }
+
+    fn slash_routes(trailing_slash: TrailingSlash) -> Router {
+        let config = crate::config::GatewayConfig::from_yaml(
+            r#"
+server: {}
+routes:
+  - path: "/api/users"
+    backend: "http://users:3000"
+  - path: "/api/orders/"
+    backend: "http://orders:3000"
+  - path: "/api/users/:id"
+    backend: "http://users:3000"
+"#,
+        )
+        .unwrap();
+        Router::new(config.routes)
+            .unwrap()
+            .with_trailing_slash(trailing_slash)
+    }
+
+    fn match_path(router: &Router, path: &str, query: Option<&str>) -> Result<RouteMatch> {
+        router.match_route(path, &Method::GET, &HeaderMap::new(), query)
+    }
+
+    #[test]
+    fn test_trailing_slash_strict() {
+        let router = slash_routes(TrailingSlash::Strict);
+        assert!(match_path(&router, "/api/users", None).is_ok());
+        assert!(matches!(
+            match_path(&router, "/api/users/", None),
+            Err(GatewayError::RouteNotFound(_))
+        ));
+        assert!(matches!(
+            match_path(&router, "/api/orders", None),
+            Err(GatewayError::RouteNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_trailing_slash_ignore() {
+        let router = slash_routes(TrailingSlash::Ignore);
+        for path in ["/api/users", "/api/users/"] {
+            let route_match = match_path(&router, path, None).unwrap();
+            assert_eq!(route_match.route.path, "/api/users");
+            assert_eq!(route_match.matched_path, path);
+        }
+        assert_eq!(
+            match_path(&router, "/api/orders", None).unwrap().route.path,
+            "/api/orders/"
+        );
+        let route_match = match_path(&router, "/api/users/42/", None).unwrap();
+        assert_eq!(route_match.params["id"], "42");
+        assert_eq!(router.find_route("/api/users/").unwrap().path, "/api/users");
+
+        // The root path has no trailing-slash variant
+        assert!(match_path(&router, "/", None).is_err());
+        assert!(match_path(&router, "/api/unknown/", None).is_err());
+    }
+
+    #[test]
+    fn test_trailing_slash_redirect() {
+        let router = slash_routes(TrailingSlash::Redirect);
+        assert!(match_path(&router, "/api/users", None).is_ok());
+
+        assert!(router.redirect_location("/api/users", None).is_none());
+
+        let redirect = |path: &str, query: Option<&str>| {
+            assert!(match_path(&router, path, query).is_err());
+            router.redirect_location(path, query).unwrap()
+        };
+        assert_eq!(redirect("/api/users/", None), "/api/users");
+        assert_eq!(redirect("/api/orders", None), "/api/orders/");
+        assert_eq!(
+            redirect("/api/users/42/", Some("fields=name&x=1")),
+            "/api/users/42?fields=name&x=1"
+        );
     }
 
     #[test]
@@ -1,3 +1,5 @@
+pub mod vault;
+
 use crate::error::{GatewayError, Result};
 use secrecy::{ExposeSecret, Secret};
 use serde::{Deserialize, Deserializer};
@@ -5,6 +7,8 @@ use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tracing::{debug, info};
 
 /// Secret string wrapper that prevents accidental exposure
@@ -31,8 +35,10 @@ impl EnvSecretsProvider {
             prefix: prefix.to_string(),
         }
     }
+}
 
-    pub fn default() -> Self {
+impl Default for EnvSecretsProvider {
+    fn default() -> Self {
         Self::new("GATEWAY_SECRET_")
     }
 }
@@ -126,6 +132,58 @@ impl SecretsProvider for InMemorySecretsProvider {
     }
 }
 
+/// Caching wrapper that memoizes another provider's secrets for a TTL
+///
+/// Values are re-read from the wrapped provider once they expire, so rotated
+/// secrets are picked up within `ttl`.
+pub struct CachingSecretsProvider<P: SecretsProvider> {
+    inner: P,
+    ttl: Duration,
+    cache: Mutex<HashMap<String, (Instant, SecretString)>>,
+}
+
+impl<P: SecretsProvider> CachingSecretsProvider<P> {
+    pub fn new(inner: P, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Unexpired cached value for `key`
+    fn cached(&self, key: &str) -> Option<SecretString> {
+        let mut cache = self.cache.lock().unwrap();
+        match cache.get(key) {
+            Some((fetched_at, value)) if fetched_at.elapsed() < self.ttl => Some(value.clone()),
+            Some(_) => {
+                cache.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+}
+
+impl<P: SecretsProvider> SecretsProvider for CachingSecretsProvider<P> {
+    fn get_secret(&self, key: &str) -> Result<SecretString> {
+        if let Some(value) = self.cached(key) {
+            return Ok(value);
+        }
+
+        let value = self.inner.get_secret(key)?;
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), (Instant::now(), value.clone()));
+        Ok(value)
+    }
+
+    fn has_secret(&self, key: &str) -> bool {
+        self.cached(key).is_some() || self.inner.has_secret(key)
+    }
+}
+
 /// Multi-provider secrets manager with fallback chain
 pub struct SecretsManager {
     providers: Vec<Box<dyn SecretsProvider + Send + Sync>>,
@@ -161,9 +219,11 @@ impl SecretsManager {
     pub fn has_secret(&self, key: &str) -> bool {
         self.providers.iter().any(|p| p.has_secret(key))
     }
+}
 
+impl Default for SecretsManager {
     /// Build a default secrets manager with common providers
-    pub fn default() -> Self {
+    fn default() -> Self {
         info!("Initializing default secrets manager");
 
         let mut manager = Self::new();
@@ -172,23 +232,26 @@ impl SecretsManager {
         debug!("Adding environment secrets provider");
         manager = manager.add_provider(EnvSecretsProvider::default());
 
-        // 2. Try Kubernetes secrets if the directory exists
+        // 2. Try Vault if VAULT_ADDR and VAULT_TOKEN are set
+        if let Some(vault) = vault::VaultSecretsProvider::from_env() {
+            info!("Vault configuration found, adding Vault secrets provider");
+            manager = manager.add_provider(vault);
+        }
+
+        // 3. Try Kubernetes secrets if the directory exists
         let k8s_path = "/var/run/secrets/gateway";
         if Path::new(k8s_path).exists() {
             info!("Kubernetes secrets directory found, adding file provider");
-            manager = manager.add_provider(FileSecretsProvider::kubernetes_default());
+            manager = manager.add_provider(CachingSecretsProvider::new(
+                FileSecretsProvider::kubernetes_default(),
+                Duration::from_secs(30),
+            ));
         }
 
         manager
     }
 }
 
-impl Default for SecretsManager {
-    fn default() -> Self {
-        Self::default()
-    }
-}
-
 /// Helper function to resolve a secret reference
 /// Supports formats:
 /// - "secret://key" - Load from secrets manager
@@ -325,4 +388,82 @@ mod tests {
         let secret = provider.get_secret(file_name).unwrap();
         assert_eq!(secret.expose_secret(), "file_secret_value");
     }
+
+    #[test]
+    fn test_caching_provider_refreshes_after_ttl() {
+        let dir = tempfile::tempdir().unwrap();
+        let secret_path = dir.path().join("db_password");
+        fs::write(&secret_path, "first").unwrap();
+
+        let provider = CachingSecretsProvider::new(
+            FileSecretsProvider::new(dir.path().to_str().unwrap()),
+            Duration::from_millis(200),
+        );
+        assert_eq!(
+            provider.get_secret("db_password").unwrap().expose_secret(),
+            "first"
+        );
+
+        // Rotated on disk, but still served from the cache within the TTL
+        fs::write(&secret_path, "second").unwrap();
+        assert_eq!(
+            provider.get_secret("db_password").unwrap().expose_secret(),
+            "first"
+        );
+
+        std::thread::sleep(Duration::from_millis(250));
+        assert_eq!(
+            provider.get_secret("db_password").unwrap().expose_secret(),
+            "second"
+        );
+    }
+
+    #[test]
+    fn test_caching_provider_drops_expired_secrets() {
+        let dir = tempfile::tempdir().unwrap();
+        let secret_path = dir.path().join("api_token");
+        fs::write(&secret_path, "token").unwrap();
+
+        let provider = CachingSecretsProvider::new(
+            FileSecretsProvider::new(dir.path().to_str().unwrap()),
+            Duration::from_millis(100),
+        );
+        assert!(provider.get_secret("api_token").is_ok());
+
+        // Cached values keep answering until they expire
+        fs::remove_file(&secret_path).unwrap();
+        assert!(provider.has_secret("api_token"));
+
+        std::thread::sleep(Duration::from_millis(150));
+        assert!(!provider.has_secret("api_token"));
+        assert!(provider.get_secret("api_token").is_err());
+    }
+
+    #[test]
+    fn test_caching_provider_is_shared_across_threads() {
+        let mut secrets = HashMap::new();
+        secrets.insert("shared".to_string(), "value".to_string());
+        let manager = std::sync::Arc::new(SecretsManager::new().add_provider(
+            CachingSecretsProvider::new(
+                InMemorySecretsProvider::new(secrets),
+                Duration::from_secs(60),
+            ),
+        ));
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let manager = manager.clone();
+                std::thread::spawn(move || {
+                    manager
+                        .get_secret("shared")
+                        .unwrap()
+                        .expose_secret()
+                        .clone()
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), "value");
+        }
+    }
 }
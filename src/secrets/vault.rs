@@ -0,0 +1,384 @@
+//! HashiCorp Vault KV v2 secrets provider
+//!
+//! `SecretsProvider` is synchronous while HTTP calls are async, so each fetch
+//! runs on a short-lived thread with its own single-threaded runtime. This
+//! works both inside the gateway's runtime (e.g. while building routes) and
+//! outside of one; fetched secrets are cached for `cache_ttl` to keep such
+//! round trips rare.
+
+use super::{SecretString, SecretsProvider};
+use crate::error::{GatewayError, Result};
+use reqwest::StatusCode;
+use secrecy::{ExposeSecret, Secret};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// Field read when a key doesn't name one
+const DEFAULT_FIELD: &str = "value";
+
+/// Vault KV v2 secrets provider
+///
+/// Keys take the form `path#field`, e.g. `gateway/db#password` reads the
+/// `password` field of the secret at `<mount>/data/gateway/db`. Without a
+/// `#field` suffix the `value` field is read.
+pub struct VaultSecretsProvider {
+    address: String,
+    token: SecretString,
+    mount: String,
+    namespace: Option<String>,
+    timeout: Duration,
+    cache_ttl: Duration,
+    cache: Mutex<HashMap<String, CachedSecret>>,
+}
+
+/// Fields of a fetched secret and when they were fetched
+struct CachedSecret {
+    fetched_at: Instant,
+    fields: Arc<HashMap<String, SecretString>>,
+}
+
+/// Response of a KV v2 read
+#[derive(Deserialize)]
+struct KvResponse {
+    data: KvData,
+}
+
+#[derive(Deserialize)]
+struct KvData {
+    data: HashMap<String, serde_json::Value>,
+}
+
+impl VaultSecretsProvider {
+    /// Create a provider for the Vault server at `address` (e.g. `https://vault:8200`)
+    pub fn new(address: &str, token: &str) -> Self {
+        Self {
+            address: address.trim_end_matches('/').to_string(),
+            token: Secret::new(token.to_string()),
+            mount: "secret".to_string(),
+            namespace: None,
+            timeout: Duration::from_secs(5),
+            cache_ttl: Duration::from_secs(60),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Create a provider from `VAULT_ADDR` and `VAULT_TOKEN`, honoring
+    /// `VAULT_NAMESPACE` and `VAULT_KV_MOUNT` if set
+    pub fn from_env() -> Option<Self> {
+        let address = env::var("VAULT_ADDR").ok()?;
+        let token = env::var("VAULT_TOKEN").ok()?;
+
+        let mut provider = Self::new(&address, &token);
+        if let Ok(namespace) = env::var("VAULT_NAMESPACE") {
+            provider = provider.with_namespace(&namespace);
+        }
+        if let Ok(mount) = env::var("VAULT_KV_MOUNT") {
+            provider = provider.with_mount(&mount);
+        }
+        Some(provider)
+    }
+
+    /// Set the KV v2 mount path (default `secret`)
+    pub fn with_mount(mut self, mount: &str) -> Self {
+        self.mount = mount.trim_matches('/').to_string();
+        self
+    }
+
+    /// Set the Vault Enterprise namespace
+    pub fn with_namespace(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.to_string());
+        self
+    }
+
+    /// Set the request timeout (default 5 seconds)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set how long fetched secrets are reused (default 60 seconds)
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    /// Fields of the secret at `path`, from the cache or Vault
+    fn secret_fields(&self, path: &str) -> Result<Arc<HashMap<String, SecretString>>> {
+        if let Some(cached) = self.cache.lock().unwrap().get(path) {
+            if cached.fetched_at.elapsed() < self.cache_ttl {
+                return Ok(cached.fields.clone());
+            }
+        }
+
+        debug!(path = %path, "Fetching secret from Vault");
+        let fields = Arc::new(self.fetch(path)?);
+        self.cache.lock().unwrap().insert(
+            path.to_string(),
+            CachedSecret {
+                fetched_at: Instant::now(),
+                fields: fields.clone(),
+            },
+        );
+        Ok(fields)
+    }
+
+    /// Read the secret at `path` on a dedicated thread
+    fn fetch(&self, path: &str) -> Result<HashMap<String, SecretString>> {
+        std::thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    let runtime = tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                        .map_err(GatewayError::Io)?;
+                    runtime.block_on(self.fetch_async(path))
+                })
+                .join()
+                .unwrap_or_else(|_| {
+                    Err(GatewayError::Internal(
+                        "Vault fetch thread panicked".to_string(),
+                    ))
+                })
+        })
+    }
+
+    async fn fetch_async(&self, path: &str) -> Result<HashMap<String, SecretString>> {
+        let url = format!("{}/v1/{}/data/{}", self.address, self.mount, path);
+        let client = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .build()
+            .map_err(|e| GatewayError::Internal(format!("Failed to create Vault client: {}", e)))?;
+
+        let mut request = client
+            .get(&url)
+            .header("X-Vault-Token", self.token.expose_secret());
+        if let Some(namespace) = &self.namespace {
+            request = request.header("X-Vault-Namespace", namespace);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            GatewayError::Config(format!("Failed to reach Vault for '{}': {}", path, e))
+        })?;
+        match response.status() {
+            status if status.is_success() => {}
+            StatusCode::NOT_FOUND => {
+                return Err(GatewayError::Config(format!(
+                    "Secret '{}' not found in Vault",
+                    path
+                )));
+            }
+            status => {
+                warn!(path = %path, status = %status, "Vault rejected secret read");
+                return Err(GatewayError::Config(format!(
+                    "Vault returned {} for secret '{}'",
+                    status, path
+                )));
+            }
+        }
+
+        let body: KvResponse = response.json().await.map_err(|e| {
+            GatewayError::Config(format!("Invalid Vault response for '{}': {}", path, e))
+        })?;
+        Ok(body
+            .data
+            .data
+            .into_iter()
+            .map(|(field, value)| {
+                let value = match value {
+                    serde_json::Value::String(value) => value,
+                    other => other.to_string(),
+                };
+                (field, Secret::new(value))
+            })
+            .collect())
+    }
+}
+
+impl std::fmt::Debug for VaultSecretsProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VaultSecretsProvider")
+            .field("address", &self.address)
+            .field("mount", &self.mount)
+            .field("namespace", &self.namespace)
+            .field("cache_ttl", &self.cache_ttl)
+            .finish()
+    }
+}
+
+impl SecretsProvider for VaultSecretsProvider {
+    fn get_secret(&self, key: &str) -> Result<SecretString> {
+        let (path, field) = key.split_once('#').unwrap_or((key, DEFAULT_FIELD));
+        self.secret_fields(path)?
+            .get(field)
+            .cloned()
+            .ok_or_else(|| {
+                GatewayError::Config(format!(
+                    "Secret '{}' has no field '{}' in Vault",
+                    path, field
+                ))
+            })
+    }
+
+    fn has_secret(&self, key: &str) -> bool {
+        self.get_secret(key).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secrets::{resolve_secret_ref, SecretsManager};
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const TOKEN: &str = "s.test-token";
+
+    async fn vault_with_secret(secret_path: &str, data: serde_json::Value) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("/v1/secret/data/{}", secret_path)))
+            .and(header("X-Vault-Token", TOKEN))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "data": data,
+                    "metadata": { "version": 3 }
+                }
+            })))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn test_reads_kv_v2_fields() {
+        let server = vault_with_secret(
+            "gateway/db",
+            serde_json::json!({ "password": "hunter2", "port": 5432 }),
+        )
+        .await;
+        let provider = VaultSecretsProvider::new(&server.uri(), TOKEN);
+
+        let password = provider.get_secret("gateway/db#password").unwrap();
+        assert_eq!(password.expose_secret(), "hunter2");
+        let port = provider.get_secret("gateway/db#port").unwrap();
+        assert_eq!(port.expose_secret(), "5432");
+        assert!(provider.get_secret("gateway/db#user").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_defaults_to_value_field() {
+        let server = vault_with_secret("jwt", serde_json::json!({ "value": "signing-key" })).await;
+        let provider = VaultSecretsProvider::new(&server.uri(), TOKEN);
+
+        assert!(provider.has_secret("jwt"));
+        assert_eq!(
+            provider.get_secret("jwt").unwrap().expose_secret(),
+            "signing-key"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_caches_fetched_secrets() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/kv/data/api"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "data": { "key": "abc", "secret": "xyz" } }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let provider = VaultSecretsProvider::new(&server.uri(), TOKEN).with_mount("kv");
+
+        assert!(provider.has_secret("api#key"));
+        assert_eq!(
+            provider.get_secret("api#key").unwrap().expose_secret(),
+            "abc"
+        );
+        assert_eq!(
+            provider.get_secret("api#secret").unwrap().expose_secret(),
+            "xyz"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refetches_after_cache_ttl() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/secret/data/rotating"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "data": { "value": "v1" } }
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let provider =
+            VaultSecretsProvider::new(&server.uri(), TOKEN).with_cache_ttl(Duration::ZERO);
+
+        provider.get_secret("rotating").unwrap();
+        provider.get_secret("rotating").unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_missing_secret_and_bad_token() {
+        let server = vault_with_secret("present", serde_json::json!({ "value": "x" })).await;
+        Mock::given(method("GET"))
+            .and(path("/v1/secret/data/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let provider = VaultSecretsProvider::new(&server.uri(), TOKEN);
+        assert!(!provider.has_secret("missing"));
+        let err = provider.get_secret("missing").unwrap_err().to_string();
+        assert!(err.contains("not found"), "{}", err);
+
+        // Requests without the right token don't match and get wiremock's 404
+        let provider = VaultSecretsProvider::new(&server.uri(), "wrong-token");
+        assert!(!provider.has_secret("present"));
+    }
+
+    #[tokio::test]
+    async fn test_sends_namespace_header() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/secret/data/ns-secret"))
+            .and(header("X-Vault-Namespace", "team-a"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "data": { "value": "scoped" } }
+            })))
+            .mount(&server)
+            .await;
+
+        let provider = VaultSecretsProvider::new(&server.uri(), TOKEN).with_namespace("team-a");
+        assert_eq!(
+            provider.get_secret("ns-secret").unwrap().expose_secret(),
+            "scoped"
+        );
+    }
+
+    #[test]
+    fn test_unreachable_vault() {
+        let provider = VaultSecretsProvider::new("http://127.0.0.1:1", TOKEN)
+            .with_timeout(Duration::from_millis(500));
+        assert!(!provider.has_secret("anything"));
+    }
+
+    #[tokio::test]
+    async fn test_resolves_through_secrets_manager() {
+        let server = vault_with_secret(
+            "gateway/redis",
+            serde_json::json!({ "url": "redis://r:6379" }),
+        )
+        .await;
+        let manager =
+            SecretsManager::new().add_provider(VaultSecretsProvider::new(&server.uri(), TOKEN));
+
+        let url = resolve_secret_ref("secret://gateway/redis#url", &manager).unwrap();
+        assert_eq!(url, "redis://r:6379");
+    }
+}
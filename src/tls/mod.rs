@@ -1,13 +1,21 @@
 use crate::error::{GatewayError, Result};
-use rustls::pki_types::{CertificateDer, PrivateKeyDer};
-use rustls::server::WebPkiClientVerifier;
-use rustls::{RootCertStore, ServerConfig};
+use crate::hotreload::watch_paths;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::server::{ClientHello, ResolvesServerCert, WebPkiClientVerifier};
+use rustls::sign::CertifiedKey;
+use rustls::{
+    ClientConfig, DigitallySignedStruct, RootCertStore, ServerConfig, SignatureScheme,
+    SupportedProtocolVersion,
+};
 use rustls_pemfile::{certs, pkcs8_private_keys, rsa_private_keys};
 use std::fs::File;
 use std::io::BufReader;
-use std::path::Path;
-use std::sync::Arc;
-use tracing::{info, warn};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{error, info, warn};
 
 /// TLS configuration for the gateway
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
@@ -28,12 +36,211 @@ pub struct TlsConfig {
     /// Require client certificate (if false, client cert is optional)
     #[serde(default = "default_require_client_cert")]
     pub require_client_cert: bool,
+
+    /// Watch the certificate and key files and serve new ones without a restart
+    #[serde(default = "default_watch_certificates")]
+    pub watch_certificates: bool,
+
+    /// Quiet period after a file change before the certificate is reloaded
+    #[serde(default = "default_watch_debounce_ms")]
+    pub watch_debounce_ms: u64,
+
+    /// Lowest protocol version accepted from clients ("1.2" or "1.3")
+    #[serde(default = "default_min_version")]
+    pub min_version: String,
+
+    /// Cipher suites to offer, by IANA name (e.g. "TLS13_AES_256_GCM_SHA384").
+    /// All suites supported by the crypto provider are offered when unset.
+    pub cipher_suites: Option<Vec<String>>,
+}
+
+/// Protocol versions enabled when `min_version` is "1.3"
+static TLS13_ONLY: &[&SupportedProtocolVersion] = &[&rustls::version::TLS13];
+
+impl TlsConfig {
+    /// Check that the protocol version and cipher suite settings are supported
+    pub fn validate(&self) -> Result<()> {
+        self.protocol_versions()?;
+        self.crypto_provider()?;
+        Ok(())
+    }
+
+    /// Protocol versions enabled by `min_version`
+    fn protocol_versions(&self) -> Result<&'static [&'static SupportedProtocolVersion]> {
+        match self.min_version.as_str() {
+            "1.2" => Ok(rustls::ALL_VERSIONS),
+            "1.3" => Ok(TLS13_ONLY),
+            other => Err(GatewayError::Config(format!(
+                "tls.min_version: Unsupported TLS version '{}' (expected \"1.2\" or \"1.3\")",
+                other
+            ))),
+        }
+    }
+
+    /// Default crypto provider restricted to the configured cipher suites
+    fn crypto_provider(&self) -> Result<CryptoProvider> {
+        let mut provider = CryptoProvider::clone(ServerConfig::builder().crypto_provider());
+
+        if let Some(allowed) = &self.cipher_suites {
+            let mut suites = Vec::with_capacity(allowed.len());
+            for (i, name) in allowed.iter().enumerate() {
+                let suite = provider
+                    .cipher_suites
+                    .iter()
+                    .find(|suite| suite.suite().as_str() == Some(name.as_str()))
+                    .ok_or_else(|| {
+                        GatewayError::Config(format!(
+                            "tls.cipher_suites[{}]: Unknown or unsupported cipher suite '{}'",
+                            i, name
+                        ))
+                    })?;
+                suites.push(*suite);
+            }
+            provider.cipher_suites = suites;
+        }
+
+        Ok(provider)
+    }
 }
 
 fn default_require_client_cert() -> bool {
     true
 }
 
+fn default_watch_certificates() -> bool {
+    true
+}
+
+fn default_watch_debounce_ms() -> u64 {
+    500
+}
+
+fn default_min_version() -> String {
+    "1.2".to_string()
+}
+
+/// TLS settings for connections from the gateway to backends
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct BackendTlsConfig {
+    /// CA bundle used to verify backend certificates (PEM format).
+    /// The bundled Mozilla roots are used when unset.
+    pub ca_cert_path: Option<String>,
+
+    /// Client certificate presented to backends that require mTLS (PEM format)
+    pub client_cert_path: Option<String>,
+
+    /// Private key for the client certificate (PEM format)
+    pub client_key_path: Option<String>,
+
+    /// Accept any backend certificate without verifying it. For testing only.
+    #[serde(default)]
+    pub insecure_skip_verify: bool,
+}
+
+/// Certificate resolver whose key pair can be swapped while the server is running.
+///
+/// New handshakes see the replacement immediately; established connections keep
+/// the certificate they negotiated with.
+#[derive(Debug)]
+pub struct ReloadableCertResolver {
+    cert_path: PathBuf,
+    key_path: PathBuf,
+    provider: Arc<CryptoProvider>,
+    current: RwLock<Arc<CertifiedKey>>,
+}
+
+impl ReloadableCertResolver {
+    /// Load the initial certificate and key
+    pub fn new(tls_config: &TlsConfig) -> Result<Self> {
+        let cert_path = PathBuf::from(&tls_config.cert_path);
+        let key_path = PathBuf::from(&tls_config.key_path);
+        let provider = ServerConfig::builder().crypto_provider().clone();
+        let current = load_certified_key(&cert_path, &key_path, &provider)?;
+
+        Ok(Self {
+            cert_path,
+            key_path,
+            provider,
+            current: RwLock::new(current),
+        })
+    }
+
+    /// Re-read the certificate and key from disk.
+    ///
+    /// On error the previously loaded certificate stays in use.
+    pub fn reload(&self) -> Result<()> {
+        let certified_key = load_certified_key(&self.cert_path, &self.key_path, &self.provider)?;
+        *self.current.write().unwrap() = certified_key;
+
+        info!(
+            cert_path = %self.cert_path.display(),
+            "Reloaded TLS certificate"
+        );
+        Ok(())
+    }
+
+    /// Watch the certificate and key files, reloading whenever they change.
+    ///
+    /// The parent directories are watched rather than the files themselves so that
+    /// atomic replacements (such as Kubernetes secret symlink swaps) are noticed.
+    pub fn watch(self: &Arc<Self>, debounce: Duration) -> Result<()> {
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        for path in [&self.cert_path, &self.key_path] {
+            let dir = match path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+                _ => PathBuf::from("."),
+            };
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+
+        let resolver = self.clone();
+        watch_paths(&dirs, debounce, move || {
+            let resolver = resolver.clone();
+            async move {
+                if let Err(e) = resolver.reload() {
+                    error!("Failed to reload TLS certificate: {}", e);
+                }
+            }
+        })?;
+
+        info!(
+            cert_path = %self.cert_path.display(),
+            key_path = %self.key_path.display(),
+            "Watching TLS certificate for changes"
+        );
+        Ok(())
+    }
+}
+
+impl ResolvesServerCert for ReloadableCertResolver {
+    fn resolve(&self, _client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        Some(self.current.read().unwrap().clone())
+    }
+}
+
+/// Load a certificate chain and its private key, checking that they belong together
+fn load_certified_key(
+    cert_path: &Path,
+    key_path: &Path,
+    provider: &CryptoProvider,
+) -> Result<Arc<CertifiedKey>> {
+    let certs = load_certs(cert_path)?;
+    let key = load_private_key(key_path)?;
+
+    let certified_key = CertifiedKey::from_der(certs, key, provider).map_err(|e| {
+        GatewayError::Config(format!(
+            "Certificate {} does not match key {}: {}",
+            cert_path.display(),
+            key_path.display(),
+            e
+        ))
+    })?;
+
+    Ok(Arc::new(certified_key))
+}
+
 /// Load certificates from a PEM file
 fn load_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
     let file = File::open(path).map_err(|e| {
@@ -159,20 +366,26 @@ fn load_ca_certs(path: &Path) -> Result<RootCertStore> {
 
 /// Build TLS server configuration
 pub fn build_tls_config(tls_config: &TlsConfig) -> Result<ServerConfig> {
-    info!("Building TLS configuration");
+    let resolver = Arc::new(ReloadableCertResolver::new(tls_config)?);
+    build_tls_config_with_resolver(tls_config, resolver)
+}
 
-    // Load server certificate and key
-    let certs = load_certs(Path::new(&tls_config.cert_path))?;
-    let key = load_private_key(Path::new(&tls_config.key_path))?;
+/// Build TLS server configuration that serves certificates from `resolver`
+pub fn build_tls_config_with_resolver(
+    tls_config: &TlsConfig,
+    resolver: Arc<ReloadableCertResolver>,
+) -> Result<ServerConfig> {
+    info!(
+        min_version = %tls_config.min_version,
+        "Building TLS configuration"
+    );
 
-    // Build server config
-    let mut config = ServerConfig::builder()
-        .with_no_client_auth()
-        .with_single_cert(certs, key)
-        .map_err(|e| GatewayError::Config(format!("Failed to build TLS config: {}", e)))?;
+    let builder = ServerConfig::builder_with_provider(Arc::new(tls_config.crypto_provider()?))
+        .with_protocol_versions(tls_config.protocol_versions()?)
+        .map_err(|e| GatewayError::Config(format!("Invalid TLS protocol settings: {}", e)))?;
 
     // Configure mTLS if enabled
-    if tls_config.enable_mtls {
+    let mut config = if tls_config.enable_mtls {
         info!("Configuring mutual TLS (client certificate verification)");
 
         let ca_cert_path = tls_config.ca_cert_path.as_ref().ok_or_else(|| {
@@ -200,17 +413,12 @@ pub fn build_tls_config(tls_config: &TlsConfig) -> Result<ServerConfig> {
                 })?
         };
 
-        // Rebuild config with client auth
-        let certs = load_certs(Path::new(&tls_config.cert_path))?;
-        let key = load_private_key(Path::new(&tls_config.key_path))?;
-
-        config = ServerConfig::builder()
+        builder
             .with_client_cert_verifier(client_verifier)
-            .with_single_cert(certs, key)
-            .map_err(|e| {
-                GatewayError::Config(format!("Failed to build TLS config with mTLS: {}", e))
-            })?;
-    }
+            .with_cert_resolver(resolver)
+    } else {
+        builder.with_no_client_auth().with_cert_resolver(resolver)
+    };
 
     // Enable ALPN for HTTP/2 and HTTP/1.1
     config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
@@ -219,11 +427,93 @@ pub fn build_tls_config(tls_config: &TlsConfig) -> Result<ServerConfig> {
     Ok(config)
 }
 
+/// Build the TLS client configuration used for backend connections
+pub fn build_backend_tls_config(backend_tls: &BackendTlsConfig) -> Result<ClientConfig> {
+    info!("Building backend TLS configuration");
+
+    let builder = ClientConfig::builder();
+
+    let builder = if backend_tls.insecure_skip_verify {
+        warn!("Backend certificate verification is disabled");
+        let verifier = Arc::new(NoServerVerification(builder.crypto_provider().clone()));
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(verifier)
+    } else if let Some(ca_cert_path) = &backend_tls.ca_cert_path {
+        builder.with_root_certificates(load_ca_certs(Path::new(ca_cert_path))?)
+    } else {
+        let root_store = RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        };
+        builder.with_root_certificates(root_store)
+    };
+
+    let config = match (&backend_tls.client_cert_path, &backend_tls.client_key_path) {
+        (Some(cert_path), Some(key_path)) => {
+            info!("Presenting client certificate to backends");
+            let certs = load_certs(Path::new(cert_path))?;
+            let key = load_private_key(Path::new(key_path))?;
+            builder.with_client_auth_cert(certs, key).map_err(|e| {
+                GatewayError::Config(format!("Invalid backend client certificate: {}", e))
+            })?
+        }
+        (None, None) => builder.with_no_client_auth(),
+        _ => {
+            return Err(GatewayError::Config(
+                "backend_tls: client_cert_path and client_key_path must be set together"
+                    .to_string(),
+            ))
+        }
+    };
+
+    Ok(config)
+}
+
+/// Certificate verifier that trusts any backend certificate.
+///
+/// Handshake signatures are still checked so the connection is at least
+/// bound to the presented key.
+#[derive(Debug)]
+struct NoServerVerification(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for NoServerVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Write;
-    use tempfile::NamedTempFile;
 
     #[test]
     fn test_tls_config_deserialization() {
@@ -253,5 +543,101 @@ key_path: "/path/to/key.pem"
         let config: TlsConfig = serde_yaml::from_str(yaml).unwrap();
         assert!(config.require_client_cert); // Default is true
         assert!(!config.enable_mtls); // Default is false
+        assert!(config.watch_certificates); // Default is true
+        assert_eq!(config.watch_debounce_ms, 500);
+    }
+
+    fn write_self_signed(dir: &Path) -> rcgen::CertifiedKey {
+        let certified = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        std::fs::write(dir.join("cert.pem"), certified.cert.pem()).unwrap();
+        std::fs::write(dir.join("key.pem"), certified.key_pair.serialize_pem()).unwrap();
+        certified
+    }
+
+    fn file_config(dir: &Path) -> TlsConfig {
+        serde_yaml::from_str(&format!(
+            "cert_path: \"{}\"\nkey_path: \"{}\"\n",
+            dir.join("cert.pem").display(),
+            dir.join("key.pem").display()
+        ))
+        .unwrap()
+    }
+
+    fn served_cert(resolver: &ReloadableCertResolver) -> CertificateDer<'static> {
+        resolver.current.read().unwrap().cert[0].clone()
+    }
+
+    #[test]
+    fn test_resolver_reload_swaps_certificate() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let first = write_self_signed(dir.path());
+        let resolver = ReloadableCertResolver::new(&file_config(dir.path())).unwrap();
+        assert_eq!(served_cert(&resolver), *first.cert.der());
+
+        let second = write_self_signed(dir.path());
+        resolver.reload().unwrap();
+        assert_eq!(served_cert(&resolver), *second.cert.der());
+    }
+
+    #[test]
+    fn test_resolver_keeps_certificate_when_key_mismatches() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let first = write_self_signed(dir.path());
+        let resolver = ReloadableCertResolver::new(&file_config(dir.path())).unwrap();
+
+        // Only the certificate has been replaced so far
+        let other = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        std::fs::write(dir.path().join("cert.pem"), other.cert.pem()).unwrap();
+
+        let err = resolver.reload().unwrap_err().to_string();
+        assert!(err.contains("does not match"), "unexpected error: {}", err);
+        assert_eq!(served_cert(&resolver), *first.cert.der());
+    }
+
+    #[test]
+    fn test_backend_tls_requires_cert_and_key_together() {
+        let yaml = r#"
+client_cert_path: "/path/to/client.pem"
+"#;
+
+        let config: BackendTlsConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(!config.insecure_skip_verify); // Default is false
+
+        let err = build_backend_tls_config(&config).unwrap_err().to_string();
+        assert!(err.contains("must be set together"), "unexpected error: {}", err);
+    }
+
+    #[test]
+    fn test_backend_tls_defaults_to_bundled_roots() {
+        let config = build_backend_tls_config(&BackendTlsConfig::default()).unwrap();
+        assert!(!config.client_auth_cert_resolver.has_certs());
+    }
+
+    #[test]
+    fn test_tls_version_and_cipher_suite_validation() {
+        let yaml = r#"
+cert_path: "/path/to/cert.pem"
+key_path: "/path/to/key.pem"
+"#;
+        let mut config: TlsConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.min_version, "1.2");
+        assert!(config.validate().is_ok());
+
+        config.min_version = "1.3".to_string();
+        config.cipher_suites = Some(vec!["TLS13_AES_256_GCM_SHA384".to_string()]);
+        assert!(config.validate().is_ok());
+        assert_eq!(config.crypto_provider().unwrap().cipher_suites.len(), 1);
+
+        config.min_version = "1.1".to_string();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("tls.min_version"), "unexpected error: {}", err);
+
+        config.min_version = "1.3".to_string();
+        config.cipher_suites = Some(vec![
+            "TLS13_AES_256_GCM_SHA384".to_string(),
+            "TLS_RSA_WITH_RC4_128_SHA".to_string(),
+        ]);
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("tls.cipher_suites[1]"), "unexpected error: {}", err);
     }
 }
@@ -1,11 +1,23 @@
 use crate::error::{GatewayError, Result};
-use axum::http::{HeaderMap, HeaderName, HeaderValue};
+use axum::http::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use bytes::Bytes;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::net::IpAddr;
 use std::str::FromStr;
+use std::time::Duration;
 use tracing::{debug, warn};
 
+/// Header carrying the total gateway + backend response time
+pub const RESPONSE_TIME_HEADER: &str = "x-response-time-ms";
+
+/// W3C Server-Timing header
+pub const SERVER_TIMING_HEADER: &str = "server-timing";
+
 /// Request/Response transformation configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct TransformConfig {
@@ -20,10 +32,12 @@ pub struct TransformConfig {
 /// Request transformation options
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct RequestTransform {
-    /// Headers to add (will not override existing)
+    /// Headers to add (will not override existing); values may use
+    /// `TemplateContext` placeholders
     #[serde(default)]
     pub add_headers: HashMap<String, String>,
-    /// Headers to set (will override existing)
+    /// Headers to set (will override existing); values may use
+    /// `TemplateContext` placeholders
     #[serde(default)]
     pub set_headers: HashMap<String, String>,
     /// Headers to remove
@@ -35,6 +49,9 @@ pub struct RequestTransform {
     /// Query parameter transformations
     #[serde(default)]
     pub query_params: Option<QueryParamTransform>,
+    /// JSON body transformations (buffers and re-encodes JSON request bodies)
+    #[serde(default)]
+    pub body: Option<BodyTransform>,
 }
 
 /// Response transformation options
@@ -49,6 +66,18 @@ pub struct ResponseTransform {
     /// Headers to remove
     #[serde(default)]
     pub remove_headers: Vec<String>,
+    /// Add an X-Response-Time-Ms header with the total response time
+    #[serde(default)]
+    pub response_time_header: bool,
+    /// Add a Server-Timing header split into gateway and backend time
+    #[serde(default)]
+    pub server_timing: bool,
+    /// Backend status codes to rewrite (e.g. `418: 400`)
+    #[serde(default)]
+    pub status_map: HashMap<u16, u16>,
+    /// Status returned for every response (takes precedence over `status_map`)
+    #[serde(default)]
+    pub set_status: Option<u16>,
 }
 
 /// Path rewrite rule using regex
@@ -74,11 +103,210 @@ pub struct QueryParamTransform {
     pub remove: Vec<String>,
 }
 
+/// Request details available to templated request header values
+///
+/// Values may reference `{request_id}`, `{client_ip}`, `{method}`, `{path}`
+/// and route parameters as `{param.<name>}`. Unknown placeholders are kept
+/// verbatim.
+#[derive(Debug, Clone, Copy)]
+pub struct TemplateContext<'a> {
+    /// Request ID assigned by the observability middleware
+    pub request_id: Option<&'a str>,
+    pub client_ip: IpAddr,
+    pub method: &'a str,
+    /// Original request path, before any rewrites
+    pub path: &'a str,
+    /// Parameters extracted by the matched route
+    pub params: &'a HashMap<String, String>,
+}
+
+impl TemplateContext<'_> {
+    /// Value of a placeholder, or `None` if the name is unknown
+    fn lookup(&self, name: &str) -> Option<String> {
+        match name {
+            "request_id" => Some(self.request_id.unwrap_or_default().to_string()),
+            "client_ip" => Some(self.client_ip.to_string()),
+            "method" => Some(self.method.to_string()),
+            "path" => Some(self.path.to_string()),
+            _ => self.params.get(name.strip_prefix("param.")?).cloned(),
+        }
+    }
+
+    /// Expand the placeholders in a header value template
+    pub fn expand<'t>(&self, template: &'t str) -> Cow<'t, str> {
+        if !template.contains('{') {
+            return Cow::Borrowed(template);
+        }
+
+        let mut expanded = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            let Some(len) = rest[start..].find('}') else {
+                break;
+            };
+            let end = start + len;
+            expanded.push_str(&rest[..start]);
+            match self.lookup(&rest[start + 1..end]) {
+                Some(value) => expanded.push_str(&value),
+                None => expanded.push_str(&rest[start..=end]),
+            }
+            rest = &rest[end + 1..];
+        }
+        // Trailing text, including any unterminated placeholder
+        expanded.push_str(rest);
+        Cow::Owned(expanded)
+    }
+}
+
+/// JSON request body transformations, with fields addressed by JSON pointer
+/// (e.g. `/tenant/id`)
+///
+/// Only applied to bodies with a JSON content type; others are forwarded
+/// unchanged.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BodyTransform {
+    /// Fields to add (will not override existing)
+    #[serde(default)]
+    pub add: HashMap<String, Value>,
+    /// Fields to set (will override existing)
+    #[serde(default)]
+    pub set: HashMap<String, Value>,
+    /// Fields to remove
+    #[serde(default)]
+    pub remove: Vec<String>,
+}
+
+/// JSON body operation with a pre-parsed pointer
+#[derive(Debug)]
+enum BodyOperation {
+    Remove(Vec<String>),
+    Add(Vec<String>, Value),
+    Set(Vec<String>, Value),
+}
+
+/// Split a JSON pointer into unescaped reference tokens (RFC 6901)
+fn parse_json_pointer(pointer: &str) -> Result<Vec<String>> {
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err(GatewayError::Config(format!(
+            "Invalid JSON pointer '{}' in body transform: must start with '/'",
+            pointer
+        )));
+    };
+    Ok(rest
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+/// Whether a content type denotes JSON (`application/json` or `*/*+json`)
+fn is_json_content_type(headers: &HeaderMap) -> bool {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(|mime| {
+            let mime = mime.trim().to_ascii_lowercase();
+            mime == "application/json" || mime.ends_with("+json")
+        })
+        .unwrap_or(false)
+}
+
+/// Insert `value` at `tokens`, creating missing parent objects
+///
+/// Array parents take an index or `-` (append); adding inserts into the
+/// array while setting replaces the element. Returns false if the location
+/// cannot be reached or, without `overwrite`, is already present.
+fn insert_at(root: &mut Value, tokens: &[String], value: Value, overwrite: bool) -> bool {
+    let Some((last, parents)) = tokens.split_last() else {
+        return false;
+    };
+
+    let mut current = root;
+    for token in parents {
+        current = match current {
+            Value::Object(map) => map
+                .entry(token.clone())
+                .or_insert_with(|| Value::Object(Default::default())),
+            Value::Array(items) => match token.parse::<usize>() {
+                Ok(index) if index < items.len() => &mut items[index],
+                _ => return false,
+            },
+            _ => return false,
+        };
+    }
+
+    match current {
+        Value::Object(map) => {
+            if !overwrite && map.contains_key(last) {
+                return false;
+            }
+            map.insert(last.clone(), value);
+            true
+        }
+        Value::Array(items) if last == "-" => {
+            items.push(value);
+            true
+        }
+        Value::Array(items) => match last.parse::<usize>() {
+            Ok(index) if overwrite && index < items.len() => {
+                items[index] = value;
+                true
+            }
+            Ok(index) if !overwrite && index <= items.len() => {
+                items.insert(index, value);
+                true
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Remove the value at `tokens`, returning whether it existed
+fn remove_at(root: &mut Value, tokens: &[String]) -> bool {
+    let Some((last, parents)) = tokens.split_last() else {
+        return false;
+    };
+
+    let mut current = root;
+    for token in parents {
+        current = match current {
+            Value::Object(map) => match map.get_mut(token) {
+                Some(value) => value,
+                None => return false,
+            },
+            Value::Array(items) => match token.parse::<usize>() {
+                Ok(index) if index < items.len() => &mut items[index],
+                _ => return false,
+            },
+            _ => return false,
+        };
+    }
+
+    match current {
+        Value::Object(map) => map.remove(last).is_some(),
+        Value::Array(items) => match last.parse::<usize>() {
+            Ok(index) if index < items.len() => {
+                items.remove(index);
+                true
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
 /// Header transformation service
 #[derive(Debug)]
 pub struct TransformService {
     config: TransformConfig,
     path_rewrite_cache: Vec<(Regex, String)>,
+    /// JSON body operations in application order (remove, add, set)
+    body_operations: Vec<BodyOperation>,
+    /// Validated `status_map`
+    status_map: HashMap<StatusCode, StatusCode>,
+    /// Validated `set_status`
+    set_status: Option<StatusCode>,
 }
 
 impl TransformService {
@@ -98,14 +326,59 @@ impl TransformService {
             }
         }
 
+        // Pre-parse JSON pointers for body transformations
+        let mut body_operations = Vec::new();
+        if let Some(body) = config.request.as_ref().and_then(|r| r.body.as_ref()) {
+            for pointer in &body.remove {
+                body_operations.push(BodyOperation::Remove(parse_json_pointer(pointer)?));
+            }
+            for (pointer, value) in &body.add {
+                body_operations.push(BodyOperation::Add(
+                    parse_json_pointer(pointer)?,
+                    value.clone(),
+                ));
+            }
+            for (pointer, value) in &body.set {
+                body_operations.push(BodyOperation::Set(
+                    parse_json_pointer(pointer)?,
+                    value.clone(),
+                ));
+            }
+        }
+
+        // Validate response status rewrites
+        let status_code = |code: u16| {
+            StatusCode::from_u16(code).map_err(|_| {
+                GatewayError::Config(format!(
+                    "Invalid status code {} in response transform",
+                    code
+                ))
+            })
+        };
+        let mut status_map = HashMap::new();
+        let mut set_status = None;
+        if let Some(response) = &config.response {
+            for (&from, &to) in &response.status_map {
+                status_map.insert(status_code(from)?, status_code(to)?);
+            }
+            set_status = response.set_status.map(status_code).transpose()?;
+        }
+
         Ok(Self {
             config,
             path_rewrite_cache,
+            body_operations,
+            status_map,
+            set_status,
         })
     }
 
-    /// Transform request headers
-    pub fn transform_request_headers(&self, headers: &mut HeaderMap) -> Result<()> {
+    /// Transform request headers, expanding templated values from `context`
+    pub fn transform_request_headers(
+        &self,
+        headers: &mut HeaderMap,
+        context: &TemplateContext<'_>,
+    ) -> Result<()> {
         if let Some(request) = &self.config.request {
             // Remove headers
             for header_name in &request.remove_headers {
@@ -118,9 +391,10 @@ impl TransformService {
             }
 
             // Add headers (only if not already present)
-            for (key, value) in &request.add_headers {
+            for (key, template) in &request.add_headers {
+                let value = context.expand(template);
                 if let (Ok(name), Ok(val)) =
-                    (HeaderName::from_str(key), HeaderValue::from_str(value))
+                    (HeaderName::from_str(key), HeaderValue::from_str(&value))
                 {
                     if !headers.contains_key(&name) {
                         headers.insert(name, val);
@@ -132,9 +406,10 @@ impl TransformService {
             }
 
             // Set headers (override existing)
-            for (key, value) in &request.set_headers {
+            for (key, template) in &request.set_headers {
+                let value = context.expand(template);
                 if let (Ok(name), Ok(val)) =
-                    (HeaderName::from_str(key), HeaderValue::from_str(value))
+                    (HeaderName::from_str(key), HeaderValue::from_str(&value))
                 {
                     headers.insert(name, val);
                     debug!(header = %key, value = %value, "Set request header");
@@ -190,6 +465,46 @@ impl TransformService {
         Ok(())
     }
 
+    /// Rewrite a backend response status using `set_status` or `status_map`
+    pub fn transform_status(&self, status: StatusCode) -> StatusCode {
+        let rewritten = self
+            .set_status
+            .or_else(|| self.status_map.get(&status).copied())
+            .unwrap_or(status);
+        if rewritten != status {
+            debug!(from = %status, to = %rewritten, "Rewrote response status");
+        }
+        rewritten
+    }
+
+    /// Add computed timing headers to a response
+    ///
+    /// `total` is the full handler time and `backend` the backend round-trip
+    /// time; the difference is reported as gateway overhead.
+    pub fn add_timing_headers(&self, headers: &mut HeaderMap, total: Duration, backend: Duration) {
+        if let Some(response) = &self.config.response {
+            let total_ms = total.as_secs_f64() * 1000.0;
+
+            if response.response_time_header {
+                if let Ok(val) = HeaderValue::from_str(&format!("{:.3}", total_ms)) {
+                    headers.insert(RESPONSE_TIME_HEADER, val);
+                }
+            }
+
+            if response.server_timing {
+                let backend_ms = backend.as_secs_f64() * 1000.0;
+                let gateway_ms = (total_ms - backend_ms).max(0.0);
+                let value = format!(
+                    "gateway;dur={:.3}, backend;dur={:.3}",
+                    gateway_ms, backend_ms
+                );
+                if let Ok(val) = HeaderValue::from_str(&value) {
+                    headers.append(SERVER_TIMING_HEADER, val);
+                }
+            }
+        }
+    }
+
     /// Transform request path using configured rewrites
     pub fn transform_path(&self, path: &str) -> String {
         let mut transformed = path.to_string();
@@ -246,6 +561,53 @@ impl TransformService {
         query.to_string()
     }
 
+    /// Transform a JSON request body
+    ///
+    /// Bodies without a JSON content type, or that fail to parse, are
+    /// returned unchanged. `Content-Length` is updated when the body changes.
+    pub fn transform_request_body(&self, headers: &mut HeaderMap, body: Bytes) -> Bytes {
+        if self.body_operations.is_empty() || !is_json_content_type(headers) {
+            return body;
+        }
+
+        let mut json: Value = match serde_json::from_slice(&body) {
+            Ok(json) => json,
+            Err(e) => {
+                warn!(error = %e, "Request body is not valid JSON, skipping body transform");
+                return body;
+            }
+        };
+
+        for operation in &self.body_operations {
+            let applied = match operation {
+                BodyOperation::Remove(tokens) => remove_at(&mut json, tokens),
+                BodyOperation::Add(tokens, value) => {
+                    insert_at(&mut json, tokens, value.clone(), false)
+                }
+                BodyOperation::Set(tokens, value) => {
+                    insert_at(&mut json, tokens, value.clone(), true)
+                }
+            };
+            debug!(operation = ?operation, applied, "Applied request body transform");
+        }
+
+        match serde_json::to_vec(&json) {
+            Ok(transformed) => {
+                headers.insert(CONTENT_LENGTH, HeaderValue::from(transformed.len()));
+                Bytes::from(transformed)
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to encode transformed request body");
+                body
+            }
+        }
+    }
+
+    /// Check if there are JSON body transformations configured
+    pub fn has_body_transform(&self) -> bool {
+        !self.body_operations.is_empty()
+    }
+
     /// Check if there are any request transformations configured
     pub fn has_request_transform(&self) -> bool {
         self.config.request.is_some()
@@ -261,6 +623,85 @@ impl TransformService {
 mod tests {
     use super::*;
 
+    fn test_context(params: &HashMap<String, String>) -> TemplateContext<'_> {
+        TemplateContext {
+            request_id: Some("req-123"),
+            client_ip: "203.0.113.7".parse().unwrap(),
+            method: "GET",
+            path: "/users/42",
+            params,
+        }
+    }
+
+    fn expand_header(template: &str, context: &TemplateContext<'_>) -> HeaderValue {
+        let service = TransformService::new(TransformConfig {
+            request: Some(RequestTransform {
+                set_headers: HashMap::from([("X-Templated".to_string(), template.to_string())]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let mut headers = HeaderMap::new();
+        service
+            .transform_request_headers(&mut headers, context)
+            .unwrap();
+        headers["X-Templated"].clone()
+    }
+
+    #[test]
+    fn test_header_template_request_id() {
+        let params = HashMap::new();
+        let context = test_context(&params);
+        assert_eq!(expand_header("{request_id}", &context), "req-123");
+
+        let context = TemplateContext {
+            request_id: None,
+            ..context
+        };
+        assert_eq!(expand_header("id={request_id}", &context), "id=");
+    }
+
+    #[test]
+    fn test_header_template_client_ip() {
+        let params = HashMap::new();
+        assert_eq!(
+            expand_header("{client_ip}", &test_context(&params)),
+            "203.0.113.7"
+        );
+    }
+
+    #[test]
+    fn test_header_template_method_and_path() {
+        let params = HashMap::new();
+        let context = test_context(&params);
+        assert_eq!(expand_header("{method}", &context), "GET");
+        assert_eq!(expand_header("{path}", &context), "/users/42");
+        assert_eq!(expand_header("{method} {path}", &context), "GET /users/42");
+    }
+
+    #[test]
+    fn test_header_template_route_params() {
+        let params = HashMap::from([("id".to_string(), "42".to_string())]);
+        let context = test_context(&params);
+        assert_eq!(expand_header("user-{param.id}", &context), "user-42");
+        // Unknown parameters are left as written
+        assert_eq!(
+            expand_header("{param.missing}", &context),
+            "{param.missing}"
+        );
+    }
+
+    #[test]
+    fn test_header_template_literal() {
+        let params = HashMap::new();
+        let context = test_context(&params);
+        assert_eq!(expand_header("static-value", &context), "static-value");
+        assert_eq!(expand_header("{unknown} {", &context), "{unknown} {");
+        assert!(matches!(context.expand("static-value"), Cow::Borrowed(_)));
+    }
+
     #[test]
     fn test_header_add_transformation() {
         let config = TransformConfig {
@@ -276,7 +717,9 @@ mod tests {
         let service = TransformService::new(config).unwrap();
         let mut headers = HeaderMap::new();
 
-        service.transform_request_headers(&mut headers).unwrap();
+        service
+            .transform_request_headers(&mut headers, &test_context(&HashMap::new()))
+            .unwrap();
 
         assert_eq!(
             headers.get("X-Custom-Header").unwrap(),
@@ -303,7 +746,9 @@ mod tests {
             HeaderValue::from_str("existing-value").unwrap(),
         );
 
-        service.transform_request_headers(&mut headers).unwrap();
+        service
+            .transform_request_headers(&mut headers, &test_context(&HashMap::new()))
+            .unwrap();
 
         // Should not override existing header
         assert_eq!(
@@ -331,7 +776,9 @@ mod tests {
             HeaderValue::from_str("existing-value").unwrap(),
         );
 
-        service.transform_request_headers(&mut headers).unwrap();
+        service
+            .transform_request_headers(&mut headers, &test_context(&HashMap::new()))
+            .unwrap();
 
         // Should override existing header
         assert_eq!(
@@ -357,7 +804,9 @@ mod tests {
             HeaderValue::from_str("value").unwrap(),
         );
 
-        service.transform_request_headers(&mut headers).unwrap();
+        service
+            .transform_request_headers(&mut headers, &test_context(&HashMap::new()))
+            .unwrap();
 
         assert!(headers.get("X-Remove-Me").is_none());
     }
@@ -470,4 +919,278 @@ mod tests {
             HeaderValue::from_str("Rust Gateway").unwrap()
         );
     }
+
+    #[test]
+    fn test_timing_headers() {
+        let config = TransformConfig {
+            response: Some(ResponseTransform {
+                response_time_header: true,
+                server_timing: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let service = TransformService::new(config).unwrap();
+        let mut headers = HeaderMap::new();
+
+        service.add_timing_headers(
+            &mut headers,
+            Duration::from_millis(50),
+            Duration::from_millis(40),
+        );
+
+        assert_eq!(headers.get(RESPONSE_TIME_HEADER).unwrap(), "50.000");
+        assert_eq!(
+            headers.get(SERVER_TIMING_HEADER).unwrap(),
+            "gateway;dur=10.000, backend;dur=40.000"
+        );
+    }
+
+    #[test]
+    fn test_timing_headers_disabled() {
+        let config = TransformConfig {
+            response: Some(ResponseTransform::default()),
+            ..Default::default()
+        };
+
+        let service = TransformService::new(config).unwrap();
+        let mut headers = HeaderMap::new();
+
+        service.add_timing_headers(
+            &mut headers,
+            Duration::from_millis(50),
+            Duration::from_millis(40),
+        );
+
+        assert!(headers.get(RESPONSE_TIME_HEADER).is_none());
+        assert!(headers.get(SERVER_TIMING_HEADER).is_none());
+    }
+
+    fn body_transform_service(body: BodyTransform) -> TransformService {
+        TransformService::new(TransformConfig {
+            request: Some(RequestTransform {
+                body: Some(body),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn json_headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_static("999"));
+        headers
+    }
+
+    fn transform_json(service: &TransformService, body: Value) -> Value {
+        let mut headers = json_headers();
+        let transformed =
+            service.transform_request_body(&mut headers, Bytes::from(body.to_string()));
+        assert_eq!(
+            headers[CONTENT_LENGTH],
+            transformed.len().to_string().as_str()
+        );
+        serde_json::from_slice(&transformed).unwrap()
+    }
+
+    #[test]
+    fn test_body_add_field() {
+        let service = body_transform_service(BodyTransform {
+            add: HashMap::from([
+                ("/tenant_id".to_string(), serde_json::json!("acme")),
+                ("/name".to_string(), serde_json::json!("ignored")),
+                ("/meta/source".to_string(), serde_json::json!("gateway")),
+                ("/tags/-".to_string(), serde_json::json!("new")),
+            ]),
+            ..Default::default()
+        });
+
+        let body = transform_json(
+            &service,
+            serde_json::json!({"name": "widget", "tags": ["a"]}),
+        );
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "name": "widget",
+                "tenant_id": "acme",
+                "meta": {"source": "gateway"},
+                "tags": ["a", "new"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_body_set_field() {
+        let service = body_transform_service(BodyTransform {
+            set: HashMap::from([
+                ("/name".to_string(), serde_json::json!("renamed")),
+                ("/items/0/qty".to_string(), serde_json::json!(5)),
+            ]),
+            ..Default::default()
+        });
+
+        let body = transform_json(
+            &service,
+            serde_json::json!({"name": "widget", "items": [{"qty": 1}]}),
+        );
+        assert_eq!(
+            body,
+            serde_json::json!({"name": "renamed", "items": [{"qty": 5}]})
+        );
+    }
+
+    #[test]
+    fn test_body_remove_field() {
+        let service = body_transform_service(BodyTransform {
+            remove: vec![
+                "/internal".to_string(),
+                "/a~1b".to_string(),
+                "/items/1".to_string(),
+                "/missing/field".to_string(),
+            ],
+            ..Default::default()
+        });
+
+        let body = transform_json(
+            &service,
+            serde_json::json!({"name": "widget", "internal": true, "a/b": 1, "items": [1, 2, 3]}),
+        );
+        assert_eq!(body, serde_json::json!({"name": "widget", "items": [1, 3]}));
+    }
+
+    #[test]
+    fn test_body_non_json_passes_through() {
+        let service = body_transform_service(BodyTransform {
+            set: HashMap::from([("/tenant_id".to_string(), serde_json::json!("acme"))]),
+            ..Default::default()
+        });
+        let binary = Bytes::from_static(&[0x89, b'P', b'N', b'G', 0x00, 0xff]);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/octet-stream"),
+        );
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_static("6"));
+        let transformed = service.transform_request_body(&mut headers, binary.clone());
+        assert_eq!(transformed, binary);
+        assert_eq!(headers[CONTENT_LENGTH], "6");
+
+        // Declared as JSON but not parseable: also left untouched
+        let mut headers = json_headers();
+        let transformed = service.transform_request_body(&mut headers, binary.clone());
+        assert_eq!(transformed, binary);
+        assert_eq!(headers[CONTENT_LENGTH], "999");
+    }
+
+    #[test]
+    fn test_body_transform_json_content_types() {
+        let service = body_transform_service(BodyTransform {
+            set: HashMap::from([("/v".to_string(), serde_json::json!(2))]),
+            ..Default::default()
+        });
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/vnd.api+json; charset=utf-8"),
+        );
+        let transformed = service.transform_request_body(&mut headers, Bytes::from("{}"));
+        assert_eq!(transformed, Bytes::from(r#"{"v":2}"#));
+        assert!(service.has_body_transform());
+    }
+
+    #[test]
+    fn test_body_transform_invalid_pointer() {
+        let result = TransformService::new(TransformConfig {
+            request: Some(RequestTransform {
+                body: Some(BodyTransform {
+                    remove: vec!["tenant_id".to_string()],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        assert!(result.is_err());
+
+        let service = TransformService::new(TransformConfig::default()).unwrap();
+        assert!(!service.has_body_transform());
+    }
+
+    #[test]
+    fn test_status_map() {
+        let service = TransformService::new(TransformConfig {
+            response: Some(ResponseTransform {
+                status_map: HashMap::from([(418, 400), (204, 200)]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(
+            service.transform_status(StatusCode::IM_A_TEAPOT),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            service.transform_status(StatusCode::NO_CONTENT),
+            StatusCode::OK
+        );
+        assert_eq!(
+            service.transform_status(StatusCode::NOT_FOUND),
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[test]
+    fn test_set_status_overrides_map() {
+        let service = TransformService::new(TransformConfig {
+            response: Some(ResponseTransform {
+                status_map: HashMap::from([(500, 502)]),
+                set_status: Some(200),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(
+            service.transform_status(StatusCode::INTERNAL_SERVER_ERROR),
+            StatusCode::OK
+        );
+        assert_eq!(service.transform_status(StatusCode::CREATED), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_invalid_status_rejected() {
+        for response in [
+            ResponseTransform {
+                status_map: HashMap::from([(418, 42)]),
+                ..Default::default()
+            },
+            ResponseTransform {
+                set_status: Some(1000),
+                ..Default::default()
+            },
+        ] {
+            let result = TransformService::new(TransformConfig {
+                response: Some(response),
+                ..Default::default()
+            });
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn test_status_map_from_yaml() {
+        let config: ResponseTransform =
+            serde_yaml::from_str("status_map:\n  418: 400\nset_status: 503\n").unwrap();
+        assert_eq!(config.status_map.get(&418), Some(&400));
+        assert_eq!(config.set_status, Some(503));
+    }
 }
@@ -0,0 +1,73 @@
+mod common;
+
+use axum::Router;
+use gateway::metrics::MetricsService;
+use http::{Request, StatusCode};
+use tower::ServiceExt;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const PRODUCTS_ROUTE: &str = "/api/products/:id";
+
+/// Gateway with a single cached route
+fn gateway(backend: &str) -> Router {
+    common::gateway(&format!(
+        r#"
+server:
+  host: "127.0.0.1"
+  port: 8080
+routes:
+  - path: "/api/products/:id"
+    backend: "{backend}"
+    cache:
+      enabled: true
+"#
+    ))
+}
+
+async fn get(app: &Router, uri: &str) -> StatusCode {
+    let request = Request::builder()
+        .uri(uri)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_cache_hits_and_misses_recorded() {
+    // Installs the global recorder
+    let metrics = MetricsService::new().unwrap();
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/products/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("widget"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    let app = gateway(&mock_server.uri());
+
+    assert_eq!(get(&app, "/api/products/1").await, StatusCode::OK);
+    let rendered = metrics.render();
+    assert_eq!(
+        common::sample(&rendered, "gateway_cache_misses_total", PRODUCTS_ROUTE),
+        Some(1.0)
+    );
+    assert_eq!(
+        common::sample(&rendered, "gateway_cache_hits_total", PRODUCTS_ROUTE),
+        None
+    );
+
+    assert_eq!(get(&app, "/api/products/1").await, StatusCode::OK);
+    let rendered = metrics.render();
+    assert_eq!(
+        common::sample(&rendered, "gateway_cache_misses_total", PRODUCTS_ROUTE),
+        Some(1.0)
+    );
+    assert_eq!(
+        common::sample(&rendered, "gateway_cache_hits_total", PRODUCTS_ROUTE),
+        Some(1.0)
+    );
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use gateway::metrics::MetricsService;
+use http::{Request, StatusCode};
+use std::time::Duration;
+use tower::ServiceExt;
+use wiremock::{
+    matchers::{method, path_regex},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const PRODUCTS_ROUTE: &str = "/api/products/:id";
+
+#[tokio::test]
+async fn test_cache_stats_reported_periodically() {
+    // Installs the global recorder
+    let metrics = MetricsService::new().unwrap();
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex("^/api/products/"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("product"))
+        .mount(&mock_server)
+        .await;
+
+    let proxy_state = common::proxy_state(&format!(
+        r#"
+server: {{}}
+routes:
+  - path: "/api/products/:id"
+    backend: "{}"
+    cache:
+      enabled: true
+"#,
+        mock_server.uri()
+    ));
+    proxy_state.spawn_cache_stats_reporter(Duration::from_millis(20));
+    let app = common::proxy_app(proxy_state);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let rendered = metrics.render();
+    assert_eq!(
+        common::sample(&rendered, "gateway_cache_entries", PRODUCTS_ROUTE),
+        Some(0.0)
+    );
+
+    for id in 1..=3 {
+        let request = Request::builder()
+            .uri(format!("/api/products/{id}"))
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    // Picked up by the next report without any further cache lookups
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let rendered = metrics.render();
+    assert_eq!(
+        common::sample(&rendered, "gateway_cache_entries", PRODUCTS_ROUTE),
+        Some(3.0),
+        "{rendered}"
+    );
+    assert_eq!(
+        common::sample(&rendered, "gateway_cache_weighted_size", PRODUCTS_ROUTE),
+        Some(3.0)
+    );
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use axum::Router;
+use http::{Request, StatusCode};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+/// Gateway with `/api/orders` split between a stable and a canary group
+fn gateway(stable: &[&str], canary: &[&str], weight: f64, sticky: Option<&str>) -> Router {
+    let list = |urls: &[&str], indent: &str| {
+        urls.iter()
+            .map(|url| format!("\n{indent}- url: \"{url}\""))
+            .collect::<String>()
+    };
+    let sticky = sticky
+        .map(|key| format!("\n      sticky: \"{key}\""))
+        .unwrap_or_default();
+    common::gateway(&format!(
+        r#"
+server: {{}}
+routes:
+  - path: "/api/orders"
+    backends:{}
+    canary:
+      canary_weight: {weight}{sticky}
+      backends:{}
+"#,
+        list(stable, "      "),
+        list(canary, "        "),
+    ))
+}
+
+async fn backend(name: &str) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/orders"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(name))
+        .mount(&server)
+        .await;
+    server
+}
+
+/// Send a request and return the name of the backend that served it
+async fn served_by(app: &Router, user: Option<&str>) -> String {
+    let mut request = Request::builder().uri("/api/orders");
+    if let Some(user) = user {
+        request = request.header("X-User-Id", user);
+    }
+    let response = app
+        .clone()
+        .oneshot(request.body(axum::body::Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_traffic_split_matches_canary_weight() {
+    let stable_a = backend("stable-a").await;
+    let stable_b = backend("stable-b").await;
+    let canary = backend("canary").await;
+    let app = gateway(
+        &[&stable_a.uri(), &stable_b.uri()],
+        &[&canary.uri()],
+        20.0,
+        None,
+    );
+
+    let mut canary_count = 0;
+    for _ in 0..500 {
+        if served_by(&app, None).await == "canary" {
+            canary_count += 1;
+        }
+    }
+
+    // 20% of 500 is 100; allow for sampling noise
+    assert!(
+        (60..=140).contains(&canary_count),
+        "canary served {canary_count}"
+    );
+    let stable_a = stable_a.received_requests().await.unwrap().len();
+    let stable_b = stable_b.received_requests().await.unwrap().len();
+    assert_eq!(stable_a + stable_b + canary_count, 500);
+    // Requests within the stable group are still load balanced
+    assert!(stable_a.abs_diff(stable_b) <= 1);
+}
+
+#[tokio::test]
+async fn test_sticky_header_pins_clients_to_a_group() {
+    let stable = backend("stable").await;
+    let canary = backend("canary").await;
+    let app = gateway(
+        &[&stable.uri()],
+        &[&canary.uri()],
+        50.0,
+        Some("header:X-User-Id"),
+    );
+
+    let mut canary_users = 0;
+    for user in 0..200 {
+        let user = format!("user-{user}");
+        let first = served_by(&app, Some(&user)).await;
+        for _ in 0..3 {
+            assert_eq!(served_by(&app, Some(&user)).await, first);
+        }
+        if first == "canary" {
+            canary_users += 1;
+        }
+    }
+    assert!(
+        (60..=140).contains(&canary_users),
+        "canary users {canary_users}"
+    );
+}
+
+#[tokio::test]
+async fn test_zero_weight_keeps_canary_out_of_rotation() {
+    let stable = backend("stable").await;
+    let app = gateway(&[&stable.uri()], &["http://127.0.0.1:1"], 0.0, None);
+
+    for _ in 0..50 {
+        assert_eq!(served_by(&app, None).await, "stable");
+    }
+}
@@ -1,6 +1,18 @@
-use gateway::circuit_breaker::{CircuitBreakerConfig, CircuitBreakerService, CircuitState};
-use std::time::Duration;
+use axum::Router;
+use gateway::circuit_breaker::{
+    CircuitBreakerConfig, CircuitBreakerService, CircuitState, RetryConfig, RetryExecutor,
+};
+use gateway::config::GatewayConfig;
+use gateway::proxy::ProxyState;
+use gateway::router::Router as GatewayRouter;
+use http::{Request, StatusCode};
+use std::time::{Duration, Instant, SystemTime};
 use tokio::time::sleep;
+use tower::ServiceExt;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
 
 #[tokio::test]
 async fn test_circuit_breaker_integration() {
@@ -10,6 +22,7 @@ async fn test_circuit_breaker_integration() {
         timeout_secs: 1,
         half_open_requests: 2,
         request_timeout_secs: 30,
+        ..Default::default()
     };
 
     let service = CircuitBreakerService::new(config);
@@ -54,6 +67,7 @@ async fn test_circuit_breaker_timeout_tracking() {
         timeout_secs: 1,
         half_open_requests: 2,
         request_timeout_secs: 30,
+        ..Default::default()
     };
 
     let service = CircuitBreakerService::new(config);
@@ -135,6 +149,7 @@ async fn test_half_open_failure_reopens_circuit() {
         timeout_secs: 1,
         half_open_requests: 3,
         request_timeout_secs: 30,
+        ..Default::default()
     };
 
     let service = CircuitBreakerService::new(config);
@@ -165,6 +180,7 @@ async fn test_circuit_breaker_metrics_accuracy() {
         timeout_secs: 1,
         half_open_requests: 2,
         request_timeout_secs: 30,
+        ..Default::default()
     };
 
     let service = CircuitBreakerService::new(config);
@@ -194,3 +210,209 @@ async fn test_circuit_breaker_metrics_accuracy() {
     assert_eq!(metrics.rejected_requests, 0);
     assert_eq!(metrics.circuit_opened_count, 0);
 }
+
+/// Gateway retrying 503s from a backend that is unavailable once
+async fn retrying_gateway(retry_after: Option<String>) -> (Router, MockServer) {
+    let mock_server = MockServer::start().await;
+    let mut unavailable = ResponseTemplate::new(503);
+    if let Some(retry_after) = retry_after {
+        unavailable = unavailable.insert_header("Retry-After", retry_after.as_str());
+    }
+    Mock::given(method("GET"))
+        .and(path("/api/reports"))
+        .respond_with(unavailable)
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/reports"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let config = GatewayConfig::from_yaml(&format!(
+        r#"
+server: {{}}
+routes:
+  - path: "/api/reports"
+    backend: "{}"
+"#,
+        mock_server.uri()
+    ))
+    .unwrap();
+    let retry = RetryExecutor::new(RetryConfig {
+        max_retries: 2,
+        initial_backoff_ms: 1,
+        max_backoff_ms: 10,
+        retry_on_status: vec![503],
+        ..Default::default()
+    });
+    let router = GatewayRouter::new(config.routes).unwrap();
+    let proxy_state = ProxyState::new(router, Duration::from_secs(30), None, None, Some(retry));
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+    (app, mock_server)
+}
+
+async fn get_reports(app: Router) -> (StatusCode, Duration) {
+    let start = Instant::now();
+    let request = Request::builder()
+        .uri("/api/reports")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let status = app.oneshot(request).await.unwrap().status();
+    (status, start.elapsed())
+}
+
+#[tokio::test]
+async fn test_retry_honors_retry_after_seconds() {
+    let (app, mock_server) = retrying_gateway(Some("1".to_string())).await;
+
+    let (status, elapsed) = get_reports(app).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(elapsed >= Duration::from_secs(1), "{:?}", elapsed);
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_retry_honors_retry_after_http_date() {
+    // HTTP-dates have second precision and setup takes time, so leave
+    // well over a second to wait
+    let retry_at = SystemTime::now() + Duration::from_secs(3);
+    let (app, mock_server) = retrying_gateway(Some(httpdate::fmt_http_date(retry_at))).await;
+
+    let (status, elapsed) = get_reports(app).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(elapsed >= Duration::from_secs(1), "{:?}", elapsed);
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_retry_503_without_retry_after_uses_backoff() {
+    let (app, mock_server) = retrying_gateway(None).await;
+
+    let (status, elapsed) = get_reports(app).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(elapsed < Duration::from_secs(1), "{:?}", elapsed);
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_last_retryable_response_returned_when_retries_exhausted() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&mock_server)
+        .await;
+
+    let config = GatewayConfig::from_yaml(&format!(
+        r#"
+server: {{}}
+routes:
+  - path: "/api/reports"
+    backend: "{}"
+"#,
+        mock_server.uri()
+    ))
+    .unwrap();
+    let retry = RetryExecutor::new(RetryConfig {
+        max_retries: 1,
+        initial_backoff_ms: 1,
+        retry_on_status: vec![503],
+        ..Default::default()
+    });
+    let router = GatewayRouter::new(config.routes).unwrap();
+    let proxy_state = ProxyState::new(router, Duration::from_secs(30), None, None, Some(retry));
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    let (status, _) = get_reports(app).await;
+
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_503_not_retried_by_default() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&mock_server)
+        .await;
+
+    let config = GatewayConfig::from_yaml(&format!(
+        r#"
+server: {{}}
+routes:
+  - path: "/api/reports"
+    backend: "{}"
+"#,
+        mock_server.uri()
+    ))
+    .unwrap();
+    let retry = RetryExecutor::new(RetryConfig {
+        max_retries: 2,
+        initial_backoff_ms: 1,
+        ..Default::default()
+    });
+    let router = GatewayRouter::new(config.routes).unwrap();
+    let proxy_state = ProxyState::new(router, Duration::from_secs(30), None, None, Some(retry));
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    let (status, _) = get_reports(app).await;
+
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_backend_429_is_not_retried() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "30"))
+        .mount(&mock_server)
+        .await;
+
+    let config = GatewayConfig::from_yaml(&format!(
+        r#"
+server: {{}}
+routes:
+  - path: "/api/reports"
+    backend: "{}"
+"#,
+        mock_server.uri()
+    ))
+    .unwrap();
+    // Even when listed explicitly, 429 is never retried
+    let retry = RetryExecutor::new(RetryConfig {
+        max_retries: 3,
+        initial_backoff_ms: 1,
+        retry_on_status: vec![429, 503],
+        ..Default::default()
+    });
+    let router = GatewayRouter::new(config.routes).unwrap();
+    let proxy_state = ProxyState::new(router, Duration::from_secs(30), None, None, Some(retry));
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    let request = Request::builder()
+        .uri("/api/reports")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let start = Instant::now();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()["retry-after"], "30");
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 1);
+}
@@ -0,0 +1,104 @@
+use axum::extract::ConnectInfo;
+use axum::{middleware, Router};
+use gateway::config::GatewayConfig;
+use gateway::ipfilter::{client_ip_middleware, TrustedProxies};
+use gateway::proxy::ProxyState;
+use gateway::router::Router as GatewayRouter;
+use http::{Request, StatusCode};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tower::ServiceExt;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+/// Gateway trusting 10.0.0.0/8, with a route blocking 203.0.113.0/24
+async fn setup() -> (Router, MockServer) {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/orders"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let config = GatewayConfig::from_yaml(&format!(
+        r#"
+server:
+  host: "127.0.0.1"
+  port: 8080
+  trusted_proxies:
+    - "10.0.0.0/8"
+routes:
+  - path: "/api/orders"
+    backend: "{}"
+    ip_filter:
+      blacklist:
+        - "203.0.113.0/24"
+"#,
+        mock_server.uri()
+    ))
+    .unwrap();
+    config.validate().unwrap();
+
+    let trusted_proxies = Arc::new(TrustedProxies::new(&config.server.trusted_proxies).unwrap());
+    let router = GatewayRouter::new(config.routes).unwrap();
+    let proxy_state = ProxyState::new(router, Duration::from_secs(30), None, None, None);
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state)
+        .layer(middleware::from_fn_with_state(
+            trusted_proxies,
+            client_ip_middleware,
+        ));
+    (app, mock_server)
+}
+
+async fn status_from(app: &Router, peer: [u8; 4], forwarded_for: &str) -> StatusCode {
+    let mut request = Request::builder()
+        .uri("/api/orders")
+        .header("X-Forwarded-For", forwarded_for)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    request
+        .extensions_mut()
+        .insert(ConnectInfo(SocketAddr::from((peer, 40000))));
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_trusted_proxy_forwards_client_ip_to_filter() {
+    let (app, _mock_server) = setup().await;
+
+    // Blocked client behind the load balancer
+    assert_eq!(
+        status_from(&app, [10, 0, 0, 5], "203.0.113.9").await,
+        StatusCode::FORBIDDEN
+    );
+    // A spoofed leftmost entry doesn't hide the real client
+    assert_eq!(
+        status_from(&app, [10, 0, 0, 5], "198.51.100.1, 203.0.113.9, 10.0.0.9").await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        status_from(&app, [10, 0, 0, 5], "203.0.113.9, 198.51.100.1").await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn test_untrusted_peer_header_ignored() {
+    let (app, _mock_server) = setup().await;
+
+    // A blocked client can't launder itself through a forged header
+    assert_eq!(
+        status_from(&app, [203, 0, 113, 9], "198.51.100.1").await,
+        StatusCode::FORBIDDEN
+    );
+    // Nor can an allowed peer get itself blocked
+    assert_eq!(
+        status_from(&app, [198, 51, 100, 1], "203.0.113.9").await,
+        StatusCode::OK
+    );
+}
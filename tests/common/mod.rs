@@ -0,0 +1,45 @@
+//! Fixtures shared by the integration test binaries
+//!
+//! Tests that install a process-wide global (metrics recorder, trace
+//! propagator or tracer provider) live in a binary of their own holding a
+//! single test, since a global can only be installed once per process.
+
+#![allow(dead_code)]
+
+use axum::Router;
+use gateway::config::GatewayConfig;
+use gateway::proxy::ProxyState;
+use gateway::router::Router as GatewayRouter;
+use std::time::Duration;
+
+/// Routes of a YAML gateway config
+pub fn router(config: &str) -> GatewayRouter {
+    let config = GatewayConfig::from_yaml(config).unwrap();
+    GatewayRouter::new(config.routes).unwrap()
+}
+
+/// Proxy state for the routes of a YAML gateway config, with a 30s timeout
+pub fn proxy_state(config: &str) -> ProxyState {
+    ProxyState::new(router(config), Duration::from_secs(30), None, None, None)
+}
+
+/// App sending every request through the proxy handler
+pub fn proxy_app(proxy_state: ProxyState) -> Router {
+    Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state)
+}
+
+/// Gateway app for the routes of a YAML gateway config
+pub fn gateway(config: &str) -> Router {
+    proxy_app(proxy_state(config))
+}
+
+/// Value of the Prometheus sample for `metric` on `route` in rendered metrics
+pub fn sample(rendered: &str, metric: &str, route: &str) -> Option<f64> {
+    let prefix = format!(r#"{metric}{{route="{route}"}} "#);
+    rendered
+        .lines()
+        .find_map(|line| line.strip_prefix(&prefix))
+        .map(|value| value.parse().unwrap())
+}
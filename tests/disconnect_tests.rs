@@ -0,0 +1,110 @@
+mod common;
+
+use axum::{routing::get, Router};
+use gateway::circuit_breaker::{RetryConfig, RetryExecutor};
+use gateway::proxy::ProxyState;
+use http::StatusCode;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Counts backend attempts that started and that ran to completion
+#[derive(Debug, Clone, Default)]
+struct Attempts {
+    started: Arc<AtomicUsize>,
+    completed: Arc<AtomicUsize>,
+}
+
+async fn serve(app: Router) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    addr
+}
+
+/// Backend that takes `delay` to answer 503, so every attempt is retried
+async fn slow_backend(attempts: Attempts, delay: Duration) -> SocketAddr {
+    let app = Router::new().route(
+        "/api/reports",
+        get(move || async move {
+            attempts.started.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(delay).await;
+            attempts.completed.fetch_add(1, Ordering::SeqCst);
+            StatusCode::SERVICE_UNAVAILABLE
+        }),
+    );
+    serve(app).await
+}
+
+async fn gateway(backend: SocketAddr) -> SocketAddr {
+    let router = common::router(&format!(
+        r#"
+server: {{}}
+routes:
+  - path: "/api/reports"
+    backend: "http://{backend}"
+"#
+    ));
+    let retry = RetryExecutor::new(RetryConfig {
+        max_retries: 3,
+        initial_backoff_ms: 10,
+        max_backoff_ms: 10,
+        retry_on_status: vec![503],
+        ..Default::default()
+    });
+    let proxy_state = ProxyState::new(router, Duration::from_secs(30), None, None, Some(retry));
+    serve(common::proxy_app(proxy_state)).await
+}
+
+async fn send_request(gateway: SocketAddr) -> TcpStream {
+    let mut stream = TcpStream::connect(gateway).await.unwrap();
+    stream
+        .write_all(b"GET /api/reports HTTP/1.1\r\nHost: gateway\r\n\r\n")
+        .await
+        .unwrap();
+    stream
+}
+
+async fn wait_for(counter: &AtomicUsize, count: usize) {
+    for _ in 0..100 {
+        if counter.load(Ordering::SeqCst) >= count {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("backend never reached {count} attempts");
+}
+
+#[tokio::test]
+async fn test_client_disconnect_aborts_backend_request_and_retries() {
+    let attempts = Attempts::default();
+    let backend = slow_backend(attempts.clone(), Duration::from_millis(500)).await;
+    let gateway = gateway(backend).await;
+
+    let stream = send_request(gateway).await;
+    wait_for(&attempts.started, 1).await;
+    drop(stream);
+
+    // Long enough for the attempt to finish and be retried had it continued
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    assert_eq!(attempts.started.load(Ordering::SeqCst), 1);
+    assert_eq!(attempts.completed.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_connected_client_gets_retried_response() {
+    let attempts = Attempts::default();
+    let backend = slow_backend(attempts.clone(), Duration::from_millis(10)).await;
+    let gateway = gateway(backend).await;
+
+    let mut stream = send_request(gateway).await;
+    let mut response = [0u8; 12];
+    stream.read_exact(&mut response).await.unwrap();
+
+    assert_eq!(&response, b"HTTP/1.1 503");
+    assert_eq!(attempts.started.load(Ordering::SeqCst), 4);
+    assert_eq!(attempts.completed.load(Ordering::SeqCst), 4);
+}
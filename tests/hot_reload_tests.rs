@@ -0,0 +1,138 @@
+use gateway::config::GatewayConfig;
+use gateway::init_gateway_with_reload;
+use std::path::PathBuf;
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::net::TcpStream;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+/// Gateway config with hot reload enabled and the given routes
+fn config_yaml(port: u16, routes: &[(&str, &str)]) -> String {
+    let mut yaml = format!(
+        r#"
+server:
+  host: "127.0.0.1"
+  port: {port}
+hot_reload:
+  enabled: true
+  debounce_ms: 100
+routes:
+"#
+    );
+    for (route_path, backend) in routes {
+        yaml.push_str(&format!(
+            "  - path: \"{route_path}\"\n    backend: \"{backend}\"\n    methods: [\"GET\"]\n"
+        ));
+    }
+    yaml
+}
+
+/// Start the gateway from `config_path` and wait until it accepts connections
+async fn start_gateway(config_path: PathBuf, port: u16) {
+    let config = GatewayConfig::from_file(&config_path).unwrap();
+    tokio::spawn(init_gateway_with_reload(config, config_path));
+
+    for _ in 0..50 {
+        if TcpStream::connect(("127.0.0.1", port)).await.is_ok() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("gateway did not start listening on port {}", port);
+}
+
+async fn backend() -> MockServer {
+    let backend = MockServer::start().await;
+    for route_path in ["/a", "/b"] {
+        Mock::given(method("GET"))
+            .and(path(route_path))
+            .respond_with(ResponseTemplate::new(200).set_body_string(route_path))
+            .mount(&backend)
+            .await;
+    }
+    backend
+}
+
+/// Poll `url` until it returns `expected`, giving the watcher time to pick up the change
+async fn wait_for_status(url: &str, expected: u16) -> bool {
+    for _ in 0..50 {
+        let response = reqwest::get(url).await.unwrap();
+        if response.status().as_u16() == expected {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    false
+}
+
+#[tokio::test]
+async fn test_reloaded_routes_are_served() {
+    let backend = backend().await;
+    let dir = TempDir::new().unwrap();
+    let config_path = dir.path().join("gateway.yaml");
+    let port = free_port();
+
+    std::fs::write(&config_path, config_yaml(port, &[("/a", &backend.uri())])).unwrap();
+    start_gateway(config_path.clone(), port).await;
+
+    let base = format!("http://127.0.0.1:{}", port);
+    assert_eq!(
+        reqwest::get(format!("{}/a", base)).await.unwrap().status(),
+        200
+    );
+    assert_eq!(
+        reqwest::get(format!("{}/b", base)).await.unwrap().status(),
+        404
+    );
+
+    std::fs::write(
+        &config_path,
+        config_yaml(port, &[("/a", &backend.uri()), ("/b", &backend.uri())]),
+    )
+    .unwrap();
+
+    assert!(wait_for_status(&format!("{}/b", base), 200).await);
+    let body = reqwest::get(format!("{}/b", base))
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert_eq!(body, "/b");
+}
+
+#[tokio::test]
+async fn test_invalid_reload_keeps_running_config() {
+    let backend = backend().await;
+    let dir = TempDir::new().unwrap();
+    let config_path = dir.path().join("gateway.yaml");
+    let port = free_port();
+
+    std::fs::write(&config_path, config_yaml(port, &[("/a", &backend.uri())])).unwrap();
+    start_gateway(config_path.clone(), port).await;
+
+    // A route without a scheme fails validation, so the reload is rejected
+    std::fs::write(&config_path, config_yaml(port, &[("/b", "not-a-url")])).unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let base = format!("http://127.0.0.1:{}", port);
+    assert_eq!(
+        reqwest::get(format!("{}/a", base)).await.unwrap().status(),
+        200
+    );
+    assert_eq!(
+        reqwest::get(format!("{}/b", base)).await.unwrap().status(),
+        404
+    );
+}
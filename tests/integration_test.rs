@@ -1,14 +1,21 @@
-use axum::Router;
+use axum::{extract::ConnectInfo, Router};
 use gateway::{
-    config::{GatewayConfig, RouteConfig, ServerConfig},
+    cache::CacheConfig,
+    circuit_breaker::{CircuitBreakerConfig, CircuitBreakerService, CircuitState, FallbackConfig},
+    config::{GatewayConfig, RouteConfig, ServerConfig, TrailingSlash},
+    cors::CorsConfig,
+    ipfilter::IpFilterService,
+    maintenance::MaintenanceConfig,
     proxy::ProxyState,
     router::Router as GatewayRouter,
+    transform::{BodyTransform, RequestTransform, ResponseTransform, TransformConfig},
 };
 use http::{Request, StatusCode};
+use std::net::SocketAddr;
 use std::time::Duration;
 use tower::ServiceExt;
 use wiremock::{
-    matchers::{method, path},
+    matchers::{body_json, header, header_exists, method, path},
     Mock, MockServer, ResponseTemplate,
 };
 
@@ -62,6 +69,22 @@ async fn setup_test_gateway() -> (ProxyState, MockServer) {
             description: "User service".to_string(),
             auth: None,
             rate_limit: None,
+            transform: None,
+            cors: None,
+            ip_filter: None,
+            cache: None,
+            honor_method_override: None,
+            circuit_breaker: None,
+            fallback: None,
+            mirror: None,
+            canary: None,
+            match_headers: Default::default(),
+            match_query: Default::default(),
+            max_response_bytes: None,
+            answer_options: None,
+            preserve_headers: Vec::new(),
+            enabled: true,
+            disabled_status: 503,
         },
         RouteConfig {
             path: "/api/users/:id".to_string(),
@@ -74,6 +97,22 @@ async fn setup_test_gateway() -> (ProxyState, MockServer) {
             description: "Get user by ID".to_string(),
             auth: None,
             rate_limit: None,
+            transform: None,
+            cors: None,
+            ip_filter: None,
+            cache: None,
+            honor_method_override: None,
+            circuit_breaker: None,
+            fallback: None,
+            mirror: None,
+            canary: None,
+            match_headers: Default::default(),
+            match_query: Default::default(),
+            max_response_bytes: None,
+            answer_options: None,
+            preserve_headers: Vec::new(),
+            enabled: true,
+            disabled_status: 503,
         },
         RouteConfig {
             path: "/health".to_string(),
@@ -86,6 +125,22 @@ async fn setup_test_gateway() -> (ProxyState, MockServer) {
             description: "Health check".to_string(),
             auth: None,
             rate_limit: None,
+            transform: None,
+            cors: None,
+            ip_filter: None,
+            cache: None,
+            honor_method_override: None,
+            circuit_breaker: None,
+            fallback: None,
+            mirror: None,
+            canary: None,
+            match_headers: Default::default(),
+            match_query: Default::default(),
+            max_response_bytes: None,
+            answer_options: None,
+            preserve_headers: Vec::new(),
+            enabled: true,
+            disabled_status: 503,
         },
     ];
 
@@ -227,6 +282,30 @@ async fn test_method_not_allowed() {
     assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
 }
 
+#[tokio::test]
+async fn test_trace_rejected_on_all_methods_route() {
+    let (proxy_state, mock_server) = setup_test_gateway().await;
+
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    // /health allows all methods, but TRACE is never proxied implicitly
+    let request = |method: &str| {
+        Request::builder()
+            .uri("/health")
+            .method(method)
+            .body(axum::body::Body::empty())
+            .unwrap()
+    };
+    let response = app.clone().oneshot(request("TRACE")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert!(mock_server.received_requests().await.unwrap().is_empty());
+
+    let response = app.oneshot(request("GET")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
 #[tokio::test]
 async fn test_health_check() {
     let (proxy_state, _mock_server) = setup_test_gateway().await;
@@ -255,6 +334,147 @@ async fn test_health_check() {
     assert_eq!(body_str, "OK");
 }
 
+#[tokio::test]
+async fn test_request_deadline_exceeded_short_circuits() {
+    let (proxy_state, _mock_server) = setup_test_gateway().await;
+
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state.with_request_deadline(true));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/users")
+                .method("GET")
+                .header("X-Request-Deadline", "0")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+}
+
+#[tokio::test]
+async fn test_request_deadline_clamps_timeout() {
+    let (proxy_state, mock_server) = setup_test_gateway().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/users/slow"))
+        .and(header_exists("x-request-deadline"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(500)))
+        .mount(&mock_server)
+        .await;
+
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state.with_request_deadline(true));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/users/slow")
+                .method("GET")
+                .header("X-Request-Deadline", "100")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // The 30s gateway timeout is clamped to the 100ms remaining deadline
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+}
+
+#[tokio::test]
+async fn test_response_timing_headers() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/timed"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(50)))
+        .mount(&mock_server)
+        .await;
+
+    let routes = vec![RouteConfig {
+        path: "/api/timed".to_string(),
+        backend: Some(mock_server.uri()),
+        backends: vec![],
+        load_balancer: None,
+        health_check: None,
+        methods: vec![],
+        strip_prefix: false,
+        description: "Timed route".to_string(),
+        auth: None,
+        rate_limit: None,
+        transform: Some(TransformConfig {
+            response: Some(ResponseTransform {
+                response_time_header: true,
+                server_timing: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        cors: None,
+        ip_filter: None,
+        cache: None,
+        honor_method_override: None,
+        circuit_breaker: None,
+        fallback: None,
+        mirror: None,
+        canary: None,
+        match_headers: Default::default(),
+        match_query: Default::default(),
+        max_response_bytes: None,
+        answer_options: None,
+        preserve_headers: Vec::new(),
+        enabled: true,
+        disabled_status: 503,
+    }];
+
+    let router = GatewayRouter::new(routes).unwrap();
+    let proxy_state = ProxyState::new(router, Duration::from_secs(30), None, None, None);
+
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/timed")
+                .method("GET")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response_time: f64 = response
+        .headers()
+        .get("x-response-time-ms")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(response_time >= 50.0);
+    assert!(response_time < 5000.0);
+
+    let server_timing = response
+        .headers()
+        .get("server-timing")
+        .unwrap()
+        .to_str()
+        .unwrap();
+    assert!(server_timing.contains("gateway;dur="));
+    assert!(server_timing.contains("backend;dur="));
+}
+
 #[test]
 fn test_config_validation() {
     let config = GatewayConfig {
@@ -270,12 +490,38 @@ fn test_config_validation() {
             description: "Test route".to_string(),
             auth: None,
             rate_limit: None,
+            transform: None,
+            cors: None,
+            ip_filter: None,
+            cache: None,
+            honor_method_override: None,
+            circuit_breaker: None,
+            fallback: None,
+            mirror: None,
+            canary: None,
+            match_headers: Default::default(),
+            match_query: Default::default(),
+            max_response_bytes: None,
+            answer_options: None,
+            preserve_headers: Vec::new(),
+            enabled: true,
+            disabled_status: 503,
         }],
         auth: None,
         rate_limiting: None,
         circuit_breaker: None,
         retry: None,
         observability: None,
+        hot_reload: None,
+        cors: None,
+        ip_filter: None,
+        cache: None,
+        max_request_size: None,
+        tls: None,
+        backend_tls: None,
+        dns: None,
+        admin: None,
+        maintenance: None,
     };
 
     assert!(config.validate().is_ok());
@@ -296,13 +542,1997 @@ fn test_config_invalid_backend() {
             description: "Test route".to_string(),
             auth: None,
             rate_limit: None,
+            transform: None,
+            cors: None,
+            ip_filter: None,
+            cache: None,
+            honor_method_override: None,
+            circuit_breaker: None,
+            fallback: None,
+            mirror: None,
+            canary: None,
+            match_headers: Default::default(),
+            match_query: Default::default(),
+            max_response_bytes: None,
+            answer_options: None,
+            preserve_headers: Vec::new(),
+            enabled: true,
+            disabled_status: 503,
         }],
         auth: None,
         rate_limiting: None,
         circuit_breaker: None,
         retry: None,
         observability: None,
+        hot_reload: None,
+        cors: None,
+        ip_filter: None,
+        cache: None,
+        max_request_size: None,
+        tls: None,
+        backend_tls: None,
+        dns: None,
+        admin: None,
+        maintenance: None,
     };
 
     assert!(config.validate().is_err());
 }
+
+/// Create a gateway with a DELETE-only route at /api/items/:id
+async fn setup_method_override_gateway(
+    honor_method_override: Option<bool>,
+) -> (ProxyState, MockServer) {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/items/42"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&mock_server)
+        .await;
+
+    let routes = vec![RouteConfig {
+        path: "/api/items/:id".to_string(),
+        backend: Some(mock_server.uri()),
+        backends: vec![],
+        load_balancer: None,
+        health_check: None,
+        methods: vec!["DELETE".to_string()],
+        strip_prefix: false,
+        description: "Items".to_string(),
+        auth: None,
+        rate_limit: None,
+        transform: None,
+        cors: None,
+        ip_filter: None,
+        cache: None,
+        honor_method_override,
+        circuit_breaker: None,
+        fallback: None,
+        mirror: None,
+        canary: None,
+        match_headers: Default::default(),
+        match_query: Default::default(),
+        max_response_bytes: None,
+        answer_options: None,
+        preserve_headers: Vec::new(),
+        enabled: true,
+        disabled_status: 503,
+    }];
+
+    let router = GatewayRouter::new(routes).unwrap();
+    let proxy_state = ProxyState::new(router, Duration::from_secs(30), None, None, None);
+    (proxy_state, mock_server)
+}
+
+fn method_override_request() -> Request<axum::body::Body> {
+    Request::builder()
+        .uri("/api/items/42")
+        .method("POST")
+        .header("X-HTTP-Method-Override", "DELETE")
+        .body(axum::body::Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_method_override_forwards_as_delete() {
+    let (proxy_state, _mock_server) = setup_method_override_gateway(Some(true)).await;
+
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    let response = app.oneshot(method_override_request()).await.unwrap();
+
+    // Routed past the DELETE-only check and forwarded as DELETE
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn test_method_override_global_setting() {
+    let (proxy_state, mock_server) = setup_method_override_gateway(None).await;
+
+    // The override header itself is not forwarded
+    Mock::given(method("DELETE"))
+        .and(header("x-http-method-override", "DELETE"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state.with_method_override(true));
+
+    let response = app.oneshot(method_override_request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn test_method_override_ignored_when_disabled() {
+    let (proxy_state, _mock_server) = setup_method_override_gateway(Some(false)).await;
+
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state.with_method_override(true));
+
+    let response = app.oneshot(method_override_request()).await.unwrap();
+
+    // The route disables overrides, so the POST is rejected by the method check
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+fn circuit_breaker_route(
+    route_path: &str,
+    backend: &str,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+) -> RouteConfig {
+    RouteConfig {
+        path: route_path.to_string(),
+        backend: Some(backend.to_string()),
+        backends: vec![],
+        load_balancer: None,
+        health_check: None,
+        methods: vec![],
+        strip_prefix: false,
+        description: String::new(),
+        auth: None,
+        rate_limit: None,
+        transform: None,
+        cors: None,
+        ip_filter: None,
+        cache: None,
+        honor_method_override: None,
+        circuit_breaker,
+        fallback: None,
+        mirror: None,
+        canary: None,
+        match_headers: Default::default(),
+        match_query: Default::default(),
+        max_response_bytes: None,
+        answer_options: None,
+        preserve_headers: Vec::new(),
+        enabled: true,
+        disabled_status: 503,
+    }
+}
+
+async fn get_status(app: &Router, uri: &str) -> StatusCode {
+    let request = Request::builder()
+        .uri(uri)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_per_route_circuit_breakers_trip_independently() {
+    let mock_server = MockServer::start().await;
+
+    // Both routes share one failing backend
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&mock_server)
+        .await;
+
+    let routes = vec![
+        circuit_breaker_route(
+            "/flaky",
+            &mock_server.uri(),
+            Some(CircuitBreakerConfig {
+                failure_threshold: 1,
+                ..Default::default()
+            }),
+        ),
+        circuit_breaker_route(
+            "/internal",
+            &mock_server.uri(),
+            Some(CircuitBreakerConfig {
+                enabled: false,
+                ..Default::default()
+            }),
+        ),
+        circuit_breaker_route("/default", &mock_server.uri(), None),
+    ];
+
+    let global = CircuitBreakerService::new(CircuitBreakerConfig {
+        failure_threshold: 3,
+        ..Default::default()
+    });
+    let router = GatewayRouter::new(routes).unwrap();
+    let proxy_state = ProxyState::new(router, Duration::from_secs(30), None, Some(global), None);
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    // The aggressive route opens after a single failure
+    assert_eq!(
+        get_status(&app, "/flaky").await,
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+    assert_eq!(
+        get_status(&app, "/flaky").await,
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+
+    // The internal route never trips
+    for _ in 0..5 {
+        assert_eq!(
+            get_status(&app, "/internal").await,
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
+    // Routes without an override use the global thresholds
+    for _ in 0..3 {
+        assert_eq!(
+            get_status(&app, "/default").await,
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+    assert_eq!(
+        get_status(&app, "/default").await,
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+}
+
+fn fallback_gateway(route: RouteConfig) -> Router {
+    let router = GatewayRouter::new(vec![route]).unwrap();
+    let proxy_state = ProxyState::new(router, Duration::from_secs(30), None, None, None);
+    Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state)
+}
+
+fn trip_on_first_failure() -> Option<CircuitBreakerConfig> {
+    Some(CircuitBreakerConfig {
+        failure_threshold: 1,
+        ..Default::default()
+    })
+}
+
+async fn get_response(app: &Router, uri: &str) -> (StatusCode, http::HeaderMap, String) {
+    let request = Request::builder()
+        .uri(uri)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, headers, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_circuit_open_serves_static_fallback() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&mock_server)
+        .await;
+
+    let mut route = circuit_breaker_route("/status", &mock_server.uri(), trip_on_first_failure());
+    route.fallback = Some(FallbackConfig {
+        serve_stale: false,
+        status: 200,
+        body: Some(r#"{"status":"maintenance"}"#.to_string()),
+        content_type: "application/json".to_string(),
+    });
+    let app = fallback_gateway(route);
+
+    assert_eq!(
+        get_status(&app, "/status").await,
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+
+    let (status, headers, body) = get_response(&app, "/status").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["content-type"], "application/json");
+    assert_eq!(body, r#"{"status":"maintenance"}"#);
+}
+
+#[tokio::test]
+async fn test_circuit_open_serves_stale_cache() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("last known good"))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&mock_server)
+        .await;
+
+    let mut route = circuit_breaker_route("/catalog", &mock_server.uri(), trip_on_first_failure());
+    // Entries expire immediately but stay available as stale fallbacks
+    route.cache = Some(CacheConfig {
+        enabled: true,
+        ttl_secs: 0,
+        stale_ttl_secs: 60,
+        ..Default::default()
+    });
+    route.fallback = Some(FallbackConfig {
+        serve_stale: true,
+        status: 503,
+        body: None,
+        content_type: "application/json".to_string(),
+    });
+    let app = fallback_gateway(route);
+
+    assert_eq!(get_status(&app, "/catalog").await, StatusCode::OK);
+    // Served stale while the background refresh fails and trips the circuit
+    assert_eq!(get_status(&app, "/catalog").await, StatusCode::OK);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Bypassing the cache reaches the open circuit, which falls back to the
+    // stale entry
+    let request = Request::builder()
+        .uri("/catalog")
+        .header("Cache-Control", "no-cache")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["x-cache"], "STALE");
+    assert_eq!(body, "last known good");
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+
+    // Without a stale entry or static body, the plain 503 is returned
+    assert_eq!(
+        get_status(&app, "/catalog?page=2").await,
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+}
+
+#[tokio::test]
+async fn test_stale_while_revalidate_refreshes_in_background() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("v1"))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string("v2")
+                .set_delay(Duration::from_millis(200)),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let mut route = circuit_breaker_route("/catalog", &mock_server.uri(), None);
+    // Entries expire immediately and are served stale while refreshing
+    route.cache = Some(CacheConfig {
+        enabled: true,
+        ttl_secs: 0,
+        stale_ttl_secs: 60,
+        ..Default::default()
+    });
+    let app = fallback_gateway(route);
+
+    let (_, headers, body) = get_response(&app, "/catalog").await;
+    assert!(headers.get("x-cache").is_none());
+    assert_eq!(body, "v1");
+
+    // Served stale without waiting on the slow backend, with a single refresh
+    for _ in 0..5 {
+        let (status, headers, body) = get_response(&app, "/catalog").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers["x-cache"], "STALE");
+        assert_eq!(body, "v1");
+    }
+
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+
+    let (_, headers, body) = get_response(&app, "/catalog").await;
+    assert_eq!(headers["x-cache"], "STALE");
+    assert_eq!(body, "v2");
+}
+
+async fn get_with_header(
+    app: &Router,
+    uri: &str,
+    name: &str,
+    value: &str,
+) -> (StatusCode, http::HeaderMap, String) {
+    let request = Request::builder()
+        .uri(uri)
+        .header(name, value)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, headers, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_cached_etag_revalidation() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "\"catalog-v1\"")
+                .set_body_string(r#"{"items": []}"#),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut route = circuit_breaker_route("/catalog", &mock_server.uri(), None);
+    route.cache = Some(CacheConfig {
+        enabled: true,
+        ..Default::default()
+    });
+    let app = fallback_gateway(route);
+
+    let (status, _, body) = get_response(&app, "/catalog").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, r#"{"items": []}"#);
+
+    // Matching ETag: 304 straight from the cache, without a body
+    let (status, headers, body) =
+        get_with_header(&app, "/catalog", "if-none-match", "\"catalog-v1\"").await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert_eq!(headers["etag"], "\"catalog-v1\"");
+    assert_eq!(headers["x-cache"], "HIT");
+    assert!(body.is_empty());
+
+    // Mismatched ETag: the full cached payload
+    let (status, headers, body) =
+        get_with_header(&app, "/catalog", "if-none-match", "\"catalog-v0\"").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["x-cache"], "HIT");
+    assert_eq!(body, r#"{"items": []}"#);
+}
+
+#[tokio::test]
+async fn test_request_body_transform_injects_tenant() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_json(
+            serde_json::json!({"name": "widget", "tenant_id": "acme"}),
+        ))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut route = circuit_breaker_route("/orders", &mock_server.uri(), None);
+    route.transform = Some(TransformConfig {
+        request: Some(RequestTransform {
+            body: Some(BodyTransform {
+                set: [("/tenant_id".to_string(), serde_json::json!("acme"))].into(),
+                remove: vec!["/debug".to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+    let app = fallback_gateway(route);
+
+    let body = r#"{"name": "widget", "debug": true}"#;
+    let request = Request::builder()
+        .method("POST")
+        .uri("/orders")
+        .header("content-type", "application/json")
+        .header("content-length", body.len())
+        .body(axum::body::Body::from(body))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn test_request_header_templates() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(header("x-user-id", "42"))
+        .and(header("x-original", "GET /users/42"))
+        .and(header("x-real-ip", "127.0.0.1"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut route = circuit_breaker_route("/users/:id", &mock_server.uri(), None);
+    route.transform = Some(TransformConfig {
+        request: Some(RequestTransform {
+            set_headers: [
+                ("X-User-Id".to_string(), "{param.id}".to_string()),
+                ("X-Original".to_string(), "{method} {path}".to_string()),
+                ("X-Real-IP".to_string(), "{client_ip}".to_string()),
+            ]
+            .into(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+    let app = fallback_gateway(route);
+
+    assert_eq!(get_status(&app, "/users/42").await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_cache_honors_vary_header() {
+    let mock_server = MockServer::start().await;
+    for (language, greeting) in [("en", "hello"), ("fr", "bonjour")] {
+        Mock::given(method("GET"))
+            .and(header("accept-language", language))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("vary", "Accept-Language")
+                    .set_body_string(greeting),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    }
+
+    let mut route = circuit_breaker_route("/greeting", &mock_server.uri(), None);
+    route.cache = Some(CacheConfig {
+        enabled: true,
+        ..Default::default()
+    });
+    let app = fallback_gateway(route);
+
+    let get_greeting = |language: &'static str| {
+        let app = app.clone();
+        async move {
+            let request = Request::builder()
+                .uri("/greeting")
+                .header("accept-language", language)
+                .body(axum::body::Body::empty())
+                .unwrap();
+            let response = app.oneshot(request).await.unwrap();
+            let cache = response
+                .headers()
+                .get("x-cache")
+                .map(|value| value.to_str().unwrap().to_string());
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            (cache, String::from_utf8(body.to_vec()).unwrap())
+        }
+    };
+
+    assert_eq!(get_greeting("en").await, (None, "hello".to_string()));
+    assert_eq!(get_greeting("fr").await, (None, "bonjour".to_string()));
+    assert_eq!(
+        get_greeting("en").await,
+        (Some("HIT".to_string()), "hello".to_string())
+    );
+    assert_eq!(
+        get_greeting("fr").await,
+        (Some("HIT".to_string()), "bonjour".to_string())
+    );
+}
+
+/// GET `uri` with the given `Cache-Control`, returning its `X-Cache` header and body
+async fn get_with_cache_control(
+    app: &Router,
+    uri: &str,
+    cache_control: Option<&str>,
+) -> (Option<String>, String) {
+    let mut request = Request::builder().uri(uri);
+    if let Some(cache_control) = cache_control {
+        request = request.header("cache-control", cache_control);
+    }
+    let response = app
+        .clone()
+        .oneshot(request.body(axum::body::Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let cache = response
+        .headers()
+        .get("x-cache")
+        .map(|value| value.to_str().unwrap().to_string());
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (cache, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_request_no_cache_bypasses_cached_response() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/prices"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("v1"))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/prices"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("v2"))
+        .mount(&mock_server)
+        .await;
+
+    let mut route = circuit_breaker_route("/prices", &mock_server.uri(), None);
+    route.cache = Some(CacheConfig {
+        enabled: true,
+        ..Default::default()
+    });
+    let app = fallback_gateway(route);
+
+    assert_eq!(
+        get_with_cache_control(&app, "/prices", None).await,
+        (None, "v1".to_string())
+    );
+    assert_eq!(
+        get_with_cache_control(&app, "/prices", None).await,
+        (Some("HIT".to_string()), "v1".to_string())
+    );
+    // The client asked for a fresh response, which then replaces the entry
+    assert_eq!(
+        get_with_cache_control(&app, "/prices", Some("no-cache")).await,
+        (None, "v2".to_string())
+    );
+    assert_eq!(
+        get_with_cache_control(&app, "/prices", None).await,
+        (Some("HIT".to_string()), "v2".to_string())
+    );
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_request_no_store_skips_caching() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/statement"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("statement"))
+        .mount(&mock_server)
+        .await;
+
+    let mut route = circuit_breaker_route("/statement", &mock_server.uri(), None);
+    route.cache = Some(CacheConfig {
+        enabled: true,
+        ..Default::default()
+    });
+    let app = fallback_gateway(route);
+
+    for _ in 0..2 {
+        assert_eq!(
+            get_with_cache_control(&app, "/statement", Some("no-store")).await,
+            (None, "statement".to_string())
+        );
+    }
+    // Nothing was stored, so a regular request still misses
+    assert_eq!(
+        get_with_cache_control(&app, "/statement", None).await,
+        (None, "statement".to_string())
+    );
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 3);
+}
+
+/// Send `count` identical concurrent GETs, returning their statuses and bodies
+async fn concurrent_gets(app: &Router, uri: &str, count: usize) -> Vec<(StatusCode, String)> {
+    let requests = (0..count).map(|_| {
+        let request = Request::builder()
+            .uri(uri)
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let app = app.clone();
+        tokio::spawn(async move {
+            let response = app.oneshot(request).await.unwrap();
+            let status = response.status();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            (status, String::from_utf8(body.to_vec()).unwrap())
+        })
+    });
+    futures::future::join_all(requests)
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_concurrent_cache_misses_share_one_backend_call() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/catalog"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string("catalog")
+                .set_delay(Duration::from_millis(200)),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut route = circuit_breaker_route("/catalog", &mock_server.uri(), None);
+    route.cache = Some(CacheConfig {
+        enabled: true,
+        ..Default::default()
+    });
+    let app = fallback_gateway(route);
+
+    let responses = concurrent_gets(&app, "/catalog", 50).await;
+    assert!(responses
+        .iter()
+        .all(|response| *response == (StatusCode::OK, "catalog".to_string())));
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_uncacheable_response_releases_waiting_requests() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/live"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("cache-control", "no-store")
+                .set_body_string("live")
+                .set_delay(Duration::from_millis(100)),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let mut route = circuit_breaker_route("/live", &mock_server.uri(), None);
+    route.cache = Some(CacheConfig {
+        enabled: true,
+        ..Default::default()
+    });
+    let app = fallback_gateway(route);
+
+    // Nothing is cached, so the waiting requests go to the backend themselves
+    let responses = concurrent_gets(&app, "/live", 5).await;
+    assert!(responses
+        .iter()
+        .all(|response| *response == (StatusCode::OK, "live".to_string())));
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 5);
+}
+
+#[tokio::test]
+async fn test_excluded_status_does_not_count_as_failure() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(501))
+        .mount(&mock_server)
+        .await;
+
+    let circuit_breaker = CircuitBreakerService::new(CircuitBreakerConfig {
+        failure_threshold: 2,
+        failure_status_codes: Some(vec![500, 502, 503, 504]),
+        ..Default::default()
+    });
+    let router = GatewayRouter::new(vec![circuit_breaker_route(
+        "/optional",
+        &mock_server.uri(),
+        None,
+    )])
+    .unwrap();
+    let proxy_state = ProxyState::new(
+        router,
+        Duration::from_secs(30),
+        None,
+        Some(circuit_breaker.clone()),
+        None,
+    );
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    for _ in 0..5 {
+        assert_eq!(
+            get_status(&app, "/optional").await,
+            StatusCode::NOT_IMPLEMENTED
+        );
+    }
+
+    let backend = mock_server.uri();
+    assert_eq!(circuit_breaker.state(&backend).await, CircuitState::Closed);
+    let metrics = circuit_breaker.metrics(&backend).await.unwrap();
+    assert_eq!(metrics.failed_requests, 0);
+    assert_eq!(metrics.successful_requests, 5);
+}
+
+fn cors_gateway(backend: &str, cors: CorsConfig) -> Router {
+    let route = RouteConfig {
+        cors: Some(cors),
+        methods: vec!["GET".to_string()],
+        ..circuit_breaker_route("/api/data", backend, None)
+    };
+    fallback_gateway(route)
+}
+
+#[tokio::test]
+async fn test_cors_preflight_answered_by_gateway() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("OPTIONS"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let cors = CorsConfig {
+        allowed_origins: vec!["https://app.example.com".to_string()],
+        allowed_methods: vec!["GET".to_string(), "POST".to_string()],
+        allowed_headers: vec!["Content-Type".to_string(), "Authorization".to_string()],
+        allow_credentials: true,
+        max_age_secs: 600,
+        ..Default::default()
+    };
+    let app = cors_gateway(&mock_server.uri(), cors);
+
+    let request = Request::builder()
+        .method("OPTIONS")
+        .uri("/api/data")
+        .header("Origin", "https://app.example.com")
+        .header("Access-Control-Request-Method", "POST")
+        .header("Access-Control-Request-Headers", "content-type")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let headers = response.headers();
+    assert_eq!(
+        headers.get("access-control-allow-origin").unwrap(),
+        "https://app.example.com"
+    );
+    assert_eq!(
+        headers.get("access-control-allow-methods").unwrap(),
+        "GET, POST"
+    );
+    assert_eq!(
+        headers.get("access-control-allow-headers").unwrap(),
+        "Content-Type, Authorization"
+    );
+    assert_eq!(
+        headers.get("access-control-allow-credentials").unwrap(),
+        "true"
+    );
+    assert_eq!(headers.get("access-control-max-age").unwrap(), "600");
+
+    // A preflight from an unknown origin gets no CORS headers
+    let request = Request::builder()
+        .method("OPTIONS")
+        .uri("/api/data")
+        .header("Origin", "https://evil.example.org")
+        .header("Access-Control-Request-Method", "POST")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(response
+        .headers()
+        .get("access-control-allow-origin")
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_cors_headers_added_to_simple_request() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/data"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("data"))
+        .mount(&mock_server)
+        .await;
+
+    let cors = CorsConfig {
+        allowed_origins: vec!["https://app.example.com".to_string()],
+        exposed_headers: vec!["X-Request-ID".to_string()],
+        ..Default::default()
+    };
+    let app = cors_gateway(&mock_server.uri(), cors);
+
+    let request = Request::builder()
+        .uri("/api/data")
+        .header("Origin", "https://app.example.com")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers();
+    assert_eq!(
+        headers.get("access-control-allow-origin").unwrap(),
+        "https://app.example.com"
+    );
+    assert_eq!(
+        headers.get("access-control-expose-headers").unwrap(),
+        "X-Request-ID"
+    );
+    assert_eq!(headers.get("vary").unwrap(), "Origin");
+
+    // Error responses carry CORS headers too so the browser can read them
+    let request = Request::builder()
+        .method("DELETE")
+        .uri("/api/data")
+        .header("Origin", "https://app.example.com")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(
+        response
+            .headers()
+            .get("access-control-allow-origin")
+            .unwrap(),
+        "https://app.example.com"
+    );
+}
+
+#[tokio::test]
+async fn test_cors_origin_pattern_reflects_subdomain_with_credentials() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/data"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let cors = CorsConfig {
+        allowed_origins: vec![],
+        allowed_origin_patterns: vec![r"https://[a-z0-9-]+\.example\.com".to_string()],
+        allow_credentials: true,
+        ..Default::default()
+    };
+    let app = cors_gateway(&mock_server.uri(), cors);
+
+    for (origin, allowed) in [
+        ("https://tenant-a.example.com", true),
+        ("https://example.com.attacker.net", false),
+    ] {
+        let request = Request::builder()
+            .uri("/api/data")
+            .header("Origin", origin)
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+
+        let allow_origin = response.headers().get("access-control-allow-origin");
+        if allowed {
+            assert_eq!(allow_origin.unwrap(), origin);
+            assert_eq!(
+                response
+                    .headers()
+                    .get("access-control-allow-credentials")
+                    .unwrap(),
+                "true"
+            );
+        } else {
+            assert!(allow_origin.is_none(), "{} should not be allowed", origin);
+        }
+    }
+}
+
+/// Gateway with a global blacklist, one open route and one whitelisted route
+async fn setup_global_ip_filter_gateway() -> (Router, MockServer) {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let config = GatewayConfig::from_yaml(&format!(
+        r#"
+server: {{}}
+ip_filter:
+  blacklist:
+    - "203.0.113.0/24"
+routes:
+  - path: "/public"
+    backend: "{backend}"
+  - path: "/admin"
+    backend: "{backend}"
+    ip_filter:
+      whitelist:
+        - "10.0.0.0/8"
+        - "203.0.113.5"
+      default_action: deny
+"#,
+        backend = mock_server.uri()
+    ))
+    .unwrap();
+
+    let global_filter = IpFilterService::new(config.ip_filter.clone().unwrap()).unwrap();
+    let router = GatewayRouter::new(config.routes).unwrap();
+    let proxy_state = ProxyState::new(router, Duration::from_secs(30), None, None, None)
+        .with_ip_filter(global_filter);
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+    (app, mock_server)
+}
+
+async fn get_status_from(app: &Router, uri: &str, peer: [u8; 4]) -> StatusCode {
+    let mut request = Request::builder()
+        .uri(uri)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    request
+        .extensions_mut()
+        .insert(ConnectInfo(SocketAddr::from((peer, 40000))));
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_global_ip_filter_blocks_every_route() {
+    let (app, _mock_server) = setup_global_ip_filter_gateway().await;
+
+    assert_eq!(
+        get_status_from(&app, "/public", [203, 0, 113, 9]).await,
+        StatusCode::FORBIDDEN
+    );
+    // The global blacklist wins over a route whitelist entry
+    assert_eq!(
+        get_status_from(&app, "/admin", [203, 0, 113, 5]).await,
+        StatusCode::FORBIDDEN
+    );
+    // Blocked even where no route matches
+    assert_eq!(
+        get_status_from(&app, "/unknown", [203, 0, 113, 9]).await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        get_status_from(&app, "/public", [198, 51, 100, 1]).await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn test_route_ip_filter_applies_after_global_filter() {
+    let (app, _mock_server) = setup_global_ip_filter_gateway().await;
+
+    assert_eq!(
+        get_status_from(&app, "/admin", [10, 1, 2, 3]).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        get_status_from(&app, "/admin", [198, 51, 100, 1]).await,
+        StatusCode::FORBIDDEN
+    );
+}
+
+#[tokio::test]
+async fn test_header_routing_selects_version_backend() {
+    let v1 = MockServer::start().await;
+    let v2 = MockServer::start().await;
+    for (server, name) in [(&v1, "v1"), (&v2, "v2")] {
+        Mock::given(method("GET"))
+            .and(path("/api/users"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(name))
+            .mount(server)
+            .await;
+    }
+
+    let config = GatewayConfig::from_yaml(&format!(
+        r#"
+server: {{}}
+routes:
+  - path: "/api/users"
+    backend: "{}"
+  - path: "/api/users"
+    backend: "{}"
+    match_headers:
+      X-API-Version: 2
+"#,
+        v1.uri(),
+        v2.uri()
+    ))
+    .unwrap();
+    config.validate().unwrap();
+    let router = GatewayRouter::new(config.routes).unwrap();
+    let proxy_state = ProxyState::new(router, Duration::from_secs(30), None, None, None);
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    for (version, expected) in [(Some("2"), "v2"), (Some("1"), "v1"), (None, "v1")] {
+        let mut request = Request::builder().uri("/api/users");
+        if let Some(version) = version {
+            request = request.header("X-API-Version", version);
+        }
+        let response = app
+            .clone()
+            .oneshot(request.body(axum::body::Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, expected, "X-API-Version: {:?}", version);
+    }
+}
+
+#[tokio::test]
+async fn test_query_routing_selects_beta_backend() {
+    let stable = MockServer::start().await;
+    let beta = MockServer::start().await;
+    for (server, name) in [(&stable, "stable"), (&beta, "beta")] {
+        Mock::given(method("GET"))
+            .and(path("/api/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(name))
+            .mount(server)
+            .await;
+    }
+
+    let config = GatewayConfig::from_yaml(&format!(
+        r#"
+server: {{}}
+routes:
+  - path: "/api/search"
+    backend: "{}"
+  - path: "/api/search"
+    backend: "{}"
+    match_query:
+      beta: "true"
+"#,
+        stable.uri(),
+        beta.uri()
+    ))
+    .unwrap();
+    let router = GatewayRouter::new(config.routes).unwrap();
+    let proxy_state = ProxyState::new(router, Duration::from_secs(30), None, None, None);
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    for (uri, expected) in [
+        ("/api/search?q=gateway&beta=true", "beta"),
+        ("/api/search?q=gateway", "stable"),
+    ] {
+        let request = Request::builder()
+            .uri(uri)
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, expected, "{}", uri);
+    }
+    // The query string is still forwarded to the selected backend
+    let requests = beta.received_requests().await.unwrap();
+    assert_eq!(requests[0].url.query(), Some("q=gateway&beta=true"));
+}
+
+#[tokio::test]
+async fn test_trailing_slash_redirect() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/users"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("users"))
+        .mount(&mock_server)
+        .await;
+
+    let router = GatewayRouter::new(vec![circuit_breaker_route(
+        "/api/users",
+        &mock_server.uri(),
+        None,
+    )])
+    .unwrap()
+    .with_trailing_slash(TrailingSlash::Redirect);
+    let proxy_state = ProxyState::new(router, Duration::from_secs(30), None, None, None);
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    let (status, headers, _) = get_response(&app, "/api/users/?page=2").await;
+    assert_eq!(status, StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(headers["location"], "/api/users?page=2");
+
+    // Following the redirect reaches the backend
+    let (status, _, body) = get_response(&app, "/api/users?page=2").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "users");
+}
+
+#[tokio::test]
+async fn test_backend_url_template_substitutes_params() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/avatars"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("avatar"))
+        .mount(&mock_server)
+        .await;
+
+    let router = GatewayRouter::new(vec![circuit_breaker_route(
+        "/users/:id/avatar",
+        &format!("{}/avatars?user={{id}}", mock_server.uri()),
+        None,
+    )])
+    .unwrap();
+    let proxy_state = ProxyState::new(router, Duration::from_secs(30), None, None, None);
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    let (status, _, body) = get_response(&app, "/users/42/avatar?size=64").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "avatar");
+
+    // The request's own query string follows the template's
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests[0].url.query(), Some("user=42&size=64"));
+}
+
+#[tokio::test]
+async fn test_maintenance_mode_short_circuits_routes() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("live"))
+        .mount(&mock_server)
+        .await;
+
+    let router = GatewayRouter::new(vec![
+        circuit_breaker_route("/api/users", &mock_server.uri(), None),
+        circuit_breaker_route("/api/status", &mock_server.uri(), None),
+    ])
+    .unwrap();
+    let proxy_state = ProxyState::new(router, Duration::from_secs(30), None, None, None)
+        .with_maintenance(MaintenanceConfig {
+            enabled: true,
+            body: "Back soon".to_string(),
+            content_type: "text/plain".to_string(),
+            retry_after_secs: Some(300),
+            allow_paths: vec!["/api/status".to_string()],
+            ..Default::default()
+        });
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .route(
+            "/healthz",
+            axum::routing::get(gateway::admin::aggregate_health_handler),
+        )
+        .with_state(proxy_state.clone());
+
+    let (status, headers, body) = get_response(&app, "/api/users").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(headers["content-type"], "text/plain");
+    assert_eq!(headers["retry-after"], "300");
+    assert_eq!(body, "Back soon");
+
+    // Allowlisted paths and the health endpoint stay up
+    let (status, _, body) = get_response(&app, "/api/status").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "live");
+    assert_eq!(get_status(&app, "/healthz").await, StatusCode::OK);
+
+    // Only the allowlisted request reached the backend
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 1);
+
+    let mut maintenance = proxy_state.maintenance.load().as_ref().clone();
+    maintenance.enabled = false;
+    proxy_state.set_maintenance(maintenance);
+    let (status, _, body) = get_response(&app, "/api/users").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "live");
+}
+
+#[tokio::test]
+async fn test_oversized_response_rejected_and_not_cached() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/export"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("x".repeat(64 * 1024)))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/summary"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("small"))
+        .mount(&mock_server)
+        .await;
+
+    let mut route = circuit_breaker_route("/*path", &mock_server.uri(), None);
+    route.max_response_bytes = Some(16 * 1024);
+    route.cache = Some(CacheConfig {
+        enabled: true,
+        ..Default::default()
+    });
+    let app = fallback_gateway(route);
+
+    // Rejected both times: the oversized response was never cached
+    for _ in 0..2 {
+        let (status, _, _) = get_response(&app, "/export").await;
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+    }
+
+    let (status, _, body) = get_response(&app, "/summary").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "small");
+}
+
+#[tokio::test]
+async fn test_oversized_responses_do_not_open_circuit() {
+    use gateway::circuit_breaker::{RetryConfig, RetryExecutor};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/export"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("x".repeat(64 * 1024)))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/summary"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("small"))
+        .mount(&mock_server)
+        .await;
+
+    // With and without retries, which handle backend errors separately
+    for retry_executor in [None, Some(RetryExecutor::new(RetryConfig::default()))] {
+        let mut route =
+            circuit_breaker_route("/*path", &mock_server.uri(), trip_on_first_failure());
+        route.max_response_bytes = Some(16 * 1024);
+        let router = GatewayRouter::new(vec![route]).unwrap();
+        let proxy_state =
+            ProxyState::new(router, Duration::from_secs(30), None, None, retry_executor);
+        let app = Router::new()
+            .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+            .with_state(proxy_state);
+
+        for _ in 0..3 {
+            let (status, _, _) = get_response(&app, "/export").await;
+            assert_eq!(status, StatusCode::BAD_GATEWAY);
+        }
+
+        let (status, _, body) = get_response(&app, "/summary").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "small");
+    }
+}
+
+#[tokio::test]
+async fn test_options_answered_with_route_methods() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("OPTIONS"))
+        .respond_with(ResponseTemplate::new(501))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let mut route = circuit_breaker_route("/api/users", &mock_server.uri(), None);
+    route.methods = vec!["GET".to_string(), "POST".to_string()];
+    route.answer_options = Some(true);
+    let (status, headers, _) = send_options(&fallback_gateway(route), "/api/users").await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(headers["allow"], "GET, POST, OPTIONS");
+
+    // An all-methods route advertises the methods it proxies implicitly
+    let mut route = circuit_breaker_route("/api/orders", &mock_server.uri(), None);
+    route.answer_options = Some(true);
+    let (status, headers, _) = send_options(&fallback_gateway(route), "/api/orders").await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(
+        headers["allow"],
+        "GET, HEAD, POST, PUT, PATCH, DELETE, OPTIONS"
+    );
+}
+
+#[tokio::test]
+async fn test_options_checks_ip_filter_and_route_predicates() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("OPTIONS"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let config = GatewayConfig::from_yaml(&format!(
+        r#"
+server: {{}}
+ip_filter:
+  blacklist:
+    - "203.0.113.0/24"
+routes:
+  - path: "/api/users"
+    backend: "{backend}"
+    methods: [GET, PUT]
+    answer_options: true
+    match_headers:
+      X-API-Version: 2
+  - path: "/api/users"
+    backend: "{backend}"
+"#,
+        backend = mock_server.uri()
+    ))
+    .unwrap();
+    let router = GatewayRouter::new(config.routes).unwrap();
+    let proxy_state = ProxyState::new(router, Duration::from_secs(30), None, None, None)
+        .with_ip_filter(IpFilterService::new(config.ip_filter.unwrap()).unwrap());
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    let options = |version: Option<&str>, peer: [u8; 4]| {
+        let mut request = Request::builder().method("OPTIONS").uri("/api/users");
+        if let Some(version) = version {
+            request = request.header("X-API-Version", version);
+        }
+        let mut request = request.body(axum::body::Body::empty()).unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from((peer, 40000))));
+        app.clone().oneshot(request)
+    };
+
+    let response = options(Some("2"), [10, 0, 0, 1]).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(response.headers()["allow"], "GET, PUT, OPTIONS");
+
+    // Blocked clients are refused before the OPTIONS responder
+    let response = options(Some("2"), [203, 0, 113, 9]).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Without the header the fallback route, which forwards OPTIONS, matches
+    let response = options(None, [10, 0, 0, 1]).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 1);
+}
+
+async fn send_options(app: &Router, uri: &str) -> (StatusCode, http::HeaderMap, String) {
+    let request = Request::builder()
+        .method("OPTIONS")
+        .uri(uri)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, headers, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_options_responder_keeps_cors_preflight() {
+    let mock_server = MockServer::start().await;
+
+    let cors = CorsConfig {
+        allowed_origins: vec!["https://app.example.com".to_string()],
+        allowed_methods: vec!["GET".to_string(), "POST".to_string()],
+        ..Default::default()
+    };
+    let route = RouteConfig {
+        cors: Some(cors),
+        answer_options: Some(true),
+        methods: vec!["GET".to_string()],
+        ..circuit_breaker_route("/api/data", &mock_server.uri(), None)
+    };
+    let app = fallback_gateway(route);
+
+    let request = Request::builder()
+        .method("OPTIONS")
+        .uri("/api/data")
+        .header("Origin", "https://app.example.com")
+        .header("Access-Control-Request-Method", "POST")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let headers = response.headers();
+    assert_eq!(
+        headers["access-control-allow-origin"],
+        "https://app.example.com"
+    );
+    assert_eq!(headers["access-control-allow-methods"], "GET, POST");
+    assert!(headers.get("allow").is_none());
+}
+
+#[tokio::test]
+async fn test_preserved_hop_by_hop_header_reaches_backend() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let mut route = circuit_breaker_route("/api/stream", &mock_server.uri(), None);
+    route.preserve_headers = vec!["TE".to_string()];
+    let app = fallback_gateway(route);
+
+    let request = Request::builder()
+        .uri("/api/stream")
+        .header("te", "trailers")
+        .header("keep-alive", "timeout=5")
+        .header("proxy-authorization", "Basic c2VjcmV0")
+        .header("x-request-tag", "kept")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let requests = mock_server.received_requests().await.unwrap();
+    let headers = &requests[0].headers;
+    assert_eq!(headers.get("te").unwrap(), "trailers");
+    assert_eq!(headers.get("x-request-tag").unwrap(), "kept");
+    // Hop-by-hop headers the route doesn't preserve are still stripped
+    assert!(headers.get("keep-alive").is_none());
+    assert!(headers.get("proxy-authorization").is_none());
+}
+
+#[tokio::test]
+async fn test_query_token_not_forwarded() {
+    use gateway::auth::AuthService;
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/events"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let config = GatewayConfig::from_yaml(&format!(
+        r#"
+server: {{}}
+auth:
+  jwt:
+    secret: "test-secret"
+    token_source: "query:access_token"
+routes:
+  - path: "/events"
+    backend: "{}"
+    auth:
+      methods: ["jwt"]
+"#,
+        mock_server.uri()
+    ))
+    .unwrap();
+    let auth_service = AuthService::new(config.auth.as_ref()).await.unwrap();
+    let router = GatewayRouter::new(config.routes).unwrap();
+    let proxy_state = ProxyState::new(
+        router,
+        Duration::from_secs(30),
+        Some(auth_service),
+        None,
+        None,
+    );
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    let claims = serde_json::json!({
+        "sub": "user123",
+        "exp": chrono::Utc::now().timestamp() + 3600,
+    });
+    let token = jsonwebtoken::encode(
+        &jsonwebtoken::Header::default(),
+        &claims,
+        &jsonwebtoken::EncodingKey::from_secret(b"test-secret"),
+    )
+    .unwrap();
+
+    let uri = format!("/events?stream=orders&access_token={}", token);
+    assert_eq!(get_status(&app, &uri).await, StatusCode::OK);
+
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests[0].url.query(), Some("stream=orders"));
+}
+
+#[tokio::test]
+async fn test_hmac_signed_request_forwards_body() {
+    use gateway::auth::{hmac, AuthService};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/partners/orders"))
+        .and(body_json(serde_json::json!({"item": "widget"})))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = GatewayConfig::from_yaml(&format!(
+        r#"
+server: {{}}
+auth:
+  hmac:
+    keys:
+      partner-a: "shared-secret"
+routes:
+  - path: "/partners/orders"
+    backend: "{}"
+    auth:
+      methods: ["hmac"]
+"#,
+        mock_server.uri()
+    ))
+    .unwrap();
+    let auth_service = AuthService::new(config.auth.as_ref()).await.unwrap();
+    let router = GatewayRouter::new(config.routes).unwrap();
+    let proxy_state = ProxyState::new(
+        router,
+        Duration::from_secs(30),
+        Some(auth_service),
+        None,
+        None,
+    );
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    let signed_request = |body: &'static str, signed_body: &str, timestamp: u64| {
+        let message = hmac::string_to_sign(
+            "POST",
+            "/partners/orders",
+            &timestamp.to_string(),
+            signed_body.as_bytes(),
+        );
+        Request::builder()
+            .method("POST")
+            .uri("/partners/orders")
+            .header("content-type", "application/json")
+            .header("X-Key-Id", "partner-a")
+            .header("X-Timestamp", timestamp)
+            .header("X-Signature", hmac::sign(b"shared-secret", &message))
+            .body(axum::body::Body::from(body))
+            .unwrap()
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let body = r#"{"item": "widget"}"#;
+
+    let response = app
+        .clone()
+        .oneshot(signed_request(body, body, now))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // Tampered body
+    let response = app
+        .clone()
+        .oneshot(signed_request(r#"{"item": "gadget"}"#, body, now))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Stale timestamp, outside the default five minute window
+    let response = app
+        .clone()
+        .oneshot(signed_request(body, body, now - 600))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Unsigned
+    let request = Request::builder()
+        .method("POST")
+        .uri("/partners/orders")
+        .body(axum::body::Body::from(body))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_hmac_signature_covers_method_before_override() {
+    use gateway::auth::{hmac, AuthService};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/partners/orders"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = GatewayConfig::from_yaml(&format!(
+        r#"
+server: {{}}
+auth:
+  hmac:
+    keys:
+      partner-a: "shared-secret"
+routes:
+  - path: "/partners/orders"
+    backend: "{}"
+    methods: ["DELETE"]
+    honor_method_override: true
+    auth:
+      methods: ["hmac"]
+"#,
+        mock_server.uri()
+    ))
+    .unwrap();
+    let auth_service = AuthService::new(config.auth.as_ref()).await.unwrap();
+    let router = GatewayRouter::new(config.routes).unwrap();
+    let proxy_state = ProxyState::new(
+        router,
+        Duration::from_secs(30),
+        Some(auth_service),
+        None,
+        None,
+    );
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let tunneled_request = |signed_method: &str| {
+        let message =
+            hmac::string_to_sign(signed_method, "/partners/orders", &now.to_string(), b"");
+        Request::builder()
+            .method("POST")
+            .uri("/partners/orders")
+            .header("X-HTTP-Method-Override", "DELETE")
+            .header("X-Key-Id", "partner-a")
+            .header("X-Timestamp", now)
+            .header("X-Signature", hmac::sign(b"shared-secret", &message))
+            .body(axum::body::Body::empty())
+            .unwrap()
+    };
+
+    // The client signed the POST it sent, not the method it tunneled
+    let response = app.clone().oneshot(tunneled_request("POST")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = app.oneshot(tunneled_request("DELETE")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_unauthorized_response_includes_challenges() {
+    use gateway::auth::AuthService;
+
+    let config = GatewayConfig::from_yaml(
+        r#"
+server: {}
+auth:
+  jwt:
+    secret: "jwt-secret"
+  api_key:
+    keys:
+      "sk_test_123": "Test key"
+  challenges:
+    jwt: 'Bearer realm="gateway"'
+routes:
+  - path: "/api/any"
+    backend: "http://127.0.0.1:1"
+    auth: {}
+  - path: "/api/jwt"
+    backend: "http://127.0.0.1:1"
+    auth:
+      methods: ["jwt"]
+  - path: "/api/keys"
+    backend: "http://127.0.0.1:1"
+    auth:
+      methods: ["apikey"]
+"#,
+    )
+    .unwrap();
+    let auth_service = AuthService::new(config.auth.as_ref()).await.unwrap();
+    let router = GatewayRouter::new(config.routes).unwrap();
+    let proxy_state = ProxyState::new(
+        router,
+        Duration::from_secs(30),
+        Some(auth_service),
+        None,
+        None,
+    );
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    for (uri, expected) in [
+        (
+            "/api/any",
+            vec![r#"Bearer realm="gateway""#, r#"ApiKey header="X-API-Key""#],
+        ),
+        ("/api/jwt", vec![r#"Bearer realm="gateway""#]),
+        ("/api/keys", vec![r#"ApiKey header="X-API-Key""#]),
+    ] {
+        let request = Request::builder()
+            .uri(uri)
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{}", uri);
+        let challenges: Vec<_> = response
+            .headers()
+            .get_all(http::header::WWW_AUTHENTICATE)
+            .iter()
+            .map(|value| value.to_str().unwrap())
+            .collect();
+        assert_eq!(challenges, expected, "{}", uri);
+    }
+}
+
+#[tokio::test]
+async fn test_configured_health_check_paths_bypass_auth() {
+    use gateway::auth::AuthService;
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let config = GatewayConfig::from_yaml(&format!(
+        r#"
+server:
+  health_check_paths: ["/status"]
+auth:
+  jwt:
+    secret: "jwt-secret"
+routes:
+  - path: "/status"
+    backend: "{0}"
+    auth: {{}}
+  - path: "/health"
+    backend: "{0}"
+    auth: {{}}
+"#,
+        mock_server.uri()
+    ))
+    .unwrap();
+    let auth_service = AuthService::new(config.auth.as_ref()).await.unwrap();
+    let router = GatewayRouter::new(config.routes).unwrap();
+    let proxy_state = ProxyState::new(
+        router,
+        Duration::from_secs(30),
+        Some(auth_service),
+        None,
+        None,
+    )
+    .with_health_check_paths(config.server.health_check_paths);
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    // The configured path bypasses authentication; the default list no longer applies
+    for (uri, expected) in [
+        ("/status", StatusCode::OK),
+        ("/health", StatusCode::UNAUTHORIZED),
+    ] {
+        let request = Request::builder()
+            .uri(uri)
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), expected, "{}", uri);
+    }
+}
+
+#[tokio::test]
+async fn test_disabled_route_returns_configured_status() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let config = GatewayConfig::from_yaml(&format!(
+        r#"
+server: {{}}
+routes:
+  - path: "/api/users"
+    backend: "{0}"
+  - path: "/api/orders"
+    backend: "{0}"
+    enabled: false
+  - path: "/api/legacy"
+    backend: "{0}"
+    enabled: false
+    disabled_status: 404
+"#,
+        mock_server.uri()
+    ))
+    .unwrap();
+    let router = GatewayRouter::new(config.routes).unwrap();
+    let proxy_state = ProxyState::new(router, Duration::from_secs(30), None, None, None);
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    for (uri, expected) in [
+        ("/api/users", StatusCode::OK),
+        ("/api/orders", StatusCode::SERVICE_UNAVAILABLE),
+        ("/api/legacy", StatusCode::NOT_FOUND),
+    ] {
+        let request = Request::builder()
+            .uri(uri)
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), expected, "{}", uri);
+    }
+
+    // Only the enabled route reached the backend
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].url.path(), "/api/users");
+}
+
+#[tokio::test]
+async fn test_hmac_signed_body_over_limit_rejected() {
+    use gateway::auth::AuthService;
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let config = GatewayConfig::from_yaml(&format!(
+        r#"
+server: {{}}
+auth:
+  hmac:
+    keys:
+      partner-a: "shared-secret"
+    max_body_bytes: 16
+routes:
+  - path: "/partners/orders"
+    backend: "{}"
+    auth:
+      methods: ["hmac"]
+"#,
+        mock_server.uri()
+    ))
+    .unwrap();
+    let auth_service = AuthService::new(config.auth.as_ref()).await.unwrap();
+    let router = GatewayRouter::new(config.routes).unwrap();
+    let proxy_state = ProxyState::new(
+        router,
+        Duration::from_secs(30),
+        Some(auth_service),
+        None,
+        None,
+    );
+    let app = Router::new()
+        .route("/*path", axum::routing::any(gateway::proxy::proxy_handler))
+        .with_state(proxy_state);
+
+    // Any X-Signature header makes the gateway buffer the body; the signature
+    // itself is never checked
+    let request = Request::builder()
+        .method("POST")
+        .uri("/partners/orders")
+        .header("X-Key-Id", "partner-a")
+        .header("X-Timestamp", "0")
+        .header("X-Signature", "00")
+        .body(axum::body::Body::from(vec![b'x'; 1024]))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use axum::Router;
+use http::{Request, StatusCode};
+use http_body_util::BodyExt;
+use std::time::Duration;
+use tower::ServiceExt;
+use wiremock::{
+    matchers::{body_string, header, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+/// Gateway with `/api/orders` served by `primary` and mirrored to `mirror`
+fn gateway(primary: &str, mirror: &str, percentage: f64) -> Router {
+    common::gateway(&format!(
+        r#"
+server: {{}}
+routes:
+  - path: "/api/orders"
+    backend: "{primary}"
+    mirror:
+      backend: "{mirror}"
+      percentage: {percentage}
+"#
+    ))
+}
+
+async fn primary_backend() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/orders"))
+        .respond_with(ResponseTemplate::new(201).set_body_string("created"))
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn post_order(app: &Router) -> (StatusCode, String) {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/orders?source=web")
+        .header("X-Tenant", "acme")
+        .body(axum::body::Body::from(r#"{"item":"book"}"#))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+/// Wait for a mock server to have received `count` requests
+async fn wait_for_requests(server: &MockServer, count: usize) -> usize {
+    for _ in 0..100 {
+        let received = server.received_requests().await.unwrap().len();
+        if received >= count {
+            return received;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    server.received_requests().await.unwrap().len()
+}
+
+#[tokio::test]
+async fn test_mirror_receives_copy_without_affecting_response() {
+    let primary = primary_backend().await;
+    let mirror = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/orders"))
+        .and(header("X-Tenant", "acme"))
+        .and(body_string(r#"{"item":"book"}"#))
+        .respond_with(ResponseTemplate::new(500).set_body_string("shadow broke"))
+        .expect(1)
+        .mount(&mirror)
+        .await;
+    let app = gateway(&primary.uri(), &mirror.uri(), 100.0);
+
+    let (status, body) = post_order(&app).await;
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body, "created");
+    assert_eq!(wait_for_requests(&mirror, 1).await, 1);
+    let mirrored = &mirror.received_requests().await.unwrap()[0];
+    assert_eq!(mirrored.url.query(), Some("source=web"));
+}
+
+#[tokio::test]
+async fn test_unreachable_mirror_does_not_affect_client() {
+    let primary = primary_backend().await;
+    let app = gateway(&primary.uri(), "http://127.0.0.1:1", 100.0);
+
+    for _ in 0..3 {
+        assert_eq!(
+            post_order(&app).await,
+            (StatusCode::CREATED, "created".to_string())
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_mirror_receives_sampled_share() {
+    let primary = primary_backend().await;
+    let mirror = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mirror)
+        .await;
+    let app = gateway(&primary.uri(), &mirror.uri(), 50.0);
+
+    for _ in 0..200 {
+        assert_eq!(post_order(&app).await.0, StatusCode::CREATED);
+    }
+
+    // Give in-flight mirror requests time to land
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let mirrored = mirror.received_requests().await.unwrap().len();
+    assert!((60..=140).contains(&mirrored), "{}", mirrored);
+    assert_eq!(primary.received_requests().await.unwrap().len(), 200);
+}
+
+#[tokio::test]
+async fn test_zero_percentage_mirrors_nothing() {
+    let primary = primary_backend().await;
+    let mirror = MockServer::start().await;
+    let app = gateway(&primary.uri(), &mirror.uri(), 0.0);
+
+    for _ in 0..20 {
+        assert_eq!(post_order(&app).await.0, StatusCode::CREATED);
+    }
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(mirror.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_saturated_mirror_drops_copies() {
+    let primary = primary_backend().await;
+    let mirror = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(300)))
+        .mount(&mirror)
+        .await;
+    let app = common::gateway(&format!(
+        r#"
+server: {{}}
+routes:
+  - path: "/api/orders"
+    backend: "{}"
+    mirror:
+      backend: "{}"
+      max_in_flight: 1
+"#,
+        primary.uri(),
+        mirror.uri()
+    ));
+
+    // Only the first copy fits while the slow mirror holds its slot
+    for _ in 0..3 {
+        assert_eq!(post_order(&app).await.0, StatusCode::CREATED);
+    }
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(mirror.received_requests().await.unwrap().len(), 1);
+
+    // Mirroring resumes once it completes
+    assert_eq!(post_order(&app).await.0, StatusCode::CREATED);
+    assert_eq!(wait_for_requests(&mirror, 2).await, 2);
+}
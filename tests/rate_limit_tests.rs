@@ -0,0 +1,214 @@
+use axum::{body::Body, extract::ConnectInfo, Router};
+use gateway::auth::AuthService;
+use gateway::config::GatewayConfig;
+use gateway::proxy::ProxyState;
+use gateway::rate_limit::RateLimitMiddleware;
+use gateway::router::Router as GatewayRouter;
+use http::{Request, StatusCode};
+use std::net::SocketAddr;
+use std::time::Duration;
+use tower::ServiceExt;
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+mod common;
+
+/// Gateway app with the auth and rate limiting settings of a YAML config
+async fn rate_limited_gateway(config: &str) -> Router {
+    let config = GatewayConfig::from_yaml(config).unwrap();
+    config.validate().unwrap();
+    let auth_service = AuthService::new(config.auth.as_ref()).await.unwrap();
+    let rate_limiter = RateLimitMiddleware::from_config(config.rate_limiting.as_ref().unwrap())
+        .await
+        .unwrap();
+    let proxy_state = ProxyState::new(
+        GatewayRouter::new(config.routes).unwrap(),
+        Duration::from_secs(30),
+        Some(auth_service),
+        None,
+        None,
+    )
+    .with_rate_limiter(rate_limiter);
+    common::proxy_app(proxy_state)
+}
+
+async fn backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+async fn get_with_key(app: &Router, api_key: &str) -> StatusCode {
+    let request = Request::builder()
+        .uri("/api/orders")
+        .header("X-API-Key", api_key)
+        .body(Body::empty())
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_api_key_override_applies_through_gateway() {
+    let mock_server = backend().await;
+    let app = rate_limited_gateway(&format!(
+        r#"
+server: {{}}
+auth:
+  api_key:
+    keys:
+      free-key: "free tier"
+      pro-key:
+        description: "pro tier"
+        rate_limit:
+          requests: 3
+          window_secs: 60
+rate_limiting:
+  algorithm: fixed_window
+  global:
+    - dimension: apikey
+      requests: 1
+      window_secs: 60
+routes:
+  - path: "/api/orders"
+    backend: "{}"
+    auth:
+      methods: ["apikey"]
+"#,
+        mock_server.uri()
+    ))
+    .await;
+
+    assert_eq!(get_with_key(&app, "free-key").await, StatusCode::OK);
+    assert_eq!(
+        get_with_key(&app, "free-key").await,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+
+    for _ in 0..3 {
+        assert_eq!(get_with_key(&app, "pro-key").await, StatusCode::OK);
+    }
+    assert_eq!(
+        get_with_key(&app, "pro-key").await,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+
+    // Only the allowed requests reach the backend
+    assert_eq!(
+        get_with_key(&app, "unknown-key").await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 4);
+}
+
+async fn get_from(app: &Router, uri: &str, peer: [u8; 4], api_key: Option<&str>) -> StatusCode {
+    let mut request = Request::builder().uri(uri);
+    if let Some(api_key) = api_key {
+        request = request.header("X-API-Key", api_key);
+    }
+    let mut request = request.body(Body::empty()).unwrap();
+    request
+        .extensions_mut()
+        .insert(ConnectInfo(SocketAddr::from((peer, 40000))));
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_exemptions_apply_through_gateway() {
+    let mock_server = backend().await;
+    let app = rate_limited_gateway(&format!(
+        r#"
+server: {{}}
+auth:
+  api_key:
+    keys:
+      partner-key: "partner"
+rate_limiting:
+  algorithm: fixed_window
+  global:
+    - dimension: ip
+      requests: 1
+      window_secs: 60
+  exempt:
+    ips: ["10.0.0.0/8"]
+    api_keys: ["partner-key"]
+routes:
+  - path: "/api/orders"
+    backend: "{uri}"
+    auth:
+      methods: ["apikey"]
+  - path: "/public"
+    backend: "{uri}"
+"#,
+        uri = mock_server.uri()
+    ))
+    .await;
+
+    for _ in 0..3 {
+        assert_eq!(
+            get_from(&app, "/public", [10, 1, 2, 3], None).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            get_from(&app, "/api/orders", [203, 0, 113, 9], Some("partner-key")).await,
+            StatusCode::OK
+        );
+    }
+
+    // The exempt key only counts once it has authenticated, so sending it
+    // to a public route doesn't bypass the limit
+    let peer = [198, 51, 100, 7];
+    assert_eq!(
+        get_from(&app, "/public", peer, Some("partner-key")).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        get_from(&app, "/public", peer, Some("partner-key")).await,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+}
+
+#[tokio::test]
+async fn test_quota_applies_through_gateway() {
+    let mock_server = backend().await;
+    let app = rate_limited_gateway(&format!(
+        r#"
+server: {{}}
+auth:
+  api_key:
+    keys:
+      key-a: "tenant a"
+      key-b: "tenant b"
+rate_limiting:
+  quota:
+    requests: 2
+    period: monthly
+routes:
+  - path: "/api/orders"
+    backend: "{}"
+    auth:
+      methods: ["apikey"]
+"#,
+        mock_server.uri()
+    ))
+    .await;
+
+    assert_eq!(get_with_key(&app, "key-a").await, StatusCode::OK);
+    assert_eq!(get_with_key(&app, "key-a").await, StatusCode::OK);
+
+    let request = Request::builder()
+        .uri("/api/orders")
+        .header("X-API-Key", "key-a")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()["x-quota-limit"], "2");
+    assert_eq!(response.headers()["x-quota-remaining"], "0");
+
+    // Each key has its own quota, and failed logins don't use one up
+    assert_eq!(get_with_key(&app, "key-c").await, StatusCode::UNAUTHORIZED);
+    assert_eq!(get_with_key(&app, "key-b").await, StatusCode::OK);
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 3);
+}
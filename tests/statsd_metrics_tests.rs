@@ -0,0 +1,47 @@
+use gateway::config::{GatewayConfig, MetricsExporter};
+use gateway::metrics::{record_request, MetricsService};
+use std::net::UdpSocket;
+use std::time::Duration;
+
+#[test]
+fn test_statsd_exporter_sends_udp() {
+    // Stand-in for the StatsD agent
+    let agent = UdpSocket::bind("127.0.0.1:0").unwrap();
+    agent
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let port = agent.local_addr().unwrap().port();
+
+    let config = GatewayConfig::from_yaml(&format!(
+        r#"
+server: {{}}
+observability:
+  metrics:
+    exporter: statsd
+    statsd:
+      host: "127.0.0.1"
+      port: {port}
+      prefix: "gateway"
+routes: []
+"#
+    ))
+    .unwrap();
+    let metrics_config = config.observability.unwrap().metrics.unwrap();
+    assert_eq!(metrics_config.exporter, MetricsExporter::Statsd);
+
+    // Installs the global recorder, so this binary holds a single test
+    MetricsService::install_statsd(&metrics_config.statsd).unwrap();
+
+    // Fill the client's send buffer so it flushes
+    for _ in 0..20 {
+        record_request("GET", "/api/users", 200, 0.012);
+    }
+
+    let mut buf = [0u8; 1500];
+    let len = agent.recv(&mut buf).unwrap();
+    let payload = String::from_utf8_lossy(&buf[..len]);
+    assert!(
+        payload.contains("gateway.gateway_requests_total:1|c|#"),
+        "{payload}"
+    );
+}
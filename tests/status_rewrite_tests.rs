@@ -0,0 +1,79 @@
+mod common;
+
+use axum::Router;
+use gateway::metrics::MetricsService;
+use http::{Request, StatusCode};
+use tower::ServiceExt;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+/// Gateway with a route that rewrites the teapot status and caches responses
+fn gateway(backend: &str) -> Router {
+    common::gateway(&format!(
+        r#"
+server:
+  host: "127.0.0.1"
+  port: 8080
+routes:
+  - path: "/teapot"
+    backend: "{backend}"
+    cache:
+      enabled: true
+      cacheable_status_codes: [200, 400]
+    transform:
+      response:
+        status_map:
+          418: 400
+"#
+    ))
+}
+
+async fn get(app: &Router, uri: &str) -> (StatusCode, Option<String>) {
+    let request = Request::builder()
+        .uri(uri)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let cache = response
+        .headers()
+        .get("x-cache")
+        .map(|value| value.to_str().unwrap().to_string());
+    (response.status(), cache)
+}
+
+#[tokio::test]
+async fn test_rewritten_status_recorded_and_cached() {
+    // Installs the global recorder
+    let metrics = MetricsService::new().unwrap();
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/teapot"))
+        .respond_with(ResponseTemplate::new(418).set_body_string("short and stout"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    let app = gateway(&mock_server.uri());
+
+    assert_eq!(get(&app, "/teapot").await, (StatusCode::BAD_REQUEST, None));
+
+    // The rewritten status is what the cache stored
+    assert_eq!(
+        get(&app, "/teapot").await,
+        (StatusCode::BAD_REQUEST, Some("HIT".to_string()))
+    );
+
+    let rendered = metrics.render();
+    let teapot_requests: Vec<&str> = rendered
+        .lines()
+        .filter(|line| {
+            line.starts_with("gateway_requests_total{") && line.contains(r#"path="/teapot""#)
+        })
+        .collect();
+    assert_eq!(teapot_requests.len(), 1, "{rendered}");
+    assert!(teapot_requests[0].contains(r#"status="400""#));
+    assert!(teapot_requests[0].ends_with(" 2"));
+    assert!(!rendered.contains(r#"status="418""#));
+}
@@ -0,0 +1,408 @@
+use axum::routing;
+use axum_server::tls_rustls::RustlsConfig;
+use gateway::config::GatewayConfig;
+use gateway::init_gateway;
+use gateway::tls::{build_tls_config, TlsConfig};
+use rcgen::{BasicConstraints, CertificateParams, CertifiedKey, IsCa, KeyPair};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer, ServerName};
+use rustls::{ClientConfig, RootCertStore};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::TlsConnector;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+/// A CA plus a server certificate for "localhost" signed by it
+struct TestPki {
+    ca: rcgen::Certificate,
+    ca_key: KeyPair,
+    server: CertifiedKey,
+}
+
+impl TestPki {
+    fn new() -> Self {
+        let mut ca_params = CertificateParams::new(Vec::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca_key = KeyPair::generate().unwrap();
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+
+        let server = issue(&ca, &ca_key, "localhost");
+
+        Self { ca, ca_key, server }
+    }
+
+    fn write(&self, dir: &Path) {
+        std::fs::write(dir.join("ca.pem"), self.ca.pem()).unwrap();
+        std::fs::write(dir.join("cert.pem"), self.server.cert.pem()).unwrap();
+        std::fs::write(dir.join("key.pem"), self.server.key_pair.serialize_pem()).unwrap();
+    }
+
+    fn client_config(&self, client_cert: Option<&CertifiedKey>) -> ClientConfig {
+        let mut roots = RootCertStore::empty();
+        roots.add(self.ca.der().clone()).unwrap();
+        let builder = ClientConfig::builder().with_root_certificates(roots);
+
+        let mut config = match client_cert {
+            Some(client) => builder
+                .with_client_auth_cert(
+                    vec![client.cert.der().clone()],
+                    PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(client.key_pair.serialize_der())),
+                )
+                .unwrap(),
+            None => builder.with_no_client_auth(),
+        };
+        config.alpn_protocols = vec![b"http/1.1".to_vec()];
+        config
+    }
+}
+
+fn issue(ca: &rcgen::Certificate, ca_key: &KeyPair, name: &str) -> CertifiedKey {
+    let key_pair = KeyPair::generate().unwrap();
+    let cert = CertificateParams::new(vec![name.to_string()])
+        .unwrap()
+        .signed_by(&key_pair, ca, ca_key)
+        .unwrap();
+    CertifiedKey { cert, key_pair }
+}
+
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+/// Start the gateway with the given `tls:` section and wait until it accepts connections
+async fn start_tls_gateway(backend: &MockServer, tls_yaml: &str) -> u16 {
+    start_gateway(&backend.uri(), &format!("tls:\n{}", tls_yaml)).await
+}
+
+/// Start the gateway proxying `/api/hello` to `backend` with extra top-level config
+async fn start_gateway(backend: &str, extra_yaml: &str) -> u16 {
+    let port = free_port();
+    let yaml = format!(
+        r#"
+server:
+  host: "127.0.0.1"
+  port: {port}
+routes:
+  - path: "/api/hello"
+    backend: "{backend}"
+    methods: ["GET"]
+{extra_yaml}
+"#
+    );
+    let config = GatewayConfig::from_yaml(&yaml).unwrap();
+    tokio::spawn(init_gateway(config));
+
+    for _ in 0..50 {
+        if TcpStream::connect(("127.0.0.1", port)).await.is_ok() {
+            return port;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("gateway did not start listening on port {}", port);
+}
+
+async fn hello_backend() -> MockServer {
+    let backend = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/hello"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("hello over tls"))
+        .mount(&backend)
+        .await;
+    backend
+}
+
+async fn connect(port: u16, config: ClientConfig) -> std::io::Result<TlsStream<TcpStream>> {
+    let stream = TcpStream::connect(("127.0.0.1", port)).await?;
+    TlsConnector::from(Arc::new(config))
+        .connect(ServerName::try_from("localhost").unwrap(), stream)
+        .await
+}
+
+/// Send a plain HTTP/1.1 GET over the stream and return the raw response
+async fn get(stream: &mut TlsStream<TcpStream>, uri: &str) -> std::io::Result<String> {
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+        uri
+    );
+    stream.write_all(request.as_bytes()).await?;
+
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).await?;
+    Ok(String::from_utf8_lossy(&response).into_owned())
+}
+
+#[tokio::test]
+async fn test_tls_handshake_and_proxy() {
+    let dir = TempDir::new().unwrap();
+    let pki = TestPki::new();
+    pki.write(dir.path());
+
+    let backend = hello_backend().await;
+    let tls_yaml = format!(
+        "  cert_path: \"{}\"\n  key_path: \"{}\"\n",
+        dir.path().join("cert.pem").display(),
+        dir.path().join("key.pem").display()
+    );
+    let port = start_tls_gateway(&backend, &tls_yaml).await;
+
+    let mut stream = connect(port, pki.client_config(None)).await.unwrap();
+    let (_, session) = stream.get_ref();
+    assert_eq!(session.alpn_protocol(), Some(&b"http/1.1"[..]));
+    assert_eq!(
+        session.peer_certificates().unwrap()[0],
+        CertificateDer::from(pki.server.cert.der().to_vec())
+    );
+
+    let response = get(&mut stream, "/api/hello").await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+    assert!(response.ends_with("hello over tls"), "{}", response);
+}
+
+#[tokio::test]
+async fn test_tls_negotiates_h2_via_alpn() {
+    let dir = TempDir::new().unwrap();
+    let pki = TestPki::new();
+    pki.write(dir.path());
+
+    let backend = hello_backend().await;
+    let tls_yaml = format!(
+        "  cert_path: \"{}\"\n  key_path: \"{}\"\n",
+        dir.path().join("cert.pem").display(),
+        dir.path().join("key.pem").display()
+    );
+    let port = start_tls_gateway(&backend, &tls_yaml).await;
+
+    let mut config = pki.client_config(None);
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    let stream = connect(port, config).await.unwrap();
+    assert_eq!(stream.get_ref().1.alpn_protocol(), Some(&b"h2"[..]));
+}
+
+#[tokio::test]
+async fn test_mtls_requires_client_certificate() {
+    let dir = TempDir::new().unwrap();
+    let pki = TestPki::new();
+    pki.write(dir.path());
+
+    let backend = hello_backend().await;
+    let tls_yaml = format!(
+        "  cert_path: \"{}\"\n  key_path: \"{}\"\n  enable_mtls: true\n  ca_cert_path: \"{}\"\n",
+        dir.path().join("cert.pem").display(),
+        dir.path().join("key.pem").display(),
+        dir.path().join("ca.pem").display()
+    );
+    let port = start_tls_gateway(&backend, &tls_yaml).await;
+
+    // Without a client certificate the server aborts the handshake; under TLS 1.3
+    // the client only learns about it once it tries to use the connection
+    let rejected = match connect(port, pki.client_config(None)).await {
+        Ok(mut stream) => get(&mut stream, "/api/hello").await,
+        Err(e) => Err(e),
+    };
+    assert!(rejected.is_err(), "unexpected response: {:?}", rejected);
+
+    let client = issue(&pki.ca, &pki.ca_key, "client");
+    let mut stream = connect(port, pki.client_config(Some(&client)))
+        .await
+        .unwrap();
+    let response = get(&mut stream, "/api/hello").await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+}
+
+#[tokio::test]
+async fn test_rotated_certificate_is_served_without_restart() {
+    let dir = TempDir::new().unwrap();
+    let pki = TestPki::new();
+    pki.write(dir.path());
+
+    let backend = hello_backend().await;
+    let tls_yaml = format!(
+        "  cert_path: \"{}\"\n  key_path: \"{}\"\n  watch_debounce_ms: 100\n",
+        dir.path().join("cert.pem").display(),
+        dir.path().join("key.pem").display()
+    );
+    let port = start_tls_gateway(&backend, &tls_yaml).await;
+
+    let original = CertificateDer::from(pki.server.cert.der().to_vec());
+    let mut existing = connect(port, pki.client_config(None)).await.unwrap();
+    assert_eq!(
+        existing.get_ref().1.peer_certificates().unwrap()[0],
+        original
+    );
+
+    // Rotate the certificate the way a cert manager would: new cert, then new key
+    let rotated = issue(&pki.ca, &pki.ca_key, "localhost");
+    std::fs::write(dir.path().join("cert.pem"), rotated.cert.pem()).unwrap();
+    std::fs::write(dir.path().join("key.pem"), rotated.key_pair.serialize_pem()).unwrap();
+
+    let expected = CertificateDer::from(rotated.cert.der().to_vec());
+    let mut presented = None;
+    for _ in 0..50 {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let stream = connect(port, pki.client_config(None)).await.unwrap();
+        let cert = stream.get_ref().1.peer_certificates().unwrap()[0].clone();
+        if cert == expected {
+            presented = Some(cert);
+            break;
+        }
+    }
+    assert_eq!(presented, Some(expected));
+
+    // Connections established before the rotation keep working
+    let response = get(&mut existing, "/api/hello").await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+}
+
+/// Start an HTTPS backend that only accepts clients with a certificate from the test CA
+async fn start_mtls_backend(dir: &Path) -> u16 {
+    let tls_config: TlsConfig = serde_yaml::from_str(&format!(
+        "cert_path: \"{}\"\nkey_path: \"{}\"\nenable_mtls: true\nca_cert_path: \"{}\"\nwatch_certificates: false\n",
+        dir.join("cert.pem").display(),
+        dir.join("key.pem").display(),
+        dir.join("ca.pem").display()
+    ))
+    .unwrap();
+    let server_config = build_tls_config(&tls_config).unwrap();
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let app = axum::Router::new().route("/api/hello", routing::get(|| async { "hello over mtls" }));
+    tokio::spawn(
+        axum_server::from_tcp_rustls(listener, RustlsConfig::from_config(Arc::new(server_config)))
+            .serve(app.into_make_service()),
+    );
+    port
+}
+
+/// Write a client certificate issued by the test CA and return the backend_tls paths
+fn write_client_identity(pki: &TestPki, dir: &Path) -> String {
+    let client = issue(&pki.ca, &pki.ca_key, "gateway");
+    std::fs::write(dir.join("client.pem"), client.cert.pem()).unwrap();
+    std::fs::write(dir.join("client-key.pem"), client.key_pair.serialize_pem()).unwrap();
+    format!(
+        "  client_cert_path: \"{}\"\n  client_key_path: \"{}\"\n",
+        dir.join("client.pem").display(),
+        dir.join("client-key.pem").display()
+    )
+}
+
+async fn gateway_get(port: u16) -> (reqwest::StatusCode, String) {
+    let response = reqwest::get(format!("http://127.0.0.1:{}/api/hello", port))
+        .await
+        .unwrap();
+    let status = response.status();
+    (status, response.text().await.unwrap())
+}
+
+#[tokio::test]
+async fn test_backend_mtls_with_client_certificate() {
+    let dir = TempDir::new().unwrap();
+    let pki = TestPki::new();
+    pki.write(dir.path());
+    let backend_port = start_mtls_backend(dir.path()).await;
+
+    let backend_tls = format!(
+        "backend_tls:\n  ca_cert_path: \"{}\"\n{}",
+        dir.path().join("ca.pem").display(),
+        write_client_identity(&pki, dir.path())
+    );
+    let port = start_gateway(&format!("https://localhost:{}", backend_port), &backend_tls).await;
+
+    let (status, body) = gateway_get(port).await;
+    assert_eq!(status, reqwest::StatusCode::OK);
+    assert_eq!(body, "hello over mtls");
+}
+
+#[tokio::test]
+async fn test_backend_mtls_without_client_certificate_fails() {
+    let dir = TempDir::new().unwrap();
+    let pki = TestPki::new();
+    pki.write(dir.path());
+    let backend_port = start_mtls_backend(dir.path()).await;
+
+    let backend_tls = format!(
+        "backend_tls:\n  ca_cert_path: \"{}\"\n",
+        dir.path().join("ca.pem").display()
+    );
+    let port = start_gateway(&format!("https://localhost:{}", backend_port), &backend_tls).await;
+
+    let (status, _) = gateway_get(port).await;
+    assert_eq!(status, reqwest::StatusCode::BAD_GATEWAY);
+}
+
+#[tokio::test]
+async fn test_backend_tls_insecure_skip_verify() {
+    let dir = TempDir::new().unwrap();
+    let pki = TestPki::new();
+    pki.write(dir.path());
+    let backend_port = start_mtls_backend(dir.path()).await;
+
+    // No CA bundle: the test CA is unknown, so only skipping verification works
+    let backend_tls = format!(
+        "backend_tls:\n  insecure_skip_verify: true\n{}",
+        write_client_identity(&pki, dir.path())
+    );
+    let port = start_gateway(&format!("https://localhost:{}", backend_port), &backend_tls).await;
+
+    let (status, body) = gateway_get(port).await;
+    assert_eq!(status, reqwest::StatusCode::OK);
+    assert_eq!(body, "hello over mtls");
+}
+
+#[tokio::test]
+async fn test_tls12_handshake_refused_when_min_version_is_13() {
+    let dir = TempDir::new().unwrap();
+    let pki = TestPki::new();
+    pki.write(dir.path());
+
+    let backend = hello_backend().await;
+    let tls_yaml = format!(
+        "  cert_path: \"{}\"\n  key_path: \"{}\"\n  min_version: \"1.3\"\n",
+        dir.path().join("cert.pem").display(),
+        dir.path().join("key.pem").display()
+    );
+    let port = start_tls_gateway(&backend, &tls_yaml).await;
+
+    let mut roots = RootCertStore::empty();
+    roots.add(pki.ca.der().clone()).unwrap();
+    let tls12_only = ClientConfig::builder_with_protocol_versions(&[&rustls::version::TLS12])
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    assert!(connect(port, tls12_only).await.is_err());
+
+    let stream = connect(port, pki.client_config(None)).await.unwrap();
+    assert_eq!(
+        stream.get_ref().1.protocol_version(),
+        Some(rustls::ProtocolVersion::TLSv1_3)
+    );
+}
+
+#[tokio::test]
+async fn test_cipher_suite_allowlist_is_enforced() {
+    let dir = TempDir::new().unwrap();
+    let pki = TestPki::new();
+    pki.write(dir.path());
+
+    let backend = hello_backend().await;
+    let tls_yaml = format!(
+        "  cert_path: \"{}\"\n  key_path: \"{}\"\n  cipher_suites: [\"TLS13_AES_256_GCM_SHA384\"]\n",
+        dir.path().join("cert.pem").display(),
+        dir.path().join("key.pem").display()
+    );
+    let port = start_tls_gateway(&backend, &tls_yaml).await;
+
+    let stream = connect(port, pki.client_config(None)).await.unwrap();
+    let suite = stream.get_ref().1.negotiated_cipher_suite().unwrap();
+    assert_eq!(suite.suite(), rustls::CipherSuite::TLS13_AES_256_GCM_SHA384);
+}
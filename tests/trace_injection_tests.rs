@@ -0,0 +1,124 @@
+mod common;
+
+use axum::{middleware, Router};
+use gateway::config::TracePropagator;
+use gateway::observability::{install_propagator, trace_context_middleware};
+use http::{Request, StatusCode};
+use opentelemetry::trace::TraceResult;
+use opentelemetry::Context;
+use opentelemetry_sdk::export::trace::SpanData;
+use opentelemetry_sdk::trace::{Span, SpanProcessor, TracerProvider};
+use std::sync::{Arc, Mutex};
+use tower::ServiceExt;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+const CALLER_SPAN_ID: &str = "00f067aa0ba902b7";
+
+/// Keeps every ended span
+#[derive(Debug, Clone, Default)]
+struct RecordingProcessor(Arc<Mutex<Vec<SpanData>>>);
+
+impl SpanProcessor for RecordingProcessor {
+    fn on_start(&self, _span: &mut Span, _cx: &Context) {}
+
+    fn on_end(&self, span: SpanData) {
+        self.0.lock().unwrap().push(span);
+    }
+
+    fn force_flush(&self) -> TraceResult<()> {
+        Ok(())
+    }
+
+    fn shutdown(&mut self) -> TraceResult<()> {
+        Ok(())
+    }
+}
+
+fn gateway(backend: &str) -> Router {
+    common::gateway(&format!(
+        r#"
+server:
+  host: "127.0.0.1"
+  port: 8080
+routes:
+  - path: "/api/orders"
+    backend: "{backend}"
+"#
+    ))
+    .layer(middleware::from_fn(trace_context_middleware))
+}
+
+/// Split a `traceparent` header into trace ID, span ID and flags
+fn traceparent(request: &wiremock::Request) -> (String, String, String) {
+    let value = request
+        .headers
+        .get("traceparent")
+        .unwrap()
+        .to_str()
+        .unwrap();
+    let parts: Vec<&str> = value.split('-').collect();
+    assert_eq!(parts.len(), 4, "{value}");
+    assert_eq!(parts[0], "00");
+    (
+        parts[1].to_string(),
+        parts[2].to_string(),
+        parts[3].to_string(),
+    )
+}
+
+#[tokio::test]
+async fn test_traceparent_injected_when_tracing_active() {
+    // Installs the global tracer provider and propagator
+    let spans = RecordingProcessor::default();
+    opentelemetry::global::set_tracer_provider(
+        TracerProvider::builder()
+            .with_span_processor(spans.clone())
+            .build(),
+    );
+    install_propagator(&[TracePropagator::TraceContext]);
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/orders"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+    let app = gateway(&mock_server.uri());
+
+    // A caller without trace context starts a trace at the gateway
+    let request = Request::builder()
+        .uri("/api/orders")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    assert_eq!(
+        app.clone().oneshot(request).await.unwrap().status(),
+        StatusCode::OK
+    );
+
+    // A traced caller is continued through the gateway's span
+    let request = Request::builder()
+        .uri("/api/orders")
+        .header("traceparent", format!("00-{TRACE_ID}-{CALLER_SPAN_ID}-01"))
+        .body(axum::body::Body::empty())
+        .unwrap();
+    assert_eq!(app.oneshot(request).await.unwrap().status(), StatusCode::OK);
+
+    let requests = mock_server.received_requests().await.unwrap();
+    let spans = spans.0.lock().unwrap();
+    assert_eq!(spans.len(), 2);
+
+    let (trace_id, span_id, flags) = traceparent(&requests[0]);
+    assert_eq!(trace_id, spans[0].span_context.trace_id().to_string());
+    assert_eq!(span_id, spans[0].span_context.span_id().to_string());
+    assert_eq!(flags, "01");
+
+    let (trace_id, span_id, _) = traceparent(&requests[1]);
+    assert_eq!(trace_id, TRACE_ID);
+    assert_ne!(span_id, CALLER_SPAN_ID);
+    assert_eq!(span_id, spans[1].span_context.span_id().to_string());
+    assert_eq!(spans[1].parent_span_id.to_string(), CALLER_SPAN_ID);
+}
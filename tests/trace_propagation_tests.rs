@@ -0,0 +1,67 @@
+mod common;
+
+use axum::{middleware, Router};
+use gateway::config::TracePropagator;
+use gateway::observability::{install_propagator, trace_context_middleware};
+use http::{Request, StatusCode};
+use tower::ServiceExt;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const TRACE_ID: &str = "80f198ee56343ba864fe8b2a57d3eff7";
+const SPAN_ID: &str = "e457b5a2e4d86bd1";
+
+fn gateway(backend: &str) -> Router {
+    common::gateway(&format!(
+        r#"
+server:
+  host: "127.0.0.1"
+  port: 8080
+routes:
+  - path: "/api/orders"
+    backend: "{backend}"
+"#
+    ))
+    .layer(middleware::from_fn(trace_context_middleware))
+}
+
+#[tokio::test]
+async fn test_b3_context_continues_to_backend() {
+    // Installs the global propagator
+    install_propagator(&[
+        TracePropagator::B3,
+        TracePropagator::TraceContext,
+        TracePropagator::Baggage,
+    ]);
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/orders"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+    let app = gateway(&mock_server.uri());
+
+    // Zipkin multi-header context from the mesh, plus W3C baggage
+    let request = Request::builder()
+        .uri("/api/orders")
+        .header("X-B3-TraceId", TRACE_ID)
+        .header("X-B3-SpanId", SPAN_ID)
+        .header("X-B3-Sampled", "1")
+        .header("baggage", "tenant=acme")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let requests = mock_server.received_requests().await.unwrap();
+    let headers = &requests[0].headers;
+    let header = |name: &str| headers.get(name).unwrap().to_str().unwrap().to_string();
+
+    // Without an exporting tracer the caller's span is the parent downstream
+    assert_eq!(header("b3"), format!("{TRACE_ID}-{SPAN_ID}-1"));
+    assert_eq!(header("traceparent"), format!("00-{TRACE_ID}-{SPAN_ID}-01"));
+    assert_eq!(header("baggage"), "tenant=acme");
+}